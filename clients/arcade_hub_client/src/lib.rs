@@ -0,0 +1,37 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Client types for the Arcade Hub application.
+//!
+//! Re-exports the application's ABI so operations can be built without
+//! copying types, and provides typed GraphQL queries against its service:
+//!
+//! ```ignore
+//! let query = queries::leaderboard(Some(10), None, None);
+//! let body = serde_json::to_value(&query)?; // POST this to the service
+//! let entries: Vec<responses::LeaderboardEntry> = query.parse(response_json)?;
+//! ```
+
+pub mod mutations;
+pub mod queries;
+pub mod responses;
+
+pub use arcade_hub::{
+    self as abi,
+    envelope::{OpV2, VersionedOperation},
+    rankings::LeaderboardMetric,
+    ArcadeError, ArcadeErrorCode, ArcadeEvent, ArcadeHubAbi, ArcadeParameters, ArcadeResponse,
+    GameType, InstantiationArgument, Message, Operation, ScoreSubmission, ScoringCategory,
+    API_VERSION,
+};
+
+/// Errors returned when reading a service response.
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("Query failed: {}", .0.join("; "))]
+    GraphQl(Vec<String>),
+    #[error("Response has no data for `{0}`")]
+    MissingData(&'static str),
+    #[error("Failed to decode response: {0}")]
+    Decode(#[from] serde_json::Error),
+}
@@ -0,0 +1,61 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! GraphQL mutations that schedule Arcade Hub operations through a node
+//! service. Each resolves to the hash of the block that included it.
+
+use serde_json::json;
+
+use crate::{
+    queries::{enum_variable, Query},
+    GameType, ScoreSubmission,
+};
+
+/// Register the chain owner as a player.
+pub fn register_player(username: &str) -> Query<String> {
+    Query::new(
+        "registerPlayer",
+        "mutation($username: String!) { registerPlayer(username: $username) }".to_string(),
+        json!({ "username": username }),
+    )
+}
+
+/// Start a game as the chain owner, opening a session, on kiosk device
+/// `device_id` if any. Its ID is read back with
+/// [`queries::open_session`](crate::queries::open_session).
+pub fn start_game(game_type: GameType, device_id: Option<&str>) -> Query<String> {
+    Query::new(
+        "startGame",
+        "mutation($gameType: GameType!, $deviceId: String) { \
+         startGame(gameType: $gameType, deviceId: $deviceId) }"
+            .to_string(),
+        json!({ "gameType": enum_variable(Some(game_type)), "deviceId": device_id }),
+    )
+}
+
+/// Submit a score as the chain owner, closing game session `session_id`.
+/// The submission's game must be the session's.
+pub fn end_game(session_id: u64, submission: &ScoreSubmission) -> Query<String> {
+    Query::new(
+        "endGame",
+        "mutation($sessionId: Int!, $score: Int!, $bonusData: Int, $deviceId: String) { \
+         endGame(sessionId: $sessionId, score: $score, bonusData: $bonusData, \
+         deviceId: $deviceId) }"
+            .to_string(),
+        json!({
+            "sessionId": session_id,
+            "score": submission.score,
+            "bonusData": submission.bonus_data,
+            "deviceId": submission.device_id,
+        }),
+    )
+}
+
+/// Store the current leaderboard as a snapshot (admin only).
+pub fn snapshot_leaderboard() -> Query<String> {
+    Query::new(
+        "snapshotLeaderboard",
+        "mutation { snapshotLeaderboard }".to_string(),
+        json!({}),
+    )
+}
@@ -0,0 +1,318 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Typed GraphQL queries against the Arcade Hub service.
+
+use std::marker::PhantomData;
+
+use async_graphql::InputType;
+use linera_sdk::linera_base_types::{AccountOwner, ChainId};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    responses::{
+        ApiVersion, ArcadeStats, ChainTime, GameHighScoreEntry, GameScore, GameSession,
+        LeaderboardEntry, LeaderboardPage, Player, PlayersPage, ScoreLogRange, ScoresPage,
+        SubmissionCheck, GAME_HIGH_SCORE_FIELDS, GAME_SCORE_FIELDS, GAME_SESSION_FIELDS,
+        LEADERBOARD_FIELDS, PLAYER_FIELDS,
+    },
+    ClientError, GameType, LeaderboardMetric, ScoreSubmission, ScoringCategory, API_VERSION,
+};
+
+/// A GraphQL query or mutation whose result decodes to `T`. Serializes to the
+/// request body the service expects, pinned to the API version this client
+/// was built against.
+#[derive(Clone, Debug, Serialize)]
+pub struct Query<T> {
+    pub query: String,
+    pub variables: Value,
+    pub extensions: Value,
+    /// The root field the result is read from.
+    #[serde(skip)]
+    field: &'static str,
+    #[serde(skip)]
+    response: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> Query<T> {
+    pub(crate) fn new(field: &'static str, query: String, variables: Value) -> Self {
+        Self {
+            query,
+            variables,
+            extensions: json!({ "apiVersion": API_VERSION }),
+            field,
+            response: PhantomData,
+        }
+    }
+
+    /// Decode the service's JSON response to this query.
+    pub fn parse(&self, response: Value) -> Result<T, ClientError> {
+        let response = serde_json::from_value::<RawResponse>(response)?;
+        if !response.errors.is_empty() {
+            return Err(ClientError::GraphQl(
+                response
+                    .errors
+                    .into_iter()
+                    .map(|error| error.message)
+                    .collect(),
+            ));
+        }
+        let value = response
+            .data
+            .and_then(|mut data| data.get_mut(self.field).map(Value::take))
+            .ok_or(ClientError::MissingData(self.field))?;
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
+/// The envelope of a GraphQL response.
+#[derive(Deserialize)]
+struct RawResponse {
+    data: Option<Value>,
+    #[serde(default)]
+    errors: Vec<RawError>,
+}
+
+#[derive(Deserialize)]
+struct RawError {
+    message: String,
+}
+
+/// Encode an enum the way GraphQL variables spell it.
+pub(crate) fn enum_variable<T: InputType>(value: Option<T>) -> Value {
+    value.map_or(Value::Null, |value| {
+        value.to_value().into_json().unwrap_or(Value::Null)
+    })
+}
+
+/// Encode an account owner as a `wallet` argument. The service accepts the
+/// JSON form for every owner kind, not just 20-byte addresses.
+pub fn wallet(owner: &AccountOwner) -> String {
+    serde_json::to_string(owner).expect("Account owners serialize to JSON")
+}
+
+/// Get a player by wallet address. `viewer` unlocks friends-only profiles.
+pub fn player(wallet: &str, viewer: Option<&str>) -> Query<Option<Player>> {
+    Query::new(
+        "player",
+        format!(
+            "query($wallet: String!, $viewer: String) {{ \
+             player(wallet: $wallet, viewer: $viewer) {{ {PLAYER_FIELDS} }} }}"
+        ),
+        json!({ "wallet": wallet, "viewer": viewer }),
+    )
+}
+
+/// Get every registered player on the queried chain, except hidden ones.
+pub fn all_players() -> Query<Vec<Player>> {
+    Query::new(
+        "allPlayers",
+        format!("query {{ allPlayers {{ {PLAYER_FIELDS} }} }}"),
+        json!({}),
+    )
+}
+
+/// Get the leaderboard, optionally restricted to one scoring category and
+/// ordered by a metric other than XP.
+pub fn leaderboard(
+    limit: Option<i32>,
+    category: Option<ScoringCategory>,
+    order_by: Option<LeaderboardMetric>,
+) -> Query<Vec<LeaderboardEntry>> {
+    Query::new(
+        "leaderboard",
+        format!(
+            "query($limit: Int, $category: ScoringCategory, $orderBy: LeaderboardMetric) {{ \
+             leaderboard(limit: $limit, category: $category, orderBy: $orderBy) {{ \
+             {LEADERBOARD_FIELDS} }} }}"
+        ),
+        json!({
+            "limit": limit,
+            "category": enum_variable(category),
+            "orderBy": enum_variable(order_by),
+        }),
+    )
+}
+
+/// Get one page of the XP leaderboard; pass the previous page's
+/// `next_page_token` to continue.
+pub fn leaderboard_page(limit: Option<i32>, page_token: Option<&str>) -> Query<LeaderboardPage> {
+    Query::new(
+        "leaderboardPage",
+        format!(
+            "query($limit: Int, $pageToken: String) {{ \
+             leaderboardPage(limit: $limit, pageToken: $pageToken) {{ \
+             entries {{ {LEADERBOARD_FIELDS} }} snapshotId takenAt nextPageToken truncated }} }}"
+        ),
+        json!({ "limit": limit, "pageToken": page_token }),
+    )
+}
+
+/// Get one page of players in storage order; pass the previous page's
+/// `next_after_owner` to continue.
+pub fn players_page(after_owner: Option<&str>, limit: Option<i32>) -> Query<PlayersPage> {
+    Query::new(
+        "playersPage",
+        format!(
+            "query($afterOwner: String, $limit: Int) {{ \
+             playersPage(afterOwner: $afterOwner, limit: $limit) {{ \
+             players {{ {PLAYER_FIELDS} }} nextAfterOwner }} }}"
+        ),
+        json!({ "afterOwner": after_owner, "limit": limit }),
+    )
+}
+
+/// Get one page of the scores played on `chain_id` (by default the queried
+/// chain) in ID order; pass the previous page's `next_after_id` to continue.
+pub fn scores_page(
+    chain_id: Option<ChainId>,
+    after_id: Option<u64>,
+    limit: Option<i32>,
+) -> Query<ScoresPage> {
+    Query::new(
+        "scoresPage",
+        format!(
+            "query($chainId: ChainId, $afterId: Int, $limit: Int) {{ \
+             scoresPage(chainId: $chainId, afterId: $afterId, limit: $limit) {{ \
+             scores {{ {GAME_SCORE_FIELDS} }} nextAfterId }} }}"
+        ),
+        json!({ "chainId": chain_id, "afterId": after_id, "limit": limit }),
+    )
+}
+
+/// Get a range of the score log starting at position `start`.
+pub fn score_log(start: u64, limit: Option<i32>) -> Query<ScoreLogRange> {
+    Query::new(
+        "scoreLog",
+        format!(
+            "query($start: Int!, $limit: Int) {{ \
+             scoreLog(start: $start, limit: $limit) {{ \
+             start scores {{ {GAME_SCORE_FIELDS} }} length }} }}"
+        ),
+        json!({ "start": start, "limit": limit }),
+    )
+}
+
+/// Get a player's rank.
+pub fn player_rank(wallet: &str) -> Query<Option<i32>> {
+    Query::new(
+        "playerRank",
+        "query($wallet: String!) { playerRank(wallet: $wallet) }".to_string(),
+        json!({ "wallet": wallet }),
+    )
+}
+
+/// Get the most recent scores.
+pub fn recent_scores(limit: Option<i32>) -> Query<Vec<GameScore>> {
+    Query::new(
+        "recentScores",
+        format!("query($limit: Int) {{ recentScores(limit: $limit) {{ {GAME_SCORE_FIELDS} }} }}"),
+        json!({ "limit": limit }),
+    )
+}
+
+/// Get the best scores for one game.
+pub fn game_scores(
+    game_type: GameType,
+    limit: Option<i32>,
+    category: Option<ScoringCategory>,
+) -> Query<Vec<GameScore>> {
+    Query::new(
+        "gameScores",
+        format!(
+            "query($gameType: GameType!, $limit: Int, $category: ScoringCategory) {{ \
+             gameScores(gameType: $gameType, limit: $limit, category: $category) {{ \
+             {GAME_SCORE_FIELDS} }} }}"
+        ),
+        json!({
+            "gameType": enum_variable(Some(game_type)),
+            "limit": limit,
+            "category": enum_variable(category),
+        }),
+    )
+}
+
+/// Get each player's best score for one game.
+pub fn game_high_scores(
+    game_type: GameType,
+    limit: Option<i32>,
+    category: Option<ScoringCategory>,
+) -> Query<Vec<GameHighScoreEntry>> {
+    Query::new(
+        "gameHighScores",
+        format!(
+            "query($gameType: GameType!, $limit: Int, $category: ScoringCategory) {{ \
+             gameHighScores(gameType: $gameType, limit: $limit, category: $category) {{ \
+             {GAME_HIGH_SCORE_FIELDS} }} }}"
+        ),
+        json!({
+            "gameType": enum_variable(Some(game_type)),
+            "limit": limit,
+            "category": enum_variable(category),
+        }),
+    )
+}
+
+/// Get arcade-wide statistics.
+pub fn stats() -> Query<ArcadeStats> {
+    Query::new(
+        "stats",
+        "query { stats { totalPlayers totalGamesPlayed totalXpEarned } }".to_string(),
+        json!({}),
+    )
+}
+
+/// Dry-run a submission closing game session `session_id` through the
+/// contract's checks.
+pub fn validate_submission(
+    wallet: &str,
+    submission: &ScoreSubmission,
+    session_id: Option<u64>,
+) -> Query<SubmissionCheck> {
+    Query::new(
+        "validateSubmission",
+        "query($wallet: String!, $gameType: GameType!, $score: Int!, $bonusData: Int, \
+         $deviceId: String, $sessionId: Int) { validateSubmission(wallet: $wallet, \
+         gameType: $gameType, score: $score, bonusData: $bonusData, deviceId: $deviceId, \
+         sessionId: $sessionId) { accepted error xpEarned } }"
+            .to_string(),
+        json!({
+            "wallet": wallet,
+            "gameType": enum_variable(Some(submission.game_type)),
+            "score": submission.score,
+            "bonusData": submission.bonus_data,
+            "deviceId": submission.device_id,
+            "sessionId": session_id,
+        }),
+    )
+}
+
+/// Get a player's open game session.
+pub fn open_session(wallet: &str) -> Query<Option<GameSession>> {
+    Query::new(
+        "openSession",
+        format!(
+            "query($wallet: String!) {{ openSession(wallet: $wallet) {{ {GAME_SESSION_FIELDS} }} }}"
+        ),
+        json!({ "wallet": wallet }),
+    )
+}
+
+/// Get the API versions the service supports.
+pub fn api_version() -> Query<ApiVersion> {
+    Query::new(
+        "apiVersion",
+        "query { apiVersion { current minimum requested } }".to_string(),
+        json!({}),
+    )
+}
+
+/// Get the chain's current time.
+pub fn chain_time() -> Query<ChainTime> {
+    Query::new(
+        "chainTime",
+        "query { chainTime { now hubLastKnown } }".to_string(),
+        json!({}),
+    )
+}
@@ -0,0 +1,230 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Service response types, in the shape the GraphQL service returns them.
+//!
+//! Each `*_FIELDS` constant is the selection the matching query in
+//! [`queries`](crate::queries) requests.
+
+use linera_sdk::linera_base_types::{AccountOwner, ChainId};
+use serde::{Deserialize, Serialize};
+
+use crate::{abi::locale::FirstDayOfWeek, GameType, ScoringCategory};
+
+/// Fields selected for a [`Player`].
+pub const PLAYER_FIELDS: &str = "owner username displayName totalXp level gamesPlayed \
+     registeredAt privacy { hideFromLeaderboard hideScoreHistory friendsOnlyProfile } \
+     playLimits { maxGamesPerDay maxMinutesPerDay } playLimitsUpdatedAt scoringCategory \
+     utcOffsetMinutes utcOffsetUpdatedAt locale { languageTag firstDayOfWeek }";
+
+/// Fields selected for a [`LeaderboardEntry`].
+pub const LEADERBOARD_FIELDS: &str =
+    "walletAddress username displayName totalXp level rank scoringCategory metricValue";
+
+/// Fields selected for a [`GameScore`].
+pub const GAME_SCORE_FIELDS: &str =
+    "id gameType player score xpEarned bonusData timestamp deviceId category chainId";
+
+/// Fields selected for a [`GameHighScoreEntry`].
+pub const GAME_HIGH_SCORE_FIELDS: &str = "player username displayName score xpEarned timestamp";
+
+/// A registered player.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Player {
+    pub owner: AccountOwner,
+    pub username: String,
+    pub display_name: String,
+    pub total_xp: u64,
+    pub level: u32,
+    pub games_played: u64,
+    pub registered_at: u64,
+    pub privacy: PrivacySettings,
+    pub play_limits: PlayLimits,
+    pub play_limits_updated_at: Option<u64>,
+    #[serde(with = "graphql_enum")]
+    pub scoring_category: ScoringCategory,
+    pub utc_offset_minutes: i32,
+    pub utc_offset_updated_at: Option<u64>,
+    pub locale: LocaleSettings,
+}
+
+/// A player's privacy settings.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivacySettings {
+    pub hide_from_leaderboard: bool,
+    pub hide_score_history: bool,
+    pub friends_only_profile: bool,
+}
+
+/// A player's locale preferences.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocaleSettings {
+    pub language_tag: String,
+    #[serde(with = "graphql_enum")]
+    pub first_day_of_week: FirstDayOfWeek,
+}
+
+/// A player's self-imposed daily play limits (0 = unlimited).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayLimits {
+    pub max_games_per_day: u32,
+    pub max_minutes_per_day: u32,
+}
+
+/// One leaderboard row.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardEntry {
+    pub wallet_address: AccountOwner,
+    pub username: String,
+    pub display_name: String,
+    pub total_xp: u64,
+    pub level: u32,
+    pub rank: u32,
+    #[serde(with = "graphql_enum")]
+    pub scoring_category: ScoringCategory,
+    pub metric_value: u64,
+}
+
+/// One page of the leaderboard.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardPage {
+    pub entries: Vec<LeaderboardEntry>,
+    pub snapshot_id: u64,
+    pub taken_at: u64,
+    pub next_page_token: Option<String>,
+    /// Whether players ranked below the snapshot's last entry are left out.
+    pub truncated: bool,
+}
+
+/// A recorded game score.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameScore {
+    pub id: u64,
+    #[serde(with = "graphql_enum")]
+    pub game_type: GameType,
+    pub player: AccountOwner,
+    pub score: u64,
+    pub xp_earned: u64,
+    pub bonus_data: Option<u64>,
+    pub timestamp: u64,
+    pub device_id: Option<String>,
+    #[serde(with = "graphql_enum")]
+    pub category: ScoringCategory,
+    pub chain_id: ChainId,
+}
+
+/// Fields selected for a [`GameSession`].
+pub const GAME_SESSION_FIELDS: &str = "id player gameType startedAt seed deviceId";
+
+/// A game a player has started but not yet submitted.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameSession {
+    pub id: u64,
+    pub player: AccountOwner,
+    #[serde(with = "graphql_enum")]
+    pub game_type: GameType,
+    pub started_at: u64,
+    pub seed: u64,
+    pub device_id: Option<String>,
+}
+
+/// One page of players in storage order.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayersPage {
+    pub players: Vec<Player>,
+    pub next_after_owner: Option<AccountOwner>,
+}
+
+/// One page of scores in ID order.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoresPage {
+    pub scores: Vec<GameScore>,
+    pub next_after_id: Option<u64>,
+}
+
+/// A range of the score log; hidden scores are `None`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoreLogRange {
+    pub start: u64,
+    pub scores: Vec<Option<GameScore>>,
+    pub length: u64,
+}
+
+/// A player's best score in one game.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameHighScoreEntry {
+    pub player: AccountOwner,
+    pub username: String,
+    pub display_name: String,
+    pub score: u64,
+    pub xp_earned: u64,
+    pub timestamp: u64,
+}
+
+/// Arcade-wide statistics.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArcadeStats {
+    pub total_players: u64,
+    pub total_games_played: u64,
+    pub total_xp_earned: u64,
+}
+
+/// The outcome of a submission dry run.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmissionCheck {
+    pub accepted: bool,
+    pub error: Option<String>,
+    pub xp_earned: Option<u64>,
+}
+
+/// Chain time, for correcting client clock skew.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainTime {
+    pub now: u64,
+    pub hub_last_known: Option<u64>,
+}
+
+/// The API versions the service supports.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ApiVersion {
+    pub current: u32,
+    pub minimum: u32,
+    pub requested: u32,
+}
+
+/// (De)serialize an enum by its GraphQL name (e.g. `SPEED_CLICKER`) rather
+/// than its Rust name.
+mod graphql_enum {
+    use async_graphql::{InputType, Value};
+    use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<T: InputType, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.to_value().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T: InputType, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        T::parse(Some(Value::String(name)))
+            .map_err(|e| D::Error::custom(e.into_server_error(Default::default()).message))
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Building and deploying the application with the `linera` CLI.
+
+use std::{path::Path, process::Command};
+
+use anyhow::{bail, Context as _};
+use arcade_hub_client::{ArcadeParameters, InstantiationArgument};
+
+/// Where `cargo build` leaves the contract bytecode.
+pub const CONTRACT_WASM: &str = "target/wasm32-unknown-unknown/release/arcade_hub_contract.wasm";
+
+/// Where `cargo build` leaves the service bytecode.
+pub const SERVICE_WASM: &str = "target/wasm32-unknown-unknown/release/arcade_hub_service.wasm";
+
+/// Build the contract and service bytecode.
+pub fn build_wasm() -> anyhow::Result<()> {
+    run(Command::new("cargo").args([
+        "build",
+        "--release",
+        "--target",
+        "wasm32-unknown-unknown",
+        "-p",
+        "arcade-hub",
+    ]))
+    .map(drop)
+}
+
+/// Publish the bytecode and create the application. Returns its ID.
+pub fn publish_and_create(
+    linera: &str,
+    contract: &Path,
+    service: &Path,
+    argument: &InstantiationArgument,
+    parameters: Option<&ArcadeParameters>,
+) -> anyhow::Result<String> {
+    let mut command = Command::new(linera);
+    command.arg("publish-and-create").arg(contract).arg(service);
+    let output = run(with_arguments(&mut command, argument, parameters)?)?;
+    last_line(&output)
+}
+
+/// Create an application from already published bytecode. Returns its ID.
+pub fn create_application(
+    linera: &str,
+    module_id: &str,
+    argument: &InstantiationArgument,
+    parameters: Option<&ArcadeParameters>,
+) -> anyhow::Result<String> {
+    let mut command = Command::new(linera);
+    command.arg("create-application").arg(module_id);
+    let output = run(with_arguments(&mut command, argument, parameters)?)?;
+    last_line(&output)
+}
+
+/// Pass the instantiation argument and any parameters to a `linera`
+/// command; without parameters the application runs in production.
+fn with_arguments<'a>(
+    command: &'a mut Command,
+    argument: &InstantiationArgument,
+    parameters: Option<&ArcadeParameters>,
+) -> anyhow::Result<&'a mut Command> {
+    command
+        .arg("--json-argument")
+        .arg(serde_json::to_string(argument)?);
+    if let Some(parameters) = parameters {
+        command
+            .arg("--json-parameters")
+            .arg(serde_json::to_string(parameters)?);
+    }
+    Ok(command)
+}
+
+/// Run a command, returning its standard output.
+fn run(command: &mut Command) -> anyhow::Result<String> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let output = command
+        .output()
+        .with_context(|| format!("Failed to run `{program}`"))?;
+    if !output.status.success() {
+        bail!(
+            "`{program}` failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The `linera` CLI prints the created application's ID last.
+fn last_line(output: &str) -> anyhow::Result<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+        .map(str::to_string)
+        .context("`linera` printed no application ID")
+}
@@ -0,0 +1,198 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Synthetic load for benchmarking a deployment before launch.
+//!
+//! Each chain's owner plays as one player, submitting a stream of plausible
+//! scores with random pauses between games. The report covers submission
+//! throughput and latency, how long the hub took to absorb the load, and how
+//! much its totals grew.
+
+use std::{
+    fmt, thread,
+    time::{Duration, Instant},
+};
+
+use arcade_hub_client::{abi::fixtures::FixtureRng, mutations, queries, responses::ArcadeStats};
+use linera_sdk::linera_base_types::ChainId;
+
+use crate::node::NodeClient;
+
+/// How often the hub is polled while waiting for it to catch up.
+const HUB_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Parameters of a load run.
+pub struct LoadConfig {
+    pub hub_chain: ChainId,
+    /// One player per chain.
+    pub chains: Vec<ChainId>,
+    /// Register each chain's owner before playing.
+    pub register: bool,
+    pub scores_per_player: u32,
+    /// Pause between one player's games, in milliseconds.
+    pub min_pause_ms: u64,
+    pub max_pause_ms: u64,
+    pub seed: u64,
+    /// How long to wait for the hub to absorb every score.
+    pub settle_timeout: Duration,
+}
+
+/// The outcome of a load run.
+pub struct LoadReport {
+    pub players: usize,
+    pub submitted: usize,
+    pub failed: usize,
+    pub elapsed: Duration,
+    /// Latency of each successful submission, fastest first.
+    pub latencies: Vec<Duration>,
+    pub hub_before: ArcadeStats,
+    pub hub_after: ArcadeStats,
+    /// Time from the last submission until the hub had counted them all;
+    /// `None` if it didn't within the timeout.
+    pub hub_lag: Option<Duration>,
+}
+
+/// Run the configured load against the application.
+pub fn run(node: &NodeClient, config: &LoadConfig) -> anyhow::Result<LoadReport> {
+    let hub_before = node.execute(config.hub_chain, &queries::stats())?;
+
+    if config.register {
+        for (i, chain_id) in config.chains.iter().enumerate() {
+            let username = format!("load{}", i + 1);
+            node.execute(*chain_id, &mutations::register_player(&username))?;
+        }
+    }
+
+    let started = Instant::now();
+    let streams = thread::scope(|scope| {
+        let handles = config
+            .chains
+            .iter()
+            .enumerate()
+            .map(|(i, chain_id)| {
+                let mut rng = FixtureRng::new(config.seed.wrapping_add(i as u64));
+                scope.spawn(move || play(node, *chain_id, config, &mut rng))
+            })
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("Load thread panicked"))
+            .collect::<Vec<_>>()
+    });
+    let elapsed = started.elapsed();
+
+    let mut latencies = Vec::new();
+    let mut failed = 0;
+    for (stream_latencies, stream_failed) in streams {
+        latencies.extend(stream_latencies);
+        failed += stream_failed;
+    }
+    latencies.sort();
+
+    // Wait for the hub to count every accepted score
+    let expected = hub_before.total_games_played + latencies.len() as u64;
+    let waiting = Instant::now();
+    let (hub_after, hub_lag) = loop {
+        let stats = node.execute(config.hub_chain, &queries::stats())?;
+        if stats.total_games_played >= expected {
+            break (stats, Some(waiting.elapsed()));
+        }
+        if waiting.elapsed() >= config.settle_timeout {
+            break (stats, None);
+        }
+        thread::sleep(HUB_POLL_INTERVAL);
+    };
+
+    Ok(LoadReport {
+        players: config.chains.len(),
+        submitted: latencies.len(),
+        failed,
+        elapsed,
+        latencies,
+        hub_before,
+        hub_after,
+        hub_lag,
+    })
+}
+
+/// Play one player's games, returning the latency of each accepted
+/// submission and the number that failed.
+fn play(
+    node: &NodeClient,
+    chain_id: ChainId,
+    config: &LoadConfig,
+    rng: &mut FixtureRng,
+) -> (Vec<Duration>, usize) {
+    let mut latencies = Vec::new();
+    let mut failed = 0;
+    let Ok(wallet) = node.player_wallet(chain_id) else {
+        return (latencies, config.scores_per_player as usize);
+    };
+    for _ in 0..config.scores_per_player {
+        let pause = rng.range(
+            config.min_pause_ms,
+            config.max_pause_ms.max(config.min_pause_ms),
+        );
+        thread::sleep(Duration::from_millis(pause));
+
+        let game_type = rng.game_type();
+        let submission = rng.submission(game_type, None);
+        let sent = Instant::now();
+        match node.play(chain_id, &wallet, &submission) {
+            Ok(_) => latencies.push(sent.elapsed()),
+            Err(_) => failed += 1,
+        }
+    }
+    (latencies, failed)
+}
+
+impl LoadReport {
+    /// The latency below which `percentile` percent of submissions landed.
+    fn latency(&self, percentile: usize) -> Duration {
+        if self.latencies.is_empty() {
+            return Duration::ZERO;
+        }
+        let index = (self.latencies.len() * percentile / 100).min(self.latencies.len() - 1);
+        self.latencies[index]
+    }
+}
+
+impl fmt::Display for LoadReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let seconds = self.elapsed.as_secs_f64();
+        writeln!(
+            f,
+            "{} players submitted {} scores ({} failed) in {seconds:.1}s: {:.2} scores/s",
+            self.players,
+            self.submitted,
+            self.failed,
+            self.submitted as f64 / seconds.max(f64::EPSILON),
+        )?;
+        writeln!(
+            f,
+            "latency p50 {:?}, p95 {:?}, max {:?}",
+            self.latency(50),
+            self.latency(95),
+            self.latency(100),
+        )?;
+        match self.hub_lag {
+            Some(lag) => writeln!(f, "hub caught up {lag:?} after the last submission")?,
+            None => writeln!(f, "hub had not caught up when the timeout expired")?,
+        }
+        write!(
+            f,
+            "hub growth: +{} players, +{} games, +{} XP (now {} players, {} games)",
+            self.hub_after
+                .total_players
+                .saturating_sub(self.hub_before.total_players),
+            self.hub_after
+                .total_games_played
+                .saturating_sub(self.hub_before.total_games_played),
+            self.hub_after
+                .total_xp_earned
+                .saturating_sub(self.hub_before.total_xp_earned),
+            self.hub_after.total_players,
+            self.hub_after.total_games_played,
+        )
+    }
+}
@@ -0,0 +1,404 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! `linera-arcade`: operator tools for the Arcade Hub.
+//!
+//! Deployment shells out to the `linera` CLI; every other command talks to a
+//! running node service (`linera service`) whose wallet owns the chains
+//! involved.
+
+mod deploy;
+#[cfg(feature = "load")]
+mod load;
+mod node;
+mod synthetic;
+
+use std::{fs, io::Write as _, path::PathBuf};
+
+use anyhow::{bail, Context as _};
+use arcade_hub_client::{
+    abi::fixtures::{FixtureConfig, FixtureRng},
+    mutations, queries,
+    responses::{LeaderboardEntry, Player},
+    ArcadeParameters, GameType, InstantiationArgument,
+};
+use clap::{Parser, Subcommand, ValueEnum};
+use linera_sdk::linera_base_types::{AccountOwner, ChainId};
+
+use self::node::NodeClient;
+
+/// Operator tools for the Linera Arcade Hub.
+#[derive(Parser)]
+#[command(name = "linera-arcade", version)]
+struct Cli {
+    /// URL of the node service.
+    #[arg(
+        long,
+        global = true,
+        env = "LINERA_ARCADE_NODE_URL",
+        default_value = "http://localhost:8080"
+    )]
+    node_url: String,
+    /// ID of the arcade application.
+    #[arg(long, global = true, env = "LINERA_ARCADE_APP_ID")]
+    application_id: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Build, publish and create the application.
+    Deploy {
+        #[command(flatten)]
+        instantiation: Instantiation,
+        /// Use previously built bytecode.
+        #[arg(long)]
+        skip_build: bool,
+        #[arg(long, default_value = deploy::CONTRACT_WASM)]
+        contract: PathBuf,
+        #[arg(long, default_value = deploy::SERVICE_WASM)]
+        service: PathBuf,
+        /// The `linera` binary to run.
+        #[arg(long, default_value = "linera")]
+        linera: String,
+    },
+    /// Create the application from already published bytecode.
+    Instantiate {
+        /// The published module ID.
+        module_id: String,
+        #[command(flatten)]
+        instantiation: Instantiation,
+        /// The `linera` binary to run.
+        #[arg(long, default_value = "linera")]
+        linera: String,
+    },
+    /// Register the owner of each chain as a test player named
+    /// `<prefix><n>`.
+    RegisterPlayers {
+        #[arg(long = "chain", required = true)]
+        chains: Vec<ChainId>,
+        #[arg(long, default_value = "tester")]
+        prefix: String,
+    },
+    /// Submit synthetic scores from each chain.
+    SubmitScores {
+        #[arg(long = "chain", required = true)]
+        chains: Vec<ChainId>,
+        /// Scores per chain.
+        #[arg(long, default_value_t = 10)]
+        count: u32,
+        /// Only play this game (e.g. `speed-clicker`); random otherwise.
+        #[arg(long, value_parser = synthetic::parse_game_type)]
+        game: Option<GameType>,
+        /// Kiosk device to submit from.
+        #[arg(long)]
+        device_id: Option<String>,
+        /// Seed for the generated scores, to reproduce a run.
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// Export the full leaderboard from the hub.
+    ExportLeaderboard {
+        #[arg(long, env = "LINERA_ARCADE_HUB_CHAIN")]
+        hub_chain: ChainId,
+        #[arg(long, value_enum, default_value_t = ExportFormat::Csv)]
+        format: ExportFormat,
+        /// Write here instead of standard output.
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Check that players on each chain are in sync with the hub. Exits
+    /// with an error if any are not.
+    Reconcile {
+        #[arg(long, env = "LINERA_ARCADE_HUB_CHAIN")]
+        hub_chain: ChainId,
+        #[arg(long = "chain", required = true)]
+        chains: Vec<ChainId>,
+    },
+    /// Drive synthetic play from every chain at once and report hub
+    /// throughput. Each chain's owner plays as one player, so open one chain
+    /// per simulated player.
+    #[cfg(feature = "load")]
+    LoadTest {
+        #[arg(long, env = "LINERA_ARCADE_HUB_CHAIN")]
+        hub_chain: ChainId,
+        #[arg(long = "chain", required = true)]
+        chains: Vec<ChainId>,
+        /// Register each chain's owner first.
+        #[arg(long)]
+        register: bool,
+        #[arg(long, default_value_t = 20)]
+        scores_per_player: u32,
+        /// Shortest pause between a player's games, in milliseconds.
+        #[arg(long, default_value_t = 500)]
+        min_pause_ms: u64,
+        /// Longest pause between a player's games, in milliseconds.
+        #[arg(long, default_value_t = 3000)]
+        max_pause_ms: u64,
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Seconds to wait for the hub to absorb every score.
+        #[arg(long, default_value_t = 120)]
+        settle_timeout_secs: u64,
+    },
+}
+
+/// Instantiation settings shared by `deploy` and `instantiate`.
+#[derive(clap::Args)]
+struct Instantiation {
+    /// The chain that aggregates all players and scores.
+    #[arg(long, env = "LINERA_ARCADE_HUB_CHAIN")]
+    hub_chain: ChainId,
+    #[arg(long)]
+    admin: Option<AccountOwner>,
+    #[arg(long)]
+    voucher_signer: Option<AccountOwner>,
+    /// Populate the application with demo players and scores generated
+    /// from this seed.
+    #[arg(long)]
+    demo_seed: Option<u64>,
+    /// Create a developer sandbox, whose chains sync nothing to the hub.
+    #[arg(long)]
+    sandbox: bool,
+    /// Also send every sync straight to the hub as a message.
+    #[arg(long)]
+    direct_syncs: bool,
+}
+
+impl Instantiation {
+    fn argument(&self) -> InstantiationArgument {
+        InstantiationArgument {
+            hub_chain_id: self.hub_chain,
+            admin: self.admin,
+            appeal_bond: None,
+            voucher_signer: self.voucher_signer,
+            score_bounds: Vec::new(),
+            score_signer: None,
+            rookie_criteria: None,
+            demo_fixture: self.demo_seed.map(|seed| FixtureConfig {
+                seed,
+                ..FixtureConfig::default()
+            }),
+            direct_syncs: self.direct_syncs,
+        }
+    }
+
+    fn parameters(&self) -> Option<ArcadeParameters> {
+        self.sandbox.then_some(ArcadeParameters { sandbox: true })
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ExportFormat {
+    Csv,
+    Json,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    match &cli.command {
+        Command::Deploy {
+            instantiation,
+            skip_build,
+            contract,
+            service,
+            linera,
+        } => {
+            if !skip_build {
+                deploy::build_wasm()?;
+            }
+            let application_id = deploy::publish_and_create(
+                linera,
+                contract,
+                service,
+                &instantiation.argument(),
+                instantiation.parameters().as_ref(),
+            )?;
+            println!("{application_id}");
+        }
+        Command::Instantiate {
+            module_id,
+            instantiation,
+            linera,
+        } => {
+            let application_id = deploy::create_application(
+                linera,
+                module_id,
+                &instantiation.argument(),
+                instantiation.parameters().as_ref(),
+            )?;
+            println!("{application_id}");
+        }
+        Command::RegisterPlayers { chains, prefix } => {
+            let node = cli.node()?;
+            for (i, chain_id) in chains.iter().enumerate() {
+                let username = format!("{prefix}{}", i + 1);
+                node.execute(*chain_id, &mutations::register_player(&username))
+                    .with_context(|| format!("Failed to register {username} on {chain_id}"))?;
+                println!("{chain_id}: registered {username}");
+            }
+        }
+        Command::SubmitScores {
+            chains,
+            count,
+            game,
+            device_id,
+            seed,
+        } => {
+            let node = cli.node()?;
+            let mut rng = FixtureRng::new(*seed);
+            for chain_id in chains {
+                let wallet = node.player_wallet(*chain_id)?;
+                for _ in 0..*count {
+                    let game_type = game.unwrap_or_else(|| rng.game_type());
+                    let submission = rng.submission(game_type, device_id.clone());
+                    node.play(*chain_id, &wallet, &submission)
+                        .with_context(|| format!("Failed to submit a score on {chain_id}"))?;
+                }
+                println!("{chain_id}: submitted {count} scores");
+            }
+        }
+        Command::ExportLeaderboard {
+            hub_chain,
+            format,
+            output,
+        } => {
+            let entries = export_leaderboard(&cli.node()?, *hub_chain)?;
+            let rendered = match format {
+                ExportFormat::Csv => leaderboard_csv(&entries),
+                ExportFormat::Json => serde_json::to_string_pretty(&entries)?,
+            };
+            match output {
+                Some(path) => fs::write(path, rendered)
+                    .with_context(|| format!("Failed to write {}", path.display()))?,
+                None => std::io::stdout().write_all(rendered.as_bytes())?,
+            }
+        }
+        Command::Reconcile { hub_chain, chains } => {
+            let node = cli.node()?;
+            let mut mismatches = 0;
+            for chain_id in chains {
+                for player in node.execute(*chain_id, &queries::all_players())? {
+                    if let Some(problem) = reconcile_player(&node, *hub_chain, &player)? {
+                        println!(
+                            "{chain_id} {} ({}): {problem}",
+                            player.owner, player.username
+                        );
+                        mismatches += 1;
+                    }
+                }
+            }
+            if mismatches > 0 {
+                bail!("{mismatches} players are out of sync with the hub");
+            }
+            println!("All players are in sync");
+        }
+        #[cfg(feature = "load")]
+        Command::LoadTest {
+            hub_chain,
+            chains,
+            register,
+            scores_per_player,
+            min_pause_ms,
+            max_pause_ms,
+            seed,
+            settle_timeout_secs,
+        } => {
+            let config = load::LoadConfig {
+                hub_chain: *hub_chain,
+                chains: chains.clone(),
+                register: *register,
+                scores_per_player: *scores_per_player,
+                min_pause_ms: *min_pause_ms,
+                max_pause_ms: *max_pause_ms,
+                seed: *seed,
+                settle_timeout: std::time::Duration::from_secs(*settle_timeout_secs),
+            };
+            println!("{}", load::run(&cli.node()?, &config)?);
+        }
+    }
+    Ok(())
+}
+
+impl Cli {
+    fn node(&self) -> anyhow::Result<NodeClient> {
+        let application_id = self
+            .application_id
+            .as_deref()
+            .context("An application ID is required (--application-id or LINERA_ARCADE_APP_ID)")?;
+        Ok(NodeClient::new(&self.node_url, application_id))
+    }
+}
+
+/// Read every page of the leaderboard, pinned to one snapshot.
+fn export_leaderboard(
+    node: &NodeClient,
+    hub_chain: ChainId,
+) -> anyhow::Result<Vec<LeaderboardEntry>> {
+    let mut entries = Vec::new();
+    let mut page_token = None;
+    loop {
+        let page = node.execute(
+            hub_chain,
+            &queries::leaderboard_page(Some(500), page_token.as_deref()),
+        )?;
+        entries.extend(page.entries);
+        match page.next_page_token {
+            Some(token) => page_token = Some(token),
+            None => {
+                if page.truncated {
+                    eprintln!(
+                        "The leaderboard snapshot is truncated; lower-ranked players are left out"
+                    );
+                }
+                return Ok(entries);
+            }
+        }
+    }
+}
+
+fn leaderboard_csv(entries: &[LeaderboardEntry]) -> String {
+    let mut csv = String::from("rank,wallet_address,username,display_name,total_xp,level\n");
+    for entry in entries {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            entry.rank,
+            entry.wallet_address,
+            csv_field(&entry.username),
+            csv_field(&entry.display_name),
+            entry.total_xp,
+            entry.level,
+        ));
+    }
+    csv
+}
+
+/// Quote a field if it contains a separator, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Compare a player's chain-local record with the hub's, describing any
+/// difference.
+fn reconcile_player(
+    node: &NodeClient,
+    hub_chain: ChainId,
+    player: &Player,
+) -> anyhow::Result<Option<String>> {
+    let wallet = queries::wallet(&player.owner);
+    let Some(on_hub) = node.execute(hub_chain, &queries::player(&wallet, Some(&wallet)))? else {
+        return Ok(Some("missing on the hub".to_string()));
+    };
+    if on_hub.total_xp != player.total_xp || on_hub.games_played != player.games_played {
+        return Ok(Some(format!(
+            "hub has {} XP over {} games, chain has {} XP over {} games",
+            on_hub.total_xp, on_hub.games_played, player.total_xp, player.games_played
+        )));
+    }
+    Ok(None)
+}
@@ -0,0 +1,80 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Access to the arcade application through a Linera node service.
+
+use anyhow::Context as _;
+use arcade_hub_client::{
+    mutations,
+    queries::{self, Query},
+    ScoreSubmission,
+};
+use linera_sdk::linera_base_types::ChainId;
+use serde::de::DeserializeOwned;
+
+/// A node service (`linera service`) serving the arcade application.
+pub struct NodeClient {
+    http: reqwest::blocking::Client,
+    node_url: String,
+    application_id: String,
+}
+
+impl NodeClient {
+    pub fn new(node_url: &str, application_id: &str) -> Self {
+        Self {
+            http: reqwest::blocking::Client::new(),
+            node_url: node_url.trim_end_matches('/').to_string(),
+            application_id: application_id.to_string(),
+        }
+    }
+
+    /// Run a query or mutation against the application on `chain_id`.
+    /// Mutations are signed by the chain's owner in the node's wallet.
+    pub fn execute<T: DeserializeOwned>(
+        &self,
+        chain_id: ChainId,
+        query: &Query<T>,
+    ) -> anyhow::Result<T> {
+        let url = format!(
+            "{}/chains/{}/applications/{}",
+            self.node_url, chain_id, self.application_id
+        );
+        let response = self
+            .http
+            .post(&url)
+            .json(query)
+            .send()
+            .and_then(|response| response.error_for_status())
+            .with_context(|| format!("Request to {url} failed"))?
+            .json()
+            .context("Node returned invalid JSON")?;
+        Ok(query.parse(response)?)
+    }
+
+    /// Play one game as the owner of `chain_id`, whose wallet is `wallet`:
+    /// start a session, then submit `submission` to close it.
+    pub fn play(
+        &self,
+        chain_id: ChainId,
+        wallet: &str,
+        submission: &ScoreSubmission,
+    ) -> anyhow::Result<()> {
+        let start = mutations::start_game(submission.game_type, submission.device_id.as_deref());
+        self.execute(chain_id, &start)?;
+        let session = self
+            .execute(chain_id, &queries::open_session(wallet))?
+            .context("Started game has no open session")?;
+        self.execute(chain_id, &mutations::end_game(session.id, submission))?;
+        Ok(())
+    }
+
+    /// The wallet of the player registered on `chain_id`.
+    pub fn player_wallet(&self, chain_id: ChainId) -> anyhow::Result<String> {
+        let player = self
+            .execute(chain_id, &queries::all_players())?
+            .into_iter()
+            .next()
+            .with_context(|| format!("No player is registered on {chain_id}"))?;
+        Ok(queries::wallet(&player.owner))
+    }
+}
@@ -0,0 +1,22 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Helpers for synthetic play. Scores themselves come from the
+//! application's [`FixtureRng`](arcade_hub_client::abi::fixtures::FixtureRng).
+
+use arcade_hub_client::GameType;
+
+/// Parse a game name such as `speed-clicker` or `SPEED_CLICKER`.
+pub fn parse_game_type(name: &str) -> Result<GameType, String> {
+    let normalize = |name: &str| {
+        name.chars()
+            .filter(|c| c.is_alphanumeric())
+            .collect::<String>()
+            .to_lowercase()
+    };
+    let wanted = normalize(name);
+    GameType::ALL
+        .into_iter()
+        .find(|game_type| normalize(game_type.name()) == wanted)
+        .ok_or_else(|| format!("Unknown game `{name}`"))
+}
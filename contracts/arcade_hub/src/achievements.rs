@@ -0,0 +1,173 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Achievements: fixed milestones in a player's career, unlocked by the
+//! contract as scores are submitted.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use async_graphql::{Enum, SimpleObject};
+use serde::{Deserialize, Serialize};
+
+use crate::{GameType, Player};
+
+/// Every achievement that can be unlocked.
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize, Enum,
+)]
+pub enum Achievement {
+    FirstGame,
+    TenGames,
+    HundredGames,
+    ReachLevel5,
+    ReachLevel10,
+    ReachLevel25,
+    AllGames,
+    FullCircuit,
+}
+
+impl Achievement {
+    /// The catalog, in display order.
+    pub const ALL: [Achievement; 8] = [
+        Achievement::FirstGame,
+        Achievement::TenGames,
+        Achievement::HundredGames,
+        Achievement::ReachLevel5,
+        Achievement::ReachLevel10,
+        Achievement::ReachLevel25,
+        Achievement::AllGames,
+        Achievement::FullCircuit,
+    ];
+
+    /// The achievement's display name.
+    pub fn name(self) -> &'static str {
+        match self {
+            Achievement::FirstGame => "First Steps",
+            Achievement::TenGames => "Regular",
+            Achievement::HundredGames => "Arcade Veteran",
+            Achievement::ReachLevel5 => "Rising Star",
+            Achievement::ReachLevel10 => "Seasoned",
+            Achievement::ReachLevel25 => "Legend",
+            Achievement::AllGames => "Explorer",
+            Achievement::FullCircuit => "Full Circuit",
+        }
+    }
+
+    /// What it takes to unlock the achievement.
+    pub fn description(self) -> &'static str {
+        match self {
+            Achievement::FirstGame => "Play your first game",
+            Achievement::TenGames => "Play 10 games",
+            Achievement::HundredGames => "Play 100 games",
+            Achievement::ReachLevel5 => "Reach level 5",
+            Achievement::ReachLevel10 => "Reach level 10",
+            Achievement::ReachLevel25 => "Reach level 25",
+            Achievement::AllGames => "Play every game in the arcade",
+            Achievement::FullCircuit => "Play every game on a single day",
+        }
+    }
+
+    /// The progress needed to unlock the achievement.
+    pub fn goal(self) -> u64 {
+        match self {
+            Achievement::FirstGame => 1,
+            Achievement::TenGames => 10,
+            Achievement::HundredGames => 100,
+            Achievement::ReachLevel5 => 5,
+            Achievement::ReachLevel10 => 10,
+            Achievement::ReachLevel25 => 25,
+            Achievement::AllGames => GameType::ALL.len() as u64,
+            Achievement::FullCircuit => 1,
+        }
+    }
+
+    /// `player`'s progress towards the achievement, capped at its goal.
+    pub fn progress(self, player: &Player, progress: &AchievementProgress) -> u64 {
+        let value = match self {
+            Achievement::FirstGame | Achievement::TenGames | Achievement::HundredGames => {
+                player.games_played
+            }
+            Achievement::ReachLevel5 | Achievement::ReachLevel10 | Achievement::ReachLevel25 => {
+                u64::from(player.level)
+            }
+            Achievement::AllGames => progress.games_tried.len() as u64,
+            Achievement::FullCircuit => u64::from(progress.full_circuits),
+        };
+        value.min(self.goal())
+    }
+
+    /// The achievement as shown in the catalog.
+    pub fn definition(self) -> AchievementDefinition {
+        AchievementDefinition {
+            achievement: self,
+            name: self.name().to_string(),
+            description: self.description().to_string(),
+            goal: self.goal(),
+        }
+    }
+}
+
+/// An entry in the achievement catalog.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct AchievementDefinition {
+    pub achievement: Achievement,
+    pub name: String,
+    pub description: String,
+    pub goal: u64,
+}
+
+/// A player's progress towards one achievement.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct AchievementStatus {
+    pub definition: AchievementDefinition,
+    pub progress: u64,
+    pub unlocked_at: Option<u64>,
+}
+
+/// A player's unlocked achievements and the progress not kept on their
+/// profile.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct AchievementProgress {
+    unlocked: BTreeMap<Achievement, u64>,
+    games_tried: BTreeSet<GameType>,
+    full_circuits: u32,
+}
+
+impl AchievementProgress {
+    /// Count a run of `game_type`, and whether it completed the day's full
+    /// circuit, returning whether the progress changed.
+    pub fn record_run(&mut self, game_type: GameType, full_circuit: bool) -> bool {
+        if full_circuit {
+            self.full_circuits = self.full_circuits.saturating_add(1);
+        }
+        self.games_tried.insert(game_type) || full_circuit
+    }
+
+    /// Unlock every achievement `player` has now reached, returning the
+    /// newly unlocked ones.
+    pub fn unlock_reached(&mut self, player: &Player, timestamp: u64) -> Vec<Achievement> {
+        let reached: Vec<Achievement> = Achievement::ALL
+            .into_iter()
+            .filter(|achievement| {
+                !self.unlocked.contains_key(achievement)
+                    && achievement.progress(player, self) >= achievement.goal()
+            })
+            .collect();
+        for achievement in &reached {
+            self.unlocked.insert(*achievement, timestamp);
+        }
+        reached
+    }
+
+    /// Every achievement in the catalog with `player`'s progress on it.
+    pub fn statuses(&self, player: &Player) -> Vec<AchievementStatus> {
+        Achievement::ALL
+            .into_iter()
+            .map(|achievement| AchievementStatus {
+                definition: achievement.definition(),
+                progress: achievement.progress(player, self),
+                unlocked_at: self.unlocked.get(&achievement).copied(),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,222 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Aggregate play statistics: activity heatmaps and score distributions.
+
+use async_graphql::SimpleObject;
+use serde::{Deserialize, Serialize};
+
+use crate::{day_index, GameType, MICROS_PER_DAY};
+
+/// Microseconds in an hour.
+const MICROS_PER_HOUR: u64 = 60 * 60 * 1_000_000;
+
+/// Number of hour-of-week slots in a heatmap.
+const HOURS_PER_WEEK: usize = 7 * 24;
+
+/// Number of buckets in a score histogram; the last one is open-ended.
+pub const HISTOGRAM_BUCKETS: usize = 64;
+
+/// Bonus XP, as a percentage of a run's base XP, for beating the game's
+/// p90 score.
+pub const TOP_DECILE_BONUS_PERCENT: u64 = 25;
+
+/// Scores a game needs on record before its p90 is trusted for bonuses.
+pub const MIN_SCORES_FOR_TOP_DECILE: u64 = 100;
+
+/// Games played per UTC hour of the week.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PlayHeatmap {
+    /// Indexed by `day_of_week * 24 + hour`, Monday first.
+    counts: Vec<u64>,
+}
+
+impl Default for PlayHeatmap {
+    fn default() -> Self {
+        Self {
+            counts: vec![0; HOURS_PER_WEEK],
+        }
+    }
+}
+
+impl PlayHeatmap {
+    /// Count a game played at `timestamp`.
+    pub fn record(&mut self, timestamp: u64) {
+        // The Unix epoch fell on a Thursday
+        let day_of_week = (day_index(timestamp) + 3) % 7;
+        let hour = (timestamp % MICROS_PER_DAY) / MICROS_PER_HOUR;
+        let slot = (day_of_week * 24 + hour) as usize;
+        if let Some(count) = self.counts.get_mut(slot) {
+            *count = count.saturating_add(1);
+        }
+    }
+
+    /// All slots, Monday 00:00 UTC first.
+    pub fn cells(&self) -> Vec<HeatmapCell> {
+        self.counts
+            .iter()
+            .enumerate()
+            .map(|(slot, games_played)| HeatmapCell {
+                day_of_week: (slot / 24) as u8,
+                hour: (slot % 24) as u8,
+                games_played: *games_played,
+            })
+            .collect()
+    }
+}
+
+/// Games played in one hour-of-week slot.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct HeatmapCell {
+    /// 0 = Monday through 6 = Sunday.
+    pub day_of_week: u8,
+    /// UTC hour of day, 0-23.
+    pub hour: u8,
+    pub games_played: u64,
+}
+
+/// Bucketed counts of every score submitted for one game.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScoreHistogram {
+    pub bucket_width: u64,
+    counts: Vec<u64>,
+}
+
+impl ScoreHistogram {
+    /// Create an empty histogram with the game's bucket width.
+    pub fn new(game_type: GameType) -> Self {
+        Self {
+            bucket_width: game_type.histogram_bucket_width(),
+            counts: vec![0; HISTOGRAM_BUCKETS],
+        }
+    }
+
+    /// Count one score.
+    pub fn record(&mut self, score: u64) {
+        let bucket = self.bucket_of(score);
+        self.counts[bucket] = self.counts[bucket].saturating_add(1);
+    }
+
+    /// Total number of scores counted.
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Number of scores strictly below `score`'s bucket.
+    pub fn count_below(&self, score: u64) -> u64 {
+        self.counts[..self.bucket_of(score)].iter().sum()
+    }
+
+    /// Number of scores strictly above `score`'s bucket.
+    pub fn count_above(&self, score: u64) -> u64 {
+        self.counts[self.bucket_of(score) + 1..].iter().sum()
+    }
+
+    /// Approximate score at `percentile` (0-100), interpolated within the
+    /// bucket it falls in. Scores in the open-ended top bucket are reported
+    /// as that bucket's lower bound.
+    pub fn percentile(&self, percentile: f64) -> Option<u64> {
+        let total = self.total();
+        if total == 0 {
+            return None;
+        }
+        let target = (percentile.clamp(0.0, 100.0) / 100.0 * total as f64).ceil() as u64;
+        let target = target.max(1);
+        let mut seen = 0u64;
+        for (bucket, count) in self.counts.iter().enumerate() {
+            if *count == 0 {
+                continue;
+            }
+            if seen + count >= target {
+                let min = bucket as u64 * self.bucket_width;
+                if bucket + 1 == HISTOGRAM_BUCKETS {
+                    return Some(min);
+                }
+                let offset = (target - seen - 1) * self.bucket_width / count;
+                return Some(min + offset);
+            }
+            seen += count;
+        }
+        None
+    }
+
+    /// Whether `score` beats the top 10% of recorded scores: above the p90,
+    /// or below the p10 where lower scores are better. Always `false` until
+    /// the game has [`MIN_SCORES_FOR_TOP_DECILE`] scores.
+    pub fn beats_top_decile(&self, score: u64, lower_is_better: bool) -> bool {
+        if self.total() < MIN_SCORES_FOR_TOP_DECILE {
+            return false;
+        }
+        if lower_is_better {
+            self.percentile(10.0).is_some_and(|p10| score < p10)
+        } else {
+            self.percentile(90.0).is_some_and(|p90| score > p90)
+        }
+    }
+
+    /// All buckets, lowest scores first.
+    pub fn buckets(&self) -> Vec<HistogramBucket> {
+        self.counts
+            .iter()
+            .enumerate()
+            .map(|(bucket, count)| {
+                let min = bucket as u64 * self.bucket_width;
+                let max = (bucket + 1 < HISTOGRAM_BUCKETS).then(|| min + self.bucket_width - 1);
+                HistogramBucket {
+                    min,
+                    max,
+                    count: *count,
+                }
+            })
+            .collect()
+    }
+
+    fn bucket_of(&self, score: u64) -> usize {
+        ((score / self.bucket_width) as usize).min(HISTOGRAM_BUCKETS - 1)
+    }
+}
+
+/// One bucket of a score histogram.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct HistogramBucket {
+    pub min: u64,
+    /// Inclusive upper bound; `None` for the open-ended top bucket.
+    pub max: Option<u64>,
+    pub count: u64,
+}
+
+/// The score distribution of a game, optionally placing one player's best
+/// run in it.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct ScoreDistribution {
+    pub game_type: GameType,
+    pub total_scores: u64,
+    pub buckets: Vec<HistogramBucket>,
+    /// The player's best score, if a player was given and has played.
+    pub player_best: Option<u64>,
+    /// Share of recorded scores (0-100) the player's best run beats.
+    pub player_percentile: Option<f64>,
+}
+
+/// Summary statistics of a game's scores, approximated from its histogram.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct GameStats {
+    pub game_type: GameType,
+    pub total_scores: u64,
+    pub median_score: Option<u64>,
+    pub p90_score: Option<u64>,
+    pub p99_score: Option<u64>,
+}
+
+impl GameStats {
+    /// Summarize a game's histogram.
+    pub fn from_histogram(game_type: GameType, histogram: &ScoreHistogram) -> Self {
+        Self {
+            game_type,
+            total_scores: histogram.total(),
+            median_score: histogram.percentile(50.0),
+            p90_score: histogram.percentile(90.0),
+            p99_score: histogram.percentile(99.0),
+        }
+    }
+}
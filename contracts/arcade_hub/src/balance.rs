@@ -0,0 +1,157 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! XP bonuses for the arcade's least-played games, nudging players towards
+//! an even spread across games.
+//!
+//! Each chain tracks the share of plays per game over a rolling window of
+//! the scores it has seen (on the hub, the whole arcade). A game played less
+//! than its fair share earns a bonus proportional to the shortfall, up to the
+//! configured maximum.
+
+use async_graphql::{InputObject, SimpleObject};
+use serde::{Deserialize, Serialize};
+
+use crate::{day_index, GameType};
+
+/// Days of play the shares are computed over.
+pub const BALANCE_WINDOW_DAYS: u64 = 7;
+
+/// Plays needed in the window before any bonus applies, so a handful of
+/// games can't swing the multipliers.
+pub const MIN_WINDOW_PLAYS: u64 = 50;
+
+/// A multiplier that leaves XP unchanged.
+pub const NEUTRAL_MULTIPLIER_PERCENT: u32 = 100;
+
+/// Underplayed-game bonus settings, managed by the admin.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject, InputObject)]
+#[graphql(input_name = "BalanceConfigInput")]
+pub struct BalanceConfig {
+    /// Bonus, in percent, for a game nobody played in the window; smaller
+    /// shortfalls earn proportionally less (0 = no bonuses).
+    pub max_bonus_percent: u32,
+}
+
+impl Default for BalanceConfig {
+    fn default() -> Self {
+        Self {
+            max_bonus_percent: 10,
+        }
+    }
+}
+
+/// Plays of each game on one UTC day.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct DailyPlays {
+    day: u64,
+    /// Indexed like [`GameType::ALL`].
+    counts: [u64; GameType::ALL.len()],
+}
+
+/// Plays per game over the rolling window, one entry per day with plays.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PlayShare {
+    days: Vec<DailyPlays>,
+}
+
+/// A game's current share of play and XP multiplier.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct GameMultiplier {
+    pub game_type: GameType,
+    /// Plays of the game in the window.
+    pub plays: u64,
+    /// Plays of every game in the window.
+    pub total_plays: u64,
+    /// XP is scaled by this percentage; 100 means no bonus.
+    pub multiplier_percent: u32,
+}
+
+impl PlayShare {
+    /// Count a game played at `timestamp`, dropping days that have left the
+    /// window.
+    pub fn record(&mut self, game_type: GameType, timestamp: u64) {
+        let day = day_index(timestamp);
+        let latest = self.days.iter().map(|plays| plays.day).max().unwrap_or(day);
+        let oldest = latest.max(day).saturating_sub(BALANCE_WINDOW_DAYS - 1);
+        self.days.retain(|plays| plays.day >= oldest);
+        if day < oldest {
+            // Synced late, after its day left the window
+            return;
+        }
+
+        let index = game_index(game_type);
+        match self.days.iter_mut().find(|plays| plays.day == day) {
+            Some(plays) => plays.counts[index] = plays.counts[index].saturating_add(1),
+            None => {
+                let mut counts = [0; GameType::ALL.len()];
+                counts[index] = 1;
+                self.days.push(DailyPlays { day, counts });
+            }
+        }
+    }
+
+    /// Plays per game in the window ending on the day of `now`.
+    fn counts(&self, now: u64) -> [u64; GameType::ALL.len()] {
+        let today = day_index(now);
+        let oldest = today.saturating_sub(BALANCE_WINDOW_DAYS - 1);
+        let mut counts = [0u64; GameType::ALL.len()];
+        for plays in self
+            .days
+            .iter()
+            .filter(|plays| (oldest..=today).contains(&plays.day))
+        {
+            for (total, count) in counts.iter_mut().zip(plays.counts) {
+                *total = total.saturating_add(count);
+            }
+        }
+        counts
+    }
+
+    /// The XP multiplier for `game_type` at `now`, in percent.
+    pub fn multiplier_percent(&self, game_type: GameType, now: u64, config: &BalanceConfig) -> u32 {
+        let counts = self.counts(now);
+        multiplier_percent(counts[game_index(game_type)], counts.iter().sum(), config)
+    }
+
+    /// Every game's share and multiplier at `now`.
+    pub fn multipliers(&self, now: u64, config: &BalanceConfig) -> Vec<GameMultiplier> {
+        let counts = self.counts(now);
+        let total_plays = counts.iter().sum();
+        GameType::ALL
+            .into_iter()
+            .zip(counts)
+            .map(|(game_type, plays)| GameMultiplier {
+                game_type,
+                plays,
+                total_plays,
+                multiplier_percent: multiplier_percent(plays, total_plays, config),
+            })
+            .collect()
+    }
+}
+
+/// Scale `xp` by a multiplier in percent.
+pub fn apply_multiplier(xp: u64, multiplier_percent: u32) -> u64 {
+    xp.saturating_mul(u64::from(multiplier_percent)) / u64::from(NEUTRAL_MULTIPLIER_PERCENT)
+}
+
+/// The multiplier for a game with `plays` of `total_plays`: the maximum
+/// bonus scaled by how far the game falls short of an equal share.
+fn multiplier_percent(plays: u64, total_plays: u64, config: &BalanceConfig) -> u32 {
+    if total_plays < MIN_WINDOW_PLAYS {
+        return NEUTRAL_MULTIPLIER_PERCENT;
+    }
+    // Shortfall from the fair share `total / games`, as a fraction of it
+    let games = GameType::ALL.len() as u64;
+    let shortfall = total_plays.saturating_sub(plays.saturating_mul(games));
+    let bonus = u64::from(config.max_bonus_percent).saturating_mul(shortfall) / total_plays;
+    NEUTRAL_MULTIPLIER_PERCENT.saturating_add(bonus as u32)
+}
+
+fn game_index(game_type: GameType) -> usize {
+    GameType::ALL
+        .iter()
+        .position(|candidate| *candidate == game_type)
+        .expect("Every game is listed in GameType::ALL")
+}
@@ -0,0 +1,178 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Boss battles: cooperative events where every run played during the
+//! window deals damage to a shared boss on the hub.
+//!
+//! A run deals damage equal to the XP it earned, so every game hits
+//! equally hard. If the boss falls before the window closes, its reward
+//! pool is split between everyone who hit it, in proportion to the damage
+//! they dealt; the blow that finishes it counts only the health it took.
+//! A boss still standing at the end escapes and nobody is rewarded.
+
+use async_graphql::{InputObject, SimpleObject};
+use linera_sdk::linera_base_types::AccountOwner;
+use serde::{Deserialize, Serialize};
+
+use crate::{ArcadeError, GameScore, GameType};
+
+/// Boss battles that can be scheduled or running at once.
+pub const MAX_OPEN_BOSS_BATTLES: u64 = 5;
+
+/// Longest a boss battle name can be, in characters.
+pub const MAX_BOSS_NAME_LENGTH: usize = 64;
+
+/// Rewards kept in each player's recent reward list.
+pub const MAX_RECENT_BOSS_REWARDS: usize = 20;
+
+/// A boss battle as the admin describes it.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject, InputObject)]
+#[graphql(input_name = "BossBattleSpecInput")]
+pub struct BossBattleSpec {
+    pub name: String,
+    pub starts_at: u64,
+    pub ends_at: u64,
+    /// Only runs of this game deal damage, if set.
+    pub game_type: Option<GameType>,
+    /// Damage needed to defeat the boss.
+    pub health: u64,
+    /// XP shared between the contributors if the boss is defeated.
+    pub reward_pool_xp: u64,
+}
+
+impl BossBattleSpec {
+    /// Check the battle is well formed and hasn't already ended at `now`.
+    pub fn validate(&self, now: u64) -> Result<(), ArcadeError> {
+        let name_ok =
+            !self.name.trim().is_empty() && self.name.chars().count() <= MAX_BOSS_NAME_LENGTH;
+        let timing_ok = self.starts_at < self.ends_at && self.ends_at > now;
+        if !name_ok || !timing_ok || self.health == 0 || self.reward_pool_xp == 0 {
+            return Err(ArcadeError::InvalidBossBattle);
+        }
+        Ok(())
+    }
+}
+
+/// A scheduled boss battle.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct BossBattle {
+    pub id: u64,
+    pub spec: BossBattleSpec,
+    pub damage: u64,
+    pub contributors: u32,
+    pub defeated_at: Option<u64>,
+    /// When the battle was settled, by defeat or escape.
+    pub settled_at: Option<u64>,
+}
+
+impl BossBattle {
+    /// A battle yet to start.
+    pub fn new(id: u64, spec: BossBattleSpec) -> Self {
+        Self {
+            id,
+            spec,
+            damage: 0,
+            contributors: 0,
+            defeated_at: None,
+            settled_at: None,
+        }
+    }
+
+    /// Deal `score`'s damage, returning how much it dealt, if it was played
+    /// in the window against a boss still standing.
+    pub fn hit(&mut self, score: &GameScore) -> Option<u64> {
+        if self.settled_at.is_some() || !self.counts(score) || score.xp_earned == 0 {
+            return None;
+        }
+        let dealt = score.xp_earned.min(self.spec.health - self.damage);
+        self.damage += dealt;
+        if self.is_defeated() {
+            self.defeated_at = Some(score.timestamp);
+        }
+        Some(dealt)
+    }
+
+    /// Undo up to `dealt` damage of a removed `score`, returning how much,
+    /// if it was played in the window of a battle not yet settled.
+    pub fn heal(&mut self, score: &GameScore, dealt: u64) -> Option<u64> {
+        if self.settled_at.is_some() || !self.counts(score) {
+            return None;
+        }
+        let healed = score.xp_earned.min(dealt).min(self.damage);
+        self.damage -= healed;
+        Some(healed)
+    }
+
+    /// Whether `score` was played in the window, in the battle's game.
+    pub fn counts(&self, score: &GameScore) -> bool {
+        let in_window = (self.spec.starts_at..self.spec.ends_at).contains(&score.timestamp);
+        let game_ok = self
+            .spec
+            .game_type
+            .is_none_or(|game_type| game_type == score.game_type);
+        in_window && game_ok
+    }
+
+    /// Whether the boss has taken its full health in damage.
+    pub fn is_defeated(&self) -> bool {
+        self.damage >= self.spec.health
+    }
+
+    /// Whether the boss survived its window, as of `now`.
+    pub fn has_escaped(&self, now: u64) -> bool {
+        !self.is_defeated() && now >= self.spec.ends_at
+    }
+}
+
+/// XP a player was awarded for helping defeat a boss.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct BossReward {
+    pub battle_id: u64,
+    pub battle_name: String,
+    pub damage: u64,
+    pub reward_xp: u64,
+    pub awarded_at: u64,
+}
+
+/// A player's boss battle rewards.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, SimpleObject)]
+pub struct BossRewards {
+    pub total_xp: u64,
+    /// The latest rewards, newest first, up to
+    /// [`MAX_RECENT_BOSS_REWARDS`].
+    pub recent: Vec<BossReward>,
+}
+
+impl BossRewards {
+    /// Add a reward.
+    pub fn add(&mut self, reward: BossReward) {
+        self.total_xp = self.total_xp.saturating_add(reward.reward_xp);
+        self.recent.insert(0, reward);
+        self.recent.truncate(MAX_RECENT_BOSS_REWARDS);
+    }
+}
+
+/// Split a defeated boss's reward pool between `contributions`, given as
+/// `(player, damage)` pairs, in proportion to their damage. Shares are
+/// rounded down.
+pub fn reward_shares(
+    battle: &BossBattle,
+    contributions: Vec<(AccountOwner, u64)>,
+    now: u64,
+) -> Vec<(AccountOwner, BossReward)> {
+    let total = u128::from(battle.damage.max(1));
+    contributions
+        .into_iter()
+        .map(|(player, damage)| {
+            let share = u128::from(battle.spec.reward_pool_xp) * u128::from(damage) / total;
+            let reward = BossReward {
+                battle_id: battle.id,
+                battle_name: battle.spec.name.clone(),
+                damage,
+                reward_xp: share as u64,
+                awarded_at: now,
+            };
+            (player, reward)
+        })
+        .collect()
+}
@@ -0,0 +1,103 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Forwarding of arcade events to other applications.
+//!
+//! Another application registers with a cross-application call to
+//! [`Operation::RegisterEventBridge`](crate::Operation::RegisterEventBridge),
+//! naming the chain it wants events delivered to. Every event the arcade
+//! publishes is then sent as a message to that chain, where the subscriber
+//! collects it with [`Operation::DrainBridgedEvents`](crate::Operation::DrainBridgedEvents).
+
+use std::collections::BTreeSet;
+
+use async_graphql::SimpleObject;
+use linera_sdk::linera_base_types::{ApplicationId, ChainId};
+use serde::{Deserialize, Serialize};
+
+use crate::{ArcadeError, ArcadeEvent};
+
+/// Maximum number of delivery chains one application can register.
+pub const MAX_BRIDGE_CHAINS: usize = 8;
+
+/// Maximum number of undrained events held per subscriber; older events are
+/// dropped first.
+pub const MAX_BRIDGE_INBOX: usize = 100;
+
+/// The chains an application has asked to receive events on.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BridgeSubscription {
+    chains: BTreeSet<ChainId>,
+}
+
+impl BridgeSubscription {
+    /// Add a delivery chain.
+    pub fn add(&mut self, chain_id: ChainId) -> Result<(), ArcadeError> {
+        if !self.chains.contains(&chain_id) && self.chains.len() >= MAX_BRIDGE_CHAINS {
+            return Err(ArcadeError::TooManyBridgeChains);
+        }
+        self.chains.insert(chain_id);
+        Ok(())
+    }
+
+    /// Remove a delivery chain.
+    pub fn remove(&mut self, chain_id: &ChainId) {
+        self.chains.remove(chain_id);
+    }
+
+    /// Whether no delivery chains are left.
+    pub fn is_empty(&self) -> bool {
+        self.chains.is_empty()
+    }
+
+    /// The delivery chains.
+    pub fn chains(&self) -> impl Iterator<Item = ChainId> + '_ {
+        self.chains.iter().copied()
+    }
+}
+
+/// An event delivered to a subscriber's chain.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BridgedEvent {
+    /// The chain that published the event.
+    pub origin_chain: ChainId,
+    pub event: ArcadeEvent,
+}
+
+/// Undrained events for one subscriber on its delivery chain.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BridgeInbox {
+    events: Vec<BridgedEvent>,
+}
+
+impl BridgeInbox {
+    /// Queue an event, dropping the oldest if the inbox is full.
+    pub fn push(&mut self, event: BridgedEvent) {
+        if self.events.len() >= MAX_BRIDGE_INBOX {
+            self.events.remove(0);
+        }
+        self.events.push(event);
+    }
+
+    /// Take every queued event, oldest first.
+    pub fn drain(self) -> Vec<BridgedEvent> {
+        self.events
+    }
+
+    /// Number of queued events.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Whether no events are queued.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+/// A registered subscriber, as listed by the service.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct BridgeSubscriber {
+    pub application_id: ApplicationId,
+    pub chains: Vec<ChainId>,
+}
@@ -0,0 +1,329 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Daily challenges and each player's record of completing them.
+//!
+//! Every day features one game and a seed for its layout, the same for
+//! everyone and derivable on any chain. A player completes the day's
+//! challenge by submitting one run of it with
+//! [`Operation::SubmitDailyChallenge`](crate::Operation::SubmitDailyChallenge)
+//! on that day in their timezone. Completions are kept for
+//! [`CHALLENGE_HISTORY_DAYS`] days, enough for a year-long calendar, and each
+//! day's leaderboard for [`CHALLENGE_BOARD_DAYS`] days.
+//!
+//! Every calendar month also runs a cumulative challenge, such as earning
+//! 50,000 XP, that every run counts towards. Reaching each of its reward
+//! tiers pays bonus XP once.
+
+use std::collections::BTreeSet;
+
+use async_graphql::{Enum, SimpleObject};
+use linera_sdk::linera_base_types::{AccountOwner, BcsSignable, ChainId, CryptoHash};
+use serde::{Deserialize, Serialize};
+
+use crate::GameType;
+
+/// Days of completion history kept per player.
+pub const CHALLENGE_HISTORY_DAYS: u64 = 365;
+
+/// Days a challenge leaderboard is kept after its challenge.
+pub const CHALLENGE_BOARD_DAYS: u64 = 7;
+
+/// Entries kept on each day's challenge leaderboard.
+pub const MAX_CHALLENGE_BOARD_ENTRIES: usize = 100;
+
+/// The challenge for one day.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, SimpleObject)]
+pub struct DailyChallenge {
+    /// Days since the Unix epoch.
+    pub day: u64,
+    pub game_type: GameType,
+    /// Seeds the game's layout, so every player gets the same run.
+    pub seed: u64,
+}
+
+impl DailyChallenge {
+    /// The challenge for `day`, rotating through the games, with a seed
+    /// derived from the day and the arcade's hub chain.
+    pub fn for_day(day: u64, hub_chain_id: Option<ChainId>) -> Self {
+        let games = GameType::ALL;
+        let hash = CryptoHash::new(&ChallengeSeedPreimage { day, hub_chain_id });
+        Self {
+            day,
+            game_type: games[(day % games.len() as u64) as usize],
+            seed: <[u64; 4]>::from(hash)[0],
+        }
+    }
+}
+
+/// The value a day's challenge seed is hashed from.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct ChallengeSeedPreimage {
+    day: u64,
+    hub_chain_id: Option<ChainId>,
+}
+
+impl BcsSignable<'_> for ChallengeSeedPreimage {}
+
+/// A player's run of a daily challenge.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct ChallengeEntry {
+    pub player: AccountOwner,
+    pub day: u64,
+    pub score: u64,
+    pub submitted_at: u64,
+}
+
+/// The best runs of one day's challenge, highest score first.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ChallengeBoard {
+    entries: Vec<ChallengeEntry>,
+}
+
+impl ChallengeBoard {
+    /// Place a run on the board. Ties go to the earlier run, and runs that
+    /// fall below the kept entries are dropped.
+    pub fn insert(&mut self, entry: ChallengeEntry) {
+        let position = self.entries.partition_point(|kept| {
+            kept.score > entry.score
+                || (kept.score == entry.score && kept.submitted_at <= entry.submitted_at)
+        });
+        self.entries.insert(position, entry);
+        self.entries.truncate(MAX_CHALLENGE_BOARD_ENTRIES);
+    }
+
+    /// The runs on the board, highest score first.
+    pub fn entries(&self) -> &[ChallengeEntry] {
+        &self.entries
+    }
+}
+
+/// What a monthly challenge counts.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Enum)]
+pub enum MonthlyObjective {
+    /// XP earned from runs, not counting the challenge's own rewards.
+    EarnXp,
+    /// Games played.
+    PlayGames,
+}
+
+impl MonthlyObjective {
+    /// The progress a run earning `xp_earned` makes on the objective.
+    pub fn progress_for(self, xp_earned: u64) -> u64 {
+        match self {
+            MonthlyObjective::EarnXp => xp_earned,
+            MonthlyObjective::PlayGames => 1,
+        }
+    }
+}
+
+/// A monthly challenge's reward tier.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize, Enum)]
+pub enum ChallengeTier {
+    Bronze,
+    Silver,
+    Gold,
+}
+
+/// A reward tier and what it takes to reach it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, SimpleObject)]
+pub struct RewardTier {
+    pub tier: ChallengeTier,
+    /// Progress needed to reach the tier.
+    pub threshold: u64,
+    /// Bonus XP paid on reaching the tier.
+    pub reward_xp: u64,
+}
+
+/// The challenge for one calendar month.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, SimpleObject)]
+pub struct MonthlyChallenge {
+    /// The calendar month (`year * 12 + month - 1`).
+    pub month: u64,
+    pub objective: MonthlyObjective,
+    /// Progress needed to complete the challenge, and reach its top tier.
+    pub target: u64,
+    /// The reward tiers, lowest first.
+    pub tiers: Vec<RewardTier>,
+}
+
+impl MonthlyChallenge {
+    /// The challenge for `month`, alternating between the objectives.
+    pub fn for_month(month: u64) -> Self {
+        let (objective, target) = if month.is_multiple_of(2) {
+            (MonthlyObjective::EarnXp, 50_000)
+        } else {
+            (MonthlyObjective::PlayGames, 300)
+        };
+        let tiers = vec![
+            RewardTier {
+                tier: ChallengeTier::Bronze,
+                threshold: target / 4,
+                reward_xp: 250,
+            },
+            RewardTier {
+                tier: ChallengeTier::Silver,
+                threshold: target / 2,
+                reward_xp: 500,
+            },
+            RewardTier {
+                tier: ChallengeTier::Gold,
+                threshold: target,
+                reward_xp: 1_500,
+            },
+        ];
+        Self {
+            month,
+            objective,
+            target,
+            tiers,
+        }
+    }
+}
+
+/// A player's progress on their current month's challenge.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct MonthlyProgress {
+    pub player: AccountOwner,
+    /// The calendar month the progress counts towards, in the player's
+    /// timezone.
+    pub month: u64,
+    pub progress: u64,
+    /// The highest tier reached, if any.
+    pub tier: Option<ChallengeTier>,
+    pub updated_at: u64,
+}
+
+impl MonthlyProgress {
+    /// No progress yet on `month`'s challenge.
+    pub fn new(player: AccountOwner, month: u64) -> Self {
+        Self {
+            player,
+            month,
+            progress: 0,
+            tier: None,
+            updated_at: 0,
+        }
+    }
+
+    /// Count a run earning `xp_earned` towards `challenge` at `now`,
+    /// starting over if the challenge is for a new month. Returns the tiers
+    /// newly reached.
+    pub fn record(
+        &mut self,
+        challenge: &MonthlyChallenge,
+        xp_earned: u64,
+        now: u64,
+    ) -> Vec<RewardTier> {
+        if self.month != challenge.month {
+            *self = MonthlyProgress::new(self.player, challenge.month);
+        }
+        self.progress = self
+            .progress
+            .saturating_add(challenge.objective.progress_for(xp_earned));
+        self.updated_at = now;
+        let reached: Vec<RewardTier> = challenge
+            .tiers
+            .iter()
+            .filter(|tier| self.progress >= tier.threshold && self.tier < Some(tier.tier))
+            .copied()
+            .collect();
+        if let Some(top) = reached.last() {
+            self.tier = Some(top.tier);
+        }
+        reached
+    }
+
+    /// Take back a removed run that earned `xp_earned`, tier rewards
+    /// included, from `challenge`. Tiers no longer reached are opened
+    /// again, since their rewards went with the run's XP.
+    pub fn forget(&mut self, challenge: &MonthlyChallenge, xp_earned: u64) {
+        if self.month != challenge.month {
+            return;
+        }
+        self.progress = self
+            .progress
+            .saturating_sub(challenge.objective.progress_for(xp_earned));
+        self.tier = challenge
+            .tiers
+            .iter()
+            .filter(|tier| self.progress >= tier.threshold)
+            .map(|tier| tier.tier)
+            .next_back();
+    }
+}
+
+/// The participants in one month's challenge with the most progress, most
+/// first.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MonthlyBoard {
+    entries: Vec<MonthlyProgress>,
+}
+
+impl MonthlyBoard {
+    /// Replace the participant's entry with `progress`. Ties go to whoever
+    /// got there first, and entries that fall below the kept ones are
+    /// dropped.
+    pub fn update(&mut self, progress: MonthlyProgress) {
+        self.entries.retain(|kept| kept.player != progress.player);
+        let position = self.entries.partition_point(|kept| {
+            kept.progress > progress.progress
+                || (kept.progress == progress.progress && kept.updated_at <= progress.updated_at)
+        });
+        self.entries.insert(position, progress);
+        self.entries.truncate(MAX_CHALLENGE_BOARD_ENTRIES);
+    }
+
+    /// The participants on the board, most progress first.
+    pub fn entries(&self) -> &[MonthlyProgress] {
+        &self.entries
+    }
+}
+
+/// The days on which a player completed the daily challenge.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ChallengeCompletions {
+    days: BTreeSet<u64>,
+}
+
+impl ChallengeCompletions {
+    /// Mark `day`'s challenge as completed, forgetting days that fall out of
+    /// the history. Returns whether it wasn't completed yet.
+    pub fn complete(&mut self, day: u64) -> bool {
+        let newly = self.days.insert(day);
+        let latest = self.days.last().copied().unwrap_or(day);
+        let oldest = latest.saturating_sub(CHALLENGE_HISTORY_DAYS - 1);
+        self.days.retain(|kept| *kept >= oldest);
+        newly
+    }
+
+    /// Whether `day`'s challenge was completed.
+    pub fn is_completed(&self, day: u64) -> bool {
+        self.days.contains(&day)
+    }
+}
+
+/// One day of a player's challenge calendar.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct ChallengeDay {
+    pub challenge: DailyChallenge,
+    pub completed: bool,
+}
+
+/// The `days` days of challenges ending on `today`, oldest first, with
+/// whether `completions` shows each as completed.
+pub fn history(
+    completions: &ChallengeCompletions,
+    today: u64,
+    days: u64,
+    hub_chain_id: Option<ChainId>,
+) -> Vec<ChallengeDay> {
+    let days = days.clamp(1, CHALLENGE_HISTORY_DAYS);
+    let first = today.saturating_sub(days - 1);
+    (first..=today)
+        .map(|day| ChallengeDay {
+            challenge: DailyChallenge::for_day(day, hub_chain_id),
+            completed: completions.is_completed(day),
+        })
+        .collect()
+}
@@ -0,0 +1,56 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! The daily "full circuit": playing every game in the arcade on one day in
+//! the player's timezone, rewarded once per day with bonus XP.
+
+use async_graphql::SimpleObject;
+use serde::{Deserialize, Serialize};
+
+use crate::GameType;
+
+/// Bonus XP for completing the full circuit, added to the run that
+/// completes it.
+pub const FULL_CIRCUIT_BONUS_XP: u64 = 250;
+
+/// The games a player has completed on one day.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, SimpleObject)]
+pub struct DailyCircuit {
+    /// Days since the Unix epoch, in the player's timezone.
+    pub day: u64,
+    /// Distinct games completed, in the order first played.
+    pub games: Vec<GameType>,
+}
+
+impl DailyCircuit {
+    /// Count a game completed on `today`, returning whether it was the last
+    /// game needed to complete the day's circuit.
+    pub fn record(&mut self, game_type: GameType, today: u64) -> bool {
+        if self.day != today {
+            *self = DailyCircuit {
+                day: today,
+                games: Vec::new(),
+            };
+        }
+        if self.games.contains(&game_type) {
+            return false;
+        }
+        self.games.push(game_type);
+        self.is_complete()
+    }
+
+    /// Take back a removed game completed on `day`. A circuit it completed
+    /// can be completed again, its bonus having gone with the run's XP; one
+    /// completed by a later game keeps the bonus that game paid.
+    pub fn forget(&mut self, game_type: GameType, day: u64) {
+        if self.day != day || (self.is_complete() && self.games.last() != Some(&game_type)) {
+            return;
+        }
+        self.games.retain(|game| *game != game_type);
+    }
+
+    /// Whether every game has been completed.
+    pub fn is_complete(&self) -> bool {
+        self.games.len() == GameType::ALL.len()
+    }
+}
@@ -0,0 +1,200 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Community games: mini-games built by third-party developers and listed
+//! on the hub once the admin approves them.
+//!
+//! A developer submits a game definition, which waits in the review queue
+//! until the admin approves or rejects it. Runs of an approved game can be
+//! submitted from any chain; they are sent to the hub, which checks them
+//! against the definition. Community scores never touch the arcade's own
+//! leaderboards or levels: each game ranks its players on a leaderboard of
+//! its own, and the XP a run earns is capped and weighted down to
+//! [`COMMUNITY_XP_WEIGHT_PERCENT`] before it is credited to the player's
+//! separate community XP.
+
+use async_graphql::{Enum, InputObject, SimpleObject};
+use linera_sdk::linera_base_types::AccountOwner;
+use serde::{Deserialize, Serialize};
+
+use crate::ArcadeError;
+
+/// Community games that can be waiting for review at once.
+pub const MAX_PENDING_COMMUNITY_GAMES: u64 = 20;
+
+/// Most XP a run of a community game can earn before weighting.
+pub const MAX_COMMUNITY_XP_PER_RUN: u64 = 200;
+
+/// Share of a community run's XP that is credited, in percent.
+pub const COMMUNITY_XP_WEIGHT_PERCENT: u64 = 25;
+
+/// Players ranked on each community game's leaderboard.
+pub const COMMUNITY_LEADERBOARD_SIZE: usize = 100;
+
+/// Longest a community game name can be, in characters.
+pub const MAX_COMMUNITY_GAME_NAME_LENGTH: usize = 32;
+
+/// Longest a community game description can be, in characters.
+pub const MAX_COMMUNITY_GAME_DESCRIPTION_LENGTH: usize = 280;
+
+/// A community game as its developer describes it.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject, InputObject)]
+#[graphql(input_name = "CommunityGameSpecInput")]
+pub struct CommunityGameSpec {
+    pub name: String,
+    pub description: String,
+    /// Highest score a run can reach.
+    pub max_score: u64,
+    /// XP earned per point scored, before the cap and weighting.
+    pub xp_per_point: u64,
+    /// Most XP a run can earn before weighting, at most
+    /// [`MAX_COMMUNITY_XP_PER_RUN`].
+    pub max_xp_per_run: u64,
+}
+
+impl CommunityGameSpec {
+    /// Check the definition is well formed.
+    pub fn validate(&self) -> Result<(), ArcadeError> {
+        let text_ok = |text: &str, max_length: usize| {
+            !text.trim().is_empty()
+                && text.chars().count() <= max_length
+                && !text.chars().any(char::is_control)
+        };
+        if !text_ok(&self.name, MAX_COMMUNITY_GAME_NAME_LENGTH)
+            || !text_ok(&self.description, MAX_COMMUNITY_GAME_DESCRIPTION_LENGTH)
+            || self.max_score == 0
+            || self.xp_per_point == 0
+            || !(1..=MAX_COMMUNITY_XP_PER_RUN).contains(&self.max_xp_per_run)
+        {
+            return Err(ArcadeError::InvalidCommunityGame);
+        }
+        Ok(())
+    }
+}
+
+/// Where a community game is in the review flow.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Enum)]
+pub enum CommunityGameStatus {
+    /// Waiting for the admin's review.
+    Pending,
+    /// Listed and accepting runs.
+    Approved,
+    Rejected,
+}
+
+/// A submitted community game.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct CommunityGame {
+    pub id: u64,
+    pub developer: AccountOwner,
+    pub spec: CommunityGameSpec,
+    pub status: CommunityGameStatus,
+    pub submitted_at: u64,
+    pub reviewed_at: Option<u64>,
+    /// Runs accepted so far.
+    pub runs: u64,
+}
+
+impl CommunityGame {
+    /// A game waiting for review.
+    pub fn new(id: u64, developer: AccountOwner, spec: CommunityGameSpec, now: u64) -> Self {
+        Self {
+            id,
+            developer,
+            spec,
+            status: CommunityGameStatus::Pending,
+            submitted_at: now,
+            reviewed_at: None,
+            runs: 0,
+        }
+    }
+
+    /// Record the admin's review at `now`.
+    pub fn review(&mut self, approved: bool, now: u64) -> Result<(), ArcadeError> {
+        if self.status != CommunityGameStatus::Pending {
+            return Err(ArcadeError::CommunityGameAlreadyReviewed);
+        }
+        self.status = if approved {
+            CommunityGameStatus::Approved
+        } else {
+            CommunityGameStatus::Rejected
+        };
+        self.reviewed_at = Some(now);
+        Ok(())
+    }
+
+    /// Check a run scoring `score` can be accepted, returning the XP it
+    /// earns after the cap and weighting.
+    pub fn score_xp(&self, score: u64) -> Result<u64, ArcadeError> {
+        if self.status != CommunityGameStatus::Approved {
+            return Err(ArcadeError::CommunityGameNotApproved);
+        }
+        if score > self.spec.max_score {
+            return Err(ArcadeError::InvalidCommunityScore);
+        }
+        let xp = score
+            .saturating_mul(self.spec.xp_per_point)
+            .min(self.spec.max_xp_per_run);
+        Ok(xp * COMMUNITY_XP_WEIGHT_PERCENT / 100)
+    }
+}
+
+/// A run of a community game, as sent to the hub.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CommunityScore {
+    pub game_id: u64,
+    pub player: AccountOwner,
+    pub score: u64,
+    pub timestamp: u64,
+}
+
+/// A player's best run of a community game.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct CommunityLeaderboardEntry {
+    pub player: AccountOwner,
+    pub score: u64,
+    pub achieved_at: u64,
+}
+
+/// A community game's best runs, one per player, highest first.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CommunityLeaderboard {
+    entries: Vec<CommunityLeaderboardEntry>,
+}
+
+impl CommunityLeaderboard {
+    /// Record `score` if it is the player's best and makes the board.
+    pub fn record(&mut self, score: &CommunityScore) {
+        if let Some(index) = self
+            .entries
+            .iter()
+            .position(|entry| entry.player == score.player)
+        {
+            if self.entries[index].score >= score.score {
+                return;
+            }
+            self.entries.remove(index);
+        }
+        // Equal scores rank in the order they were reached
+        let index = self
+            .entries
+            .partition_point(|entry| entry.score >= score.score);
+        if index >= COMMUNITY_LEADERBOARD_SIZE {
+            return;
+        }
+        self.entries.insert(
+            index,
+            CommunityLeaderboardEntry {
+                player: score.player,
+                score: score.score,
+                achieved_at: score.timestamp,
+            },
+        );
+        self.entries.truncate(COMMUNITY_LEADERBOARD_SIZE);
+    }
+
+    /// The best runs, highest first.
+    pub fn entries(&self) -> &[CommunityLeaderboardEntry] {
+        &self.entries
+    }
+}
@@ -5,22 +5,94 @@
 
 mod state;
 
+use std::collections::{BTreeMap, BTreeSet};
+
 use arcade_hub::{
-    validate_username, ArcadeError, ArcadeHubAbi, ArcadeResponse, GameScore, InstantiationArgument,
-    LeaderboardEntry, Message, Operation, Player,
+    achievements::Achievement,
+    analytics::ScoreHistogram,
+    balance::BalanceConfig,
+    bosses::{self, BossBattle, BossBattleSpec},
+    bridge::BridgedEvent,
+    challenges::{
+        self, ChallengeBoard, ChallengeEntry, DailyChallenge, MonthlyChallenge, MonthlyProgress,
+    },
+    circuit,
+    community_games::{self, CommunityGame, CommunityGameSpec, CommunityScore},
+    cosmetics::OwnedCosmetic,
+    crossovers::CrossoverEventSpec,
+    day_index,
+    dispute::{self, Appeal, BondSettlement},
+    envelope::{OpV2, MAX_IDEMPOTENCY_KEY_LENGTH},
+    fixtures::Fixture,
+    goals::{Goal, GoalSpec},
+    guild_prizes::{self, GuildPayoutRule, GuildPrize, PrizeClaim},
+    guilds::{
+        self, Guild, GuildAction, GuildAnnouncement, GuildAuditEntry, GuildContribution,
+        GuildPermission, GuildRole,
+    },
+    humanity::{HumanityChallenge, HumanityCheckConfig},
+    invariants::{InvariantCheck, InvariantSection, INVARIANT_CHUNK_SIZE},
+    invites::{self, InviteCode},
+    journal::{
+        JournalCheckpoint, JournalEntry, JournalKind, ReplayProgress, ReplayTarget,
+        JOURNAL_CHECKPOINT_INTERVAL, REPLAY_CHUNK_SIZE,
+    },
+    kiosk::{self, GatewaySubmission, KioskConfig},
+    limits::{self, PlayLimits},
+    locale::LocaleSettings,
+    mentorship::{self, MentorRecord, Mentorship, MentorshipOffers},
+    migration::{ProgressExport, ProgressImport, ProgressSnapshot},
+    milestones::{Milestone, MilestoneKind},
+    name_auctions::{self, SealedBid, UsernameAuction, UsernameAuctionSpec},
+    plausibility::PlausibilityConfig,
+    polls::GuildPoll,
+    raffles::{self, Raffle, RaffleDraw, RaffleSpec},
+    reactions::{self, Emote, ReactionTarget},
+    runtime::{self, RuntimeFacade},
+    score_signing::ScoreSignature,
+    scoring::{ScoringCanary, ScoringConfig, XpFactors},
+    seasons::{self, Season, SeasonArchive, SeasonStanding, SeasonTheme, SeasonTier},
+    sessions::{self, GameSession},
+    smurfing::{FlagReason, ModerationFlag, SmurfDetectionConfig},
+    snapshots::{self, LeaderboardSnapshot, SnapshotEntry},
+    social::{self, Blocklist, FriendList, KudosRecord, Presence},
+    streaks,
+    submission_windows::ClosedWindow,
+    usage::{self, UsageQuota},
+    usernames::{
+        self, RegistrationVoucher, ReleaseReason, UsernameClaim, UsernameRecyclingConfig,
+        UsernameRelease, UsernameReservation,
+    },
+    validate_display_name, validate_username, ArcadeError, ArcadeEvent, ArcadeHubAbi,
+    ArcadeParameters, ArcadeResponse, GameScore, GameType, InstantiationArgument, LeaderboardEntry,
+    MaintenanceWindow, Message, Operation, Player, PrivacySettings, ScoreResult, ScoreSubmission,
+    ScoringCategory, ARCADE_EVENT_STREAM, MAX_MAINTENANCE_MICROS, MAX_SCORE_BATCH_SIZE,
+    MAX_UTC_OFFSET_MINUTES, MIN_UTC_OFFSET_MINUTES, RECENT_SCORES_CAPACITY, SYNC_EVENT_STREAM,
+    TIMEZONE_COOLDOWN_MICROS,
 };
 use linera_sdk::{
-    linera_base_types::{AccountOwner, WithContractAbi},
-    views::{RootView, View},
+    bcs,
+    linera_base_types::{
+        Account, AccountOwner, Amount, ApplicationId, ChainId, CryptoHash, GenericApplicationId,
+        StreamName, StreamUpdate, WithContractAbi,
+    },
+    views::{MapView, RootView, View},
     Contract, ContractRuntime,
 };
 
-use self::state::ArcadeHubState;
+use serde::{de::DeserializeOwned, Serialize};
+
+use self::state::{ArcadeHubState, PendingSubmissions};
 
 /// The Arcade Hub contract.
 pub struct ArcadeHubContract {
     state: ArcadeHubState,
     runtime: ContractRuntime<Self>,
+    /// Whether a sync from another chain is being applied.
+    applying_sync: bool,
+    /// Progress syncs held back while a batch of runs is applied, to be
+    /// sent with the batch.
+    batched_progress: Option<Vec<Message>>,
 }
 
 linera_sdk::contract!(ArcadeHubContract);
@@ -32,14 +104,19 @@ impl WithContractAbi for ArcadeHubContract {
 impl Contract for ArcadeHubContract {
     type Message = Message;
     type InstantiationArgument = InstantiationArgument;
-    type Parameters = ();
-    type EventValue = ();
+    type Parameters = Option<ArcadeParameters>;
+    type EventValue = ArcadeEvent;
 
     async fn load(runtime: ContractRuntime<Self>) -> Self {
         let state = ArcadeHubState::load(runtime.root_view_storage_context())
             .await
             .expect("Failed to load state");
-        ArcadeHubContract { state, runtime }
+        ArcadeHubContract {
+            state,
+            runtime,
+            applying_sync: false,
+            batched_progress: None,
+        }
     }
 
     async fn instantiate(&mut self, argument: Self::InstantiationArgument) {
@@ -47,34 +124,476 @@ impl Contract for ArcadeHubContract {
         self.state
             .hub_chain_id
             .set(Some(argument.hub_chain_id));
+        self.state.admin.set(argument.admin);
+        self.state.direct_syncs.set(argument.direct_syncs);
+        // Kiosk rules are set on the hub and published on its arcade stream,
+        // and the hub reads this chain's syncs from its sync stream
+        if argument.hub_chain_id != self.runtime.chain() && !self.is_sandbox() {
+            let application_id = self.runtime.application_id().forget_abi();
+            self.runtime.subscribe_to_events(
+                argument.hub_chain_id,
+                application_id,
+                ARCADE_EVENT_STREAM.into(),
+            );
+            self.send_request(argument.hub_chain_id, Message::AnnounceSyncSource);
+        }
+        self.state.voucher_signer.set(argument.voucher_signer);
+        self.state.score_signer.set(argument.score_signer);
+        self.state
+            .plausibility
+            .set(PlausibilityConfig::new(argument.score_bounds));
+        self.state
+            .rookie_criteria
+            .set(argument.rookie_criteria.unwrap_or_default());
+        self.state
+            .appeal_bond
+            .set(argument.appeal_bond.unwrap_or(dispute::DEFAULT_APPEAL_BOND));
         self.state.score_counter.set(0);
+        self.state.total_players.set(0);
         self.state.total_games_played.set(0);
         self.state.total_xp_earned.set(0);
+
+        if let Some(config) = argument.demo_fixture {
+            let now = self.runtime.now_micros();
+            let chain_id = self.runtime.chain();
+            self.apply_fixture(Fixture::generate(&config, chain_id, now))
+                .await;
+        }
     }
 
     async fn execute_operation(&mut self, operation: Self::Operation) -> Self::Response {
+        let encoded = bcs::to_bytes(&operation).expect("Failed to encode operation");
+        let OpV2 {
+            operation,
+            idempotency_key,
+            session_id,
+            voucher,
+            device_id,
+            signature,
+        } = operation.into_latest();
+        let name = operation.name();
+
         // Get authenticated signer
-        let owner = match self.runtime.authenticated_signer() {
+        let owner = match self.runtime.signer() {
             Some(signer) => AccountOwner::from(signer),
-            None => return ArcadeError::NotAuthenticated.into_response(),
+            None => {
+                let response = ArcadeError::NotAuthenticated.into_response();
+                self.log_rejection(name, None, &response);
+                return response;
+            }
+        };
+
+        // Only the admin's way out of maintenance mode and the replays it
+        // exists for get through it
+        let now = self.runtime.now_micros();
+        let in_maintenance = self
+            .state
+            .maintenance
+            .get()
+            .is_some_and(|window| window.is_active(now));
+        if in_maintenance
+            && !matches!(
+                operation,
+                Operation::SetMaintenanceMode { .. } | Operation::ReplayJournal { .. }
+            )
+        {
+            let response = ArcadeError::MaintenanceMode.into_response();
+            self.log_rejection(name, Some(owner), &response);
+            return response;
+        }
+
+        // A retried operation gets the response it got the first time
+        let idempotency_key = match idempotency_key {
+            Some(key) if key.is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_LENGTH => {
+                let response = ArcadeError::InvalidIdempotencyKey.into_response();
+                self.log_rejection(name, Some(owner), &response);
+                return response;
+            }
+            Some(key) => {
+                let key = (owner, key);
+                let earlier = self
+                    .state
+                    .idempotent_responses
+                    .get(&key)
+                    .await
+                    .expect("Failed to load idempotent response");
+                if let Some(response) = earlier {
+                    return response;
+                }
+                Some(key)
+            }
+            None => None,
         };
 
-        match operation {
+        let bytes = encoded.len() as u64;
+        let response = match operation {
             Operation::RegisterPlayer { username } => {
-                self.handle_register_player(owner, username).await
+                self.handle_register_player(owner, username, voucher).await
             }
             Operation::SubmitScore {
                 game_type,
                 score,
                 bonus_data,
             } => {
-                self.handle_submit_score(owner, game_type, score, bonus_data)
+                let submission = ScoreSubmission {
+                    game_type,
+                    score,
+                    bonus_data,
+                    device_id,
+                };
+                self.handle_submit_score(owner, submission, session_id, signature)
+                    .await
+            }
+            Operation::SubmitScores {
+                submissions,
+                signatures,
+                session_ids,
+            } => {
+                self.handle_submit_scores(owner, submissions, signatures, session_ids)
+                    .await
+            }
+            Operation::StartGame {
+                game_type,
+                device_id,
+            } => self.handle_start_game(owner, game_type, device_id).await,
+            Operation::EndGame {
+                session_id,
+                score,
+                bonus_data,
+                device_id,
+                signature,
+            } => {
+                self.handle_end_game(owner, session_id, score, bonus_data, device_id, signature)
+                    .await
+            }
+            Operation::SubmitDailyChallenge {
+                session_id,
+                submission,
+                signature,
+            } => {
+                self.handle_submit_daily_challenge(owner, session_id, submission, signature)
                     .await
             }
+            Operation::UpdateDisplayName { display_name } => {
+                self.handle_update_display_name(owner, display_name).await
+            }
             Operation::UpdateUsername { new_username } => {
                 self.handle_update_username(owner, new_username).await
             }
+            Operation::UpdatePrivacy { privacy } => {
+                self.handle_update_privacy(owner, privacy).await
+            }
+            Operation::ShadowBanPlayer { wallet_address } => {
+                self.handle_set_shadow_ban(owner, wallet_address, true)
+                    .await
+            }
+            Operation::ClearShadowBan { wallet_address } => {
+                self.handle_set_shadow_ban(owner, wallet_address, false)
+                    .await
+            }
+            Operation::BanPlayer { wallet_address } => {
+                self.handle_set_player_ban(owner, wallet_address, true)
+                    .await
+            }
+            Operation::UnbanPlayer { wallet_address } => {
+                self.handle_set_player_ban(owner, wallet_address, false)
+                    .await
+            }
+            Operation::FileAppeal { receipt, reason } => {
+                self.handle_file_appeal(owner, receipt, reason).await
+            }
+            Operation::RemoveScore { receipt } => self.handle_remove_score(owner, receipt).await,
+            Operation::ResolveAppeal { appeal_id, upheld } => {
+                self.handle_resolve_appeal(owner, appeal_id, upheld).await
+            }
+            Operation::ConfigureKiosk { config } => {
+                self.handle_configure_kiosk(owner, config).await
+            }
+            Operation::ConfigureBalance { config } => {
+                self.handle_configure_balance(owner, config).await
+            }
+            Operation::ConfigureScoring { config } => {
+                self.handle_configure_scoring(owner, config).await
+            }
+            Operation::StartScoringCanary { config } => {
+                self.handle_start_scoring_canary(owner, config).await
+            }
+            Operation::EndScoringCanary { promote } => {
+                self.handle_end_scoring_canary(owner, promote).await
+            }
+            Operation::SetSubmissionWindows {
+                game_type,
+                closed_windows,
+            } => {
+                self.handle_set_submission_windows(owner, game_type, closed_windows)
+                    .await
+            }
+            Operation::SetUsageQuota { quota } => self.handle_set_usage_quota(owner, quota).await,
+            Operation::ConfigureSmurfDetection { config } => {
+                self.handle_configure_smurf_detection(owner, config).await
+            }
+            Operation::ResolveModerationFlag { flag_id } => {
+                self.handle_resolve_moderation_flag(owner, flag_id).await
+            }
+            Operation::ConfigureHumanityChecks { config } => {
+                self.handle_configure_humanity_checks(owner, config).await
+            }
+            Operation::IssueHumanityChallenge { wallet_address } => {
+                self.handle_issue_humanity_challenge(owner, wallet_address)
+                    .await
+            }
+            Operation::CompleteHumanityChallenge {
+                verifier,
+                signature,
+            } => {
+                self.handle_complete_humanity_challenge(owner, verifier, signature)
+                    .await
+            }
+            Operation::ScheduleCrossover { event } => {
+                self.handle_schedule_crossover(owner, event).await
+            }
+            Operation::CancelCrossover { event_id } => {
+                self.handle_cancel_crossover(owner, event_id).await
+            }
+            Operation::BanDevice { device_id } => {
+                self.handle_set_device_ban(owner, device_id, true).await
+            }
+            Operation::UnbanDevice { device_id } => {
+                self.handle_set_device_ban(owner, device_id, false).await
+            }
+            Operation::AuthorizeGateway { gateway } => {
+                self.handle_set_gateway(owner, gateway, true).await
+            }
+            Operation::RevokeGateway { gateway } => {
+                self.handle_set_gateway(owner, gateway, false).await
+            }
+            Operation::RelayScores { submissions } => {
+                self.handle_relay_scores(owner, submissions).await
+            }
+            Operation::SetVoucherSigner { signer } => {
+                self.handle_set_voucher_signer(owner, signer).await
+            }
+            Operation::SetMaintenanceMode { until } => {
+                self.handle_set_maintenance_mode(owner, until).await
+            }
+            Operation::ReserveUsername {
+                username,
+                reserved_for,
+            } => {
+                self.handle_reserve_username(owner, username, reserved_for)
+                    .await
+            }
+            Operation::AddBlockedUsername { username } => {
+                self.handle_set_username_blocked(owner, username, true)
+                    .await
+            }
+            Operation::RemoveBlockedUsername { username } => {
+                self.handle_set_username_blocked(owner, username, false)
+                    .await
+            }
+            Operation::StartUsernameAuction { auction } => {
+                self.handle_start_username_auction(owner, auction).await
+            }
+            Operation::BidOnUsername {
+                auction_id,
+                commitment,
+                deposit,
+            } => {
+                self.handle_bid_on_username(owner, auction_id, commitment, deposit)
+                    .await
+            }
+            Operation::RevealUsernameBid {
+                auction_id,
+                amount,
+                salt,
+            } => {
+                self.handle_reveal_username_bid(owner, auction_id, amount, salt)
+                    .await
+            }
+            Operation::SettleUsernameAuction { auction_id } => {
+                self.handle_settle_username_auction(auction_id).await
+            }
+            Operation::DeleteAccount => self.handle_delete_account(owner).await,
+            Operation::FlagInactiveAccount { player } => {
+                self.handle_flag_inactive_account(owner, player).await
+            }
+            Operation::ConfigureUsernameRecycling { config } => {
+                self.handle_configure_username_recycling(owner, config)
+                    .await
+            }
+            Operation::SetPlayLimits { limits } => self.handle_set_play_limits(owner, limits).await,
+            Operation::BlockPlayer { target } => self.handle_set_block(owner, target, true).await,
+            Operation::UnblockPlayer { target } => {
+                self.handle_set_block(owner, target, false).await
+            }
+            Operation::BuyStreakShield => self.handle_buy_streak_shield(owner).await,
+            Operation::AddGoal { goal } => self.handle_add_goal(owner, goal).await,
+            Operation::RemoveGoal { goal_id } => self.handle_remove_goal(owner, goal_id).await,
+            Operation::Follow { target } => self.handle_set_follow(owner, target, true).await,
+            Operation::Unfollow { target } => self.handle_set_follow(owner, target, false).await,
+            Operation::AddFriend { target } => self.handle_set_friend(owner, target, true).await,
+            Operation::RemoveFriend { target } => {
+                self.handle_set_friend(owner, target, false).await
+            }
+            Operation::GiveKudos { recipient } => self.handle_give_kudos(owner, recipient).await,
+            Operation::React { target, emote } => {
+                self.handle_react(owner, target, emote, true).await
+            }
+            Operation::RemoveReaction { target, emote } => {
+                self.handle_react(owner, target, emote, false).await
+            }
+            Operation::OfferMentorship { apprentice } => {
+                self.handle_offer_mentorship(owner, apprentice).await
+            }
+            Operation::AcceptMentorship { mentor } => {
+                self.handle_accept_mentorship(owner, mentor).await
+            }
+            Operation::EndMentorship { counterpart } => {
+                self.handle_end_mentorship(owner, counterpart).await
+            }
+            Operation::CreateGuild { name } => self.handle_create_guild(owner, name).await,
+            Operation::InviteToGuild { player } => self.handle_invite_to_guild(owner, player).await,
+            Operation::JoinGuild { guild_id } => self.handle_join_guild(owner, guild_id).await,
+            Operation::LeaveGuild => self.handle_leave_guild(owner).await,
+            Operation::KickFromGuild { member } => self.handle_kick_from_guild(owner, member).await,
+            Operation::RenameGuild { name } => self.handle_rename_guild(owner, name).await,
+            Operation::SetGuildRole { member, role } => {
+                self.handle_set_guild_role(owner, member, role).await
+            }
+            Operation::CreateInviteCode {
+                code_hash,
+                max_uses,
+                duration_hours,
+            } => {
+                self.handle_create_invite_code(owner, code_hash, max_uses, duration_hours)
+                    .await
+            }
+            Operation::RedeemInviteCode { code } => {
+                self.handle_redeem_invite_code(owner, code).await
+            }
+            Operation::RevokeInviteCode { code_hash } => {
+                self.handle_revoke_invite_code(owner, code_hash).await
+            }
+            Operation::CreateGuildPoll {
+                question,
+                options,
+                duration_hours,
+            } => {
+                self.handle_create_guild_poll(owner, question, options, duration_hours)
+                    .await
+            }
+            Operation::VoteInGuildPoll { poll_id, option } => {
+                self.handle_vote_in_guild_poll(owner, poll_id, option).await
+            }
+            Operation::SetGuildPayoutRule { rule } => {
+                self.handle_set_guild_payout_rule(owner, rule).await
+            }
+            Operation::AwardGuildPrize {
+                guild_id,
+                amount,
+                note,
+            } => {
+                self.handle_award_guild_prize(owner, guild_id, amount, note)
+                    .await
+            }
+            Operation::ClaimGuildPrize { prize_id } => {
+                self.handle_claim_guild_prize(owner, prize_id).await
+            }
+            Operation::ExportProgress => self.handle_export_progress(owner).await,
+            Operation::SetMigrationSource { admin } => {
+                self.handle_set_migration_source(owner, admin).await
+            }
+            Operation::ImportProgress {
+                snapshot,
+                signature,
+            } => {
+                self.handle_import_progress(owner, snapshot, signature)
+                    .await
+            }
+            Operation::SetGuildAnnouncement { announcement } => {
+                self.handle_set_guild_announcement(owner, announcement)
+                    .await
+            }
+            Operation::SetTimezone { utc_offset_minutes } => {
+                self.handle_set_timezone(owner, utc_offset_minutes).await
+            }
+            Operation::SetLocale { locale } => self.handle_set_locale(owner, locale).await,
+            Operation::SetScoringCategory { category } => {
+                self.handle_set_scoring_category(owner, category).await
+            }
+            Operation::SnapshotLeaderboard => self.handle_snapshot_leaderboard(owner).await,
+            Operation::CheckInvariants { section } => {
+                self.handle_check_invariants(owner, section).await
+            }
+            Operation::ReplayJournal { target, checkpoint } => {
+                self.handle_replay_journal(owner, target, checkpoint).await
+            }
+            Operation::SetHistoryRetention { top_k } => {
+                self.handle_set_history_retention(owner, top_k).await
+            }
+            Operation::StartSeason { length_days } => {
+                self.handle_start_season(owner, length_days).await
+            }
+            Operation::RollOverSeason => self.handle_roll_over_season(owner).await,
+            Operation::SetSeasonTheme { season, theme } => {
+                self.handle_set_season_theme(owner, season, theme).await
+            }
+            Operation::ScheduleRaffle { raffle } => {
+                self.handle_schedule_raffle(owner, raffle).await
+            }
+            Operation::DrawRaffle { raffle_id, secret } => {
+                self.handle_draw_raffle(raffle_id, secret).await
+            }
+            Operation::ScheduleBossBattle { battle } => {
+                self.handle_schedule_boss_battle(owner, battle).await
+            }
+            Operation::RegisterEventBridge { chain_id } => {
+                self.handle_set_event_bridge(chain_id, true).await
+            }
+            Operation::UnregisterEventBridge { chain_id } => {
+                self.handle_set_event_bridge(chain_id, false).await
+            }
+            Operation::DrainBridgedEvents => self.handle_drain_bridged_events().await,
+            Operation::SubscribeToSyncs { chain_id } => {
+                self.handle_set_sync_source(owner, chain_id, true).await
+            }
+            Operation::UnsubscribeFromSyncs { chain_id } => {
+                self.handle_set_sync_source(owner, chain_id, false).await
+            }
+            Operation::SubmitCommunityGame { game } => {
+                self.handle_submit_community_game(owner, game).await
+            }
+            Operation::ReviewCommunityGame { game_id, approved } => {
+                self.handle_review_community_game(owner, game_id, approved)
+                    .await
+            }
+            Operation::SubmitCommunityScore { game_id, score } => {
+                self.handle_submit_community_score(owner, game_id, score)
+                    .await
+            }
+        };
+        self.prune_leaderboard_history().await;
+        self.roll_over_season_if_due().await;
+        self.record_usage(owner, name, bytes).await;
+        if let Some(key) = idempotency_key {
+            self.state
+                .idempotent_responses
+                .insert(&key, response.clone())
+                .expect("Failed to record idempotent response");
+        }
+
+        if matches!(response, ArcadeResponse::Error { .. }) {
+            self.log_rejection(name, Some(owner), &response);
+        } else {
+            self.journal(
+                JournalKind::Operation,
+                Some(name),
+                Some(owner),
+                None,
+                encoded,
+            );
         }
+        response
     }
 
     async fn execute_message(&mut self, message: Self::Message) {
@@ -84,31 +603,97 @@ impl Contract for ArcadeHubContract {
             None => return, // Not initialized yet
         };
 
-        // Only process messages on the hub chain
-        if self.runtime.chain_id() != hub_chain_id {
+        // Bridged events are delivered to subscribers' chains, not the hub
+        if let Message::BridgeEvent {
+            application_id,
+            event,
+        } = message
+        {
+            self.handle_bridge_event(hub_chain_id, application_id, event)
+                .await;
+            return;
+        }
+
+        // Requests are sent straight between the hub and other chains
+        if message.is_request() {
+            if !self.is_sandbox() {
+                let origin = self
+                    .runtime
+                    .message_origin_chain_id()
+                    .expect("Requests arrive in messages from other chains");
+                self.apply_request(message, hub_chain_id, origin).await;
+            }
+            return;
+        }
+
+        // Only process messages on the hub chain; other chains take syncs
+        // from the streams they subscribed to. Sandboxes take none at all
+        if self.runtime.chain() != hub_chain_id || self.is_sandbox() {
             return;
         }
 
+        let origin = self
+            .runtime
+            .message_origin_chain_id()
+            .expect("Syncs arrive in messages from other chains");
         match message {
-            Message::SyncPlayer(player) => {
-                self.handle_sync_player(player).await;
+            Message::SequencedSync { index, message } => {
+                self.apply_sync(*message, origin, Some(index)).await;
             }
-            Message::SyncScore(score) => {
-                self.handle_sync_score(score).await;
+            message => self.apply_sync(message, origin, None).await,
+        }
+    }
+
+    async fn process_streams(&mut self, updates: Vec<StreamUpdate>) {
+        if self.is_sandbox() {
+            return;
+        }
+        let application_id = GenericApplicationId::User(self.runtime.application_id().forget_abi());
+        let hub_chain_id = *self.state.hub_chain_id.get();
+        let arcade_stream = StreamName::from(ARCADE_EVENT_STREAM);
+        let stream_name = StreamName::from(SYNC_EVENT_STREAM);
+        for update in updates {
+            if update.stream_id.application_id != application_id {
+                continue;
             }
-            Message::SyncXpUpdate {
-                wallet_address,
-                total_xp,
-                level,
-                games_played,
-            } => {
-                self.handle_sync_xp_update(wallet_address, total_xp, level, games_played)
-                    .await;
+            // Kiosk rules, bans and challenges set on the hub apply on every
+            // chain
+            if update.stream_id.stream_name == arcade_stream
+                && Some(update.chain_id) == hub_chain_id
+            {
+                for index in update.new_indices() {
+                    let event = self
+                        .runtime
+                        .read_event(update.chain_id, arcade_stream.clone(), index);
+                    self.apply_hub_rule(event).await;
+                }
+                continue;
+            }
+            if update.stream_id.stream_name != stream_name
+                || !self
+                    .state
+                    .sync_sources
+                    .contains(&update.chain_id)
+                    .await
+                    .unwrap_or(false)
+            {
+                continue;
+            }
+            for index in update.new_indices() {
+                let event = self
+                    .runtime
+                    .read_event(update.chain_id, stream_name.clone(), index);
+                if let ArcadeEvent::Sync(message) = event {
+                    self.apply_sync(*message, update.chain_id, Some(index))
+                        .await;
+                }
             }
         }
     }
 
     async fn store(mut self) {
+        let now = self.runtime.now_micros();
+        self.state.last_updated_at.set(Some(now));
         self.state.save().await.expect("Failed to save state");
     }
 }
@@ -119,6 +704,7 @@ impl ArcadeHubContract {
         &mut self,
         owner: AccountOwner,
         username: String,
+        voucher: Option<RegistrationVoucher>,
     ) -> ArcadeResponse {
         // Validate username
         if let Err(e) = validate_username(&username) {
@@ -131,247 +717,7913 @@ impl ArcadeHubContract {
         }
 
         // Get current timestamp
-        let timestamp = self.runtime.system_time().micros();
+        let timestamp = self.runtime.now_micros();
+
+        // The name must not be held for someone else
+        let holds_reservation = match self.check_reservation(&owner, &username, timestamp).await {
+            Ok(holds) => holds,
+            Err(e) => return e.into_response(),
+        };
+
+        // Verify the voucher, if one was presented
+        if let Some(voucher) = &voucher {
+            if let Err(e) = self
+                .check_voucher(&owner, &username, voucher, timestamp)
+                .await
+            {
+                return e.into_response();
+            }
+        }
 
-        // Create new player
+        // Consume the reservation and voucher
+        if holds_reservation {
+            self.release_reservation(&usernames::normalize_username(&username), &owner);
+        }
+        if let Some(voucher) = voucher {
+            self.state
+                .consumed_vouchers
+                .insert(&voucher.voucher_id, owner)
+                .expect("Failed to consume voucher");
+        }
+
+        // Create new player and claim the name, replacing any recyclable
+        // claim on it
         let player = Player::new(owner.clone(), username.clone(), timestamp);
+        self.state
+            .username_claims
+            .insert(
+                &usernames::normalize_username(&username),
+                UsernameClaim::new(username.clone(), owner, timestamp),
+            )
+            .expect("Failed to claim username");
 
         // Insert into local state
         self.state
             .players
             .insert(&owner, player.clone())
             .expect("Failed to insert player");
+        self.count_players(1);
 
         // Create leaderboard entry
-        let entry = LeaderboardEntry::from_player(&player, 0);
-        self.state
-            .leaderboard
-            .insert(&owner, entry)
-            .expect("Failed to insert leaderboard entry");
+        self.update_leaderboard(&player);
 
+        self.publish_registration(&player).await;
         // Send sync message to hub if not on hub chain
         self.send_to_hub_if_needed(Message::SyncPlayer(player));
 
         ArcadeResponse::PlayerRegistered
     }
 
-    /// Handle score submission.
-    async fn handle_submit_score(
+    /// Handle starting a game session.
+    async fn handle_start_game(
         &mut self,
         owner: AccountOwner,
-        game_type: arcade_hub::GameType,
-        score: u64,
-        bonus_data: Option<u64>,
+        game_type: GameType,
+        device_id: Option<String>,
     ) -> ArcadeResponse {
-        // Check if player is registered
-        let mut player = match self.state.players.get(&owner).await {
-            Ok(Some(p)) => p,
-            _ => return ArcadeError::PlayerNotRegistered.into_response(),
-        };
-
-        // Calculate XP earned
-        let xp_earned = game_type.calculate_xp(score, bonus_data);
-
-        // Update player stats
-        player.add_xp(xp_earned);
-        player.increment_games();
-
-        // Save updated player
-        self.state
+        if !self
+            .state
             .players
-            .insert(&owner, player.clone())
-            .expect("Failed to update player");
-
-        // Update leaderboard entry
-        let entry = LeaderboardEntry::from_player(&player, 0);
-        self.state
-            .leaderboard
-            .insert(&owner, entry)
-            .expect("Failed to update leaderboard");
+            .contains_key(&owner)
+            .await
+            .unwrap_or(false)
+        {
+            return ArcadeError::PlayerNotRegistered.into_response();
+        }
+        let now = self.runtime.now_micros();
+        if let Err(e) = self.state.submission_windows.get().check(game_type, now) {
+            return e.into_response();
+        }
+        if let Some(device_id) = &device_id {
+            if let Err(e) = kiosk::validate_device_id(device_id) {
+                return e.into_response();
+            }
+        }
 
-        // Generate score ID and create score record
-        let score_id = {
-            let current = *self.state.score_counter.get();
-            self.state.score_counter.set(current + 1);
+        let session_id = {
+            let current = *self.state.session_counter.get();
+            self.state.session_counter.set(current + 1);
             current
         };
+        // A session still open is set aside, where a batch can close it
+        let open = self
+            .state
+            .game_sessions
+            .get(&owner)
+            .await
+            .expect("Failed to load game session");
+        if let Some(open) = open {
+            self.set_aside_session(owner, open, now).await;
+        }
+        let chain_id = self.runtime.chain();
+        let session = GameSession::start(session_id, owner, game_type, device_id, chain_id, now);
+        let seed = session.seed;
+        self.state
+            .game_sessions
+            .insert(&owner, session)
+            .expect("Failed to open game session");
 
-        let timestamp = self.runtime.system_time().micros();
-        let game_score = GameScore {
-            id: score_id,
-            game_type,
-            player: owner.clone(),
+        ArcadeResponse::GameStarted { session_id, seed }
+    }
+
+    /// Handle submitting the score of an open game session.
+    async fn handle_end_game(
+        &mut self,
+        owner: AccountOwner,
+        session_id: u64,
+        score: u64,
+        bonus_data: Option<u64>,
+        device_id: Option<String>,
+        signature: Option<ScoreSignature>,
+    ) -> ArcadeResponse {
+        let session = self
+            .state
+            .game_sessions
+            .get(&owner)
+            .await
+            .expect("Failed to load game session");
+        let Some(session) = session.filter(|session| session.id == session_id) else {
+            return ArcadeError::SessionNotFound.into_response();
+        };
+        let submission = ScoreSubmission {
+            game_type: session.game_type,
             score,
-            xp_earned,
             bonus_data,
-            timestamp,
+            device_id,
         };
-
-        // Insert score
-        self.state
-            .game_scores
-            .insert(&score_id, game_score.clone())
-            .expect("Failed to insert score");
-
-        // Update totals
-        let total_games = *self.state.total_games_played.get();
-        self.state.total_games_played.set(total_games + 1);
-
-        let total_xp = *self.state.total_xp_earned.get();
-        self.state.total_xp_earned.set(total_xp + xp_earned);
-
-        // Send sync messages to hub if not on hub chain
-        self.send_to_hub_if_needed(Message::SyncScore(game_score));
-        self.send_to_hub_if_needed(Message::SyncXpUpdate {
-            wallet_address: owner,
-            total_xp: player.total_xp,
-            level: player.level,
-            games_played: player.games_played,
-        });
-
-        ArcadeResponse::ScoreSubmitted { xp_earned }
+        self.handle_submit_score(owner, submission, Some(session_id), signature)
+            .await
     }
 
-    /// Handle username update.
-    async fn handle_update_username(
+    /// Handle score submission, closing the game session `session_id`.
+    async fn handle_submit_score(
         &mut self,
         owner: AccountOwner,
-        new_username: String,
+        submission: ScoreSubmission,
+        session_id: Option<u64>,
+        signature: Option<ScoreSignature>,
     ) -> ArcadeResponse {
-        // Validate username
-        if let Err(e) = validate_username(&new_username) {
-            return e.into_response();
-        }
-
-        // Check if player exists
-        let mut player = match self.state.players.get(&owner).await {
+        // Check if player is registered
+        let player = match self.state.players.get(&owner).await {
             Ok(Some(p)) => p,
             _ => return ArcadeError::PlayerNotRegistered.into_response(),
         };
 
-        // Update username
-        player.username = new_username;
+        // Enforce the game session, the game server's signature, kiosk
+        // device rules and the player's own play limits
+        let timestamp = self.runtime.now_micros();
+        if let Err(e) = self
+            .state
+            .check_session(&owner, session_id, &submission, timestamp)
+            .await
+        {
+            return e.into_response();
+        }
+        let mut pending = PendingSubmissions::default();
+        if let Err(e) = self
+            .state
+            .check_score_signature(&owner, &submission, signature.as_ref(), &mut pending)
+            .await
+        {
+            return e.into_response();
+        }
+        if let Err(e) = self
+            .state
+            .check_submission(&player, &submission, timestamp, &mut pending)
+            .await
+        {
+            return e.into_response();
+        }
+        self.commit_pending(pending);
+        self.close_game_session(owner);
+
+        let ScoreResult {
+            xp_earned,
+            full_circuit_bonus,
+            achievements_unlocked,
+            receipt,
+            total_xp,
+            level,
+            leveled_up,
+            personal_best,
+            rank,
+        } = self.record_score(player, submission).await;
+
+        ArcadeResponse::ScoreSubmitted {
+            xp_earned,
+            full_circuit_bonus,
+            achievements_unlocked,
+            receipt,
+            total_xp,
+            level,
+            leveled_up,
+            personal_best,
+            rank,
+        }
+    }
+
+    /// Handle a batch of the player's own finished runs.
+    ///
+    /// Every run is validated before any is applied, so the batch is
+    /// accepted or rejected as a whole, and the hub hears about it, and the
+    /// progress it made, in one message.
+    async fn handle_submit_scores(
+        &mut self,
+        owner: AccountOwner,
+        submissions: Vec<ScoreSubmission>,
+        signatures: Vec<ScoreSignature>,
+        session_ids: Vec<u64>,
+    ) -> ArcadeResponse {
+        let Some(mut player) = self.state.players.get(&owner).await.ok().flatten() else {
+            return ArcadeError::PlayerNotRegistered.into_response();
+        };
+        if submissions.is_empty() {
+            return ArcadeError::EmptyBatch.into_response();
+        }
+        if submissions.len() > MAX_SCORE_BATCH_SIZE {
+            return ArcadeError::BatchTooLarge.into_response();
+        }
+        // Without a game server vouching for each run, each must close a
+        // game session
+        let signed = self.state.score_signer.get().is_some();
+
+        // Validate every run against state plus the batch's own pending
+        // updates
+        let timestamp = self.runtime.now_micros();
+        let mut pending = PendingSubmissions::default();
+        for (index, submission) in submissions.iter().enumerate() {
+            let checked = async {
+                match session_ids.get(index) {
+                    Some(session_id) => {
+                        self.state
+                            .check_batch_session(
+                                &owner,
+                                *session_id,
+                                submission,
+                                timestamp,
+                                &mut pending,
+                            )
+                            .await?
+                    }
+                    None if !signed => return Err(ArcadeError::SessionRequired),
+                    None => {}
+                }
+                self.state
+                    .check_score_signature(&owner, submission, signatures.get(index), &mut pending)
+                    .await?;
+                self.state
+                    .check_submission(&player, submission, timestamp, &mut pending)
+                    .await
+            }
+            .await;
+            if let Err(e) = checked {
+                return ArcadeError::BatchItemRejected {
+                    index,
+                    reason: Box::new(e),
+                }
+                .into_response();
+            }
+        }
+
+        // Apply the whole batch, holding back the progress syncs it makes
+        let closing = std::mem::take(&mut pending.sessions);
+        self.commit_pending(pending);
+        for (owner, session_ids) in closing {
+            self.close_sessions(owner, &session_ids).await;
+        }
+        let mut results = Vec::with_capacity(submissions.len());
+        let mut scores = Vec::with_capacity(submissions.len());
+        self.batched_progress = Some(Vec::new());
+        for submission in submissions {
+            let (game_score, updated, result) = self.apply_score(player, submission).await;
+            player = updated;
+            scores.push(game_score);
+            results.push(result);
+        }
+        let progress = self.batched_progress.take().unwrap_or_default();
+
+        self.send_to_hub_if_needed(Message::SyncScoreBatch {
+            wallet_address: owner,
+            scores,
+            total_xp: player.total_xp,
+            level: player.level,
+            games_played: player.games_played,
+            progress,
+        });
+
+        ArcadeResponse::ScoresSubmitted { results }
+    }
+
+    /// Handle a player's one run of today's daily challenge, in their
+    /// timezone.
+    async fn handle_submit_daily_challenge(
+        &mut self,
+        owner: AccountOwner,
+        session_id: u64,
+        submission: ScoreSubmission,
+        signature: Option<ScoreSignature>,
+    ) -> ArcadeResponse {
+        let Some(player) = self.state.players.get(&owner).await.ok().flatten() else {
+            return ArcadeError::PlayerNotRegistered.into_response();
+        };
+
+        let timestamp = self.runtime.now_micros();
+        let today = player.local_day(timestamp);
+        let challenge = DailyChallenge::for_day(today, *self.state.hub_chain_id.get());
+        if submission.game_type != challenge.game_type {
+            return ArcadeError::WrongChallengeGame.into_response();
+        }
+        let completed = self
+            .state
+            .challenge_completions
+            .get(&owner)
+            .await
+            .expect("Failed to load challenge completions")
+            .is_some_and(|completions| completions.is_completed(today));
+        if completed {
+            return ArcadeError::ChallengeAlreadySubmitted.into_response();
+        }
+
+        // The run is held to the same rules as any other submission
+        if let Err(e) = self
+            .state
+            .check_session(&owner, Some(session_id), &submission, timestamp)
+            .await
+        {
+            return e.into_response();
+        }
+        let mut pending = PendingSubmissions::default();
+        if let Err(e) = self
+            .state
+            .check_score_signature(&owner, &submission, signature.as_ref(), &mut pending)
+            .await
+        {
+            return e.into_response();
+        }
+        if let Err(e) = self
+            .state
+            .check_submission(&player, &submission, timestamp, &mut pending)
+            .await
+        {
+            return e.into_response();
+        }
+        self.commit_pending(pending);
+        self.close_game_session(owner);
+
+        let entry = ChallengeEntry {
+            player: owner,
+            day: today,
+            score: submission.score,
+            submitted_at: timestamp,
+        };
+        let ScoreResult {
+            xp_earned,
+            full_circuit_bonus,
+            achievements_unlocked,
+            receipt,
+            total_xp,
+            level,
+            leveled_up,
+            personal_best,
+            rank,
+        } = self.record_score(player, submission).await;
+        self.record_challenge_entry(entry.clone())
+            .await
+            .expect("Daily challenge was checked as not yet submitted");
+        self.send_to_hub_if_needed(Message::SyncDailyChallenge { entry });
+
+        ArcadeResponse::ScoreSubmitted {
+            xp_earned,
+            full_circuit_bonus,
+            achievements_unlocked,
+            receipt,
+            total_xp,
+            level,
+            leveled_up,
+            personal_best,
+            rank,
+        }
+    }
+
+    /// Close a player's open game session once its score is accepted.
+    fn close_game_session(&mut self, owner: AccountOwner) {
+        self.state
+            .game_sessions
+            .remove(&owner)
+            .expect("Failed to close game session");
+    }
+
+    /// Set aside `owner`'s open `session` as another game starts at `now`,
+    /// dropping set-aside sessions that timed out and, past the limit, the
+    /// oldest.
+    async fn set_aside_session(&mut self, owner: AccountOwner, session: GameSession, now: u64) {
+        let mut set_aside = self
+            .state
+            .set_aside_sessions
+            .get(&owner)
+            .await
+            .expect("Failed to load set-aside sessions")
+            .unwrap_or_default();
+        set_aside.retain(|kept| !kept.has_expired(now));
+        if !session.has_expired(now) {
+            set_aside.push(session);
+        }
+        if set_aside.len() > sessions::MAX_SET_ASIDE_SESSIONS {
+            set_aside.remove(0);
+        }
+        self.save_set_aside_sessions(owner, set_aside);
+    }
+
+    /// Close `owner`'s sessions with the given IDs, open or set aside.
+    async fn close_sessions(&mut self, owner: AccountOwner, session_ids: &BTreeSet<u64>) {
+        let open = self
+            .state
+            .game_sessions
+            .get(&owner)
+            .await
+            .expect("Failed to load game session");
+        if open.is_some_and(|session| session_ids.contains(&session.id)) {
+            self.close_game_session(owner);
+        }
+        let mut set_aside = self
+            .state
+            .set_aside_sessions
+            .get(&owner)
+            .await
+            .expect("Failed to load set-aside sessions")
+            .unwrap_or_default();
+        set_aside.retain(|session| !session_ids.contains(&session.id));
+        self.save_set_aside_sessions(owner, set_aside);
+    }
+
+    /// Store `owner`'s set-aside sessions, dropping the entry once empty.
+    fn save_set_aside_sessions(&mut self, owner: AccountOwner, set_aside: Vec<GameSession>) {
+        if set_aside.is_empty() {
+            self.state
+                .set_aside_sessions
+                .remove(&owner)
+                .expect("Failed to update set-aside sessions");
+        } else {
+            self.state
+                .set_aside_sessions
+                .insert(&owner, set_aside)
+                .expect("Failed to update set-aside sessions");
+        }
+    }
+
+    /// Handle a batch of attested submissions relayed by a kiosk gateway.
+    ///
+    /// Every item is validated before any is applied, so the batch is
+    /// accepted or rejected as a whole. Relayed runs need no game session:
+    /// each is signed by its player and carries a nonce against replays.
+    async fn handle_relay_scores(
+        &mut self,
+        owner: AccountOwner,
+        submissions: Vec<GatewaySubmission>,
+    ) -> ArcadeResponse {
+        if !self.state.gateways.contains(&owner).await.unwrap_or(false) {
+            return ArcadeError::NotGateway.into_response();
+        }
+        if submissions.is_empty() {
+            return ArcadeError::EmptyBatch.into_response();
+        }
+        if submissions.len() > kiosk::MAX_RELAY_BATCH_SIZE {
+            return ArcadeError::BatchTooLarge.into_response();
+        }
+
+        // Validate every item against state plus the batch's own pending updates
+        let chain_id = self.runtime.chain();
+        let timestamp = self.runtime.now_micros();
+        let mut nonces = BTreeMap::new();
+        let mut pending = PendingSubmissions::default();
+        for (index, item) in submissions.iter().enumerate() {
+            if let Err(e) = self
+                .check_relayed_submission(chain_id, timestamp, item, &mut nonces, &mut pending)
+                .await
+            {
+                return ArcadeError::BatchItemRejected {
+                    index,
+                    reason: Box::new(e),
+                }
+                .into_response();
+            }
+        }
+
+        // Apply the whole batch
+        for (player, nonce) in nonces {
+            self.state
+                .gateway_nonces
+                .insert(&player, nonce)
+                .expect("Failed to update gateway nonce");
+        }
+        self.commit_pending(pending);
+
+        let mut xp_earned = Vec::with_capacity(submissions.len());
+        for item in submissions {
+            let player = self
+                .state
+                .players
+                .get(&item.player)
+                .await
+                .expect("Failed to load player")
+                .expect("Player was checked during validation");
+            xp_earned.push(self.record_score(player, item.submission).await.xp_earned);
+        }
+
+        ArcadeResponse::ScoresRelayed { xp_earned }
+    }
+
+    /// Handle display name update.
+    async fn handle_update_display_name(
+        &mut self,
+        owner: AccountOwner,
+        display_name: String,
+    ) -> ArcadeResponse {
+        // Validate display name
+        if let Err(e) = validate_display_name(&display_name) {
+            return e.into_response();
+        }
+
+        // Check if player exists
+        let mut player = match self.state.players.get(&owner).await {
+            Ok(Some(p)) => p,
+            _ => return ArcadeError::PlayerNotRegistered.into_response(),
+        };
+
+        // Update display name (the username handle is left alone)
+        player.display_name = display_name.clone();
+
+        // Save updated player
+        self.state
+            .players
+            .insert(&owner, player.clone())
+            .expect("Failed to update player");
+
+        // Update leaderboard entry
+        self.update_leaderboard(&player);
+
+        // Send sync message to hub
+        self.send_to_hub_if_needed(Message::SyncDisplayName {
+            wallet_address: owner,
+            display_name,
+        });
+
+        ArcadeResponse::DisplayNameUpdated
+    }
+
+    /// Handle changing the player's username handle.
+    async fn handle_update_username(
+        &mut self,
+        owner: AccountOwner,
+        username: String,
+    ) -> ArcadeResponse {
+        if let Err(e) = validate_username(&username) {
+            return e.into_response();
+        }
+        if !self
+            .state
+            .players
+            .contains_key(&owner)
+            .await
+            .unwrap_or(false)
+        {
+            return ArcadeError::PlayerNotRegistered.into_response();
+        }
+
+        // The new name must be free, or reserved for the player themselves
+        let timestamp = self.runtime.now_micros();
+        let holds_reservation = match self.check_reservation(&owner, &username, timestamp).await {
+            Ok(holds) => holds,
+            Err(e) => return e.into_response(),
+        };
+        if holds_reservation {
+            self.release_reservation(&usernames::normalize_username(&username), &owner);
+        }
+        if let Err(e) = self.change_username(owner, username.clone()).await {
+            return e.into_response();
+        }
+
+        self.send_to_hub_if_needed(Message::SyncUsername {
+            wallet_address: owner,
+            username,
+        });
+
+        ArcadeResponse::UsernameUpdated
+    }
+
+    /// Handle privacy settings update.
+    async fn handle_update_privacy(
+        &mut self,
+        owner: AccountOwner,
+        privacy: PrivacySettings,
+    ) -> ArcadeResponse {
+        let mut player = match self.state.players.get(&owner).await {
+            Ok(Some(p)) => p,
+            _ => return ArcadeError::PlayerNotRegistered.into_response(),
+        };
+
+        player.privacy = privacy.clone();
+        self.state
+            .players
+            .insert(&owner, player)
+            .expect("Failed to update player");
+        self.apply_privacy(owner, &privacy);
+
+        self.send_to_hub_if_needed(Message::SyncPrivacy {
+            wallet_address: owner,
+            privacy,
+        });
+
+        ArcadeResponse::PrivacyUpdated
+    }
+
+    /// Handle setting or clearing a player's shadow-ban (admin only).
+    async fn handle_set_shadow_ban(
+        &mut self,
+        owner: AccountOwner,
+        wallet_address: AccountOwner,
+        banned: bool,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+
+        if self.is_shadow_banned(&wallet_address).await == banned {
+            return ArcadeResponse::ShadowBanUpdated;
+        }
+        if banned {
+            self.state
+                .shadow_banned
+                .insert(&wallet_address)
+                .expect("Failed to shadow-ban player");
+        } else {
+            self.state
+                .shadow_banned
+                .remove(&wallet_address)
+                .expect("Failed to clear shadow-ban");
+        }
+        // The ban covers the player's earlier runs too
+        self.move_logged_totals(&wallet_address, banned).await;
+
+        ArcadeResponse::ShadowBanUpdated
+    }
+
+    /// Take a player's logged runs out of the arcade totals when they are
+    /// shadow-banned, or put them back when the ban is cleared.
+    async fn move_logged_totals(&mut self, player: &AccountOwner, banned: bool) {
+        let positions = match self
+            .state
+            .player_scores
+            .try_load_entry(player)
+            .await
+            .expect("Failed to load player scores")
+        {
+            Some(log) => log.read(..).await.expect("Failed to load player scores"),
+            None => Vec::new(),
+        };
+        let mut games = 0u64;
+        let mut xp = 0u64;
+        for position in positions {
+            if self.is_score_removed(position).await {
+                continue;
+            }
+            let score = self
+                .state
+                .score_log
+                .get(position as usize)
+                .await
+                .expect("Failed to load score");
+            if let Some(score) = score {
+                games += 1;
+                xp = xp.saturating_add(score.xp_earned);
+            }
+        }
+
+        let total_games = *self.state.total_games_played.get();
+        let total_xp = *self.state.total_xp_earned.get();
+        if banned {
+            self.state
+                .total_games_played
+                .set(total_games.saturating_sub(games));
+            self.state.total_xp_earned.set(total_xp.saturating_sub(xp));
+        } else {
+            self.state
+                .total_games_played
+                .set(total_games.saturating_add(games));
+            self.state.total_xp_earned.set(total_xp.saturating_add(xp));
+        }
+    }
+
+    /// Handle banning or unbanning a player (admin only).
+    async fn handle_set_player_ban(
+        &mut self,
+        owner: AccountOwner,
+        wallet_address: AccountOwner,
+        banned: bool,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+
+        self.update_player_ban(&wallet_address, banned);
+        if self.is_hub_chain() {
+            self.publish_event(ArcadeEvent::PlayerBanUpdated {
+                wallet_address,
+                banned,
+            })
+            .await;
+        }
+
+        ArcadeResponse::PlayerBanUpdated
+    }
+
+    /// Ban or unban a player on this chain.
+    fn update_player_ban(&mut self, wallet_address: &AccountOwner, banned: bool) {
+        if banned {
+            self.state
+                .banned_players
+                .insert(wallet_address)
+                .expect("Failed to ban player");
+        } else {
+            self.state
+                .banned_players
+                .remove(wallet_address)
+                .expect("Failed to unban player");
+        }
+    }
+
+    /// Handle filing an appeal, taking the bond from the appellant. Off the
+    /// hub, the bond is moved to the hub's escrow and the appeal sent there
+    /// to be taken up.
+    async fn handle_file_appeal(
+        &mut self,
+        owner: AccountOwner,
+        receipt: CryptoHash,
+        reason: String,
+    ) -> ArcadeResponse {
+        if let Err(e) = dispute::validate_appeal_reason(&reason) {
+            return e.into_response();
+        }
+
+        let bond = *self.state.appeal_bond.get();
+        if !bond.is_zero() && self.runtime.owner_balance(owner) < bond {
+            return ArcadeError::InsufficientBond.into_response();
+        }
+
+        if let Some(hub_chain_id) = self.remote_hub() {
+            if !bond.is_zero() {
+                let escrow = Account::new(
+                    hub_chain_id,
+                    AccountOwner::from(self.runtime.application_id()),
+                );
+                self.runtime.transfer(owner, escrow, bond);
+            }
+            self.send_request(
+                hub_chain_id,
+                Message::FileAppeal {
+                    appellant: owner,
+                    receipt,
+                    reason,
+                    bond,
+                },
+            );
+            return ArcadeResponse::AppealFiled { appeal_id: None };
+        }
+
+        // The appealed score must exist
+        if !self.is_score_recorded(receipt).await {
+            return ArcadeError::ScoreNotFound.into_response();
+        }
+
+        // Move the bond into the application's account
+        if !bond.is_zero() {
+            let escrow = self.escrow_account();
+            self.runtime.transfer(owner, escrow, bond);
+        }
+        let chain_id = self.runtime.chain();
+        let appeal_id = self.take_up_appeal(owner, chain_id, receipt, reason, bond);
+
+        ArcadeResponse::AppealFiled {
+            appeal_id: Some(appeal_id),
+        }
+    }
+
+    /// Handle an appeal filed on `origin` and sent to the hub, refunding its
+    /// bond there if the appealed score isn't on record.
+    async fn handle_appeal_request(
+        &mut self,
+        origin: ChainId,
+        appellant: AccountOwner,
+        receipt: CryptoHash,
+        reason: String,
+        bond: Amount,
+    ) {
+        if !self.is_score_recorded(receipt).await {
+            log::warn!(
+                "Refunded {appellant}'s appeal from chain {origin} of unknown score {receipt}: {}",
+                ArcadeError::ScoreNotFound.code(),
+            );
+            if !bond.is_zero() {
+                let source = AccountOwner::from(self.runtime.application_id());
+                self.runtime
+                    .transfer(source, Account::new(origin, appellant), bond);
+            }
+            return;
+        }
+        self.take_up_appeal(appellant, origin, receipt, reason, bond);
+    }
+
+    /// Whether a score with the given receipt is on record.
+    async fn is_score_recorded(&self, receipt: CryptoHash) -> bool {
+        self.state
+            .score_receipts
+            .contains_key(&receipt)
+            .await
+            .unwrap_or(false)
+    }
+
+    /// Record a pending appeal whose bond is already in escrow, returning
+    /// its ID.
+    fn take_up_appeal(
+        &mut self,
+        appellant: AccountOwner,
+        chain_id: ChainId,
+        receipt: CryptoHash,
+        reason: String,
+        bond: Amount,
+    ) -> u64 {
+        let appeal_id = {
+            let current = *self.state.appeal_counter.get();
+            self.state.appeal_counter.set(current + 1);
+            current
+        };
+
+        let timestamp = self.runtime.now_micros();
+        let appeal = Appeal::new(appeal_id, receipt, appellant, chain_id, reason, bond, timestamp);
+        self.state
+            .appeals
+            .insert(&appeal_id, appeal)
+            .expect("Failed to insert appeal");
+        self.state.bond_accounts.get_mut().deposit(bond);
+        appeal_id
+    }
+
+    /// Handle resolving an appeal (admin only), settling its bond.
+    async fn handle_resolve_appeal(
+        &mut self,
+        owner: AccountOwner,
+        appeal_id: u64,
+        upheld: bool,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+
+        let mut appeal = match self.state.appeals.get(&appeal_id).await {
+            Ok(Some(appeal)) => appeal,
+            _ => return ArcadeError::AppealNotFound.into_response(),
+        };
+
+        let timestamp = self.runtime.now_micros();
+        let settlement = match appeal.resolve(upheld, timestamp) {
+            Ok(settlement) => settlement,
+            Err(e) => return e.into_response(),
+        };
+
+        // Refunds leave the escrow; forfeited bonds simply stay in it
+        if let BondSettlement::Refund { to, amount } = settlement {
+            if !amount.is_zero() {
+                let source = AccountOwner::from(self.runtime.application_id());
+                self.runtime.transfer(source, to, amount);
+            }
+        }
+
+        self.state.bond_accounts.get_mut().settle(&settlement);
+        self.state
+            .appeals
+            .insert(&appeal_id, appeal)
+            .expect("Failed to update appeal");
+
+        ArcadeResponse::AppealResolved
+    }
+
+    /// Handle removing a fraudulent score (admin only), and tell the hub.
+    async fn handle_remove_score(
+        &mut self,
+        owner: AccountOwner,
+        receipt: CryptoHash,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        let Some(position) = self
+            .state
+            .score_receipts
+            .get(&receipt)
+            .await
+            .expect("Failed to load score position")
+        else {
+            return ArcadeError::ScoreNotFound.into_response();
+        };
+        let score = self
+            .state
+            .score_log
+            .get(position as usize)
+            .await
+            .expect("Failed to load score")
+            .expect("Score position points past the log");
+        if self.is_paid_out(&score).await {
+            return ArcadeError::ScorePaidOut.into_response();
+        }
+
+        // The player's chain removes its own copy and syncs the correction
+        // back, or its next XP update would undo the removal here
+        if self.is_hub_chain() && score.chain_id != self.runtime.chain() && !self.is_sandbox() {
+            let chain_id = score.chain_id;
+            self.send_request(chain_id, Message::RemoveScore { receipt });
+            return ArcadeResponse::ScoreRemovalForwarded { chain_id };
+        }
+
+        self.remove_score(position, &score).await;
+        self.send_to_hub_if_needed(Message::SyncScoreRemoved(score));
+
+        ArcadeResponse::ScoreRemoved
+    }
+
+    /// Remove the score with `receipt` at the hub's request.
+    async fn handle_score_removal_request(&mut self, receipt: CryptoHash) {
+        let position = self
+            .state
+            .score_receipts
+            .get(&receipt)
+            .await
+            .expect("Failed to load score position");
+        let Some(position) = position else {
+            log::warn!(
+                "Failed to remove score {receipt} at the hub's request: {}",
+                ArcadeError::ScoreNotFound.code(),
+            );
+            return;
+        };
+        let score = self
+            .state
+            .score_log
+            .get(position as usize)
+            .await
+            .expect("Failed to load score")
+            .expect("Score position points past the log");
+        self.remove_score(position, &score).await;
+        self.send_to_hub_if_needed(Message::SyncScoreRemoved(score));
+    }
+
+    /// Handle updating kiosk-mode settings (admin only). Settings made on
+    /// the hub are published for every other chain to take up.
+    async fn handle_configure_kiosk(
+        &mut self,
+        owner: AccountOwner,
+        config: KioskConfig,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        self.state.kiosk_config.set(config.clone());
+        if self.is_hub_chain() {
+            self.publish_event(ArcadeEvent::KioskConfigured(config))
+                .await;
+        }
+        ArcadeResponse::KioskConfigured
+    }
+
+    /// Handle updating the underplayed-game bonus settings (admin only).
+    async fn handle_configure_balance(
+        &mut self,
+        owner: AccountOwner,
+        config: BalanceConfig,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        self.state.balance_config.set(config);
+        ArcadeResponse::BalanceConfigured
+    }
+
+    /// Handle replacing the live scoring config (admin only).
+    async fn handle_configure_scoring(
+        &mut self,
+        owner: AccountOwner,
+        config: ScoringConfig,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        if let Err(e) = config.validate() {
+            return e.into_response();
+        }
+        self.state.scoring_config.set(config);
+        ArcadeResponse::ScoringConfigured
+    }
+
+    /// Handle starting a scoring canary (admin only), replacing the record
+    /// of the last one.
+    async fn handle_start_scoring_canary(
+        &mut self,
+        owner: AccountOwner,
+        config: ScoringConfig,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        if let Err(e) = config.validate() {
+            return e.into_response();
+        }
+        if self
+            .state
+            .scoring_canary
+            .get()
+            .as_ref()
+            .is_some_and(ScoringCanary::is_running)
+        {
+            return ArcadeError::ScoringCanaryRunning.into_response();
+        }
+        let canary = ScoringCanary::new(config, self.runtime.now_micros());
+        self.state.scoring_canary.set(Some(canary));
+        ArcadeResponse::ScoringCanaryStarted
+    }
+
+    /// Handle ending the scoring canary (admin only), keeping its tallies.
+    async fn handle_end_scoring_canary(
+        &mut self,
+        owner: AccountOwner,
+        promote: bool,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        let now = self.runtime.now_micros();
+        let Some(canary) = self
+            .state
+            .scoring_canary
+            .get_mut()
+            .as_mut()
+            .filter(|canary| canary.is_running())
+        else {
+            return ArcadeError::ScoringCanaryNotRunning.into_response();
+        };
+        canary.end(promote, now);
+        if promote {
+            let config = canary.config.clone();
+            self.state.scoring_config.set(config);
+        }
+        ArcadeResponse::ScoringCanaryEnded
+    }
+
+    /// Handle replacing a game's closed windows (admin only).
+    async fn handle_set_submission_windows(
+        &mut self,
+        owner: AccountOwner,
+        game_type: GameType,
+        closed_windows: Vec<ClosedWindow>,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        let mut windows = self.state.submission_windows.get().clone();
+        if let Err(e) = windows.set(game_type, closed_windows) {
+            return e.into_response();
+        }
+        self.state.submission_windows.set(windows);
+        ArcadeResponse::SubmissionWindowsUpdated
+    }
+
+    /// Handle updating the daily soft quotas (admin only).
+    async fn handle_set_usage_quota(
+        &mut self,
+        owner: AccountOwner,
+        quota: UsageQuota,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        self.state.usage_quota.set(quota);
+        ArcadeResponse::UsageQuotaUpdated
+    }
+
+    /// Handle updating the smurf detection thresholds (admin only).
+    async fn handle_configure_smurf_detection(
+        &mut self,
+        owner: AccountOwner,
+        config: SmurfDetectionConfig,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        if let Err(e) = config.validate() {
+            return e.into_response();
+        }
+        self.state.smurf_detection.set(config);
+        ArcadeResponse::SmurfDetectionConfigured
+    }
+
+    /// Handle taking a reviewed account off the moderation queue (admin
+    /// only).
+    async fn handle_resolve_moderation_flag(
+        &mut self,
+        owner: AccountOwner,
+        flag_id: u64,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        let Ok(Some(flag)) = self.state.moderation_queue.get(&flag_id).await else {
+            return ArcadeError::ModerationFlagNotFound.into_response();
+        };
+        self.state
+            .moderation_queue
+            .remove(&flag_id)
+            .expect("Failed to update moderation queue");
+
+        // The moderator's review supersedes the challenge issued for the flag
+        let challenge = self
+            .state
+            .humanity_challenges
+            .get(&flag.player)
+            .await
+            .expect("Failed to load humanity challenge");
+        if let Some(challenge) = challenge.filter(|challenge| challenge.flag_id == Some(flag_id)) {
+            self.clear_humanity_challenge(&challenge).await;
+        }
+        ArcadeResponse::ModerationFlagResolved
+    }
+
+    /// Handle updating the humanity check settings (admin only).
+    async fn handle_configure_humanity_checks(
+        &mut self,
+        owner: AccountOwner,
+        config: HumanityCheckConfig,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        if let Err(e) = config.validate() {
+            return e.into_response();
+        }
+        self.state.humanity_checks.set(config);
+        ArcadeResponse::HumanityChecksConfigured
+    }
+
+    /// Handle challenging an account to prove it is human (admin only).
+    async fn handle_issue_humanity_challenge(
+        &mut self,
+        owner: AccountOwner,
+        wallet_address: AccountOwner,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        if !self
+            .state
+            .players
+            .contains_key(&wallet_address)
+            .await
+            .unwrap_or(false)
+        {
+            return ArcadeError::PlayerNotRegistered.into_response();
+        }
+        let timestamp = self.runtime.now_micros();
+        let challenge_id = self
+            .issue_humanity_challenge(wallet_address, None, timestamp)
+            .await;
+        ArcadeResponse::HumanityChallengeIssued { challenge_id }
+    }
+
+    /// Handle completing the signer's humanity challenge with a signature
+    /// from their second wallet. A challenge the hub issued is cleared
+    /// there, so its proof is sent on to the hub.
+    async fn handle_complete_humanity_challenge(
+        &mut self,
+        owner: AccountOwner,
+        verifier: AccountOwner,
+        signature: String,
+    ) -> ArcadeResponse {
+        let Ok(Some(challenge)) = self.state.humanity_challenges.get(&owner).await else {
+            return ArcadeError::HumanityChallengeNotFound.into_response();
+        };
+        if challenge.chain_id != self.runtime.chain_id() {
+            if let Err(e) = challenge.verify(&verifier, &signature) {
+                return e.into_response();
+            }
+            self.send_request(
+                challenge.chain_id,
+                Message::CompleteHumanityChallenge {
+                    player: owner,
+                    verifier,
+                    signature,
+                },
+            );
+            return ArcadeResponse::HumanityProofForwarded;
+        }
+        match self
+            .complete_humanity_challenge(challenge, verifier, &signature)
+            .await
+        {
+            Ok(()) => ArcadeResponse::HumanityChallengeCompleted,
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Clear `challenge` with `verifier`'s signature, if the verifier is in
+    /// good standing and has vouches left.
+    async fn complete_humanity_challenge(
+        &mut self,
+        challenge: HumanityChallenge,
+        verifier: AccountOwner,
+        signature: &str,
+    ) -> Result<(), ArcadeError> {
+        challenge.verify(&verifier, signature)?;
+        let player = challenge.player;
+
+        let banned = self
+            .state
+            .banned_players
+            .contains(&verifier)
+            .await
+            .expect("Failed to load banned players");
+        let challenged = self
+            .state
+            .humanity_challenges
+            .contains_key(&verifier)
+            .await
+            .expect("Failed to load humanity challenges");
+        if banned || challenged {
+            return Err(ArcadeError::InvalidHumanityProof);
+        }
+        let previous_verifier = self
+            .state
+            .humanity_verifiers
+            .get(&player)
+            .await
+            .expect("Failed to load humanity verifier");
+        let vouches = self
+            .state
+            .humanity_vouches
+            .get(&verifier)
+            .await
+            .expect("Failed to load humanity vouches")
+            .unwrap_or(0);
+        let vouched_before = previous_verifier == Some(verifier);
+        if !vouched_before && vouches >= self.state.humanity_checks.get().vouches_per_verifier {
+            return Err(ArcadeError::InvalidHumanityProof);
+        }
+
+        self.clear_humanity_challenge(&challenge).await;
+        if !vouched_before {
+            self.state
+                .humanity_verifiers
+                .insert(&player, verifier)
+                .expect("Failed to record humanity verifier");
+            self.state
+                .humanity_vouches
+                .insert(&verifier, vouches + 1)
+                .expect("Failed to record humanity vouch");
+        }
+        Ok(())
+    }
+
+    /// Handle the proof for a humanity challenge the hub issued, completed
+    /// on the player's chain `origin` (hub only).
+    async fn handle_humanity_proof_request(
+        &mut self,
+        origin: ChainId,
+        player: AccountOwner,
+        verifier: AccountOwner,
+        signature: String,
+    ) {
+        let challenge = self
+            .state
+            .humanity_challenges
+            .get(&player)
+            .await
+            .expect("Failed to load humanity challenge");
+        let result = match challenge {
+            Some(challenge) => {
+                self.complete_humanity_challenge(challenge, verifier, &signature)
+                    .await
+            }
+            None => Err(ArcadeError::HumanityChallengeNotFound),
+        };
+        if let Err(error) = result {
+            log::warn!(
+                "Dropped {player}'s humanity proof from {origin}: {}",
+                error.code()
+            );
+        }
+    }
+
+    /// Lift `challenge`. The hub publishes it lifted for every other chain
+    /// to take up.
+    async fn clear_humanity_challenge(&mut self, challenge: &HumanityChallenge) {
+        self.state
+            .humanity_challenges
+            .remove(&challenge.player)
+            .expect("Failed to clear humanity challenge");
+        if self.is_hub_chain() {
+            self.publish_event(ArcadeEvent::HumanityChallengeCleared {
+                player: challenge.player,
+                nonce: challenge.nonce,
+            })
+            .await;
+        }
+    }
+
+    /// Handle scheduling a crossover event (admin only).
+    async fn handle_schedule_crossover(
+        &mut self,
+        owner: AccountOwner,
+        event: CrossoverEventSpec,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        let now = self.runtime.now_micros();
+        match self.state.crossover_schedule.get_mut().add(event, now) {
+            Ok(event_id) => ArcadeResponse::CrossoverScheduled { event_id },
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Handle cancelling a crossover event (admin only).
+    async fn handle_cancel_crossover(
+        &mut self,
+        owner: AccountOwner,
+        event_id: u64,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        match self.state.crossover_schedule.get_mut().cancel(event_id) {
+            Ok(()) => ArcadeResponse::CrossoverCancelled,
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Handle banning or unbanning a kiosk device (admin only). Bans set on
+    /// the hub are published for every other chain to take up.
+    async fn handle_set_device_ban(
+        &mut self,
+        owner: AccountOwner,
+        device_id: String,
+        banned: bool,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        if let Err(e) = kiosk::validate_device_id(&device_id) {
+            return e.into_response();
+        }
+
+        self.update_device_ban(&device_id, banned);
+        if self.is_hub_chain() {
+            self.publish_event(ArcadeEvent::DeviceBanUpdated { device_id, banned })
+                .await;
+        }
+
+        ArcadeResponse::DeviceBanUpdated
+    }
+
+    /// Take up a kiosk rule, player ban or humanity challenge the hub
+    /// published.
+    async fn apply_hub_rule(&mut self, event: ArcadeEvent) {
+        match event {
+            ArcadeEvent::KioskConfigured(config) => self.state.kiosk_config.set(config),
+            ArcadeEvent::DeviceBanUpdated { device_id, banned } => {
+                self.update_device_ban(&device_id, banned);
+            }
+            ArcadeEvent::PlayerBanUpdated {
+                wallet_address,
+                banned,
+            } => self.update_player_ban(&wallet_address, banned),
+            ArcadeEvent::HumanityChallengeIssued(challenge) => {
+                self.state
+                    .humanity_challenges
+                    .insert(&challenge.player, challenge.clone())
+                    .expect("Failed to take up humanity challenge");
+            }
+            ArcadeEvent::HumanityChallengeCleared { player, nonce } => {
+                let challenge = self
+                    .state
+                    .humanity_challenges
+                    .get(&player)
+                    .await
+                    .expect("Failed to load humanity challenge");
+                // A challenge issued since is left in place
+                if challenge.is_some_and(|challenge| challenge.nonce == nonce) {
+                    self.state
+                        .humanity_challenges
+                        .remove(&player)
+                        .expect("Failed to clear humanity challenge");
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Ban or unban a kiosk device on this chain.
+    fn update_device_ban(&mut self, device_id: &str, banned: bool) {
+        if banned {
+            self.state
+                .banned_devices
+                .insert(device_id)
+                .expect("Failed to ban device");
+        } else {
+            self.state
+                .banned_devices
+                .remove(device_id)
+                .expect("Failed to unban device");
+        }
+    }
+
+    /// Handle authorizing or revoking a kiosk gateway (admin only).
+    async fn handle_set_gateway(
+        &mut self,
+        owner: AccountOwner,
+        gateway: AccountOwner,
+        authorized: bool,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+
+        if authorized {
+            self.state
+                .gateways
+                .insert(&gateway)
+                .expect("Failed to authorize gateway");
+        } else {
+            self.state
+                .gateways
+                .remove(&gateway)
+                .expect("Failed to revoke gateway");
+        }
+
+        ArcadeResponse::GatewayUpdated
+    }
+
+    /// Handle setting the registration voucher signer (admin only).
+    async fn handle_set_voucher_signer(
+        &mut self,
+        owner: AccountOwner,
+        signer: Option<AccountOwner>,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        self.state.voucher_signer.set(signer);
+        ArcadeResponse::VoucherSignerUpdated
+    }
+
+    /// Handle a player exporting their progress, locking their record.
+    async fn handle_export_progress(&mut self, owner: AccountOwner) -> ArcadeResponse {
+        let player = match self.state.players.get(&owner).await {
+            Ok(Some(p)) => p,
+            _ => return ArcadeError::PlayerNotRegistered.into_response(),
+        };
+        if self
+            .state
+            .progress_exports
+            .contains_key(&owner)
+            .await
+            .unwrap_or(false)
+        {
+            return ArcadeError::ProgressLocked.into_response();
+        }
+
+        let snapshot = ProgressSnapshot::new(
+            &player,
+            self.runtime.application_id().forget_abi(),
+            self.runtime.chain_id(),
+            self.runtime.now_micros(),
+        );
+        let record_hash = snapshot.hash();
+        let export = ProgressExport {
+            snapshot,
+            record_hash,
+        };
+        self.state
+            .progress_exports
+            .insert(&owner, export.clone())
+            .expect("Failed to record progress export");
+        self.send_to_hub_if_needed(Message::SyncProgressExported(export));
+
+        ArcadeResponse::ProgressExported { record_hash }
+    }
+
+    /// Handle setting the migration source key (admin only).
+    async fn handle_set_migration_source(
+        &mut self,
+        owner: AccountOwner,
+        admin: Option<AccountOwner>,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        self.state.migration_source.set(admin);
+        ArcadeResponse::MigrationSourceUpdated
+    }
+
+    /// Handle a player importing progress exported from another deployment.
+    async fn handle_import_progress(
+        &mut self,
+        owner: AccountOwner,
+        snapshot: ProgressSnapshot,
+        signature: String,
+    ) -> ArcadeResponse {
+        if !self
+            .state
+            .players
+            .contains_key(&owner)
+            .await
+            .unwrap_or(false)
+        {
+            return ArcadeError::PlayerNotRegistered.into_response();
+        }
+        if self
+            .state
+            .progress_exports
+            .contains_key(&owner)
+            .await
+            .unwrap_or(false)
+        {
+            return ArcadeError::ProgressLocked.into_response();
+        }
+        let Some(source_admin) = *self.state.migration_source.get() else {
+            return ArcadeError::InvalidMigrationProof.into_response();
+        };
+        if snapshot.player != owner {
+            return ArcadeError::InvalidMigrationProof.into_response();
+        }
+        let destination = self.runtime.application_id().forget_abi();
+        let record_hash = match snapshot.verify(&source_admin, destination, &signature) {
+            Ok(hash) => hash,
+            Err(e) => return e.into_response(),
+        };
+        if self
+            .state
+            .progress_imports
+            .contains_key(&record_hash)
+            .await
+            .unwrap_or(false)
+        {
+            return ArcadeError::ProgressAlreadyImported.into_response();
+        }
+
+        let import = ProgressImport {
+            player: owner,
+            record_hash,
+            source_application: snapshot.source_application,
+            xp: snapshot.total_xp,
+            games_played: snapshot.games_played,
+            imported_at: self.runtime.now_micros(),
+        };
+
+        // Imports are only unique per chain until the hub sees them, so it
+        // confirms the import before it is credited
+        if let Some(hub_chain_id) = self.remote_hub() {
+            self.send_request(hub_chain_id, Message::ImportProgress(import));
+            return ArcadeResponse::ProgressImported { xp: None };
+        }
+        self.record_progress_import(import.clone()).await;
+        self.credit_progress_import(import).await;
+
+        ArcadeResponse::ProgressImported {
+            xp: Some(snapshot.total_xp),
+        }
+    }
+
+    /// Confirm or refuse a progress import `origin` asked the hub about.
+    async fn handle_import_request(&mut self, origin: ChainId, import: ProgressImport) {
+        let accepted = self.record_progress_import(import.clone()).await;
+        if !accepted {
+            log::warn!(
+                "Refused {}'s import of {} on chain {origin}: {}",
+                import.player,
+                import.record_hash,
+                ArcadeError::ProgressAlreadyImported.code(),
+            );
+        }
+        self.send_request(origin, Message::ProgressImportSettled { import, accepted });
+    }
+
+    /// Record a progress import and count it in the arcade totals, returning
+    /// false if its snapshot was already imported.
+    async fn record_progress_import(&mut self, import: ProgressImport) -> bool {
+        if self
+            .state
+            .progress_imports
+            .contains_key(&import.record_hash)
+            .await
+            .expect("Failed to load progress import")
+        {
+            return false;
+        }
+        if !self.is_shadow_banned(&import.player).await {
+            let total_games = *self.state.total_games_played.get();
+            self.state
+                .total_games_played
+                .set(total_games.saturating_add(import.games_played));
+            let total_xp = *self.state.total_xp_earned.get();
+            self.state
+                .total_xp_earned
+                .set(total_xp.saturating_add(import.xp));
+        }
+        self.state
+            .progress_imports
+            .insert(&import.record_hash.clone(), import)
+            .expect("Failed to record progress import");
+        true
+    }
+
+    /// Add an import's XP and games to its player's record and sync it.
+    async fn credit_progress_import(&mut self, import: ProgressImport) {
+        let Some(mut player) = self
+            .state
+            .players
+            .get(&import.player)
+            .await
+            .expect("Failed to load player")
+        else {
+            log::warn!(
+                "Dropped {}'s import of {}: {}",
+                import.player,
+                import.record_hash,
+                ArcadeError::PlayerNotRegistered.code(),
+            );
+            return;
+        };
+        player.add_xp(import.xp);
+        player.games_played = player.games_played.saturating_add(import.games_played);
+        self.state
+            .players
+            .insert(&import.player, player.clone())
+            .expect("Failed to update player");
+        self.update_leaderboard(&player);
+
+        self.send_to_hub_if_needed(Message::SyncProgressImported(import));
+        self.send_to_hub_if_needed(Message::SyncXpUpdate {
+            wallet_address: player.owner,
+            total_xp: player.total_xp,
+            level: player.level,
+            games_played: player.games_played,
+        });
+    }
+
+    /// Handle entering or leaving maintenance mode (admin only).
+    async fn handle_set_maintenance_mode(
+        &mut self,
+        owner: AccountOwner,
+        until: Option<u64>,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        let now = self.runtime.now_micros();
+        let window = match until {
+            // Maintenance always ends on its own, so a lost admin key can't
+            // leave the arcade read-only for good
+            Some(ends_at) if ends_at > now && ends_at - now <= MAX_MAINTENANCE_MICROS => {
+                Some(MaintenanceWindow {
+                    started_at: now,
+                    ends_at,
+                })
+            }
+            Some(_) => return ArcadeError::InvalidMaintenanceWindow.into_response(),
+            None => None,
+        };
+        self.state.maintenance.set(window);
+        ArcadeResponse::MaintenanceModeUpdated
+    }
+
+    /// Handle reserving a username ahead of registration.
+    async fn handle_reserve_username(
+        &mut self,
+        owner: AccountOwner,
+        username: String,
+        reserved_for: Option<AccountOwner>,
+    ) -> ArcadeResponse {
+        if let Err(e) = validate_username(&username) {
+            return e.into_response();
+        }
+
+        // Reserving on behalf of someone else, or reserving a blocked name,
+        // is an admin privilege
+        let holder = reserved_for.unwrap_or(owner);
+        if holder != owner {
+            if let Err(e) = self.ensure_admin(&owner) {
+                return e.into_response();
+            }
+        }
+        if self.ensure_admin(&owner).is_err() {
+            if let Err(e) = self.ensure_username_not_blocked(&username).await {
+                return e.into_response();
+            }
+        }
+
+        // Reservations are for names not yet registered
+        if self
+            .state
+            .players
+            .contains_key(&holder)
+            .await
+            .unwrap_or(false)
+        {
+            return ArcadeError::PlayerAlreadyRegistered.into_response();
+        }
+
+        // An owner holds at most one reservation, and can't renew it before
+        // it expires; an expired one is released here
+        let timestamp = self.runtime.now_micros();
+        if let Ok(Some(previous)) = self.state.owner_reservations.get(&holder).await {
+            if self
+                .active_reservation(&previous, timestamp)
+                .await
+                .is_some_and(|reservation| reservation.owner == holder)
+            {
+                return ArcadeError::ReservationActive.into_response();
+            }
+        }
+
+        let key = usernames::normalize_username(&username);
+        if let Err(e) = self.ensure_not_auctioned(&key).await {
+            return e.into_response();
+        }
+        if let Err(e) = self
+            .ensure_username_available(&key, &holder, timestamp)
+            .await
+        {
+            return e.into_response();
+        }
+        if self.active_reservation(&key, timestamp).await.is_some() {
+            return ArcadeError::UsernameReserved.into_response();
+        }
+
+        self.recycle_username_claim(&key, &holder).await;
+        let reservation = UsernameReservation::new(username, holder, timestamp);
+        let expires_at = reservation.expires_at;
+        self.state
+            .reservations
+            .insert(&key, reservation)
+            .expect("Failed to insert reservation");
+        self.state
+            .owner_reservations
+            .insert(&holder, key)
+            .expect("Failed to record reservation owner");
+
+        ArcadeResponse::ReservationCreated { expires_at }
+    }
+
+    /// Handle adding or removing a username blocklist term (admin only).
+    async fn handle_set_username_blocked(
+        &mut self,
+        owner: AccountOwner,
+        username: String,
+        blocked: bool,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        let key = match usernames::validate_blocked_username(&username) {
+            Ok(key) => key,
+            Err(e) => return e.into_response(),
+        };
+
+        if blocked {
+            let count = self
+                .state
+                .blocked_usernames
+                .count()
+                .await
+                .expect("Failed to count blocked usernames");
+            let listed = self
+                .state
+                .blocked_usernames
+                .contains(&key)
+                .await
+                .expect("Failed to load blocked usernames");
+            if !listed && count as u64 >= usernames::MAX_BLOCKED_USERNAMES {
+                return ArcadeError::UsernameBlocklistFull.into_response();
+            }
+            self.state
+                .blocked_usernames
+                .insert(&key)
+                .expect("Failed to block username");
+        } else {
+            self.state
+                .blocked_usernames
+                .remove(&key)
+                .expect("Failed to unblock username");
+        }
+
+        ArcadeResponse::UsernameBlocklistUpdated
+    }
+
+    /// Handle putting a premium username up for auction (admin only).
+    async fn handle_start_username_auction(
+        &mut self,
+        owner: AccountOwner,
+        spec: UsernameAuctionSpec,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        let timestamp = self.runtime.now_micros();
+        if let Err(e) = spec.validate(timestamp) {
+            return e.into_response();
+        }
+        let key = usernames::normalize_username(&spec.username);
+        if let Err(e) = self.ensure_not_auctioned(&key).await {
+            return e.into_response();
+        }
+        if self.active_reservation(&key, timestamp).await.is_some() {
+            return ArcadeError::UsernameReserved.into_response();
+        }
+        let open = self
+            .state
+            .auctioned_usernames
+            .count()
+            .await
+            .expect("Failed to count username auctions");
+        if open as u64 >= name_auctions::MAX_OPEN_USERNAME_AUCTIONS {
+            return ArcadeError::TooManyUsernameAuctions.into_response();
+        }
+
+        let auction_id = {
+            let current = *self.state.username_auction_counter.get();
+            self.state.username_auction_counter.set(current + 1);
+            current
+        };
+        self.state
+            .username_auctions
+            .insert(&auction_id, UsernameAuction::new(auction_id, spec, owner))
+            .expect("Failed to insert username auction");
+        self.state
+            .auctioned_usernames
+            .insert(&key, auction_id)
+            .expect("Failed to index username auction");
+
+        ArcadeResponse::UsernameAuctionStarted { auction_id }
+    }
+
+    /// Handle placing a sealed bid on a username auction, escrowing its
+    /// deposit.
+    async fn handle_bid_on_username(
+        &mut self,
+        owner: AccountOwner,
+        auction_id: u64,
+        commitment: CryptoHash,
+        deposit: Amount,
+    ) -> ArcadeResponse {
+        let Some(mut auction) = self.username_auction(auction_id).await else {
+            return ArcadeError::UsernameAuctionNotFound.into_response();
+        };
+        // The handle can only be taken at registration
+        if self
+            .state
+            .players
+            .contains_key(&owner)
+            .await
+            .unwrap_or(false)
+        {
+            return ArcadeError::PlayerAlreadyRegistered.into_response();
+        }
+        let timestamp = self.runtime.now_micros();
+        if let Err(e) = auction.check_bid(&owner, deposit, timestamp) {
+            return e.into_response();
+        }
+        if self.runtime.owner_balance(owner) < deposit {
+            return ArcadeError::InsufficientBidDeposit.into_response();
+        }
+
+        let escrow = self.escrow_account();
+        self.runtime.transfer(owner, escrow, deposit);
+        auction.bids.push(SealedBid {
+            bidder: owner,
+            commitment,
+            deposit,
+            placed_at: timestamp,
+            revealed_amount: None,
+        });
+        self.save_username_auction(auction);
+
+        ArcadeResponse::UsernameBidPlaced
+    }
+
+    /// Handle revealing a sealed bid on a username auction.
+    async fn handle_reveal_username_bid(
+        &mut self,
+        owner: AccountOwner,
+        auction_id: u64,
+        amount: Amount,
+        salt: String,
+    ) -> ArcadeResponse {
+        let Some(mut auction) = self.username_auction(auction_id).await else {
+            return ArcadeError::UsernameAuctionNotFound.into_response();
+        };
+        let timestamp = self.runtime.now_micros();
+        if let Err(e) = auction.reveal(&owner, amount, &salt, timestamp) {
+            return e.into_response();
+        }
+        self.save_username_auction(auction);
+
+        ArcadeResponse::UsernameBidRevealed
+    }
+
+    /// Handle settling a username auction: paying the proceeds to the admin
+    /// who started it, refunding the other bids and reserving the username
+    /// for the winner.
+    async fn handle_settle_username_auction(&mut self, auction_id: u64) -> ArcadeResponse {
+        let Some(mut auction) = self.username_auction(auction_id).await else {
+            return ArcadeError::UsernameAuctionNotFound.into_response();
+        };
+        let timestamp = self.runtime.now_micros();
+        let payouts = match auction.settle(timestamp) {
+            Ok(payouts) => payouts,
+            Err(e) => return e.into_response(),
+        };
+
+        let source = AccountOwner::from(self.runtime.application_id());
+        let chain_id = self.runtime.chain();
+        for (bidder, refund) in payouts.refunds {
+            self.runtime
+                .transfer(source, Account::new(chain_id, bidder), refund);
+        }
+        if !payouts.to_beneficiary.is_zero() {
+            self.runtime.transfer(
+                source,
+                Account::new(chain_id, auction.beneficiary),
+                payouts.to_beneficiary,
+            );
+        }
+
+        let key = usernames::normalize_username(&auction.spec.username);
+        self.state
+            .auctioned_usernames
+            .remove(&key)
+            .expect("Failed to close username auction");
+        let winner = payouts.result.winner;
+        if let Some(winner) = winner {
+            // Won handles are held until the winner registers
+            if let Ok(Some(previous)) = self.state.owner_reservations.get(&winner).await {
+                self.state
+                    .reservations
+                    .remove(&previous)
+                    .expect("Failed to release reservation");
+            }
+            let reservation = UsernameReservation {
+                username: auction.spec.username.clone(),
+                owner: winner,
+                reserved_at: timestamp,
+                expires_at: u64::MAX,
+            };
+            self.state
+                .reservations
+                .insert(&key, reservation)
+                .expect("Failed to insert reservation");
+            self.state
+                .owner_reservations
+                .insert(&winner, key)
+                .expect("Failed to record reservation owner");
+        }
+        self.save_username_auction(auction);
+
+        ArcadeResponse::UsernameAuctionSettled { winner }
+    }
+
+    /// Handle a player deleting their account.
+    async fn handle_delete_account(&mut self, owner: AccountOwner) -> ArcadeResponse {
+        let player = self
+            .state
+            .players
+            .get(&owner)
+            .await
+            .expect("Failed to load player");
+        let Some(player) = player else {
+            return ArcadeError::PlayerNotRegistered.into_response();
+        };
+        if self
+            .state
+            .player_guilds
+            .contains_key(&owner)
+            .await
+            .unwrap_or(false)
+        {
+            return ArcadeError::AccountInGuild.into_response();
+        }
+
+        let recyclable_at = self.delete_account(&player).await;
+
+        self.send_to_hub_if_needed(Message::SyncAccountDeleted {
+            wallet_address: owner,
+        });
+
+        ArcadeResponse::AccountDeleted { recyclable_at }
+    }
+
+    /// Handle flagging an account that has sat idle for the inactivity
+    /// period, starting its username's grace period (admin only).
+    async fn handle_flag_inactive_account(
+        &mut self,
+        owner: AccountOwner,
+        target: AccountOwner,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        let player = self
+            .state
+            .players
+            .get(&target)
+            .await
+            .expect("Failed to load player");
+        let Some(player) = player else {
+            return ArcadeError::PlayerNotRegistered.into_response();
+        };
+
+        let now = self.runtime.now_micros();
+        let last_seen_at = self
+            .last_seen_at(&target)
+            .await
+            .unwrap_or(player.registered_at);
+        if !self
+            .state
+            .username_recycling
+            .get()
+            .is_inactive(last_seen_at, now)
+        {
+            return ArcadeError::AccountNotInactive.into_response();
+        }
+
+        let recyclable_at = self
+            .release_username(&player, ReleaseReason::Inactive, now)
+            .await;
+        ArcadeResponse::AccountFlaggedInactive { recyclable_at }
+    }
+
+    /// Handle updating the username recycling periods (admin only).
+    async fn handle_configure_username_recycling(
+        &mut self,
+        owner: AccountOwner,
+        config: UsernameRecyclingConfig,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        if let Err(e) = config.validate() {
+            return e.into_response();
+        }
+        self.state.username_recycling.set(config);
+        ArcadeResponse::UsernameRecyclingConfigured
+    }
+
+    /// Handle setting the player's own daily play limits.
+    async fn handle_set_play_limits(
+        &mut self,
+        owner: AccountOwner,
+        limits: PlayLimits,
+    ) -> ArcadeResponse {
+        let mut player = match self.state.players.get(&owner).await {
+            Ok(Some(p)) => p,
+            _ => return ArcadeError::PlayerNotRegistered.into_response(),
+        };
+
+        // Tightening is always allowed; loosening waits out the cooldown
+        let timestamp = self.runtime.now_micros();
+        if player.play_limits.is_loosened_by(&limits) {
+            let unlocked_at = player
+                .play_limits_updated_at
+                .unwrap_or(0)
+                .saturating_add(limits::PLAY_LIMIT_COOLDOWN_MICROS);
+            if timestamp < unlocked_at {
+                return ArcadeError::PlayLimitCooldown.into_response();
+            }
+        }
+
+        player.play_limits = limits;
+        player.play_limits_updated_at = Some(timestamp);
+        self.state
+            .players
+            .insert(&owner, player.clone())
+            .expect("Failed to update player");
+
+        self.send_to_hub_if_needed(Message::SyncPlayer(player));
+
+        ArcadeResponse::PlayLimitsUpdated
+    }
+
+    /// Handle changing the timezone the player's daily resets follow.
+    async fn handle_set_timezone(
+        &mut self,
+        owner: AccountOwner,
+        utc_offset_minutes: i32,
+    ) -> ArcadeResponse {
+        let mut player = match self.state.players.get(&owner).await {
+            Ok(Some(p)) => p,
+            _ => return ArcadeError::PlayerNotRegistered.into_response(),
+        };
+        if !(MIN_UTC_OFFSET_MINUTES..=MAX_UTC_OFFSET_MINUTES).contains(&utc_offset_minutes) {
+            return ArcadeError::InvalidUtcOffset.into_response();
+        }
+
+        // Changing timezones could otherwise replay a day's resets
+        let timestamp = self.runtime.now_micros();
+        if let Some(updated_at) = player.utc_offset_updated_at {
+            if timestamp < updated_at.saturating_add(TIMEZONE_COOLDOWN_MICROS) {
+                return ArcadeError::TimezoneCooldown.into_response();
+            }
+        }
+
+        player.utc_offset_minutes = utc_offset_minutes;
+        player.utc_offset_updated_at = Some(timestamp);
+        self.state
+            .players
+            .insert(&owner, player.clone())
+            .expect("Failed to update player");
+
+        self.send_to_hub_if_needed(Message::SyncPlayer(player));
+
+        ArcadeResponse::TimezoneUpdated
+    }
+
+    /// Handle changing the player's locale.
+    async fn handle_set_locale(
+        &mut self,
+        owner: AccountOwner,
+        locale: LocaleSettings,
+    ) -> ArcadeResponse {
+        let mut player = match self.state.players.get(&owner).await {
+            Ok(Some(p)) => p,
+            _ => return ArcadeError::PlayerNotRegistered.into_response(),
+        };
+        if let Err(e) = locale.validate() {
+            return e.into_response();
+        }
+
+        player.locale = locale;
+        self.state
+            .players
+            .insert(&owner, player.clone())
+            .expect("Failed to update player");
+
+        self.send_to_hub_if_needed(Message::SyncPlayer(player));
+
+        ArcadeResponse::LocaleUpdated
+    }
+
+    /// Handle switching the player's scoring category.
+    async fn handle_set_scoring_category(
+        &mut self,
+        owner: AccountOwner,
+        category: ScoringCategory,
+    ) -> ArcadeResponse {
+        let mut player = match self.state.players.get(&owner).await {
+            Ok(Some(p)) => p,
+            _ => return ArcadeError::PlayerNotRegistered.into_response(),
+        };
+
+        player.scoring_category = category;
+        self.state
+            .players
+            .insert(&owner, player.clone())
+            .expect("Failed to update player");
+
+        self.send_to_hub_if_needed(Message::SyncPlayer(player));
+
+        ArcadeResponse::ScoringCategoryUpdated
+    }
+
+    /// Handle storing a snapshot of the public leaderboard (admin only).
+    async fn handle_snapshot_leaderboard(&mut self, owner: AccountOwner) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+
+        let timestamp = self.runtime.now_micros();
+        let snapshot_id = self.take_snapshot(timestamp, false).await;
+
+        ArcadeResponse::SnapshotTaken { snapshot_id }
+    }
+
+    /// Handle checking the next chunk of an invariant section (admin only).
+    async fn handle_check_invariants(
+        &mut self,
+        owner: AccountOwner,
+        section: InvariantSection,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+
+        let now = self.runtime.now_micros();
+        let mut check = self
+            .state
+            .invariant_checks
+            .get(&section)
+            .await
+            .expect("Failed to load invariant check")
+            .unwrap_or_else(|| InvariantCheck::new(section, now));
+        let (violations, complete) = match section {
+            InvariantSection::Leaderboard => self.check_leaderboard(&mut check).await,
+            InvariantSection::ScoreTotals => self.check_score_totals(&mut check).await,
+            InvariantSection::ScorePositions => self.check_score_positions(&mut check).await,
+            InvariantSection::PlayerScores => self.check_player_scores(&mut check).await,
+        };
+
+        check.violations = check.violations.saturating_add(violations.len() as u32);
+        for detail in violations {
+            log::warn!("Invariant violated ({section:?}): {detail}");
+            self.publish_event(ArcadeEvent::InvariantViolated { section, detail })
+                .await;
+        }
+        if complete {
+            self.publish_event(ArcadeEvent::InvariantCheckCompleted {
+                section,
+                checked: check.checked,
+                violations: check.violations,
+            })
+            .await;
+            self.state
+                .invariant_checks
+                .remove(&section)
+                .expect("Failed to finish invariant check");
+        } else {
+            self.state
+                .invariant_checks
+                .insert(&section, check.clone())
+                .expect("Failed to update invariant check");
+        }
+
+        ArcadeResponse::InvariantsChecked {
+            checked: check.checked,
+            violations: check.violations,
+            complete,
+        }
+    }
+
+    /// Handle rebuilding the next chunk of a group of derived views (admin
+    /// only, in maintenance mode).
+    async fn handle_replay_journal(
+        &mut self,
+        owner: AccountOwner,
+        target: ReplayTarget,
+        checkpoint: Option<u64>,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        let now = self.runtime.now_micros();
+        if !self
+            .state
+            .maintenance
+            .get()
+            .is_some_and(|window| window.is_active(now))
+        {
+            return ArcadeError::MaintenanceRequired.into_response();
+        }
+
+        let progress = self
+            .state
+            .replays
+            .get(&target)
+            .await
+            .expect("Failed to load replay");
+        let mut progress = match progress {
+            Some(progress) => progress,
+            None => {
+                let checkpoint = match checkpoint {
+                    Some(index) => {
+                        let found = self
+                            .state
+                            .journal_checkpoints
+                            .get(index as usize)
+                            .await
+                            .expect("Failed to load journal checkpoint");
+                        let Some(found) = found else {
+                            return ArcadeError::CheckpointNotFound.into_response();
+                        };
+                        Some((index, found))
+                    }
+                    None => None,
+                };
+                self.start_replay(target);
+                ReplayProgress::new(target, checkpoint.as_ref(), now)
+            }
+        };
+        let complete = match target {
+            ReplayTarget::Leaderboard => self.replay_leaderboard(&mut progress).await,
+            ReplayTarget::ScoreIndexes => self.replay_score_indexes(&mut progress).await,
+            ReplayTarget::Totals => self.replay_totals(&mut progress).await,
+        };
+
+        if complete {
+            log::info!(
+                "Replay of {target:?} finished after {} entries",
+                progress.replayed
+            );
+            self.state
+                .replays
+                .remove(&target)
+                .expect("Failed to finish replay");
+        } else {
+            self.state
+                .replays
+                .insert(&target, progress.clone())
+                .expect("Failed to update replay");
+        }
+        ArcadeResponse::JournalReplayed {
+            replayed: progress.replayed,
+            complete,
+        }
+    }
+
+    /// Handle changing how much of each archived snapshot is kept.
+    async fn handle_set_history_retention(
+        &mut self,
+        owner: AccountOwner,
+        top_k: Option<u32>,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        if top_k == Some(0) {
+            return ArcadeError::InvalidHistoryRetention.into_response();
+        }
+
+        self.state.history_top_k.set(top_k);
+        // Revisit every archived snapshot under the new limit
+        self.state.history_prune_cursor.set(0);
+        // Seasons are few, so their standings are trimmed at once
+        if let Some(top_k) = top_k {
+            self.trim_season_archives(top_k).await;
+        }
+        ArcadeResponse::HistoryRetentionUpdated
+    }
+
+    /// Handle starting the first season (admin only).
+    async fn handle_start_season(
+        &mut self,
+        owner: AccountOwner,
+        length_days: u32,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        if !self.is_hub_chain() {
+            return ArcadeError::SeasonsRequireHub.into_response();
+        }
+        if !(seasons::MIN_SEASON_LENGTH_DAYS..=seasons::MAX_SEASON_LENGTH_DAYS)
+            .contains(&length_days)
+        {
+            return ArcadeError::InvalidSeasonLength.into_response();
+        }
+        if self.state.current_season.get().is_some() {
+            return ArcadeError::SeasonAlreadyRunning.into_response();
+        }
+
+        let season = Season::first(length_days, self.runtime.now_micros());
+        self.state.current_season.set(Some(season));
+        ArcadeResponse::SeasonStarted {
+            season: season.number,
+        }
+    }
+
+    /// Handle ending the current season early (admin only).
+    async fn handle_roll_over_season(&mut self, owner: AccountOwner) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        let Some(season) = *self.state.current_season.get() else {
+            return ArcadeError::NoActiveSeason.into_response();
+        };
+
+        let next = self.roll_over_season(season).await;
+        ArcadeResponse::SeasonStarted {
+            season: next.number,
+        }
+    }
+
+    /// Handle scheduling a raffle (admin only), escrowing its prize.
+    async fn handle_schedule_raffle(
+        &mut self,
+        owner: AccountOwner,
+        spec: RaffleSpec,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        if !self.is_hub_chain() {
+            return ArcadeError::RafflesRequireHub.into_response();
+        }
+        if let Err(e) = spec.validate(self.runtime.now_micros()) {
+            return e.into_response();
+        }
+        let open = self
+            .state
+            .open_raffles
+            .count()
+            .await
+            .expect("Failed to count open raffles");
+        if open as u64 >= raffles::MAX_OPEN_RAFFLES {
+            return ArcadeError::TooManyRaffles.into_response();
+        }
+        if self.runtime.owner_balance(owner) < spec.prize {
+            return ArcadeError::InsufficientRafflePrize.into_response();
+        }
+
+        let escrow = self.escrow_account();
+        self.runtime.transfer(owner, escrow, spec.prize);
+
+        let raffle_id = {
+            let current = *self.state.raffle_counter.get();
+            self.state.raffle_counter.set(current + 1);
+            current
+        };
+        self.state
+            .raffles
+            .insert(&raffle_id, Raffle::new(raffle_id, spec, owner))
+            .expect("Failed to insert raffle");
+        self.state
+            .open_raffles
+            .insert(&raffle_id)
+            .expect("Failed to open raffle");
+
+        ArcadeResponse::RaffleScheduled { raffle_id }
+    }
+
+    /// Handle drawing a raffle, paying its prize to the winner, or back to
+    /// its funder if nobody earned a ticket.
+    async fn handle_draw_raffle(
+        &mut self,
+        raffle_id: u64,
+        secret: Option<String>,
+    ) -> ArcadeResponse {
+        let Some(mut raffle) = self.state.raffles.get(&raffle_id).await.ok().flatten() else {
+            return ArcadeError::RaffleNotFound.into_response();
+        };
+        let now = self.runtime.now_micros();
+        let winning_ticket = match raffle.winning_ticket(secret.as_deref(), now) {
+            Ok(winning_ticket) => winning_ticket,
+            Err(e) => return e.into_response(),
+        };
+
+        // Collect the raffle's ticket holders, releasing their entries
+        let mut holders = Vec::new();
+        self.state
+            .raffle_tickets
+            .for_each_index_value(|(id, holder), tickets| {
+                if id == raffle_id {
+                    holders.push((holder, *tickets));
+                }
+                Ok(())
+            })
+            .await
+            .expect("Failed to read raffle tickets");
+        for (holder, _) in &holders {
+            self.state
+                .raffle_tickets
+                .remove(&(raffle_id, *holder))
+                .expect("Failed to release raffle tickets");
+        }
+        let winner = winning_ticket.and_then(|ticket| raffles::holder_of(holders, ticket));
+
+        let source = AccountOwner::from(self.runtime.application_id());
+        let destination = Account::new(self.runtime.chain(), winner.unwrap_or(raffle.funded_by));
+        self.runtime
+            .transfer(source, destination, raffle.spec.prize);
+
+        raffle.draw = Some(RaffleDraw {
+            winner,
+            winning_ticket,
+            secret,
+            drawn_at: now,
+        });
+        self.state
+            .raffles
+            .insert(&raffle_id, raffle)
+            .expect("Failed to update raffle");
+        self.state
+            .open_raffles
+            .remove(&raffle_id)
+            .expect("Failed to close raffle");
+
+        ArcadeResponse::RaffleDrawn { winner }
+    }
+
+    /// Handle scheduling a boss battle (admin only).
+    async fn handle_schedule_boss_battle(
+        &mut self,
+        owner: AccountOwner,
+        spec: BossBattleSpec,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        if !self.is_hub_chain() {
+            return ArcadeError::BossBattlesRequireHub.into_response();
+        }
+        let now = self.runtime.now_micros();
+        if let Err(e) = spec.validate(now) {
+            return e.into_response();
+        }
+        self.settle_escaped_boss_battles(now).await;
+        let open = self
+            .state
+            .open_boss_battles
+            .count()
+            .await
+            .expect("Failed to count open boss battles");
+        if open as u64 >= bosses::MAX_OPEN_BOSS_BATTLES {
+            return ArcadeError::TooManyBossBattles.into_response();
+        }
+
+        let battle_id = {
+            let current = *self.state.boss_battle_counter.get();
+            self.state.boss_battle_counter.set(current + 1);
+            current
+        };
+        self.state
+            .boss_battles
+            .insert(&battle_id, BossBattle::new(battle_id, spec))
+            .expect("Failed to insert boss battle");
+        self.state
+            .open_boss_battles
+            .insert(&battle_id)
+            .expect("Failed to open boss battle");
+
+        ArcadeResponse::BossBattleScheduled { battle_id }
+    }
+
+    /// Handle setting the theme of the current or a future season (admin
+    /// only).
+    async fn handle_set_season_theme(
+        &mut self,
+        owner: AccountOwner,
+        season: u32,
+        theme: SeasonTheme,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        if !self.is_hub_chain() {
+            return ArcadeError::SeasonsRequireHub.into_response();
+        }
+        if let Err(e) = theme.validate() {
+            return e.into_response();
+        }
+        let current = self
+            .state
+            .current_season
+            .get()
+            .map_or(1, |season| season.number);
+        if season < current {
+            return ArcadeError::SeasonAlreadyEnded.into_response();
+        }
+
+        self.state
+            .season_themes
+            .insert(&season, theme)
+            .expect("Failed to set season theme");
+        ArcadeResponse::SeasonThemeSet
+    }
+
+    /// Handle blocking or unblocking another player.
+    async fn handle_set_block(
+        &mut self,
+        owner: AccountOwner,
+        target: AccountOwner,
+        blocked: bool,
+    ) -> ArcadeResponse {
+        if !self
+            .state
+            .players
+            .contains_key(&owner)
+            .await
+            .unwrap_or(false)
+        {
+            return ArcadeError::PlayerNotRegistered.into_response();
+        }
+        if target == owner {
+            return ArcadeError::CannotBlockSelf.into_response();
+        }
+
+        if let Err(e) = self.update_blocklist(owner, target, blocked).await {
+            return e.into_response();
+        }
+
+        self.send_to_hub_if_needed(Message::SyncBlock {
+            wallet_address: owner,
+            target,
+            blocked,
+        });
+
+        ArcadeResponse::BlocklistUpdated
+    }
+
+    /// Handle buying a streak shield.
+    async fn handle_buy_streak_shield(&mut self, owner: AccountOwner) -> ArcadeResponse {
+        let Some(player) = self.state.players.get(&owner).await.ok().flatten() else {
+            return ArcadeError::PlayerNotRegistered.into_response();
+        };
+
+        let timestamp = self.runtime.now_micros();
+        let mut streak = self
+            .state
+            .streaks
+            .get(&owner)
+            .await
+            .expect("Failed to load streak")
+            .unwrap_or_default();
+        if let Err(e) = streak.gain_shield(player.local_day(timestamp)) {
+            return e.into_response();
+        }
+        if self.runtime.owner_balance(owner) < streaks::SHIELD_PRICE {
+            return ArcadeError::InsufficientShieldPayment.into_response();
+        }
+
+        let account = self.escrow_account();
+        self.runtime.transfer(owner, account, streaks::SHIELD_PRICE);
+        let shields = streak.shields;
+        self.state
+            .streaks
+            .insert(&owner, streak)
+            .expect("Failed to update streak");
+
+        ArcadeResponse::StreakShieldBought { shields }
+    }
+
+    /// Handle setting a personal goal.
+    async fn handle_add_goal(&mut self, owner: AccountOwner, spec: GoalSpec) -> ArcadeResponse {
+        let Some(player) = self.state.players.get(&owner).await.ok().flatten() else {
+            return ArcadeError::PlayerNotRegistered.into_response();
+        };
+
+        let timestamp = self.runtime.now_micros();
+        let mut goals = self
+            .state
+            .goals
+            .get(&owner)
+            .await
+            .expect("Failed to load goals")
+            .unwrap_or_default();
+        let goal_id = match goals.add(spec, &player, timestamp) {
+            Ok(goal_id) => goal_id,
+            Err(e) => return e.into_response(),
+        };
+        self.state
+            .goals
+            .insert(&owner, goals)
+            .expect("Failed to update goals");
+
+        ArcadeResponse::GoalAdded { goal_id }
+    }
+
+    /// Handle removing a personal goal.
+    async fn handle_remove_goal(&mut self, owner: AccountOwner, goal_id: u64) -> ArcadeResponse {
+        let Some(mut goals) = self.state.goals.get(&owner).await.ok().flatten() else {
+            return ArcadeError::GoalNotFound.into_response();
+        };
+        if let Err(e) = goals.remove(goal_id) {
+            return e.into_response();
+        }
+        self.state
+            .goals
+            .insert(&owner, goals)
+            .expect("Failed to update goals");
+
+        ArcadeResponse::GoalRemoved
+    }
+
+    /// Handle following or unfollowing another player.
+    async fn handle_set_follow(
+        &mut self,
+        owner: AccountOwner,
+        target: AccountOwner,
+        followed: bool,
+    ) -> ArcadeResponse {
+        if !self
+            .state
+            .players
+            .contains_key(&owner)
+            .await
+            .unwrap_or(false)
+        {
+            return ArcadeError::PlayerNotRegistered.into_response();
+        }
+        if target == owner {
+            return ArcadeError::CannotFollowSelf.into_response();
+        }
+        if let Err(e) = self.update_follow(owner, target, followed).await {
+            return e.into_response();
+        }
+
+        self.send_to_hub_if_needed(Message::SyncFollow {
+            wallet_address: owner,
+            target,
+            followed,
+        });
+
+        ArcadeResponse::FollowsUpdated
+    }
+
+    /// Handle adding or removing a friend.
+    async fn handle_set_friend(
+        &mut self,
+        owner: AccountOwner,
+        target: AccountOwner,
+        added: bool,
+    ) -> ArcadeResponse {
+        if !self
+            .state
+            .players
+            .contains_key(&owner)
+            .await
+            .unwrap_or(false)
+        {
+            return ArcadeError::PlayerNotRegistered.into_response();
+        }
+        if target == owner {
+            return ArcadeError::CannotFriendSelf.into_response();
+        }
+        if let Err(e) = self.update_friend(owner, target, added).await {
+            return e.into_response();
+        }
+
+        self.send_to_hub_if_needed(Message::SyncFriend {
+            wallet_address: owner,
+            target,
+            added,
+        });
+
+        ArcadeResponse::FriendsUpdated
+    }
+
+    /// Handle sending kudos to another player.
+    async fn handle_give_kudos(
+        &mut self,
+        owner: AccountOwner,
+        recipient: AccountOwner,
+    ) -> ArcadeResponse {
+        if !self
+            .state
+            .players
+            .contains_key(&owner)
+            .await
+            .unwrap_or(false)
+        {
+            return ArcadeError::PlayerNotRegistered.into_response();
+        }
+        if recipient == owner {
+            return ArcadeError::CannotKudosSelf.into_response();
+        }
+        let timestamp = self.runtime.now_micros();
+        if let Err(e) = self.give_kudos(owner, recipient, timestamp).await {
+            return e.into_response();
+        }
+
+        self.send_to_hub_if_needed(Message::SyncKudos {
+            wallet_address: owner,
+            recipient,
+            timestamp,
+        });
+
+        ArcadeResponse::KudosSent
+    }
+
+    /// Handle leaving or taking back an emote on a record or leaderboard
+    /// entry.
+    async fn handle_react(
+        &mut self,
+        owner: AccountOwner,
+        target: ReactionTarget,
+        emote: Emote,
+        added: bool,
+    ) -> ArcadeResponse {
+        if !self
+            .state
+            .players
+            .contains_key(&owner)
+            .await
+            .unwrap_or(false)
+        {
+            return ArcadeError::PlayerNotRegistered.into_response();
+        }
+        // Emotes can be taken back after their target drops out of view
+        if added {
+            if let Err(e) = self.ensure_reactable(target).await {
+                return e.into_response();
+            }
+        }
+
+        if let Err(e) = self.update_reaction(owner, target, emote, added).await {
+            return e.into_response();
+        }
+
+        self.send_to_hub_if_needed(Message::SyncReaction {
+            wallet_address: owner,
+            target,
+            emote,
+            added,
+        });
+
+        ArcadeResponse::ReactionsUpdated
+    }
+
+    /// Handle a mentor offering to take on an apprentice.
+    async fn handle_offer_mentorship(
+        &mut self,
+        owner: AccountOwner,
+        apprentice: AccountOwner,
+    ) -> ArcadeResponse {
+        let Some(mentor_player) = self.state.players.get(&owner).await.ok().flatten() else {
+            return ArcadeError::PlayerNotRegistered.into_response();
+        };
+        let Some(apprentice_player) = self.state.players.get(&apprentice).await.ok().flatten()
+        else {
+            return ArcadeError::PlayerNotRegistered.into_response();
+        };
+        if let Err(e) = mentorship::check_pairing(&mentor_player, &apprentice_player) {
+            return e.into_response();
+        }
+        if let Err(e) =
+            social::ensure_not_blocked(self.blocklist(&apprentice).await.as_ref(), &owner)
+        {
+            return e.into_response();
+        }
+        if self
+            .state
+            .mentorships
+            .contains_key(&apprentice)
+            .await
+            .unwrap_or(false)
+        {
+            return ArcadeError::AlreadyMentored.into_response();
+        }
+        if self.mentor_record(owner).await.is_full() {
+            return ArcadeError::TooManyApprentices.into_response();
+        }
+
+        let mut offers = self.mentorship_offers(&apprentice).await;
+        if let Err(e) = offers.offer(owner) {
+            return e.into_response();
+        }
+        self.save_mentorship_offers(apprentice, offers);
+
+        ArcadeResponse::MentorshipOffered
+    }
+
+    /// Handle an apprentice accepting a mentorship offer.
+    async fn handle_accept_mentorship(
+        &mut self,
+        owner: AccountOwner,
+        mentor: AccountOwner,
+    ) -> ArcadeResponse {
+        let Some(apprentice_player) = self.state.players.get(&owner).await.ok().flatten() else {
+            return ArcadeError::PlayerNotRegistered.into_response();
+        };
+        let mut offers = self.mentorship_offers(&owner).await;
+        if !offers.withdraw(&mentor) {
+            return ArcadeError::MentorshipOfferNotFound.into_response();
+        }
+        let Some(mentor_player) = self.state.players.get(&mentor).await.ok().flatten() else {
+            return ArcadeError::PlayerNotRegistered.into_response();
+        };
+
+        // Either player's level may have changed since the offer was made
+        if let Err(e) = mentorship::check_pairing(&mentor_player, &apprentice_player) {
+            return e.into_response();
+        }
+        if self
+            .state
+            .mentorships
+            .contains_key(&owner)
+            .await
+            .unwrap_or(false)
+        {
+            return ArcadeError::AlreadyMentored.into_response();
+        }
+        let mut record = self.mentor_record(mentor).await;
+        if let Err(e) = record.add_apprentice(owner) {
+            return e.into_response();
+        }
+
+        self.state
+            .mentors
+            .insert(&mentor, record)
+            .expect("Failed to update mentor");
+        self.state
+            .mentorships
+            .insert(
+                &owner,
+                Mentorship {
+                    mentor,
+                    apprentice: owner,
+                    started_at: self.runtime.now_micros(),
+                    mentor_xp_earned: 0,
+                },
+            )
+            .expect("Failed to store mentorship");
+        // With a mentor, the apprentice's other offers lapse
+        self.save_mentorship_offers(owner, MentorshipOffers::default());
+
+        ArcadeResponse::MentorshipStarted
+    }
+
+    /// Handle either side ending a mentorship or a pending offer.
+    async fn handle_end_mentorship(
+        &mut self,
+        owner: AccountOwner,
+        counterpart: AccountOwner,
+    ) -> ArcadeResponse {
+        let pairings = [(owner, counterpart), (counterpart, owner)];
+        for (mentor, apprentice) in pairings {
+            if self.end_mentorship(mentor, apprentice, false).await {
+                return ArcadeResponse::MentorshipEnded;
+            }
+        }
+
+        let mut withdrawn = false;
+        for (mentor, apprentice) in pairings {
+            let mut offers = self.mentorship_offers(&apprentice).await;
+            if offers.withdraw(&mentor) {
+                self.save_mentorship_offers(apprentice, offers);
+                withdrawn = true;
+            }
+        }
+        if !withdrawn {
+            return ArcadeError::MentorshipNotFound.into_response();
+        }
+        ArcadeResponse::MentorshipEnded
+    }
+
+    /// Handle founding a guild.
+    async fn handle_create_guild(&mut self, owner: AccountOwner, name: String) -> ArcadeResponse {
+        if !self
+            .state
+            .players
+            .contains_key(&owner)
+            .await
+            .unwrap_or(false)
+        {
+            return ArcadeError::PlayerNotRegistered.into_response();
+        }
+        if let Err(e) = guilds::validate_guild_name(&name) {
+            return e.into_response();
+        }
+        if self
+            .state
+            .player_guilds
+            .contains_key(&owner)
+            .await
+            .unwrap_or(false)
+        {
+            return ArcadeError::AlreadyInGuild.into_response();
+        }
+        let key = guilds::normalize_guild_name(&name);
+        if self
+            .state
+            .guild_names
+            .contains_key(&key)
+            .await
+            .unwrap_or(false)
+        {
+            return ArcadeError::GuildNameTaken.into_response();
+        }
+
+        let guild_id = {
+            let current = *self.state.guild_counter.get();
+            self.state.guild_counter.set(current + 1);
+            current
+        };
+        let guild = Guild::new(guild_id, name.clone(), owner, self.runtime.now_micros());
+        self.index_guild(&guild);
+        self.save_guild(guild);
+        self.state
+            .guild_contributions
+            .remove(&owner)
+            .expect("Failed to reset guild contribution");
+        self.audit_guild(guild_id, GuildAction::Created, owner, None, Some(name))
+            .await;
+
+        ArcadeResponse::GuildCreated { guild_id }
+    }
+
+    /// Handle an officer or the leader inviting a player to their guild.
+    async fn handle_invite_to_guild(
+        &mut self,
+        owner: AccountOwner,
+        player: AccountOwner,
+    ) -> ArcadeResponse {
+        let (mut guild, _) = match self.guild_for_action(&owner, GuildPermission::Invite).await {
+            Ok(found) => found,
+            Err(e) => return e.into_response(),
+        };
+        if !self
+            .state
+            .players
+            .contains_key(&player)
+            .await
+            .unwrap_or(false)
+        {
+            return ArcadeError::PlayerNotRegistered.into_response();
+        }
+        if let Err(e) = social::ensure_not_blocked(self.blocklist(&player).await.as_ref(), &owner) {
+            return e.into_response();
+        }
+        if self
+            .state
+            .player_guilds
+            .contains_key(&player)
+            .await
+            .unwrap_or(false)
+        {
+            return ArcadeError::AlreadyInGuild.into_response();
+        }
+        if let Err(e) = guild.invite(player) {
+            return e.into_response();
+        }
+
+        let guild_id = guild.id;
+        self.save_guild(guild);
+        self.audit_guild(guild_id, GuildAction::Invited, owner, Some(player), None)
+            .await;
+
+        ArcadeResponse::GuildUpdated
+    }
+
+    /// Handle a player joining a guild that invited them.
+    async fn handle_join_guild(&mut self, owner: AccountOwner, guild_id: u64) -> ArcadeResponse {
+        if let Err(e) = self.ensure_guildless_player(&owner).await {
+            return e.into_response();
+        }
+        let Some(mut guild) = self.state.guilds.get(&guild_id).await.ok().flatten() else {
+            return ArcadeError::GuildNotFound.into_response();
+        };
+        let tier = guilds::guild_tier(self.guild_xp(guild_id).await);
+        if let Err(e) = guild.join(owner, self.runtime.now_micros(), &tier) {
+            return e.into_response();
+        }
+
+        self.record_guild_join(guild, owner, None).await;
+
+        ArcadeResponse::GuildUpdated
+    }
+
+    /// Handle a member leaving their guild, disbanding it if they were the
+    /// last.
+    async fn handle_leave_guild(&mut self, owner: AccountOwner) -> ArcadeResponse {
+        let mut guild = match self.guild_of(&owner).await {
+            Ok(guild) => guild,
+            Err(e) => return e.into_response(),
+        };
+        let guild_id = guild.id;
+        let last_member = guild.members.len() == 1;
+        if !last_member && guild.role_of(&owner) == Some(GuildRole::Leader) {
+            return ArcadeError::GuildLeaderCannotLeave.into_response();
+        }
+
+        if last_member {
+            self.disband_guild(guild).await;
+            return ArcadeResponse::GuildUpdated;
+        }
+
+        self.state
+            .player_guilds
+            .remove(&owner)
+            .expect("Failed to update guild membership");
+        guild.remove(&owner);
+        self.save_guild(guild);
+        self.audit_guild(guild_id, GuildAction::Left, owner, None, None)
+            .await;
+
+        ArcadeResponse::GuildUpdated
+    }
+
+    /// Handle an officer or the leader removing a lower-ranked member.
+    async fn handle_kick_from_guild(
+        &mut self,
+        owner: AccountOwner,
+        member: AccountOwner,
+    ) -> ArcadeResponse {
+        let (mut guild, role) = match self.guild_for_action(&owner, GuildPermission::Kick).await {
+            Ok(found) => found,
+            Err(e) => return e.into_response(),
+        };
+        match guild.role_of(&member) {
+            Some(member_role) if member_role < role => {}
+            Some(_) => return ArcadeError::GuildPermissionDenied.into_response(),
+            None => return ArcadeError::NotInGuild.into_response(),
+        }
+
+        let guild_id = guild.id;
+        guild.remove(&member);
+        self.save_guild(guild);
+        self.state
+            .player_guilds
+            .remove(&member)
+            .expect("Failed to update guild membership");
+        self.audit_guild(guild_id, GuildAction::Kicked, owner, Some(member), None)
+            .await;
+
+        ArcadeResponse::GuildUpdated
+    }
+
+    /// Handle the leader renaming their guild.
+    async fn handle_rename_guild(&mut self, owner: AccountOwner, name: String) -> ArcadeResponse {
+        let (mut guild, _) = match self.guild_for_action(&owner, GuildPermission::Rename).await {
+            Ok(found) => found,
+            Err(e) => return e.into_response(),
+        };
+        if let Err(e) = guilds::validate_guild_name(&name) {
+            return e.into_response();
+        }
+        let key = guilds::normalize_guild_name(&name);
+        match self.state.guild_names.get(&key).await.ok().flatten() {
+            Some(holder) if holder != guild.id => {
+                return ArcadeError::GuildNameTaken.into_response();
+            }
+            _ => {}
+        }
+
+        self.state
+            .guild_names
+            .remove(&guilds::normalize_guild_name(&guild.name))
+            .expect("Failed to release guild name");
+        self.state
+            .guild_names
+            .insert(&key, guild.id)
+            .expect("Failed to index guild name");
+        let guild_id = guild.id;
+        guild.name = name.clone();
+        self.save_guild(guild);
+        self.audit_guild(guild_id, GuildAction::Renamed, owner, None, Some(name))
+            .await;
+
+        ArcadeResponse::GuildUpdated
+    }
+
+    /// Handle the leader changing a member's role.
+    async fn handle_set_guild_role(
+        &mut self,
+        owner: AccountOwner,
+        member: AccountOwner,
+        role: GuildRole,
+    ) -> ArcadeResponse {
+        let (mut guild, _) = match self
+            .guild_for_action(&owner, GuildPermission::AssignRoles)
+            .await
+        {
+            Ok(found) => found,
+            Err(e) => return e.into_response(),
+        };
+        // The leader steps down by naming a successor, never directly
+        if member == owner {
+            return ArcadeError::GuildPermissionDenied.into_response();
+        }
+        if let Err(e) = guild.set_role(&member, role) {
+            return e.into_response();
+        }
+
+        let guild_id = guild.id;
+        self.save_guild(guild);
+        self.audit_guild(
+            guild_id,
+            GuildAction::RoleChanged,
+            owner,
+            Some(member),
+            Some(format!("{role:?}")),
+        )
+        .await;
+
+        ArcadeResponse::GuildUpdated
+    }
+
+    /// Handle an officer or the leader setting or clearing their guild's
+    /// announcement.
+    async fn handle_set_guild_announcement(
+        &mut self,
+        owner: AccountOwner,
+        announcement: Option<String>,
+    ) -> ArcadeResponse {
+        let (mut guild, _) = match self
+            .guild_for_action(&owner, GuildPermission::Announce)
+            .await
+        {
+            Ok(found) => found,
+            Err(e) => return e.into_response(),
+        };
+        if let Some(text) = &announcement {
+            if let Err(e) = guilds::validate_announcement(text) {
+                return e.into_response();
+            }
+        }
+
+        let guild_id = guild.id;
+        guild.announcement = announcement.clone().map(|text| GuildAnnouncement {
+            text,
+            author: owner,
+            posted_at: self.runtime.now_micros(),
+        });
+        self.save_guild(guild);
+        self.audit_guild(guild_id, GuildAction::Announced, owner, None, announcement)
+            .await;
+
+        ArcadeResponse::GuildUpdated
+    }
+
+    /// Handle the leader choosing how their guild's prizes are split.
+    async fn handle_set_guild_payout_rule(
+        &mut self,
+        owner: AccountOwner,
+        rule: GuildPayoutRule,
+    ) -> ArcadeResponse {
+        let (guild, _) = match self
+            .guild_for_action(&owner, GuildPermission::SetPayoutRule)
+            .await
+        {
+            Ok(found) => found,
+            Err(e) => return e.into_response(),
+        };
+        if let Err(e) = rule.validate(&guild) {
+            return e.into_response();
+        }
+
+        let detail = format!("{:?}", rule.split);
+        self.state
+            .guild_payout_rules
+            .insert(&guild.id, rule.clone())
+            .expect("Failed to update guild payout rule");
+        self.audit_guild(
+            guild.id,
+            GuildAction::PayoutRuleChanged,
+            owner,
+            None,
+            Some(detail),
+        )
+        .await;
+        self.send_to_hub_if_needed(Message::SyncGuildPayoutRule {
+            guild_id: guild.id,
+            rule,
+        });
+
+        ArcadeResponse::GuildPayoutRuleUpdated
+    }
+
+    /// Handle awarding a prize to a guild (admin only), escrowing it and
+    /// recording each member's share by the guild's payout rule.
+    async fn handle_award_guild_prize(
+        &mut self,
+        owner: AccountOwner,
+        guild_id: u64,
+        amount: Amount,
+        note: String,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        if !self.is_hub_chain() {
+            return ArcadeError::GuildPrizesRequireHub.into_response();
+        }
+        if let Err(e) = guild_prizes::validate_prize(amount, &note) {
+            return e.into_response();
+        }
+        let Ok(Some(guild)) = self.state.guilds.get(&guild_id).await else {
+            return ArcadeError::GuildNotFound.into_response();
+        };
+        if self.runtime.owner_balance(owner) < amount {
+            return ArcadeError::InsufficientGuildPrize.into_response();
+        }
+
+        let rule = self
+            .state
+            .guild_payout_rules
+            .get(&guild_id)
+            .await
+            .expect("Failed to load guild payout rule")
+            .unwrap_or_default();
+        let mut members = Vec::new();
+        for member in &guild.members {
+            let xp = self
+                .state
+                .guild_contributions
+                .get(&member.owner)
+                .await
+                .expect("Failed to load guild contribution")
+                .filter(|contribution| contribution.guild_id == guild_id)
+                .map_or(0, |contribution| contribution.xp_contributed);
+            members.push((member.owner, xp));
+        }
+        let shares = rule.shares_of(amount, &members);
+
+        let escrow = self.escrow_account();
+        self.runtime.transfer(owner, escrow, amount);
+
+        let prize_id = {
+            let current = *self.state.guild_prize_counter.get();
+            self.state.guild_prize_counter.set(current + 1);
+            current
+        };
+        let timestamp = self.runtime.now_micros();
+        self.state
+            .guild_prizes
+            .insert(
+                &prize_id,
+                GuildPrize {
+                    id: prize_id,
+                    guild_id,
+                    amount,
+                    note,
+                    split: rule.split,
+                    awarded_by: owner,
+                    awarded_at: timestamp,
+                },
+            )
+            .expect("Failed to insert guild prize");
+        for (member, amount) in shares {
+            self.state
+                .prize_claims
+                .insert(
+                    &(prize_id, member),
+                    PrizeClaim {
+                        prize_id,
+                        guild_id,
+                        member,
+                        amount,
+                        claimed_at: None,
+                    },
+                )
+                .expect("Failed to record prize claim");
+        }
+
+        ArcadeResponse::GuildPrizeAwarded { prize_id }
+    }
+
+    /// Handle a member collecting their share of a guild prize from escrow.
+    async fn handle_claim_guild_prize(
+        &mut self,
+        owner: AccountOwner,
+        prize_id: u64,
+    ) -> ArcadeResponse {
+        if !self.is_hub_chain() {
+            return ArcadeError::GuildPrizesRequireHub.into_response();
+        }
+        let Ok(Some(mut claim)) = self.state.prize_claims.get(&(prize_id, owner)).await else {
+            return ArcadeError::GuildPrizeClaimNotFound.into_response();
+        };
+        if claim.claimed_at.is_some() {
+            return ArcadeError::GuildPrizeAlreadyClaimed.into_response();
+        }
+
+        let source = AccountOwner::from(self.runtime.application_id());
+        let destination = Account::new(self.runtime.chain(), owner);
+        self.runtime.transfer(source, destination, claim.amount);
+
+        let amount = claim.amount;
+        claim.claimed_at = Some(self.runtime.now_micros());
+        self.state
+            .prize_claims
+            .insert(&(prize_id, owner), claim)
+            .expect("Failed to update prize claim");
+
+        ArcadeResponse::GuildPrizeClaimed { amount }
+    }
+
+    /// Handle an officer or the leader registering an invite code for their
+    /// guild.
+    async fn handle_create_invite_code(
+        &mut self,
+        owner: AccountOwner,
+        code_hash: CryptoHash,
+        max_uses: u32,
+        duration_hours: u32,
+    ) -> ArcadeResponse {
+        let (guild, _) = match self.guild_for_action(&owner, GuildPermission::Invite).await {
+            Ok(found) => found,
+            Err(e) => return e.into_response(),
+        };
+        if self
+            .state
+            .invite_codes
+            .contains_key(&code_hash)
+            .await
+            .unwrap_or(false)
+        {
+            return ArcadeError::InviteCodeExists.into_response();
+        }
+        let timestamp = self.runtime.now_micros();
+        let code = match InviteCode::new(
+            code_hash,
+            guild.id,
+            owner,
+            max_uses,
+            duration_hours,
+            timestamp,
+        ) {
+            Ok(code) => code,
+            Err(e) => return e.into_response(),
+        };
+
+        self.state
+            .invite_codes
+            .insert(&code_hash, code)
+            .expect("Failed to store invite code");
+
+        ArcadeResponse::InviteCodeUpdated
+    }
+
+    /// Handle a player joining a guild with an invite code.
+    async fn handle_redeem_invite_code(
+        &mut self,
+        owner: AccountOwner,
+        code: String,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_guildless_player(&owner).await {
+            return e.into_response();
+        }
+        let code_hash = invites::hash_code(&code);
+        let Some(mut invite) = self.state.invite_codes.get(&code_hash).await.ok().flatten() else {
+            return ArcadeError::InvalidInviteCode.into_response();
+        };
+        let timestamp = self.runtime.now_micros();
+        if !invite.is_active(timestamp) {
+            self.state
+                .invite_codes
+                .remove(&code_hash)
+                .expect("Failed to drop expired invite code");
+            return ArcadeError::InviteCodeExpired.into_response();
+        }
+        let Some(mut guild) = self.state.guilds.get(&invite.guild_id).await.ok().flatten() else {
+            return ArcadeError::GuildNotFound.into_response();
+        };
+        let tier = guilds::guild_tier(self.guild_xp(guild.id).await);
+        if let Err(e) = guild.admit(owner, timestamp, &tier) {
+            return e.into_response();
+        }
+
+        invite.uses += 1;
+        if invite.is_active(timestamp) {
+            self.state
+                .invite_codes
+                .insert(&code_hash, invite)
+                .expect("Failed to update invite code");
+        } else {
+            self.state
+                .invite_codes
+                .remove(&code_hash)
+                .expect("Failed to drop used invite code");
+        }
+        self.record_guild_join(guild, owner, Some(code_hash.to_string()))
+            .await;
+
+        ArcadeResponse::GuildUpdated
+    }
+
+    /// Handle a creator revoking their invite code.
+    async fn handle_revoke_invite_code(
+        &mut self,
+        owner: AccountOwner,
+        code_hash: CryptoHash,
+    ) -> ArcadeResponse {
+        match self.state.invite_codes.get(&code_hash).await {
+            Ok(Some(invite)) if invite.creator == owner => {}
+            Ok(Some(_)) => return ArcadeError::NotInviteCodeCreator.into_response(),
+            _ => return ArcadeError::InvalidInviteCode.into_response(),
+        }
+
+        self.state
+            .invite_codes
+            .remove(&code_hash)
+            .expect("Failed to revoke invite code");
+
+        ArcadeResponse::InviteCodeUpdated
+    }
+
+    /// Handle an officer or the leader opening a poll in their guild.
+    async fn handle_create_guild_poll(
+        &mut self,
+        owner: AccountOwner,
+        question: String,
+        options: Vec<String>,
+        duration_hours: u32,
+    ) -> ArcadeResponse {
+        let (guild, _) = match self
+            .guild_for_action(&owner, GuildPermission::CreatePoll)
+            .await
+        {
+            Ok(found) => found,
+            Err(e) => return e.into_response(),
+        };
+        let poll_id = *self.state.poll_counter.get();
+        let timestamp = self.runtime.now_micros();
+        let poll = match GuildPoll::new(
+            poll_id,
+            guild.id,
+            question,
+            options,
+            duration_hours,
+            owner,
+            timestamp,
+        ) {
+            Ok(poll) => poll,
+            Err(e) => return e.into_response(),
+        };
+
+        self.state.poll_counter.set(poll_id + 1);
+        self.state
+            .guild_polls
+            .insert(&poll_id, poll)
+            .expect("Failed to store poll");
+
+        ArcadeResponse::GuildPollCreated { poll_id }
+    }
+
+    /// Handle a guild member voting in one of their guild's polls.
+    async fn handle_vote_in_guild_poll(
+        &mut self,
+        owner: AccountOwner,
+        poll_id: u64,
+        option: u32,
+    ) -> ArcadeResponse {
+        let guild = match self.guild_of(&owner).await {
+            Ok(guild) => guild,
+            Err(e) => return e.into_response(),
+        };
+        let mut poll = match self.state.guild_polls.get(&poll_id).await {
+            Ok(Some(poll)) if poll.guild_id == guild.id => poll,
+            _ => return ArcadeError::PollNotFound.into_response(),
+        };
+        if let Err(e) = poll.vote(owner, option, self.runtime.now_micros()) {
+            return e.into_response();
+        }
+
+        self.state
+            .guild_polls
+            .insert(&poll_id, poll)
+            .expect("Failed to update poll");
+
+        ArcadeResponse::GuildVoteCast
+    }
+
+    /// Apply a sync from `origin`, received in a message on the hub or from
+    /// the sync stream of a chain this one subscribed to. Nothing it changes
+    /// is synced onwards. A sync with a stream `index` is skipped if it was
+    /// already applied, having arrived both ways.
+    async fn apply_sync(&mut self, message: Message, origin: ChainId, index: Option<u32>) {
+        if let Some(index) = index {
+            if !self.claim_sync_index(origin, index).await {
+                return;
+            }
+        }
+        self.applying_sync = true;
+        let encoded = bcs::to_bytes(&message).expect("Failed to encode message");
+        match message {
+            Message::SyncPlayer(player) => {
+                self.handle_sync_player(player, origin).await;
+            }
+            Message::SyncScore(score) => {
+                self.handle_sync_score(score, origin).await;
+            }
+            Message::SyncScoreBatch {
+                wallet_address,
+                scores,
+                total_xp,
+                level,
+                games_played,
+                progress,
+            } => {
+                for score in scores {
+                    self.handle_sync_score(score, origin).await;
+                }
+                self.handle_sync_xp_update(wallet_address, total_xp, level, games_played)
+                    .await;
+                for message in progress {
+                    self.apply_run_progress(message, origin).await;
+                }
+            }
+            Message::SyncXpUpdate {
+                wallet_address,
+                total_xp,
+                level,
+                games_played,
+            } => {
+                self.handle_sync_xp_update(wallet_address, total_xp, level, games_played)
+                    .await;
+            }
+            Message::SyncDisplayName {
+                wallet_address,
+                display_name,
+            } => {
+                self.handle_sync_display_name(wallet_address, display_name)
+                    .await;
+            }
+            Message::SyncUsername {
+                wallet_address,
+                username,
+            } => {
+                if let Err(error) = self.change_username(wallet_address, username).await {
+                    log::warn!(
+                        "Failed to sync {wallet_address}'s username change: {}: {error}",
+                        error.code(),
+                    );
+                    if matches!(error, ArcadeError::UsernameTaken) {
+                        self.force_rename_to_hub_handle(origin, wallet_address)
+                            .await;
+                    }
+                }
+            }
+            Message::SyncPrivacy {
+                wallet_address,
+                privacy,
+            } => {
+                self.handle_sync_privacy(wallet_address, privacy).await;
+            }
+            Message::SyncBlock {
+                wallet_address,
+                target,
+                blocked,
+            } => {
+                if let Err(error) = self.update_blocklist(wallet_address, target, blocked).await {
+                    log::warn!(
+                        "Failed to sync {wallet_address}'s block of {target}: {}: {error}",
+                        error.code(),
+                    );
+                }
+            }
+            Message::SyncDailyChallenge { entry } => {
+                let player = entry.player;
+                if let Err(error) = self.record_challenge_entry(entry).await {
+                    log::warn!(
+                        "Failed to sync {player}'s daily challenge run: {}: {error}",
+                        error.code(),
+                    );
+                }
+            }
+            message @ (Message::SyncMonthlyProgress { .. }
+            | Message::SyncEventAchievements { .. }
+            | Message::SyncAchievements { .. }
+            | Message::SyncGoalCompleted { .. }
+            | Message::SyncFullCircuit { .. }) => {
+                self.apply_run_progress(message, origin).await;
+            }
+            Message::SyncFollow {
+                wallet_address,
+                target,
+                followed,
+            } => {
+                if let Err(error) = self.update_follow(wallet_address, target, followed).await {
+                    log::warn!(
+                        "Failed to sync {wallet_address}'s follow of {target}: {}: {error}",
+                        error.code(),
+                    );
+                }
+            }
+            Message::SyncAccountDeleted { wallet_address } => {
+                let player = self
+                    .state
+                    .players
+                    .get(&wallet_address)
+                    .await
+                    .expect("Failed to load player");
+                match player {
+                    Some(player) => {
+                        self.delete_account(&player).await;
+                    }
+                    None => log::warn!(
+                        "Failed to sync {wallet_address}'s account deletion: {}",
+                        ArcadeError::PlayerNotRegistered.code(),
+                    ),
+                }
+            }
+            Message::SyncScoreRemoved(score) => {
+                // Score IDs are numbered per chain, but receipts are unique
+                let position = self
+                    .state
+                    .score_receipts
+                    .get(&score.receipt())
+                    .await
+                    .expect("Failed to load score receipt");
+                match position {
+                    Some(position) => self.remove_score(position, &score).await,
+                    None => log::warn!(
+                        "Failed to sync the removal of {}'s score {}: {}",
+                        score.player,
+                        score.id,
+                        ArcadeError::ScoreNotFound.code(),
+                    ),
+                }
+            }
+            Message::SyncFriend {
+                wallet_address,
+                target,
+                added,
+            } => {
+                if let Err(error) = self.update_friend(wallet_address, target, added).await {
+                    log::warn!(
+                        "Failed to sync {wallet_address}'s friend addition of {target}: {}: {error}",
+                        error.code(),
+                    );
+                }
+            }
+            Message::SyncKudos {
+                wallet_address,
+                recipient,
+                timestamp,
+            } => {
+                if let Err(error) = self.give_kudos(wallet_address, recipient, timestamp).await {
+                    log::warn!(
+                        "Failed to sync {wallet_address}'s kudos to {recipient}: {}: {error}",
+                        error.code(),
+                    );
+                }
+            }
+            Message::SyncReaction {
+                wallet_address,
+                target,
+                emote,
+                added,
+            } => {
+                let result = if added {
+                    self.ensure_reactable(target).await
+                } else {
+                    Ok(())
+                };
+                let result = match result {
+                    Ok(()) => {
+                        self.update_reaction(wallet_address, target, emote, added)
+                            .await
+                    }
+                    Err(error) => Err(error),
+                };
+                if let Err(error) = result {
+                    log::warn!(
+                        "Failed to sync {wallet_address}'s {emote:?} on {target:?}: {}: {error}",
+                        error.code(),
+                    );
+                }
+            }
+            Message::SyncGuild(guild) => {
+                self.handle_sync_guild(guild, origin).await;
+            }
+            Message::SyncGuildDisbanded { guild_id } => {
+                if let Some(guild_id) = self.hub_guild_id(origin, guild_id).await {
+                    if let Ok(Some(guild)) = self.state.guilds.get(&guild_id).await {
+                        self.disband_guild(guild).await;
+                    }
+                }
+            }
+            Message::SyncProgressExported(export) => {
+                self.state
+                    .progress_exports
+                    .insert(&export.snapshot.player.clone(), export)
+                    .expect("Failed to record progress export");
+            }
+            Message::SyncProgressImported(import) => {
+                // The hub recorded and counted the import when confirming it
+                self.record_progress_import(import).await;
+            }
+            Message::SyncGuildPayoutRule { guild_id, rule } => {
+                if let Some(guild_id) = self.hub_guild_id(origin, guild_id).await {
+                    self.state
+                        .guild_payout_rules
+                        .insert(&guild_id, rule)
+                        .expect("Failed to update guild payout rule");
+                }
+            }
+            Message::SyncCommunityScore(score) => {
+                let player = score.player;
+                if let Err(error) = self.record_community_score(score).await {
+                    log::warn!(
+                        "Failed to sync {player}'s community game run: {}: {error}",
+                        error.code(),
+                    );
+                }
+            }
+            Message::BridgeEvent { application_id, .. } => {
+                // Bridged events are delivered directly, never as syncs
+                log::warn!("Dropped bridged event for {application_id} synced from {origin}");
+                self.applying_sync = false;
+                return;
+            }
+            Message::SequencedSync { index, .. } => {
+                // Sequenced syncs are unwrapped before they are applied
+                log::warn!("Dropped nested sync {index} from {origin}");
+                self.applying_sync = false;
+                return;
+            }
+            Message::FileAppeal { .. }
+            | Message::AnnounceSyncSource
+            | Message::ImportProgress(_)
+            | Message::ProgressImportSettled { .. }
+            | Message::RemoveScore { .. }
+            | Message::ForceRename { .. }
+            | Message::CompleteHumanityChallenge { .. } => {
+                // Requests are delivered directly, never as syncs
+                log::warn!("Dropped request synced from {origin}");
+                self.applying_sync = false;
+                return;
+            }
+        }
+
+        self.journal(JournalKind::Message, None, None, Some(origin), encoded);
+        self.refresh_leaderboard_snapshot().await;
+        self.applying_sync = false;
+    }
+
+    /// Apply a sync of progress a player's run made, sent on its own or in
+    /// a batch of runs.
+    async fn apply_run_progress(&mut self, message: Message, origin: ChainId) {
+        match message {
+            Message::SyncMonthlyProgress { progress } => {
+                self.state
+                    .monthly_progress
+                    .insert(&progress.player, progress.clone())
+                    .expect("Failed to update monthly challenge progress");
+                self.update_monthly_board(progress).await;
+            }
+            Message::SyncEventAchievements {
+                wallet_address,
+                progress,
+            } => {
+                self.state
+                    .event_achievements
+                    .insert(&wallet_address, progress)
+                    .expect("Failed to update event achievements");
+            }
+            Message::SyncAchievements {
+                wallet_address,
+                progress,
+            } => {
+                self.state
+                    .achievements
+                    .insert(&wallet_address, progress)
+                    .expect("Failed to update achievements");
+            }
+            Message::SyncGoalCompleted {
+                wallet_address,
+                goal,
+            } => {
+                self.publish_goal_completed(wallet_address, goal).await;
+            }
+            Message::SyncFullCircuit {
+                wallet_address,
+                day,
+            } => {
+                self.publish_full_circuit(wallet_address, day).await;
+            }
+            message => {
+                log::warn!("Dropped batched sync from {origin}: {message:?}");
+            }
+        }
+    }
+
+    /// Log the rejection of operation `name`, if `response` is one, with
+    /// its signer if it had one.
+    fn log_rejection(
+        &mut self,
+        name: &str,
+        owner: Option<AccountOwner>,
+        response: &ArcadeResponse,
+    ) {
+        let ArcadeResponse::Error { code, message } = response else {
+            return;
+        };
+        let chain_id = self.runtime.chain();
+        match owner {
+            Some(owner) => {
+                log::warn!("{name} from {owner} on chain {chain_id} rejected with {code}: {message}");
+            }
+            None => {
+                log::warn!("Unsigned {name} on chain {chain_id} rejected with {code}: {message}");
+            }
+        }
+    }
+
+    /// Have the player's chain `origin` take back `wallet_address`'s handle
+    /// on the hub, after a rename to a name the hub already holds for
+    /// someone else was refused.
+    async fn force_rename_to_hub_handle(&mut self, origin: ChainId, wallet_address: AccountOwner) {
+        if !self.is_hub_chain() || origin == self.runtime.chain() {
+            return;
+        }
+        let Some(player) = self
+            .state
+            .players
+            .get(&wallet_address)
+            .await
+            .expect("Failed to load player")
+        else {
+            return;
+        };
+        self.send_request(
+            origin,
+            Message::ForceRename {
+                wallet_address,
+                username: player.username,
+            },
+        );
+    }
+
+    /// Apply a request `origin` sent straight to this chain, dropping it if
+    /// it was meant for a chain on the other side of the hub.
+    async fn apply_request(&mut self, message: Message, hub_chain_id: ChainId, origin: ChainId) {
+        let on_hub = self.runtime.chain() == hub_chain_id;
+        let encoded = bcs::to_bytes(&message).expect("Failed to encode message");
+        match message {
+            Message::FileAppeal {
+                appellant,
+                receipt,
+                reason,
+                bond,
+            } if on_hub => {
+                self.handle_appeal_request(origin, appellant, receipt, reason, bond)
+                    .await;
+            }
+            Message::AnnounceSyncSource if on_hub => {
+                self.update_sync_source(origin, true).await;
+            }
+            Message::ImportProgress(import) if on_hub => {
+                self.handle_import_request(origin, import).await;
+            }
+            Message::CompleteHumanityChallenge {
+                player,
+                verifier,
+                signature,
+            } if on_hub => {
+                self.handle_humanity_proof_request(origin, player, verifier, signature)
+                    .await;
+            }
+            Message::ProgressImportSettled { import, accepted }
+                if !on_hub && origin == hub_chain_id =>
+            {
+                if accepted {
+                    self.record_progress_import(import.clone()).await;
+                    self.credit_progress_import(import).await;
+                } else {
+                    log::warn!(
+                        "Dropped {}'s import of {}: {}",
+                        import.player,
+                        import.record_hash,
+                        ArcadeError::ProgressAlreadyImported.code(),
+                    );
+                }
+            }
+            Message::RemoveScore { receipt } if !on_hub && origin == hub_chain_id => {
+                self.handle_score_removal_request(receipt).await;
+            }
+            Message::ForceRename {
+                wallet_address,
+                username,
+            } if !on_hub && origin == hub_chain_id => {
+                if let Err(error) = self.change_username(wallet_address, username).await {
+                    log::warn!(
+                        "Failed to adopt {wallet_address}'s handle from the hub: {}: {error}",
+                        error.code(),
+                    );
+                }
+            }
+            message => {
+                log::warn!("Dropped misdirected request from {origin}: {message:?}");
+                return;
+            }
+        }
+        self.journal(JournalKind::Message, None, None, Some(origin), encoded);
+    }
+
+    /// Record that the sync at `index` on `origin`'s stream is being
+    /// applied, returning false if it or a later one already was.
+    async fn claim_sync_index(&mut self, origin: ChainId, index: u32) -> bool {
+        let next = self
+            .state
+            .next_sync_index
+            .get(&origin)
+            .await
+            .expect("Failed to load sync index")
+            .unwrap_or(0);
+        if index < next {
+            return false;
+        }
+        self.state
+            .next_sync_index
+            .insert(&origin, index + 1)
+            .expect("Failed to update sync index");
+        true
+    }
+
+    /// Handle subscribing to or unsubscribing from another chain's sync
+    /// stream (admin only on the hub).
+    async fn handle_set_sync_source(
+        &mut self,
+        owner: AccountOwner,
+        chain_id: ChainId,
+        subscribed: bool,
+    ) -> ArcadeResponse {
+        if self.is_hub_chain() {
+            if let Err(e) = self.ensure_admin(&owner) {
+                return e.into_response();
+            }
+        }
+        if chain_id == self.runtime.chain() {
+            return ArcadeError::InvalidSyncSource.into_response();
+        }
+        self.update_sync_source(chain_id, subscribed).await;
+        ArcadeResponse::SyncSourcesUpdated
+    }
+
+    /// Subscribe to or unsubscribe from `chain_id`'s sync stream, unless
+    /// already done.
+    async fn update_sync_source(&mut self, chain_id: ChainId, subscribed: bool) {
+        let known = self
+            .state
+            .sync_sources
+            .contains(&chain_id)
+            .await
+            .expect("Failed to load sync sources");
+        if subscribed == known {
+            return;
+        }
+
+        let application_id = self.runtime.application_id().forget_abi();
+        if subscribed {
+            self.runtime
+                .subscribe_to_events(chain_id, application_id, SYNC_EVENT_STREAM.into());
+            self.state
+                .sync_sources
+                .insert(&chain_id)
+                .expect("Failed to update sync sources");
+        } else {
+            self.runtime.unsubscribe_from_events(
+                chain_id,
+                application_id,
+                SYNC_EVENT_STREAM.into(),
+            );
+            self.state
+                .sync_sources
+                .remove(&chain_id)
+                .expect("Failed to update sync sources");
+        }
+    }
+
+    /// Handle a developer submitting a community game for review (hub
+    /// only).
+    async fn handle_submit_community_game(
+        &mut self,
+        owner: AccountOwner,
+        spec: CommunityGameSpec,
+    ) -> ArcadeResponse {
+        if !self.is_hub_chain() {
+            return ArcadeError::CommunityGamesRequireHub.into_response();
+        }
+        if let Err(e) = spec.validate() {
+            return e.into_response();
+        }
+        let pending = self
+            .state
+            .pending_community_games
+            .count()
+            .await
+            .expect("Failed to count pending community games");
+        if pending as u64 >= community_games::MAX_PENDING_COMMUNITY_GAMES {
+            return ArcadeError::TooManyPendingCommunityGames.into_response();
+        }
+
+        let game_id = {
+            let current = *self.state.community_game_counter.get();
+            self.state.community_game_counter.set(current + 1);
+            current
+        };
+        let game = CommunityGame::new(game_id, owner, spec, self.runtime.now_micros());
+        self.state
+            .community_games
+            .insert(&game_id, game)
+            .expect("Failed to insert community game");
+        self.state
+            .pending_community_games
+            .insert(&game_id)
+            .expect("Failed to queue community game");
+
+        ArcadeResponse::CommunityGameSubmitted { game_id }
+    }
+
+    /// Handle approving or rejecting a community game (admin only).
+    async fn handle_review_community_game(
+        &mut self,
+        owner: AccountOwner,
+        game_id: u64,
+        approved: bool,
+    ) -> ArcadeResponse {
+        if let Err(e) = self.ensure_admin(&owner) {
+            return e.into_response();
+        }
+        let Some(mut game) = self
+            .state
+            .community_games
+            .get(&game_id)
+            .await
+            .ok()
+            .flatten()
+        else {
+            return ArcadeError::CommunityGameNotFound.into_response();
+        };
+        if let Err(e) = game.review(approved, self.runtime.now_micros()) {
+            return e.into_response();
+        }
+
+        self.state
+            .community_games
+            .insert(&game_id, game)
+            .expect("Failed to update community game");
+        self.state
+            .pending_community_games
+            .remove(&game_id)
+            .expect("Failed to dequeue community game");
+
+        ArcadeResponse::CommunityGameReviewed
+    }
+
+    /// Handle a run of a community game, recorded right away on the hub and
+    /// sent there from any other chain.
+    async fn handle_submit_community_score(
+        &mut self,
+        owner: AccountOwner,
+        game_id: u64,
+        score: u64,
+    ) -> ArcadeResponse {
+        if !matches!(self.state.players.get(&owner).await, Ok(Some(_))) {
+            return ArcadeError::PlayerNotRegistered.into_response();
+        }
+        let score = CommunityScore {
+            game_id,
+            player: owner,
+            score,
+            timestamp: self.runtime.now_micros(),
+        };
+
+        if !self.is_hub_chain() {
+            self.send_to_hub_if_needed(Message::SyncCommunityScore(score));
+            return ArcadeResponse::CommunityScoreSubmitted { xp_earned: None };
+        }
+        match self.record_community_score(score).await {
+            Ok(xp_earned) => ArcadeResponse::CommunityScoreSubmitted {
+                xp_earned: Some(xp_earned),
+            },
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Record a run of a community game on the hub: count it, rank it on
+    /// the game's leaderboard and credit its weighted XP. Returns the XP
+    /// credited.
+    async fn record_community_score(&mut self, score: CommunityScore) -> Result<u64, ArcadeError> {
+        let Some(mut game) = self
+            .state
+            .community_games
+            .get(&score.game_id)
+            .await
+            .expect("Failed to load community game")
+        else {
+            return Err(ArcadeError::CommunityGameNotFound);
+        };
+        let xp_earned = game.score_xp(score.score)?;
+
+        game.runs += 1;
+        self.state
+            .community_games
+            .insert(&score.game_id, game)
+            .expect("Failed to update community game");
+
+        // Hidden players earn XP but stay off the leaderboard
+        let listed = !self.is_shadow_banned(&score.player).await
+            && !self
+                .state
+                .leaderboard_opt_outs
+                .contains(&score.player)
+                .await
+                .unwrap_or(false);
+        if listed {
+            let mut leaderboard = self
+                .state
+                .community_leaderboards
+                .get(&score.game_id)
+                .await
+                .expect("Failed to load community leaderboard")
+                .unwrap_or_default();
+            leaderboard.record(&score);
+            self.state
+                .community_leaderboards
+                .insert(&score.game_id, leaderboard)
+                .expect("Failed to update community leaderboard");
+        }
+
+        let community_xp = self
+            .state
+            .community_xp
+            .get(&score.player)
+            .await
+            .expect("Failed to load community XP")
+            .unwrap_or(0);
+        self.state
+            .community_xp
+            .insert(&score.player, community_xp.saturating_add(xp_earned))
+            .expect("Failed to update community XP");
+        Ok(xp_earned)
+    }
+
+    /// Handle registering or unregistering the calling application's event
+    /// bridge (hub only).
+    async fn handle_set_event_bridge(
+        &mut self,
+        chain_id: ChainId,
+        registered: bool,
+    ) -> ArcadeResponse {
+        let Some(application_id) = self.runtime.authenticated_caller_id() else {
+            return ArcadeError::NotApplicationCaller.into_response();
+        };
+        if !self.is_hub_chain() {
+            return ArcadeError::BridgeRequiresHub.into_response();
+        }
+
+        let mut subscription = self
+            .state
+            .bridge_subscriptions
+            .get(&application_id)
+            .await
+            .expect("Failed to load bridge subscription")
+            .unwrap_or_default();
+        if registered {
+            if let Err(e) = subscription.add(chain_id) {
+                return e.into_response();
+            }
+        } else {
+            subscription.remove(&chain_id);
+        }
+
+        if subscription.is_empty() {
+            self.state
+                .bridge_subscriptions
+                .remove(&application_id)
+                .expect("Failed to update bridge subscription");
+        } else {
+            self.state
+                .bridge_subscriptions
+                .insert(&application_id, subscription)
+                .expect("Failed to update bridge subscription");
+        }
+
+        ArcadeResponse::EventBridgeUpdated
+    }
+
+    /// Handle the calling application taking its forwarded events.
+    async fn handle_drain_bridged_events(&mut self) -> ArcadeResponse {
+        let Some(application_id) = self.runtime.authenticated_caller_id() else {
+            return ArcadeError::NotApplicationCaller.into_response();
+        };
+
+        let inbox = self
+            .state
+            .bridge_inboxes
+            .get(&application_id)
+            .await
+            .expect("Failed to load bridge inbox")
+            .unwrap_or_default();
+        self.state
+            .bridge_inboxes
+            .remove(&application_id)
+            .expect("Failed to clear bridge inbox");
+
+        ArcadeResponse::BridgedEvents {
+            events: inbox.drain(),
+        }
+    }
+
+    /// Handle an event forwarded from the hub to a bridged application.
+    async fn handle_bridge_event(
+        &mut self,
+        hub_chain_id: ChainId,
+        application_id: ApplicationId,
+        event: ArcadeEvent,
+    ) {
+        // Only the hub publishes events
+        let origin = self.runtime.message_origin_chain_id();
+        if origin != Some(hub_chain_id) {
+            log::warn!("Dropped bridged event for {application_id} from {origin:?}, not the hub");
+            return;
+        }
+
+        let mut inbox = self
+            .state
+            .bridge_inboxes
+            .get(&application_id)
+            .await
+            .expect("Failed to load bridge inbox")
+            .unwrap_or_default();
+        inbox.push(BridgedEvent {
+            origin_chain: hub_chain_id,
+            event,
+        });
+        self.state
+            .bridge_inboxes
+            .insert(&application_id, inbox)
+            .expect("Failed to update bridge inbox");
+    }
+
+    /// Handle syncing a player from `origin` (hub only).
+    async fn handle_sync_player(&mut self, mut player: Player, origin: ChainId) {
+        let owner = player.owner.clone();
+
+        // Upsert player - preserve XP if exists
+        if let Ok(Some(existing)) = self.state.players.get(&owner).await {
+            // The hub's handle wins; renames arrive as their own message
+            let mut updated = player.clone();
+            updated.username = existing.username.clone();
+            self.backfill_username_claim(&existing).await;
+
+            // Keep the higher XP value
+            if existing.total_xp > updated.total_xp {
+                log::info!(
+                    "Kept the hub's {} XP for {owner} over {} XP synced from another chain",
+                    existing.total_xp,
+                    updated.total_xp,
+                );
+                updated.total_xp = existing.total_xp;
+                updated.level = existing.level;
+                updated.games_played = existing.games_played;
+            }
+            self.state
+                .players
+                .insert(&owner, updated.clone())
+                .expect("Failed to update player");
+
+            self.update_leaderboard(&updated);
+        } else {
+            // Names are only unique per chain until the hub sees them; a
+            // player whose name is already held here is admitted under a
+            // fallback handle, which their chain is told to adopt
+            let mut key = usernames::normalize_username(&player.username);
+            let now = self.runtime.now_micros();
+            if self
+                .ensure_username_available(&key, &owner, now)
+                .await
+                .is_err()
+            {
+                let mut fallback = None;
+                for attempt in 0..usernames::MAX_FALLBACK_ATTEMPTS {
+                    let username = usernames::fallback_username(&player.username, &owner, attempt);
+                    key = usernames::normalize_username(&username);
+                    if self
+                        .ensure_username_available(&key, &owner, now)
+                        .await
+                        .is_ok()
+                    {
+                        fallback = Some(username);
+                        break;
+                    }
+                }
+                let Some(username) = fallback else {
+                    log::warn!(
+                        "Failed to sync {owner}'s registration as {}: no fallback handle is free",
+                        player.username,
+                    );
+                    return;
+                };
+                log::info!(
+                    "Admitted {owner} as {username}, {} being taken on the hub",
+                    player.username,
+                );
+                player.username = username.clone();
+                if self.is_hub_chain() && origin != self.runtime.chain() {
+                    self.send_request(
+                        origin,
+                        Message::ForceRename {
+                            wallet_address: owner,
+                            username,
+                        },
+                    );
+                }
+            }
+            self.state
+                .username_claims
+                .insert(
+                    &key,
+                    UsernameClaim::new(player.username.clone(), owner, player.registered_at),
+                )
+                .expect("Failed to claim username");
+
+            self.state
+                .players
+                .insert(&owner, player.clone())
+                .expect("Failed to insert player");
+            self.count_players(1);
+
+            self.update_leaderboard(&player);
+            self.publish_registration(&player).await;
+        }
+    }
+
+    /// Handle display name sync from another chain (hub only).
+    async fn handle_sync_display_name(
+        &mut self,
+        wallet_address: AccountOwner,
+        display_name: String,
+    ) {
+        if let Ok(Some(mut player)) = self.state.players.get(&wallet_address).await {
+            player.display_name = display_name;
+            self.state
+                .players
+                .insert(&wallet_address, player.clone())
+                .expect("Failed to update player");
+
+            self.update_leaderboard(&player);
+        }
+    }
+
+    /// Handle privacy settings sync from another chain (hub only).
+    async fn handle_sync_privacy(
+        &mut self,
+        wallet_address: AccountOwner,
+        privacy: PrivacySettings,
+    ) {
+        if let Ok(Some(mut player)) = self.state.players.get(&wallet_address).await {
+            player.privacy = privacy.clone();
+            self.state
+                .players
+                .insert(&wallet_address, player)
+                .expect("Failed to update player");
+            self.apply_privacy(wallet_address, &privacy);
+        }
+    }
+
+    /// Handle syncing a score from another chain (hub only).
+    async fn handle_sync_score(&mut self, score: GameScore, origin: ChainId) {
+        if score.chain_id != origin {
+            log::warn!(
+                "Ignored score {} from chain {origin}, which was played on chain {}",
+                score.id,
+                score.chain_id
+            );
+            return;
+        }
+        // A banned player's runs count for nothing, wherever they were played
+        if self.is_banned(&score.player).await {
+            log::warn!(
+                "Dropped score {} from chain {origin}: {}",
+                score.id,
+                ArcadeError::PlayerBanned.code(),
+            );
+            return;
+        }
+        // So are a challenged player's, until they prove they are human
+        if self
+            .state
+            .humanity_challenges
+            .contains_key(&score.player)
+            .await
+            .expect("Failed to load humanity challenges")
+        {
+            log::warn!(
+                "Dropped score {} from chain {origin}: {}",
+                score.id,
+                ArcadeError::HumanityCheckRequired.code(),
+            );
+            return;
+        }
+        // A score is identified by its ID on the chain it was played on
+        let key = (origin, score.id);
+        if self
+            .state
+            .synced_scores
+            .contains(&key)
+            .await
+            .expect("Failed to load synced scores")
+        {
+            log::info!(
+                "Ignored score {} from chain {origin}, which was already synced",
+                score.id
+            );
+            return;
+        }
+        self.state
+            .synced_scores
+            .insert(&key)
+            .expect("Failed to record synced score");
+
+        // Log score with its original ID
+        self.log_score(score.clone()).await;
+
+        self.update_champion_score(&score).await;
+        self.update_recent_form(&score).await;
+        self.update_personal_best(&score).await;
+        self.credit_guild_contribution(&score).await;
+        self.credit_seasonal_xp(&score).await;
+        self.award_raffle_tickets(&score).await;
+        self.hit_boss_battles(&score).await;
+        // Synced scores double as the player's presence on the hub
+        self.update_presence(&score).await;
+
+        // Update totals
+        if !self.is_shadow_banned(&score.player).await {
+            self.add_to_totals(&score).await;
+            self.screen_early_run(&score).await;
+        }
+    }
+
+    /// Handle syncing a guild from another chain (hub only). Guild IDs are
+    /// numbered per chain, so the hub gives each synced guild an ID of its
+    /// own the first time it sees it.
+    async fn handle_sync_guild(&mut self, mut guild: Guild, origin: ChainId) {
+        let key = (origin, guild.id);
+        guild.id = match self
+            .state
+            .synced_guilds
+            .get(&key)
+            .await
+            .expect("Failed to load synced guild")
+        {
+            Some(guild_id) => guild_id,
+            None => {
+                let guild_id = {
+                    let current = *self.state.guild_counter.get();
+                    self.state.guild_counter.set(current + 1);
+                    current
+                };
+                self.state
+                    .synced_guilds
+                    .insert(&key, guild_id)
+                    .expect("Failed to record synced guild");
+                guild_id
+            }
+        };
+        if let Ok(Some(previous)) = self.state.guilds.get(&guild.id).await {
+            self.unindex_guild(&previous).await;
+        }
+
+        // Names are only unique per chain; the guild that took a name on the
+        // hub first keeps it
+        let name_key = guilds::normalize_guild_name(&guild.name);
+        let holder = self
+            .state
+            .guild_names
+            .get(&name_key)
+            .await
+            .expect("Failed to load guild name");
+        if holder.is_some_and(|holder| holder != guild.id) {
+            log::warn!(
+                "Synced guild {} from chain {origin} keeps its name {}, which another guild \
+                 holds on the hub",
+                key.1,
+                guild.name,
+            );
+            for member in &guild.members {
+                self.state
+                    .player_guilds
+                    .insert(&member.owner, guild.id)
+                    .expect("Failed to update guild membership");
+            }
+        } else {
+            self.index_guild(&guild);
+        }
+        self.save_guild(guild);
+    }
+
+    /// The hub's ID for guild `guild_id` synced from `origin`, if it was.
+    async fn hub_guild_id(&self, origin: ChainId, guild_id: u64) -> Option<u64> {
+        self.state
+            .synced_guilds
+            .get(&(origin, guild_id))
+            .await
+            .expect("Failed to load synced guild")
+    }
+
+    /// Handle XP update sync from another chain (hub only).
+    async fn handle_sync_xp_update(
+        &mut self,
+        wallet_address: AccountOwner,
+        total_xp: u64,
+        level: u32,
+        games_played: u64,
+    ) {
+        // Update leaderboard entry if exists
+        if let Ok(Some(mut entry)) = self.state.leaderboard.get(&wallet_address).await {
+            // Only update if the new values are higher
+            if total_xp >= entry.total_xp {
+                entry.total_xp = total_xp;
+                entry.level = level;
+                let registered_at = self
+                    .state
+                    .players
+                    .get(&wallet_address)
+                    .await
+                    .expect("Failed to load player")
+                    .map_or(0, |player| player.registered_at);
+                self.save_leaderboard_entry(entry, registered_at);
+            }
+        }
+
+        // Update player if exists, ignoring updates already applied
+        if let Ok(Some(mut player)) = self.state.players.get(&wallet_address).await {
+            if (total_xp, games_played) > (player.total_xp, player.games_played) {
+                let previous_level = player.level;
+                player.total_xp = total_xp;
+                player.level = level;
+                player.games_played = games_played;
+                self.state
+                    .players
+                    .insert(&wallet_address, player)
+                    .expect("Failed to update player");
+                self.publish_level_up(wallet_address, previous_level, level)
+                    .await;
+            } else {
+                log::info!(
+                    "Ignored stale XP update for {wallet_address}: {total_xp} XP synced, hub has {}",
+                    player.total_xp,
+                );
+            }
+        } else {
+            log::warn!("Ignored XP update for {wallet_address}, who is not known to the hub");
+        }
+    }
+
+    /// Store `player`'s leaderboard entry.
+    fn update_leaderboard(&mut self, player: &Player) {
+        self.save_leaderboard_entry(
+            LeaderboardEntry::from_player(player, 0),
+            player.registered_at,
+        );
+    }
+
+    /// Store a leaderboard entry, placing the player on the rookie
+    /// leaderboard while they qualify and graduating them once they don't.
+    fn save_leaderboard_entry(&mut self, entry: LeaderboardEntry, registered_at: u64) {
+        let owner = entry.wallet_address;
+        let now = self.runtime.now_micros();
+        if self
+            .state
+            .rookie_criteria
+            .get()
+            .is_rookie(registered_at, entry.level, now)
+        {
+            self.state
+                .rookie_leaderboard
+                .insert(&owner, entry.clone())
+                .expect("Failed to update rookie leaderboard");
+        } else {
+            self.state
+                .rookie_leaderboard
+                .remove(&owner)
+                .expect("Failed to update rookie leaderboard");
+        }
+        self.state
+            .leaderboard
+            .insert(&owner, entry)
+            .expect("Failed to update leaderboard");
+    }
+
+    /// Graduate the rookies whose accounts have aged past the rookie
+    /// criteria without playing since.
+    async fn graduate_rookies(&mut self, now: u64) {
+        let criteria = *self.state.rookie_criteria.get();
+        let mut rookies = Vec::new();
+        self.state
+            .rookie_leaderboard
+            .for_each_index_value(|owner, entry| {
+                rookies.push((owner, entry.level));
+                Ok(())
+            })
+            .await
+            .expect("Failed to read rookie leaderboard");
+        for (owner, level) in rookies {
+            let registered_at = self
+                .state
+                .players
+                .get(&owner)
+                .await
+                .expect("Failed to load player")
+                .map_or(0, |player| player.registered_at);
+            if !criteria.is_rookie(registered_at, level, now) {
+                self.state
+                    .rookie_leaderboard
+                    .remove(&owner)
+                    .expect("Failed to update rookie leaderboard");
+            }
+        }
+    }
+
+    /// Update the opt-out indexes that queries use to honor privacy settings.
+    fn apply_privacy(&mut self, owner: AccountOwner, privacy: &PrivacySettings) {
+        for (opted_out, index) in [
+            (
+                privacy.hide_from_leaderboard,
+                &mut self.state.leaderboard_opt_outs,
+            ),
+            (privacy.hide_score_history, &mut self.state.history_opt_outs),
+        ] {
+            if opted_out {
+                index
+                    .insert(&owner)
+                    .expect("Failed to update privacy index");
+            } else {
+                index
+                    .remove(&owner)
+                    .expect("Failed to update privacy index");
+            }
+        }
+    }
+
+    /// Add `target` to or remove it from `owner`'s blocklist.
+    async fn update_blocklist(
+        &mut self,
+        owner: AccountOwner,
+        target: AccountOwner,
+        blocked: bool,
+    ) -> Result<(), ArcadeError> {
+        let mut blocklist = self.blocklist(&owner).await.unwrap_or_default();
+        if blocked {
+            blocklist.block(target)?;
+        } else {
+            blocklist.unblock(&target);
+        }
+
+        if blocklist.is_empty() {
+            self.state
+                .blocklists
+                .remove(&owner)
+                .expect("Failed to update blocklist");
+        } else {
+            self.state
+                .blocklists
+                .insert(&owner, blocklist)
+                .expect("Failed to update blocklist");
+        }
+        Ok(())
+    }
+
+    /// Add `target` to or remove them from `owner`'s follows, keeping
+    /// `target`'s follower count in step, unless `target` blocked `owner`.
+    /// Blocks are checked here, so the hub enforces blocks made on any chain.
+    async fn update_follow(
+        &mut self,
+        owner: AccountOwner,
+        target: AccountOwner,
+        followed: bool,
+    ) -> Result<(), ArcadeError> {
+        if followed {
+            social::ensure_not_blocked(self.blocklist(&target).await.as_ref(), &owner)?;
+        }
+        let mut follows = self
+            .state
+            .following
+            .get(&owner)
+            .await
+            .expect("Failed to load follows")
+            .unwrap_or_default();
+        let changed = if followed {
+            follows.follow(target)?
+        } else {
+            follows.unfollow(&target)
+        };
+        if !changed {
+            return Ok(());
+        }
+
+        if follows.is_empty() {
+            self.state
+                .following
+                .remove(&owner)
+                .expect("Failed to update follows");
+        } else {
+            self.state
+                .following
+                .insert(&owner, follows)
+                .expect("Failed to update follows");
+        }
+        let followers = self
+            .state
+            .follower_counts
+            .get(&target)
+            .await
+            .expect("Failed to load follower count")
+            .unwrap_or(0);
+        let followers = if followed {
+            followers.saturating_add(1)
+        } else {
+            followers.saturating_sub(1)
+        };
+        if followers == 0 {
+            self.state
+                .follower_counts
+                .remove(&target)
+                .expect("Failed to update follower count");
+        } else {
+            self.state
+                .follower_counts
+                .insert(&target, followers)
+                .expect("Failed to update follower count");
+        }
+        Ok(())
+    }
+
+    /// Add `target` to or remove them from `owner`'s friends, noting the
+    /// change on `target`'s side too, unless `target` blocked `owner`.
+    async fn update_friend(
+        &mut self,
+        owner: AccountOwner,
+        target: AccountOwner,
+        added: bool,
+    ) -> Result<(), ArcadeError> {
+        if added {
+            social::ensure_not_blocked(self.blocklist(&target).await.as_ref(), &owner)?;
+        }
+        let mut friends = self.friend_list(owner).await;
+        let mut target_friends = self.friend_list(target).await;
+        let changed = if added {
+            target_friends.check_request(&owner)?;
+            friends.add(target)?
+        } else {
+            friends.remove(&target)
+        };
+        if !changed {
+            return Ok(());
+        }
+        target_friends.set_added_by(owner, added);
+
+        self.save_friend_list(owner, friends);
+        self.save_friend_list(target, target_friends);
+        Ok(())
+    }
+
+    /// Load a player's friend list.
+    async fn friend_list(&self, owner: AccountOwner) -> FriendList {
+        self.state
+            .friends
+            .get(&owner)
+            .await
+            .expect("Failed to load friends")
+            .unwrap_or_default()
+    }
+
+    /// Store a player's friend list, dropping it once empty.
+    fn save_friend_list(&mut self, owner: AccountOwner, friends: FriendList) {
+        if friends.is_empty() {
+            self.state
+                .friends
+                .remove(&owner)
+                .expect("Failed to update friends");
+        } else {
+            self.state
+                .friends
+                .insert(&owner, friends)
+                .expect("Failed to update friends");
+        }
+    }
+
+    /// Spend one of `sender`'s daily kudos on `recipient` and count it on
+    /// both profiles, unless `recipient` blocked them.
+    async fn give_kudos(
+        &mut self,
+        sender: AccountOwner,
+        recipient: AccountOwner,
+        timestamp: u64,
+    ) -> Result<(), ArcadeError> {
+        social::ensure_not_blocked(self.blocklist(&recipient).await.as_ref(), &sender)?;
+        let mut allowance = self
+            .state
+            .kudos_allowances
+            .get(&sender)
+            .await
+            .expect("Failed to load kudos allowance")
+            .unwrap_or_default();
+        let today = match self.state.players.get(&sender).await {
+            Ok(Some(player)) => player.local_day(timestamp),
+            _ => day_index(timestamp),
+        };
+        allowance.send(recipient, today)?;
+        self.state
+            .kudos_allowances
+            .insert(&sender, allowance)
+            .expect("Failed to update kudos allowance");
+
+        let mut sent = self.kudos_record(sender).await;
+        sent.given = sent.given.saturating_add(1);
+        self.state
+            .kudos
+            .insert(&sender, sent)
+            .expect("Failed to update kudos");
+        let mut received = self.kudos_record(recipient).await;
+        received.received = received.received.saturating_add(1);
+        self.state
+            .kudos
+            .insert(&recipient, received)
+            .expect("Failed to update kudos");
+        Ok(())
+    }
+
+    /// Check that `target` is a standing hall-of-fame record or in the top
+    /// [`reactions::REACTABLE_RANKS`] of the latest leaderboard snapshot.
+    /// Only the hub holds the arcade-wide standings, so other chains accept
+    /// any target and leave the check to the hub.
+    async fn ensure_reactable(&mut self, target: ReactionTarget) -> Result<(), ArcadeError> {
+        if !self.is_hub_chain() {
+            return Ok(());
+        }
+        let reactable = match target {
+            ReactionTarget::Record(score_id) => {
+                let mut found = false;
+                self.state
+                    .hall_of_fame
+                    .for_each_index_value(|_, record| {
+                        found |= record.id == score_id;
+                        Ok(())
+                    })
+                    .await
+                    .expect("Failed to read hall of fame");
+                found
+            }
+            ReactionTarget::LeaderboardEntry(player) => match *self.state.latest_snapshot.get() {
+                Some(snapshot_id) => self
+                    .state
+                    .leaderboard_snapshots
+                    .get(&snapshot_id)
+                    .await
+                    .expect("Failed to load snapshot")
+                    .is_some_and(|snapshot| {
+                        snapshot.entries.iter().any(|entry| {
+                            entry.wallet_address == player
+                                && entry.rank <= reactions::REACTABLE_RANKS
+                        })
+                    }),
+                None => false,
+            },
+        };
+        if !reactable {
+            return Err(ArcadeError::InvalidReactionTarget);
+        }
+        Ok(())
+    }
+
+    /// Leave or take back `owner`'s `emote` on `target`, within the
+    /// per-target limits.
+    async fn update_reaction(
+        &mut self,
+        owner: AccountOwner,
+        target: ReactionTarget,
+        emote: Emote,
+        added: bool,
+    ) -> Result<(), ArcadeError> {
+        let key = (owner, target);
+        let mut left = self
+            .state
+            .player_reactions
+            .get(&key)
+            .await
+            .expect("Failed to load reactions")
+            .unwrap_or_default();
+        if added {
+            left.add(emote)?;
+        } else {
+            left.remove(emote)?;
+        }
+        if left.is_empty() {
+            self.state
+                .player_reactions
+                .remove(&key)
+                .expect("Failed to update reactions");
+        } else {
+            self.state
+                .player_reactions
+                .insert(&key, left)
+                .expect("Failed to update reactions");
+        }
+
+        let mut tally = self
+            .state
+            .reactions
+            .get(&target)
+            .await
+            .expect("Failed to load reaction tally")
+            .unwrap_or_default();
+        if added {
+            tally.add(emote);
+        } else {
+            tally.remove(emote);
+        }
+        if tally.is_empty() {
+            self.state
+                .reactions
+                .remove(&target)
+                .expect("Failed to update reaction tally");
+        } else {
+            self.state
+                .reactions
+                .insert(&target, tally)
+                .expect("Failed to update reaction tally");
+        }
+        Ok(())
+    }
+
+    /// Load a player's kudos counters, starting them at zero.
+    async fn kudos_record(&self, owner: AccountOwner) -> KudosRecord {
+        self.state
+            .kudos
+            .get(&owner)
+            .await
+            .expect("Failed to load kudos")
+            .unwrap_or_else(|| KudosRecord::new(owner))
+    }
+
+    /// Load a player's blocklist. Follows, friend requests and kudos check
+    /// the recipient's list with
+    /// [`social::ensure_not_blocked`](arcade_hub::social::ensure_not_blocked)
+    /// wherever they are applied, including the hub, which holds every
+    /// chain's lists; mentorship offers and guild invites check it on the
+    /// chain they are made on.
+    async fn blocklist(&self, owner: &AccountOwner) -> Option<Blocklist> {
+        self.state.blocklists.get(owner).await.ok().flatten()
+    }
+
+    /// Load the guild `owner` belongs to.
+    async fn guild_of(&self, owner: &AccountOwner) -> Result<Guild, ArcadeError> {
+        let guild_id = self
+            .state
+            .player_guilds
+            .get(owner)
+            .await
+            .ok()
+            .flatten()
+            .ok_or(ArcadeError::NotInGuild)?;
+        self.state
+            .guilds
+            .get(&guild_id)
+            .await
+            .ok()
+            .flatten()
+            .ok_or(ArcadeError::GuildNotFound)
+    }
+
+    /// Load the guild `owner` belongs to, checking that their role grants
+    /// `permission`. Returns the guild and their role.
+    async fn guild_for_action(
+        &self,
+        owner: &AccountOwner,
+        permission: GuildPermission,
+    ) -> Result<(Guild, GuildRole), ArcadeError> {
+        let guild = self.guild_of(owner).await?;
+        let role = guild.ensure_permitted(owner, permission)?;
+        Ok((guild, role))
+    }
+
+    /// Store a guild and sync it to the hub.
+    fn save_guild(&mut self, guild: Guild) {
+        self.state
+            .guilds
+            .insert(&guild.id, guild.clone())
+            .expect("Failed to update guild");
+        self.send_to_hub_if_needed(Message::SyncGuild(guild));
+    }
+
+    /// Check that `owner` is registered and not already in a guild.
+    async fn ensure_guildless_player(&self, owner: &AccountOwner) -> Result<(), ArcadeError> {
+        if !self
+            .state
+            .players
+            .contains_key(owner)
+            .await
+            .unwrap_or(false)
+        {
+            return Err(ArcadeError::PlayerNotRegistered);
+        }
+        if self
+            .state
+            .player_guilds
+            .contains_key(owner)
+            .await
+            .unwrap_or(false)
+        {
+            return Err(ArcadeError::AlreadyInGuild);
+        }
+        Ok(())
+    }
+
+    /// Store a guild `owner` was just admitted to, with their membership.
+    /// `detail` notes how they joined, e.g. the invite code's hash.
+    async fn record_guild_join(
+        &mut self,
+        guild: Guild,
+        owner: AccountOwner,
+        detail: Option<String>,
+    ) {
+        let guild_id = guild.id;
+        self.save_guild(guild);
+        self.state
+            .player_guilds
+            .insert(&owner, guild_id)
+            .expect("Failed to update guild membership");
+        // Contributions count from joining, even when rejoining
+        self.state
+            .guild_contributions
+            .remove(&owner)
+            .expect("Failed to reset guild contribution");
+        self.audit_guild(guild_id, GuildAction::Joined, owner, None, detail)
+            .await;
+    }
+
+    /// Note a score's player as last seen playing its game.
+    async fn update_presence(&mut self, score: &GameScore) {
+        let presence = self
+            .state
+            .presence
+            .get(&score.player)
+            .await
+            .expect("Failed to load presence");
+        self.state
+            .presence
+            .insert(
+                &score.player,
+                Presence::record(presence, score.game_type, score.timestamp),
+            )
+            .expect("Failed to update presence");
+    }
+
+    /// Count a score towards its player's contribution to their guild.
+    /// Shadow-banned players don't count towards their guild.
+    async fn credit_guild_contribution(&mut self, score: &GameScore) {
+        if self.is_shadow_banned(&score.player).await {
+            return;
+        }
+        let Ok(Some(guild_id)) = self.state.player_guilds.get(&score.player).await else {
+            return;
+        };
+        let mut contribution = self
+            .state
+            .guild_contributions
+            .get(&score.player)
+            .await
+            .expect("Failed to load guild contribution")
+            .filter(|contribution| contribution.guild_id == guild_id)
+            .unwrap_or_else(|| GuildContribution::new(guild_id));
+        contribution.record(score.xp_earned, score.timestamp);
+        self.state
+            .guild_contributions
+            .insert(&score.player, contribution)
+            .expect("Failed to update guild contribution");
+
+        let before = self.guild_xp(guild_id).await;
+        let after = before.saturating_add(score.xp_earned);
+        self.state
+            .guild_xp
+            .insert(&guild_id, after)
+            .expect("Failed to update guild XP");
+        let tier = guilds::guild_tier(after);
+        if tier.level > guilds::guild_tier(before).level {
+            self.audit_guild(
+                guild_id,
+                GuildAction::LevelUp,
+                score.player,
+                None,
+                Some(tier.level.to_string()),
+            )
+            .await;
+        }
+    }
+
+    /// The XP a guild's members have earned while in it.
+    async fn guild_xp(&self, guild_id: u64) -> u64 {
+        self.state
+            .guild_xp
+            .get(&guild_id)
+            .await
+            .expect("Failed to load guild XP")
+            .unwrap_or(0)
+    }
+
+    /// Point a guild's name and members at it.
+    fn index_guild(&mut self, guild: &Guild) {
+        self.state
+            .guild_names
+            .insert(&guilds::normalize_guild_name(&guild.name), guild.id)
+            .expect("Failed to index guild name");
+        for member in &guild.members {
+            self.state
+                .player_guilds
+                .insert(&member.owner, guild.id)
+                .expect("Failed to update guild membership");
+        }
+    }
+
+    /// Release a guild's name and memberships, leaving any that have since
+    /// moved to another guild.
+    async fn unindex_guild(&mut self, guild: &Guild) {
+        let key = guilds::normalize_guild_name(&guild.name);
+        if self.state.guild_names.get(&key).await.ok().flatten() == Some(guild.id) {
+            self.state
+                .guild_names
+                .remove(&key)
+                .expect("Failed to release guild name");
+        }
+        for member in &guild.members {
+            if self
+                .state
+                .player_guilds
+                .get(&member.owner)
+                .await
+                .ok()
+                .flatten()
+                == Some(guild.id)
+            {
+                self.state
+                    .player_guilds
+                    .remove(&member.owner)
+                    .expect("Failed to update guild membership");
+            }
+        }
+    }
+
+    /// Remove a guild with everything indexed under it, and tell the hub.
+    async fn disband_guild(&mut self, guild: Guild) {
+        let guild_id = guild.id;
+        self.unindex_guild(&guild).await;
+        self.state
+            .guilds
+            .remove(&guild_id)
+            .expect("Failed to disband guild");
+        self.state
+            .guild_audit
+            .remove(&guild_id)
+            .expect("Failed to remove guild audit trail");
+        self.state
+            .guild_xp
+            .remove(&guild_id)
+            .expect("Failed to remove guild XP");
+        self.state
+            .guild_payout_rules
+            .remove(&guild_id)
+            .expect("Failed to remove guild payout rule");
+        self.send_to_hub_if_needed(Message::SyncGuildDisbanded { guild_id });
+    }
+
+    /// Record a management action in a guild's audit trail.
+    async fn audit_guild(
+        &mut self,
+        guild_id: u64,
+        action: GuildAction,
+        actor: AccountOwner,
+        target: Option<AccountOwner>,
+        detail: Option<String>,
+    ) {
+        let mut trail = self
+            .state
+            .guild_audit
+            .get(&guild_id)
+            .await
+            .expect("Failed to load guild audit trail")
+            .unwrap_or_default();
+        trail.record(GuildAuditEntry {
+            action,
+            actor,
+            target,
+            detail,
+            timestamp: self.runtime.now_micros(),
+        });
+        self.state
+            .guild_audit
+            .insert(&guild_id, trail)
+            .expect("Failed to update guild audit trail");
+    }
+
+    /// Count an operation the player signed, warning the first time in a
+    /// day it takes them over the soft quota.
+    async fn record_usage(&mut self, owner: AccountOwner, name: &str, bytes: u64) {
+        let mut usage = self
+            .state
+            .operation_usage
+            .get(&owner)
+            .await
+            .expect("Failed to load operation usage")
+            .unwrap_or_default();
+        let quota = *self.state.usage_quota.get();
+        if usage::record_operation(&mut self.runtime, &mut usage, name, bytes, &quota) {
+            log::warn!(
+                "{owner} went over the daily soft quota with {} operations ({} bytes) today",
+                usage.operations_today,
+                usage.bytes_today,
+            );
+        }
+        self.state
+            .operation_usage
+            .insert(&owner, usage)
+            .expect("Failed to update operation usage");
+    }
+
+    /// Check that the signer is the configured admin.
+    fn ensure_admin(&self, owner: &AccountOwner) -> Result<(), ArcadeError> {
+        match self.state.admin.get() {
+            Some(admin) if admin == owner => Ok(()),
+            _ => Err(ArcadeError::NotAdmin),
+        }
+    }
+
+    /// Check whether a player is shadow-banned.
+    async fn is_shadow_banned(&self, owner: &AccountOwner) -> bool {
+        self.state
+            .shadow_banned
+            .contains(owner)
+            .await
+            .unwrap_or(false)
+    }
+
+    /// Check whether a player is banned.
+    async fn is_banned(&self, owner: &AccountOwner) -> bool {
+        self.state
+            .banned_players
+            .contains(owner)
+            .await
+            .unwrap_or(false)
+    }
+
+    /// Get the active reservation for a normalized username, dropping it if
+    /// it has expired.
+    async fn active_reservation(
+        &mut self,
+        key: &str,
+        timestamp: u64,
+    ) -> Option<UsernameReservation> {
+        let key = key.to_string();
+        let reservation = self.state.reservations.get(&key).await.ok().flatten()?;
+        if reservation.is_active(timestamp) {
+            return Some(reservation);
+        }
+        self.release_reservation(&key, &reservation.owner);
+        None
+    }
+
+    /// Ensure `username` isn't reserved for anyone but `owner`. Returns
+    /// whether `owner` holds the reservation themselves.
+    async fn check_reservation(
+        &mut self,
+        owner: &AccountOwner,
+        username: &str,
+        timestamp: u64,
+    ) -> Result<bool, ArcadeError> {
+        let key = usernames::normalize_username(username);
+        self.ensure_not_auctioned(&key).await?;
+        self.ensure_username_available(&key, owner, timestamp)
+            .await?;
+        match self.active_reservation(&key, timestamp).await {
+            Some(reservation) if &reservation.owner != owner => Err(ArcadeError::UsernameReserved),
+            Some(_) => Ok(true),
+            None => {
+                // Holding a reservation lets a blocked name through
+                self.ensure_username_not_blocked(username).await?;
+                Ok(false)
+            }
+        }
+    }
+
+    /// Ensure `username` contains no term on the username blocklist.
+    async fn ensure_username_not_blocked(&self, username: &str) -> Result<(), ArcadeError> {
+        let keys = self
+            .state
+            .blocked_usernames
+            .indices()
+            .await
+            .expect("Failed to load blocked usernames");
+        if usernames::is_blocked_username(username, &keys) {
+            return Err(ArcadeError::UsernameBlocked);
+        }
+        Ok(())
+    }
+
+    /// Ensure the normalized username `key` isn't being auctioned.
+    async fn ensure_not_auctioned(&self, key: &String) -> Result<(), ArcadeError> {
+        if self
+            .state
+            .auctioned_usernames
+            .contains_key(key)
+            .await
+            .unwrap_or(false)
+        {
+            return Err(ArcadeError::UsernameInAuction);
+        }
+        Ok(())
+    }
+
+    /// Ensure the normalized username `key` isn't held by an account other
+    /// than `owner`, unless its holder released it and the grace period has
+    /// passed. A recyclable claim is left in place until the name is taken,
+    /// once every other check has passed.
+    async fn ensure_username_available(
+        &self,
+        key: &String,
+        owner: &AccountOwner,
+        timestamp: u64,
+    ) -> Result<(), ArcadeError> {
+        let claim = self
+            .state
+            .username_claims
+            .get(key)
+            .await
+            .expect("Failed to load username claim");
+        let Some(claim) = claim.filter(|claim| &claim.owner != owner) else {
+            return Ok(());
+        };
+        let last_seen_at = self.last_seen_at(&claim.owner).await;
+        if !claim.is_recyclable(last_seen_at, timestamp) {
+            return Err(ArcadeError::UsernameTaken);
+        }
+        Ok(())
+    }
+
+    /// Free the normalized username `key` if an account other than `owner`
+    /// holds it, after [`Self::ensure_username_available`] found the claim
+    /// recyclable.
+    async fn recycle_username_claim(&mut self, key: &String, owner: &AccountOwner) {
+        let claim = self
+            .state
+            .username_claims
+            .get(key)
+            .await
+            .expect("Failed to load username claim");
+        if claim.is_some_and(|claim| &claim.owner != owner) {
+            self.state
+                .username_claims
+                .remove(key)
+                .expect("Failed to recycle username");
+        }
+    }
+
+    /// Move `owner`'s handle to `username`, freeing the old one. The new
+    /// name must not be held by anyone else.
+    async fn change_username(
+        &mut self,
+        owner: AccountOwner,
+        username: String,
+    ) -> Result<(), ArcadeError> {
+        let Some(mut player) = self
+            .state
+            .players
+            .get(&owner)
+            .await
+            .expect("Failed to load player")
+        else {
+            return Err(ArcadeError::PlayerNotRegistered);
+        };
+        let timestamp = self.runtime.now_micros();
+        let key = usernames::normalize_username(&username);
+        self.ensure_username_available(&key, &owner, timestamp)
+            .await?;
+
+        let old_key = usernames::normalize_username(&player.username);
+        let old_claim = self
+            .state
+            .username_claims
+            .get(&old_key)
+            .await
+            .expect("Failed to load username claim");
+        if old_claim.is_some_and(|claim| claim.owner == owner) {
+            self.state
+                .username_claims
+                .remove(&old_key)
+                .expect("Failed to free username");
+        }
+        self.state
+            .username_claims
+            .insert(&key, UsernameClaim::new(username.clone(), owner, timestamp))
+            .expect("Failed to claim username");
+
+        player.username = username;
+        self.state
+            .players
+            .insert(&owner, player.clone())
+            .expect("Failed to update player");
+        self.update_leaderboard(&player);
+        Ok(())
+    }
+
+    /// Claim `player`'s username for them if no one holds it yet, as for
+    /// players registered before names were claimed.
+    async fn backfill_username_claim(&mut self, player: &Player) {
+        let key = usernames::normalize_username(&player.username);
+        if self
+            .state
+            .username_claims
+            .contains_key(&key)
+            .await
+            .unwrap_or(false)
+        {
+            return;
+        }
+        self.state
+            .username_claims
+            .insert(
+                &key,
+                UsernameClaim::new(player.username.clone(), player.owner, player.registered_at),
+            )
+            .expect("Failed to claim username");
+    }
+
+    /// Start the grace period on `player`'s username, returning when it
+    /// becomes recyclable.
+    async fn release_username(&mut self, player: &Player, reason: ReleaseReason, now: u64) -> u64 {
+        let release = UsernameRelease {
+            reason,
+            released_at: now,
+            recyclable_at: self.state.username_recycling.get().release_time(now),
+        };
+        let key = usernames::normalize_username(&player.username);
+        let claim = self
+            .state
+            .username_claims
+            .get(&key)
+            .await
+            .expect("Failed to load username claim");
+        // Players registered before names were claimed hold theirs
+        // implicitly
+        let mut claim = match claim {
+            Some(claim) if claim.owner != player.owner => return release.recyclable_at,
+            Some(claim) => claim,
+            None => UsernameClaim::new(player.username.clone(), player.owner, player.registered_at),
+        };
+        claim.release = Some(release);
+        self.state
+            .username_claims
+            .insert(&key, claim)
+            .expect("Failed to release username");
+        release.recyclable_at
+    }
+
+    /// Add `delta` to the registered player count.
+    fn count_players(&mut self, delta: i64) {
+        let total = self.state.total_players.get_mut();
+        *total = total.saturating_add_signed(delta);
+    }
+
+    /// Remove a player's account and leaderboard entries, releasing their
+    /// username. Returns when the username becomes recyclable.
+    async fn delete_account(&mut self, player: &Player) -> u64 {
+        let owner = player.owner;
+        self.state
+            .players
+            .remove(&owner)
+            .expect("Failed to delete player");
+        self.count_players(-1);
+        self.state
+            .leaderboard
+            .remove(&owner)
+            .expect("Failed to update leaderboard");
+        self.state
+            .rookie_leaderboard
+            .remove(&owner)
+            .expect("Failed to update rookie leaderboard");
+        for category in [ScoringCategory::Standard, ScoringCategory::Assisted] {
+            self.state
+                .category_xp
+                .remove(&(owner, category))
+                .expect("Failed to remove category XP");
+        }
+        let now = self.runtime.now_micros();
+        self.release_username(player, ReleaseReason::AccountDeleted, now)
+            .await
+    }
+
+    /// When a player last finished a run, if they ever have.
+    async fn last_seen_at(&self, owner: &AccountOwner) -> Option<u64> {
+        self.state
+            .presence
+            .get(owner)
+            .await
+            .expect("Failed to load presence")
+            .map(|presence| presence.last_seen_at)
+    }
+
+    /// Load a username auction.
+    async fn username_auction(&self, auction_id: u64) -> Option<UsernameAuction> {
+        self.state
+            .username_auctions
+            .get(&auction_id)
+            .await
+            .expect("Failed to load username auction")
+    }
+
+    /// Store a username auction.
+    fn save_username_auction(&mut self, auction: UsernameAuction) {
+        let auction_id = auction.id;
+        self.state
+            .username_auctions
+            .insert(&auction_id, auction)
+            .expect("Failed to update username auction");
+    }
+
+    /// Remove a reservation and its owner index entry.
+    fn release_reservation(&mut self, key: &String, owner: &AccountOwner) {
+        self.state
+            .reservations
+            .remove(key)
+            .expect("Failed to release reservation");
+        self.state
+            .owner_reservations
+            .remove(owner)
+            .expect("Failed to release reservation owner");
+    }
+
+    /// Verify a registration voucher that hasn't been used yet.
+    async fn check_voucher(
+        &self,
+        owner: &AccountOwner,
+        username: &str,
+        voucher: &RegistrationVoucher,
+        timestamp: u64,
+    ) -> Result<(), ArcadeError> {
+        let Some(signer) = *self.state.voucher_signer.get() else {
+            return Err(ArcadeError::InvalidVoucher);
+        };
+        if self
+            .state
+            .consumed_vouchers
+            .contains_key(&voucher.voucher_id)
+            .await
+            .unwrap_or(false)
+        {
+            return Err(ArcadeError::VoucherAlreadyUsed);
+        }
+        voucher.verify(&signer, owner, username, timestamp)
+    }
+
+    /// Apply an accepted score and sync it to the hub, returning what the
+    /// run earned.
+    async fn record_score(&mut self, player: Player, submission: ScoreSubmission) -> ScoreResult {
+        let (game_score, player, result) = self.apply_score(player, submission).await;
+
+        // Send sync messages to hub if not on hub chain
+        self.send_to_hub_if_needed(Message::SyncScore(game_score));
+        self.send_to_hub_if_needed(Message::SyncXpUpdate {
+            wallet_address: player.owner,
+            total_xp: player.total_xp,
+            level: player.level,
+            games_played: player.games_played,
+        });
+        result
+    }
+
+    /// Tally a run awarded `live_xp` against the running scoring canary.
+    fn tally_scoring_canary(&mut self, factors: &XpFactors, live_xp: u64) {
+        if let Some(canary) = self
+            .state
+            .scoring_canary
+            .get_mut()
+            .as_mut()
+            .filter(|canary| canary.is_running())
+        {
+            canary.record(factors, live_xp);
+        }
+    }
+
+    /// Apply an accepted score: award XP, store the score and update totals.
+    /// Returns the stored score, the updated player and what the run earned.
+    async fn apply_score(
+        &mut self,
+        mut player: Player,
+        submission: ScoreSubmission,
+    ) -> (GameScore, Player, ScoreResult) {
+        let owner = player.owner;
+        let timestamp = self.runtime.now_micros();
+
+        // Calculate XP earned in the player's scoring category, with any
+        // underplayed-game and top-decile bonuses
+        let category = player.scoring_category;
+        let factors = self.state.xp_factors(&player, &submission, timestamp).await;
+        let xp_earned = self.state.scoring_config.get().xp(&factors);
+        self.tally_scoring_canary(&factors, xp_earned);
+        let ScoreSubmission {
+            game_type,
+            score,
+            bonus_data,
+            device_id,
+        } = submission;
+
+        let today = player.local_day(timestamp);
+        self.record_streak_day(&player).await;
+
+        // The run that completes the day's full circuit carries its bonus
+        let full_circuit_bonus = self
+            .record_circuit(owner, game_type, today)
+            .await
+            .then_some(circuit::FULL_CIRCUIT_BONUS_XP);
+
+        // Apprentices and their mentors each earn a share of the run's XP
+        let mentorship = self
+            .state
+            .mentorships
+            .get(&owner)
+            .await
+            .expect("Failed to load mentorship");
+        let mentorship_bonus = match mentorship {
+            Some(_) => mentorship::bonus_xp(xp_earned),
+            None => 0,
+        };
+        let xp_earned = xp_earned + mentorship_bonus + full_circuit_bonus.unwrap_or(0);
+
+        // The run counts towards the month's challenge, whose tiers pay out
+        // on top of it
+        let monthly_reward = self
+            .record_monthly_progress(owner, today, xp_earned, timestamp)
+            .await;
+        let xp_earned = xp_earned + monthly_reward;
+
+        // Update player stats
+        let previous_level = player.level;
+        player.add_xp(xp_earned);
+        player.increment_games();
+
+        // Save updated player
+        self.state
+            .players
+            .insert(&owner, player.clone())
+            .expect("Failed to update player");
+
+        // Update leaderboard entry
+        self.update_leaderboard(&player);
+
+        // Generate score ID and create score record
+        let score_id = {
+            let current = *self.state.score_counter.get();
+            self.state.score_counter.set(current + 1);
+            current
+        };
+
+        let game_score = GameScore {
+            id: score_id,
+            game_type,
+            player: owner.clone(),
+            score,
+            xp_earned,
+            bonus_data,
+            timestamp,
+            device_id,
+            category,
+            chain_id: self.runtime.chain(),
+        };
+
+        // Log score
+        self.log_score(game_score.clone()).await;
+
+        self.update_champion_score(&game_score).await;
+        self.update_recent_form(&game_score).await;
+        let personal_best = self.update_personal_best(&game_score).await;
+        self.credit_guild_contribution(&game_score).await;
+        self.credit_seasonal_xp(&game_score).await;
+        self.award_raffle_tickets(&game_score).await;
+        self.hit_boss_battles(&game_score).await;
+        self.update_presence(&game_score).await;
+
+        // Update totals (shadow-banned players don't count towards aggregates)
+        if !self.is_shadow_banned(&owner).await {
+            self.add_to_totals(&game_score).await;
+            self.screen_early_run(&game_score).await;
+        }
+
+        self.publish_level_up(owner, previous_level, player.level)
+            .await;
+        if full_circuit_bonus.is_some() {
+            self.publish_full_circuit(owner, today).await;
+        }
+        self.complete_goals(&player, &game_score).await;
+        self.unlock_event_achievements(&game_score).await;
+        let achievements_unlocked = self
+            .unlock_achievements(&player, &game_score, full_circuit_bonus.is_some())
+            .await;
+        if let Some(mentorship) = mentorship {
+            self.reward_mentor(mentorship, mentorship_bonus, player.level)
+                .await;
+        }
+        self.refresh_leaderboard_snapshot().await;
+
+        let result = ScoreResult {
+            xp_earned,
+            full_circuit_bonus,
+            achievements_unlocked,
+            receipt: game_score.receipt(),
+            total_xp: player.total_xp,
+            level: player.level,
+            leveled_up: player.level > previous_level,
+            personal_best,
+            rank: self.leaderboard_rank(&player).await,
+        };
+        (game_score, player, result)
+    }
+
+    /// Count a run earning `xp_earned` towards the player's challenge for the
+    /// month containing `today`, returning the bonus XP of the tiers it
+    /// reached.
+    async fn record_monthly_progress(
+        &mut self,
+        owner: AccountOwner,
+        today: u64,
+        xp_earned: u64,
+        timestamp: u64,
+    ) -> u64 {
+        let challenge = MonthlyChallenge::for_month(streaks::month_index(today));
+        let mut progress = self
+            .state
+            .monthly_progress
+            .get(&owner)
+            .await
+            .expect("Failed to load monthly challenge progress")
+            .unwrap_or_else(|| MonthlyProgress::new(owner, challenge.month));
+        let reached = progress.record(&challenge, xp_earned, timestamp);
+        for tier in &reached {
+            log::info!(
+                "{owner} reached the {:?} tier of the monthly challenge",
+                tier.tier
+            );
+        }
+        self.state
+            .monthly_progress
+            .insert(&owner, progress.clone())
+            .expect("Failed to update monthly challenge progress");
+        self.update_monthly_board(progress.clone()).await;
+        self.send_to_hub_if_needed(Message::SyncMonthlyProgress { progress });
+        reached.iter().map(|tier| tier.reward_xp).sum()
+    }
+
+    /// Put a player's monthly challenge progress on its month's leaderboard.
+    async fn update_monthly_board(&mut self, progress: MonthlyProgress) {
+        let month = progress.month;
+        let mut board = self
+            .state
+            .monthly_boards
+            .get(&month)
+            .await
+            .expect("Failed to load monthly challenge leaderboard")
+            .unwrap_or_default();
+        board.update(progress);
+        self.state
+            .monthly_boards
+            .insert(&month, board)
+            .expect("Failed to update monthly challenge leaderboard");
+    }
+
+    /// Count today towards the player's daily streak, spending shields on
+    /// any days missed since their last run.
+    async fn record_streak_day(&mut self, player: &Player) {
+        let owner = player.owner;
+        let mut streak = self
+            .state
+            .streaks
+            .get(&owner)
+            .await
+            .expect("Failed to load streak")
+            .unwrap_or_default();
+        let spent = streaks::record_run(&mut self.runtime, player, &mut streak);
+        if spent > 0 {
+            log::info!("{owner} spent {spent} streak shield(s) on missed days");
+        }
+        self.state
+            .streaks
+            .insert(&owner, streak)
+            .expect("Failed to update streak");
+    }
+
+    /// Count a completed game towards the player's daily circuit, returning
+    /// whether it completed the circuit.
+    async fn record_circuit(
+        &mut self,
+        owner: AccountOwner,
+        game_type: GameType,
+        today: u64,
+    ) -> bool {
+        let mut circuit = self
+            .state
+            .daily_circuits
+            .get(&owner)
+            .await
+            .expect("Failed to load daily circuit")
+            .unwrap_or_default();
+        let completed = circuit.record(game_type, today);
+        self.state
+            .daily_circuits
+            .insert(&owner, circuit)
+            .expect("Failed to update daily circuit");
+        completed
+    }
+
+    /// Credit a mentor with their share of an apprentice's run, ending the
+    /// mentorship once the apprentice reaches the graduation level.
+    async fn reward_mentor(
+        &mut self,
+        mut mentorship: Mentorship,
+        bonus: u64,
+        apprentice_level: u32,
+    ) {
+        let mentor = mentorship.mentor;
+        let apprentice = mentorship.apprentice;
+        let mut record = self.mentor_record(mentor).await;
+        record.bonus_xp = record.bonus_xp.saturating_add(bonus);
+        self.state
+            .mentors
+            .insert(&mentor, record)
+            .expect("Failed to update mentor");
+
+        if let Ok(Some(mut player)) = self.state.players.get(&mentor).await {
+            let previous_level = player.level;
+            player.add_xp(bonus);
+            self.state
+                .players
+                .insert(&mentor, player.clone())
+                .expect("Failed to update player");
+            self.update_leaderboard(&player);
+            self.publish_level_up(mentor, previous_level, player.level)
+                .await;
+            self.send_to_hub_if_needed(Message::SyncXpUpdate {
+                wallet_address: mentor,
+                total_xp: player.total_xp,
+                level: player.level,
+                games_played: player.games_played,
+            });
+        }
+
+        if apprentice_level >= mentorship::GRADUATION_LEVEL {
+            self.end_mentorship(mentor, apprentice, true).await;
+        } else {
+            mentorship.mentor_xp_earned = mentorship.mentor_xp_earned.saturating_add(bonus);
+            self.state
+                .mentorships
+                .insert(&apprentice, mentorship)
+                .expect("Failed to update mentorship");
+        }
+    }
+
+    /// End `apprentice`'s mentorship under `mentor`, if there is one.
+    /// Returns whether it existed.
+    async fn end_mentorship(
+        &mut self,
+        mentor: AccountOwner,
+        apprentice: AccountOwner,
+        graduated: bool,
+    ) -> bool {
+        let mentorship = self
+            .state
+            .mentorships
+            .get(&apprentice)
+            .await
+            .expect("Failed to load mentorship");
+        if mentorship.is_none_or(|mentorship| mentorship.mentor != mentor) {
+            return false;
+        }
+
+        let mut record = self.mentor_record(mentor).await;
+        record.remove_apprentice(&apprentice, graduated);
+        self.state
+            .mentors
+            .insert(&mentor, record)
+            .expect("Failed to update mentor");
+        self.state
+            .mentorships
+            .remove(&apprentice)
+            .expect("Failed to end mentorship");
+        true
+    }
+
+    /// Load a mentor's record, or a fresh one if they've never had an
+    /// apprentice.
+    async fn mentor_record(&self, mentor: AccountOwner) -> MentorRecord {
+        self.state
+            .mentors
+            .get(&mentor)
+            .await
+            .expect("Failed to load mentor")
+            .unwrap_or_else(|| MentorRecord::new(mentor))
+    }
+
+    /// Load the mentorship offers pending for an apprentice.
+    async fn mentorship_offers(&self, apprentice: &AccountOwner) -> MentorshipOffers {
+        self.state
+            .mentorship_offers
+            .get(apprentice)
+            .await
+            .expect("Failed to load mentorship offers")
+            .unwrap_or_default()
+    }
+
+    /// Store an apprentice's pending offers, dropping the entry once empty.
+    fn save_mentorship_offers(&mut self, apprentice: AccountOwner, offers: MentorshipOffers) {
+        if offers.is_empty() {
+            self.state
+                .mentorship_offers
+                .remove(&apprentice)
+                .expect("Failed to update mentorship offers");
+        } else {
+            self.state
+                .mentorship_offers
+                .insert(&apprentice, offers)
+                .expect("Failed to update mentorship offers");
+        }
+    }
+
+    /// Append a score to the log, making it the one its ID resolves to, and
+    /// index it among its player's scores.
+    async fn log_score(&mut self, score: GameScore) {
+        let position = self.state.score_log.count() as u64;
+        self.index_score(&score, position).await;
+        self.state.recent_scores.push_back(score.clone());
+        if self.state.recent_scores.count() > RECENT_SCORES_CAPACITY {
+            self.state.recent_scores.delete_front();
+        }
+        self.tally_category_xp(&score, false).await;
+        self.state.score_log.push(score);
+    }
+
+    /// Add a run's XP to its player's XP in its scoring category, or take it
+    /// back if the run was removed.
+    async fn tally_category_xp(&mut self, score: &GameScore, removed: bool) {
+        let key = (score.player, score.category);
+        let xp = self
+            .state
+            .category_xp
+            .get(&key)
+            .await
+            .expect("Failed to load category XP")
+            .unwrap_or(0);
+        let xp = if removed {
+            xp.saturating_sub(score.xp_earned)
+        } else {
+            xp.saturating_add(score.xp_earned)
+        };
+        self.state
+            .category_xp
+            .insert(&key, xp)
+            .expect("Failed to update category XP");
+    }
+
+    /// Remove the score at log `position`: stop looking it up and listing
+    /// it, and take its XP and game back from the player, the arcade totals
+    /// and any record it set, along with its guild, seasonal, challenge and
+    /// event credits. Payouts already made from it stand. The score itself
+    /// stays in the log.
+    async fn remove_score(&mut self, position: u64, score: &GameScore) {
+        self.state
+            .removed_scores
+            .insert(&position)
+            .expect("Failed to record removed score");
+        self.tally_category_xp(score, true).await;
+        self.state
+            .score_receipts
+            .remove(&score.receipt())
+            .expect("Failed to unindex score receipt");
+        self.state
+            .score_positions
+            .remove(&(score.chain_id, score.id))
+            .expect("Failed to unindex score");
+
+        if let Some(mut player) = self
+            .state
+            .players
+            .get(&score.player)
+            .await
+            .expect("Failed to load player")
+        {
+            player.remove_run(score.xp_earned);
+            self.state
+                .players
+                .insert(&score.player, player.clone())
+                .expect("Failed to update player");
+            self.update_leaderboard(&player);
+            // The run's monthly and circuit bonuses went with its XP
+            if score.chain_id == self.runtime.chain() {
+                self.forget_run_progress(&player, score).await;
+            }
+        }
+        self.uncredit_guild_contribution(score).await;
+        self.uncredit_seasonal_xp(score).await;
+        self.take_back_raffle_tickets(score).await;
+        self.heal_boss_battles(score).await;
+
+        // A shadow-banned player's runs are already out of the totals
+        if !self.is_shadow_banned(&score.player).await {
+            let total_games = *self.state.total_games_played.get();
+            self.state
+                .total_games_played
+                .set(total_games.saturating_sub(1));
+            let total_xp = *self.state.total_xp_earned.get();
+            self.state
+                .total_xp_earned
+                .set(total_xp.saturating_sub(score.xp_earned));
+        }
+
+        // A record set by the score is vacated until another run sets one
+        let record = self
+            .state
+            .hall_of_fame
+            .get(&score.game_type)
+            .await
+            .expect("Failed to load game record");
+        if record.is_some_and(|record| record.receipt() == score.receipt()) {
+            self.state
+                .hall_of_fame
+                .remove(&score.game_type)
+                .expect("Failed to update game record");
+        }
+        self.restore_personal_best(score).await;
+        self.forget_recent_score(score).await;
+        self.refresh_leaderboard_snapshot().await;
+    }
+
+    /// Take a removed run out of its player's monthly challenge and daily
+    /// circuit on the chain it was played on.
+    async fn forget_run_progress(&mut self, player: &Player, score: &GameScore) {
+        let day = player.local_day(score.timestamp);
+        let challenge = MonthlyChallenge::for_month(streaks::month_index(day));
+        let progress = self
+            .state
+            .monthly_progress
+            .get(&score.player)
+            .await
+            .expect("Failed to load monthly challenge progress");
+        if let Some(mut progress) = progress.filter(|progress| progress.month == challenge.month) {
+            progress.forget(&challenge, score.xp_earned);
+            self.state
+                .monthly_progress
+                .insert(&score.player, progress.clone())
+                .expect("Failed to update monthly challenge progress");
+            self.update_monthly_board(progress.clone()).await;
+            self.send_to_hub_if_needed(Message::SyncMonthlyProgress { progress });
+        }
+
+        let circuit = self
+            .state
+            .daily_circuits
+            .get(&score.player)
+            .await
+            .expect("Failed to load daily circuit");
+        if let Some(mut circuit) = circuit {
+            circuit.forget(score.game_type, day);
+            self.state
+                .daily_circuits
+                .insert(&score.player, circuit)
+                .expect("Failed to update daily circuit");
+        }
+    }
+
+    /// Take a removed score back out of its player's contribution to their
+    /// guild, if it was played since they joined.
+    async fn uncredit_guild_contribution(&mut self, score: &GameScore) {
+        if self.is_shadow_banned(&score.player).await {
+            return;
+        }
+        let Ok(Some(guild_id)) = self.state.player_guilds.get(&score.player).await else {
+            return;
+        };
+        let Ok(Some(guild)) = self.state.guilds.get(&guild_id).await else {
+            return;
+        };
+        let joined_before = guild
+            .members
+            .iter()
+            .any(|member| member.owner == score.player && member.joined_at <= score.timestamp);
+        let contribution = self
+            .state
+            .guild_contributions
+            .get(&score.player)
+            .await
+            .expect("Failed to load guild contribution")
+            .filter(|contribution| contribution.guild_id == guild_id);
+        let Some(mut contribution) = contribution.filter(|_| joined_before) else {
+            return;
+        };
+        contribution.forget(score.xp_earned);
+        self.state
+            .guild_contributions
+            .insert(&score.player, contribution)
+            .expect("Failed to update guild contribution");
+        let guild_xp = self.guild_xp(guild_id).await;
+        self.state
+            .guild_xp
+            .insert(&guild_id, guild_xp.saturating_sub(score.xp_earned))
+            .expect("Failed to update guild XP");
+    }
+
+    /// Take a removed score's XP back out of its player's seasonal XP, if it
+    /// was played in the current season.
+    async fn uncredit_seasonal_xp(&mut self, score: &GameScore) {
+        let Some(season) = *self.state.current_season.get() else {
+            return;
+        };
+        if score.timestamp < season.started_at {
+            return;
+        }
+        let Ok(Some(mut seasonal)) = self.state.seasonal_xp.get(&score.player).await else {
+            return;
+        };
+        seasonal.remove(season.number, score.xp_earned);
+        self.state
+            .seasonal_xp
+            .insert(&score.player, seasonal)
+            .expect("Failed to update seasonal XP");
+    }
+
+    /// Take back a removed score's tickets from each undrawn raffle it
+    /// earned them in.
+    async fn take_back_raffle_tickets(&mut self, score: &GameScore) {
+        let open = self
+            .state
+            .open_raffles
+            .indices()
+            .await
+            .expect("Failed to read open raffles");
+        for raffle_id in open {
+            let Some(mut raffle) = self
+                .state
+                .raffles
+                .get(&raffle_id)
+                .await
+                .expect("Failed to load raffle")
+            else {
+                continue;
+            };
+            let key = (raffle_id, score.player);
+            let held = self
+                .state
+                .raffle_tickets
+                .get(&key)
+                .await
+                .expect("Failed to load raffle tickets")
+                .unwrap_or(0);
+            if held < u64::from(raffle.spec.tickets_per_game) {
+                continue;
+            }
+            let Some(tickets) = raffle.take_back_tickets(score.timestamp) else {
+                continue;
+            };
+            if held == tickets {
+                self.state
+                    .raffle_tickets
+                    .remove(&key)
+                    .expect("Failed to take back raffle tickets");
+            } else {
+                self.state
+                    .raffle_tickets
+                    .insert(&key, held - tickets)
+                    .expect("Failed to take back raffle tickets");
+            }
+            self.state
+                .raffles
+                .insert(&raffle_id, raffle)
+                .expect("Failed to update raffle");
+        }
+    }
+
+    /// Undo a removed score's damage to each open boss battle it hit.
+    async fn heal_boss_battles(&mut self, score: &GameScore) {
+        let open = self
+            .state
+            .open_boss_battles
+            .indices()
+            .await
+            .expect("Failed to read open boss battles");
+        for battle_id in open {
+            let Some(mut battle) = self
+                .state
+                .boss_battles
+                .get(&battle_id)
+                .await
+                .expect("Failed to load boss battle")
+            else {
+                continue;
+            };
+            let key = (battle_id, score.player);
+            let dealt = self
+                .state
+                .boss_damage
+                .get(&key)
+                .await
+                .expect("Failed to load boss damage")
+                .unwrap_or(0);
+            let Some(healed) = battle.heal(score, dealt).filter(|healed| *healed > 0) else {
+                continue;
+            };
+            if dealt == healed {
+                battle.contributors = battle.contributors.saturating_sub(1);
+                self.state
+                    .boss_damage
+                    .remove(&key)
+                    .expect("Failed to update boss damage");
+            } else {
+                self.state
+                    .boss_damage
+                    .insert(&key, dealt - healed)
+                    .expect("Failed to update boss damage");
+            }
+            self.state
+                .boss_battles
+                .insert(&battle_id, battle)
+                .expect("Failed to update boss battle");
+        }
+    }
+
+    /// Whether a score counted towards a raffle already drawn or a boss
+    /// already defeated, whose payouts can't be taken back.
+    async fn is_paid_out(&self, score: &GameScore) -> bool {
+        let mut drawn = Vec::new();
+        self.state
+            .raffles
+            .for_each_index_value(|raffle_id, raffle| {
+                let window = raffle.spec.starts_at..raffle.spec.ends_at;
+                if raffle.draw.is_some() && window.contains(&score.timestamp) {
+                    drawn.push(raffle_id);
+                }
+                Ok(())
+            })
+            .await
+            .expect("Failed to read raffles");
+        for raffle_id in drawn {
+            let tickets = self
+                .state
+                .raffle_tickets
+                .get(&(raffle_id, score.player))
+                .await
+                .expect("Failed to load raffle tickets");
+            if tickets.is_some_and(|tickets| tickets > 0) {
+                return true;
+            }
+        }
+
+        let mut defeated = Vec::new();
+        self.state
+            .boss_battles
+            .for_each_index_value(|battle_id, battle| {
+                if battle.defeated_at.is_some() && battle.counts(score) {
+                    defeated.push(battle_id);
+                }
+                Ok(())
+            })
+            .await
+            .expect("Failed to read boss battles");
+        for battle_id in defeated {
+            let damage = self
+                .state
+                .boss_damage
+                .get(&(battle_id, score.player))
+                .await
+                .expect("Failed to load boss damage");
+            if damage.is_some_and(|damage| damage > 0) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Drop `score` from the recent scores, if it is still among them.
+    async fn forget_recent_score(&mut self, score: &GameScore) {
+        let recent = self
+            .state
+            .recent_scores
+            .elements()
+            .await
+            .expect("Failed to load recent scores");
+        if recent
+            .iter()
+            .all(|recent| recent.receipt() != score.receipt())
+        {
+            return;
+        }
+        self.state.recent_scores.clear();
+        for recent in recent {
+            if recent.receipt() != score.receipt() {
+                self.state.recent_scores.push_back(recent);
+            }
+        }
+    }
+
+    /// Replace a removed score that was its player's personal best with
+    /// their best remaining run of the game.
+    async fn restore_personal_best(&mut self, score: &GameScore) {
+        let mut bests = self
+            .state
+            .personal_bests
+            .get(&score.player)
+            .await
+            .expect("Failed to load personal bests")
+            .unwrap_or_default();
+        if !bests.forget(score) {
+            return;
+        }
+        let positions = match self
+            .state
+            .player_scores
+            .try_load_entry(&score.player)
+            .await
+            .expect("Failed to load player scores")
+        {
+            Some(log) => log.read(..).await.expect("Failed to load player scores"),
+            None => Vec::new(),
+        };
+        for position in positions {
+            if self.is_score_removed(position).await {
+                continue;
+            }
+            let run = self
+                .state
+                .score_log
+                .get(position as usize)
+                .await
+                .expect("Failed to load score");
+            if let Some(run) = run.filter(|run| run.game_type == score.game_type) {
+                bests.record(&run);
+            }
+        }
+        self.state
+            .personal_bests
+            .insert(&score.player, bests)
+            .expect("Failed to update personal bests");
+    }
+
+    /// Whether the score at log `position` was removed by moderation.
+    async fn is_score_removed(&self, position: u64) -> bool {
+        self.state
+            .removed_scores
+            .contains(&position)
+            .await
+            .expect("Failed to load removed scores")
+    }
+
+    /// Challenge `player` to prove they are human, replacing any challenge
+    /// they had, and return the new challenge's ID.
+    async fn issue_humanity_challenge(
+        &mut self,
+        player: AccountOwner,
+        flag_id: Option<u64>,
+        timestamp: u64,
+    ) -> u64 {
+        let challenge_id = *self.state.humanity_challenge_counter.get() + 1;
+        self.state.humanity_challenge_counter.set(challenge_id);
+        let challenge = HumanityChallenge::new(
+            challenge_id,
+            player,
+            flag_id,
+            self.runtime.chain_id(),
+            timestamp,
+        );
+        self.state
+            .humanity_challenges
+            .insert(&player, challenge.clone())
+            .expect("Failed to issue humanity challenge");
+        if self.is_hub_chain() {
+            self.publish_event(ArcadeEvent::HumanityChallengeIssued(challenge))
+                .await;
+        }
+        challenge_id
+    }
+
+    /// Index the score at log `position` by its chain and ID, its receipt
+    /// and among its player's scores.
+    async fn index_score(&mut self, score: &GameScore, position: u64) {
+        self.state
+            .score_receipts
+            .insert(&score.receipt(), position)
+            .expect("Failed to index score receipt");
+        self.state
+            .score_positions
+            .insert(&(score.chain_id, score.id), position)
+            .expect("Failed to index score");
+        self.state
+            .player_scores
+            .load_entry_mut(&score.player)
+            .await
+            .expect("Failed to load player scores")
+            .push(position);
+    }
+
+    /// Journal a state change, taking a checkpoint of the arcade totals
+    /// every `JOURNAL_CHECKPOINT_INTERVAL` entries.
+    fn journal(
+        &mut self,
+        kind: JournalKind,
+        name: Option<&str>,
+        signer: Option<AccountOwner>,
+        origin: Option<ChainId>,
+        encoded: Vec<u8>,
+    ) {
+        let now = self.runtime.now_micros();
+        let sequence = self.state.journal.count() as u64;
+        self.state.journal.push(JournalEntry {
+            sequence,
+            kind,
+            name: name.map(str::to_string),
+            signer,
+            origin,
+            encoded,
+            recorded_at: now,
+        });
+        let journal_length = sequence + 1;
+        if journal_length.is_multiple_of(JOURNAL_CHECKPOINT_INTERVAL) {
+            self.state.journal_checkpoints.push(JournalCheckpoint {
+                journal_length,
+                score_log_length: self.state.score_log.count() as u64,
+                total_games_played: *self.state.total_games_played.get(),
+                total_xp_earned: *self.state.total_xp_earned.get(),
+                taken_at: now,
+            });
+        }
+    }
+
+    /// Clear the views a replay of `target` rebuilds. Totals are only
+    /// overwritten once the replay is complete.
+    fn start_replay(&mut self, target: ReplayTarget) {
+        match target {
+            ReplayTarget::Leaderboard => {
+                self.state.leaderboard.clear();
+                self.state.rookie_leaderboard.clear();
+            }
+            ReplayTarget::ScoreIndexes => {
+                self.state.score_positions.clear();
+                self.state.score_receipts.clear();
+                self.state.player_scores.clear();
+            }
+            ReplayTarget::Totals => {}
+        }
+    }
+
+    /// Put the next players back on the leaderboards. Returns whether the
+    /// replay is complete.
+    async fn replay_leaderboard(&mut self, progress: &mut ReplayProgress) -> bool {
+        let players = map_page(
+            &self.state.players,
+            progress.replayed as usize,
+            REPLAY_CHUNK_SIZE,
+        )
+        .await;
+        for (_, player) in &players {
+            self.update_leaderboard(player);
+        }
+        progress.replayed += players.len() as u64;
+        players.len() < REPLAY_CHUNK_SIZE
+    }
+
+    /// Index the next logged scores. Returns whether the replay is
+    /// complete.
+    async fn replay_score_indexes(&mut self, progress: &mut ReplayProgress) -> bool {
+        let count = self.state.score_log.count();
+        let start = (progress.replayed as usize).min(count);
+        let end = (start + REPLAY_CHUNK_SIZE).min(count);
+        let scores = self
+            .state
+            .score_log
+            .read(start..end)
+            .await
+            .expect("Failed to read score log");
+        for (position, score) in (start as u64..).zip(&scores) {
+            if !self.is_score_removed(position).await {
+                self.index_score(score, position).await;
+            }
+        }
+        progress.replayed = end as u64;
+        end == count
+    }
+
+    /// Add the next logged scores to the totals, storing them once the
+    /// whole log is counted. Shadow-banned players' scores are left out, as
+    /// the ban takes all of them out of the totals. Returns whether the
+    /// replay is complete.
+    async fn replay_totals(&mut self, progress: &mut ReplayProgress) -> bool {
+        let count = self.state.score_log.count();
+        let start = (progress.replayed as usize).min(count);
+        let end = (start + REPLAY_CHUNK_SIZE).min(count);
+        let scores = self
+            .state
+            .score_log
+            .read(start..end)
+            .await
+            .expect("Failed to read score log");
+        for (position, score) in (start as u64..).zip(&scores) {
+            if !self.is_score_removed(position).await && !self.is_shadow_banned(&score.player).await
+            {
+                progress.games += 1;
+                progress.xp = progress.xp.saturating_add(score.xp_earned);
+            }
+        }
+        progress.replayed = end as u64;
+        if end < count {
+            return false;
+        }
+        self.state.total_games_played.set(progress.games);
+        self.state.total_xp_earned.set(progress.xp);
+        true
+    }
+
+    /// Load generated players, scores and seasons into empty state.
+    async fn apply_fixture(&mut self, fixture: Fixture) {
+        for player in fixture.players {
+            let owner = player.owner;
+            self.update_leaderboard(&player);
+            self.backfill_username_claim(&player).await;
+            self.state
+                .players
+                .insert(&owner, player)
+                .expect("Failed to insert player");
+            self.count_players(1);
+        }
+        self.state.score_counter.set(fixture.scores.len() as u64);
+        for score in fixture.scores {
+            self.log_score(score.clone()).await;
+            self.update_champion_score(&score).await;
+            self.add_to_totals(&score).await;
+        }
+        for archive in fixture.archived_seasons {
+            let number = archive.season.number;
+            self.state
+                .season_archive
+                .insert(&number, archive)
+                .expect("Failed to archive season");
+        }
+        for (owner, seasonal) in fixture.seasonal_xp {
+            self.state
+                .seasonal_xp
+                .insert(&owner, seasonal)
+                .expect("Failed to update seasonal XP");
+        }
+        if let Some(season) = fixture.current_season {
+            self.state.current_season.set(Some(season));
+        }
+    }
+
+    /// Store the current public leaderboard as a snapshot.
+    async fn take_snapshot(&mut self, timestamp: u64, automatic: bool) -> u64 {
+        let hidden = self.hidden_from_leaderboards().await;
+        let mut entries = Vec::new();
+        self.state
+            .leaderboard
+            .for_each_index_value(|owner, entry| {
+                if !hidden.contains(&owner) {
+                    entries.push((owner, entry.total_xp));
+                }
+                Ok(())
+            })
+            .await
+            .expect("Failed to read leaderboard");
+        entries.sort_by_key(|(_, total_xp)| std::cmp::Reverse(*total_xp));
+        let truncated = entries.len() > snapshots::MAX_SNAPSHOT_ENTRIES;
+        entries.truncate(snapshots::MAX_SNAPSHOT_ENTRIES);
+
+        let snapshot_id = {
+            let current = *self.state.snapshot_counter.get();
+            self.state.snapshot_counter.set(current + 1);
+            current
+        };
+        let snapshot = LeaderboardSnapshot {
+            id: snapshot_id,
+            taken_at: timestamp,
+            automatic,
+            entries: entries
+                .into_iter()
+                .enumerate()
+                .map(|(i, (wallet_address, total_xp))| SnapshotEntry {
+                    wallet_address,
+                    rank: (i + 1) as u32,
+                    total_xp,
+                })
+                .collect(),
+            truncated,
+        };
+        self.state
+            .leaderboard_snapshots
+            .insert(&snapshot_id, snapshot)
+            .expect("Failed to store snapshot");
+
+        self.state.latest_snapshot.set(Some(snapshot_id));
+        snapshot_id
+    }
+
+    /// On the hub, replace the oldest automatic snapshot with a fresh one once
+    /// the latest is older than the refresh interval. Paginated leaderboard
+    /// queries read from these snapshots. Rookies who aged out since the
+    /// last refresh are graduated at the same time.
+    async fn refresh_leaderboard_snapshot(&mut self) {
+        if !self.is_hub_chain() {
+            return;
+        }
+        let timestamp = self.runtime.now_micros();
+        if let Some(latest) = *self.state.latest_snapshot.get() {
+            let taken_at = self
+                .state
+                .leaderboard_snapshots
+                .get(&latest)
+                .await
+                .expect("Failed to load snapshot")
+                .map_or(0, |snapshot| snapshot.taken_at);
+            if timestamp.saturating_sub(taken_at) < snapshots::SNAPSHOT_REFRESH_INTERVAL_MICROS {
+                return;
+            }
+        }
+
+        let snapshot_id = self.take_snapshot(timestamp, true).await;
+        self.graduate_rookies(timestamp).await;
+        self.state.automatic_snapshots.push_back(snapshot_id);
+        while self.state.automatic_snapshots.count() > snapshots::MAX_AUTOMATIC_SNAPSHOTS {
+            let expired = self
+                .state
+                .automatic_snapshots
+                .front()
+                .await
+                .expect("Failed to read automatic snapshots")
+                .expect("Queue is not empty");
+            self.state.automatic_snapshots.delete_front();
+            self.state
+                .leaderboard_snapshots
+                .remove(&expired)
+                .expect("Failed to prune snapshot");
+        }
+    }
+
+    /// Trim the next few archived snapshots to the retention limit, resuming
+    /// where the last call stopped, so storage is reclaimed a chunk at a time
+    /// during normal operations. The latest snapshot is left whole.
+    async fn prune_leaderboard_history(&mut self) {
+        let Some(top_k) = *self.state.history_top_k.get() else {
+            return;
+        };
+        let Some(latest) = *self.state.latest_snapshot.get() else {
+            return;
+        };
+        let mut cursor = *self.state.history_prune_cursor.get();
+        let end = latest.min(cursor + snapshots::HISTORY_PRUNE_CHUNK);
+        while cursor < end {
+            let snapshot = self
+                .state
+                .leaderboard_snapshots
+                .get(&cursor)
+                .await
+                .expect("Failed to load snapshot");
+            // Automatic snapshots are pruned whole, and trimming one would
+            // break the page tokens pinned to it
+            if let Some(mut snapshot) = snapshot.filter(|snapshot| !snapshot.automatic) {
+                if snapshot.retain_top(top_k) {
+                    self.state
+                        .leaderboard_snapshots
+                        .insert(&cursor, snapshot)
+                        .expect("Failed to trim snapshot");
+                }
+            }
+            cursor += 1;
+        }
+        self.state.history_prune_cursor.set(cursor);
+    }
+
+    /// Roll the current season over if it has ended.
+    async fn roll_over_season_if_due(&mut self) {
+        if let Some(season) = *self.state.current_season.get() {
+            if seasons::is_due(&mut self.runtime, &season) {
+                self.roll_over_season(season).await;
+            }
+        }
+    }
+
+    /// Archive `season`'s final standings and start the next season,
+    /// returning it.
+    async fn roll_over_season(&mut self, season: Season) -> Season {
+        let now = self.runtime.now_micros();
+        let hidden = self.hidden_from_leaderboards().await;
+        let mut entries = Vec::new();
+        self.state
+            .seasonal_xp
+            .for_each_index_value(|owner, seasonal| {
+                let xp = seasonal.in_season(season.number);
+                if xp > 0 && !hidden.contains(&owner) {
+                    entries.push((owner, xp));
+                }
+                Ok(())
+            })
+            .await
+            .expect("Failed to read seasonal XP");
+
+        let standings = seasons::rank(entries);
+        self.grant_season_cosmetics(season.number, &standings, now)
+            .await;
+
+        let mut archive = SeasonArchive {
+            season,
+            archived_at: now,
+            standings,
+        };
+        archive.retain_top(seasons::MAX_ARCHIVED_STANDINGS as u32);
+        if let Some(top_k) = *self.state.history_top_k.get() {
+            archive.retain_top(top_k);
+        }
+        self.state
+            .season_archive
+            .insert(&season.number, archive)
+            .expect("Failed to archive season");
+
+        let next = seasons::start_next(&mut self.runtime, &season);
+        self.state.current_season.set(Some(next));
+        log::info!(
+            "Season {} ended; season {} started",
+            season.number,
+            next.number
+        );
+        next
+    }
+
+    /// Grant every ranked player of season `number` the cosmetic its theme
+    /// rewards for their final tier, if it has a theme.
+    async fn grant_season_cosmetics(
+        &mut self,
+        number: u32,
+        standings: &[SeasonStanding],
+        now: u64,
+    ) {
+        let Some(theme) = self
+            .state
+            .season_themes
+            .get(&number)
+            .await
+            .expect("Failed to load season theme")
+        else {
+            return;
+        };
+        for standing in standings {
+            let tier = SeasonTier::for_rank(standing.rank);
+            let Some(cosmetic) = theme.reward_for(tier) else {
+                continue;
+            };
+            let player = standing.wallet_address;
+            let mut inventory = self
+                .state
+                .cosmetics
+                .get(&player)
+                .await
+                .expect("Failed to load cosmetics")
+                .unwrap_or_default();
+            let granted = inventory.grant(OwnedCosmetic {
+                cosmetic: cosmetic.clone(),
+                season: number,
+                theme: theme.name.clone(),
+                tier,
+                granted_at: now,
+            });
+            if granted {
+                self.state
+                    .cosmetics
+                    .insert(&player, inventory)
+                    .expect("Failed to grant cosmetic");
+            }
+        }
+    }
+
+    /// Trim every archived season's standings to `top_k`.
+    async fn trim_season_archives(&mut self, top_k: u32) {
+        let numbers = self
+            .state
+            .season_archive
+            .indices()
+            .await
+            .expect("Failed to read season archive");
+        for number in numbers {
+            let archive = self
+                .state
+                .season_archive
+                .get(&number)
+                .await
+                .expect("Failed to load archived season");
+            if let Some(mut archive) = archive {
+                if archive.retain_top(top_k) {
+                    self.state
+                        .season_archive
+                        .insert(&number, archive)
+                        .expect("Failed to trim archived season");
+                }
+            }
+        }
+    }
+
+    /// Count a score's XP towards its player's seasonal XP, if a season is
+    /// running, rolling the season over first if it has ended.
+    async fn credit_seasonal_xp(&mut self, score: &GameScore) {
+        self.roll_over_season_if_due().await;
+        let Some(season) = *self.state.current_season.get() else {
+            return;
+        };
+        let mut seasonal = self
+            .state
+            .seasonal_xp
+            .get(&score.player)
+            .await
+            .expect("Failed to load seasonal XP")
+            .unwrap_or_default();
+        seasonal.add(season.number, score.xp_earned);
+        self.state
+            .seasonal_xp
+            .insert(&score.player, seasonal)
+            .expect("Failed to update seasonal XP");
+    }
+
+    /// Award a score's player the tickets it earns in each open raffle whose
+    /// window it was played in. Shadow-banned players earn none, and neither
+    /// do the admin and a raffle's funder, who know the secret it's drawn
+    /// from.
+    async fn award_raffle_tickets(&mut self, score: &GameScore) {
+        let open = self
+            .state
+            .open_raffles
+            .indices()
+            .await
+            .expect("Failed to read open raffles");
+        if open.is_empty()
+            || self.ensure_admin(&score.player).is_ok()
+            || self.is_shadow_banned(&score.player).await
+        {
+            return;
+        }
+        for raffle_id in open {
+            let Some(mut raffle) = self
+                .state
+                .raffles
+                .get(&raffle_id)
+                .await
+                .expect("Failed to load raffle")
+            else {
+                continue;
+            };
+            if raffle.funded_by == score.player {
+                continue;
+            }
+            let Some(tickets) = raffle.award_tickets(score.receipt(), score.timestamp) else {
+                continue;
+            };
+            let key = (raffle_id, score.player);
+            let held = self
+                .state
+                .raffle_tickets
+                .get(&key)
+                .await
+                .expect("Failed to load raffle tickets")
+                .unwrap_or(0);
+            self.state
+                .raffle_tickets
+                .insert(&key, held + tickets)
+                .expect("Failed to award raffle tickets");
+            self.state
+                .raffles
+                .insert(&raffle_id, raffle)
+                .expect("Failed to update raffle");
+        }
+    }
 
-        // Save updated player
+    /// Deal a score's damage to each open boss battle whose window it was
+    /// played in, settling any boss it defeats. Battles that have escaped
+    /// are settled first, so late runs don't count towards them.
+    /// Shadow-banned players deal no damage.
+    async fn hit_boss_battles(&mut self, score: &GameScore) {
+        let now = self.runtime.now_micros();
+        self.settle_escaped_boss_battles(now).await;
+        let open = self
+            .state
+            .open_boss_battles
+            .indices()
+            .await
+            .expect("Failed to read open boss battles");
+        if open.is_empty() || self.is_shadow_banned(&score.player).await {
+            return;
+        }
+        for battle_id in open {
+            let Some(mut battle) = self
+                .state
+                .boss_battles
+                .get(&battle_id)
+                .await
+                .expect("Failed to load boss battle")
+            else {
+                continue;
+            };
+            let Some(dealt) = battle.hit(score) else {
+                continue;
+            };
+            let key = (battle_id, score.player);
+            let dealt_before = self
+                .state
+                .boss_damage
+                .get(&key)
+                .await
+                .expect("Failed to load boss damage")
+                .unwrap_or(0);
+            if dealt_before == 0 {
+                battle.contributors += 1;
+            }
+            self.state
+                .boss_damage
+                .insert(&key, dealt_before + dealt)
+                .expect("Failed to record boss damage");
+
+            if battle.is_defeated() {
+                log::info!(
+                    "Boss battle {battle_id} won by {} players",
+                    battle.contributors
+                );
+                self.settle_boss_battle(battle, now).await;
+            } else {
+                self.state
+                    .boss_battles
+                    .insert(&battle_id, battle)
+                    .expect("Failed to update boss battle");
+            }
+        }
+    }
+
+    /// Settle every open boss battle that escaped before `now`.
+    async fn settle_escaped_boss_battles(&mut self, now: u64) {
+        let open = self
+            .state
+            .open_boss_battles
+            .indices()
+            .await
+            .expect("Failed to read open boss battles");
+        for battle_id in open {
+            let battle = self
+                .state
+                .boss_battles
+                .get(&battle_id)
+                .await
+                .expect("Failed to load boss battle");
+            if let Some(battle) = battle.filter(|battle| battle.has_escaped(now)) {
+                self.settle_boss_battle(battle, now).await;
+            }
+        }
+    }
+
+    /// Close a boss battle, releasing its damage records and, if the boss
+    /// was defeated, rewarding its contributors.
+    async fn settle_boss_battle(&mut self, mut battle: BossBattle, now: u64) {
+        let battle_id = battle.id;
+        let mut contributions = Vec::new();
         self.state
-            .players
-            .insert(&owner, player.clone())
-            .expect("Failed to update player");
+            .boss_damage
+            .for_each_index_value(|(id, player), damage| {
+                if id == battle_id {
+                    contributions.push((player, *damage));
+                }
+                Ok(())
+            })
+            .await
+            .expect("Failed to read boss damage");
+        for (player, _) in &contributions {
+            self.state
+                .boss_damage
+                .remove(&(battle_id, *player))
+                .expect("Failed to release boss damage");
+        }
 
-        // Update leaderboard entry
-        let entry = LeaderboardEntry::from_player(&player, 0);
+        if battle.is_defeated() {
+            for (player, reward) in bosses::reward_shares(&battle, contributions, now) {
+                let mut rewards = self
+                    .state
+                    .boss_rewards
+                    .get(&player)
+                    .await
+                    .expect("Failed to load boss rewards")
+                    .unwrap_or_default();
+                rewards.add(reward);
+                self.state
+                    .boss_rewards
+                    .insert(&player, rewards)
+                    .expect("Failed to update boss rewards");
+            }
+        }
+
+        battle.settled_at = Some(now);
+        self.state
+            .boss_battles
+            .insert(&battle_id, battle)
+            .expect("Failed to settle boss battle");
+        self.state
+            .open_boss_battles
+            .remove(&battle_id)
+            .expect("Failed to close boss battle");
+    }
+
+    /// `player`'s rank on this chain's leaderboard, one more than the listed
+    /// players with more XP. Counts them even if they are hidden from it.
+    async fn leaderboard_rank(&self, player: &Player) -> u32 {
+        let hidden = self.hidden_from_leaderboards().await;
+        let mut ahead = 0;
         self.state
             .leaderboard
-            .insert(&owner, entry)
-            .expect("Failed to update leaderboard");
+            .for_each_index_value(|owner, entry| {
+                if entry.total_xp > player.total_xp && !hidden.contains(&owner) {
+                    ahead += 1;
+                }
+                Ok(())
+            })
+            .await
+            .expect("Failed to read leaderboard");
+        ahead + 1
+    }
 
-        // Send sync message to hub
-        self.send_to_hub_if_needed(Message::SyncPlayer(player));
+    /// Players left off public leaderboards: the banned, the shadow-banned
+    /// and those who opted out.
+    async fn hidden_from_leaderboards(&self) -> BTreeSet<AccountOwner> {
+        let mut hidden = BTreeSet::new();
+        for index in [
+            &self.state.banned_players,
+            &self.state.shadow_banned,
+            &self.state.leaderboard_opt_outs,
+        ] {
+            hidden.extend(index.indices().await.unwrap_or_default());
+        }
+        hidden
+    }
 
-        ArcadeResponse::UsernameUpdated
+    /// Count a score towards its player's champion score.
+    async fn update_champion_score(&mut self, score: &GameScore) {
+        let mut champion = self
+            .state
+            .champion_scores
+            .get(&score.player)
+            .await
+            .expect("Failed to load champion score")
+            .unwrap_or_default();
+        champion.record(score.game_type, score.xp_earned);
+        self.state
+            .champion_scores
+            .insert(&score.player, champion)
+            .expect("Failed to update champion score");
     }
 
-    /// Handle syncing a player from another chain (hub only).
-    async fn handle_sync_player(&mut self, player: Player) {
-        let owner = player.owner.clone();
+    /// Count a score towards its player's current form.
+    async fn update_recent_form(&mut self, score: &GameScore) {
+        let mut form = self
+            .state
+            .recent_form
+            .get(&score.player)
+            .await
+            .expect("Failed to load recent form")
+            .unwrap_or_default();
+        form.record(score.xp_earned, score.timestamp);
+        self.state
+            .recent_form
+            .insert(&score.player, form)
+            .expect("Failed to update recent form");
+    }
 
-        // Upsert player - preserve XP if exists
-        if let Ok(Some(existing)) = self.state.players.get(&owner).await {
-            // Keep the higher XP value
-            let mut updated = player.clone();
-            if existing.total_xp > updated.total_xp {
-                updated.total_xp = existing.total_xp;
-                updated.level = existing.level;
-                updated.games_played = existing.games_played;
-            }
-            self.state
-                .players
-                .insert(&owner, updated.clone())
-                .expect("Failed to update player");
+    /// Mark a daily challenge run's day as completed for its player and
+    /// place it on that day's leaderboard.
+    async fn record_challenge_entry(&mut self, entry: ChallengeEntry) -> Result<(), ArcadeError> {
+        let mut completions = self
+            .state
+            .challenge_completions
+            .get(&entry.player)
+            .await
+            .expect("Failed to load challenge completions")
+            .unwrap_or_default();
+        if !completions.complete(entry.day) {
+            return Err(ArcadeError::ChallengeAlreadySubmitted);
+        }
+        self.state
+            .challenge_completions
+            .insert(&entry.player, completions)
+            .expect("Failed to update challenge completions");
 
-            let entry = LeaderboardEntry::from_player(&updated, 0);
-            self.state
-                .leaderboard
-                .insert(&owner, entry)
-                .expect("Failed to update leaderboard");
-        } else {
-            self.state
-                .players
-                .insert(&owner, player.clone())
-                .expect("Failed to insert player");
+        let day = entry.day;
+        let mut board = match self
+            .state
+            .challenge_boards
+            .get(&day)
+            .await
+            .expect("Failed to load challenge leaderboard")
+        {
+            Some(board) => board,
+            None => {
+                self.state.challenge_board_days.push_back(day);
+                self.prune_challenge_boards(day).await;
+                ChallengeBoard::default()
+            }
+        };
+        board.insert(entry);
+        self.state
+            .challenge_boards
+            .insert(&day, board)
+            .expect("Failed to update challenge leaderboard");
+        Ok(())
+    }
 
-            let entry = LeaderboardEntry::from_player(&player, 0);
+    /// Drop the challenge leaderboards of days more than
+    /// [`challenges::CHALLENGE_BOARD_DAYS`] before `today`.
+    async fn prune_challenge_boards(&mut self, today: u64) {
+        while let Some(oldest) = self
+            .state
+            .challenge_board_days
+            .front()
+            .await
+            .expect("Failed to read challenge leaderboard days")
+        {
+            if oldest + challenges::CHALLENGE_BOARD_DAYS >= today {
+                break;
+            }
+            self.state.challenge_board_days.delete_front();
             self.state
-                .leaderboard
-                .insert(&owner, entry)
-                .expect("Failed to insert leaderboard");
+                .challenge_boards
+                .remove(&oldest)
+                .expect("Failed to prune challenge leaderboard");
         }
     }
 
-    /// Handle syncing a score from another chain (hub only).
-    async fn handle_sync_score(&mut self, score: GameScore) {
-        // Insert score with its original ID
+    /// Keep a score if it's its player's best run in its game, for followers'
+    /// feeds. Returns whether it was.
+    async fn update_personal_best(&mut self, score: &GameScore) -> bool {
+        let mut bests = self
+            .state
+            .personal_bests
+            .get(&score.player)
+            .await
+            .expect("Failed to load personal bests")
+            .unwrap_or_default();
+        if !bests.record(score) {
+            return false;
+        }
         self.state
-            .game_scores
-            .insert(&score.id, score.clone())
-            .expect("Failed to insert score");
+            .personal_bests
+            .insert(&score.player, bests)
+            .expect("Failed to update personal bests");
+        true
+    }
 
-        // Update totals
+    /// Add a score to the arcade-wide totals, activity heatmap, score
+    /// histograms and hall of fame. On the hub, publish it and celebrate
+    /// any milestones it crosses.
+    async fn add_to_totals(&mut self, score: &GameScore) {
         let total_games = *self.state.total_games_played.get();
         self.state.total_games_played.set(total_games + 1);
 
         let total_xp = *self.state.total_xp_earned.get();
         self.state.total_xp_earned.set(total_xp + score.xp_earned);
+        self.state.play_heatmap.get_mut().record(score.timestamp);
+        self.state
+            .play_share
+            .get_mut()
+            .record(score.game_type, score.timestamp);
+        let mut histogram = self
+            .state
+            .score_histograms
+            .get(&score.game_type)
+            .await
+            .expect("Failed to load score histogram")
+            .unwrap_or_else(|| ScoreHistogram::new(score.game_type));
+        histogram.record(score.score);
+        self.state
+            .score_histograms
+            .insert(&score.game_type, histogram)
+            .expect("Failed to update score histogram");
+        let mut new_record = None;
+        if score.category == ScoringCategory::Standard {
+            let record = self
+                .state
+                .hall_of_fame
+                .get(&score.game_type)
+                .await
+                .expect("Failed to load game record");
+            if record
+                .as_ref()
+                .is_none_or(|record| score.game_type.beats(score.score, record.score))
+            {
+                self.state
+                    .hall_of_fame
+                    .insert(&score.game_type, score.clone())
+                    .expect("Failed to update game record");
+                new_record = Some(record.map(|record| record.score));
+            }
+        }
+
+        // Only the hub sees arcade-wide totals
+        if !self.is_hub_chain() {
+            return;
+        }
+        self.publish_score(score, new_record).await;
+        let crossed = MilestoneKind::GamesPlayed
+            .crossed(total_games, total_games + 1)
+            .map(|threshold| (MilestoneKind::GamesPlayed, threshold))
+            .chain(
+                MilestoneKind::XpEarned
+                    .crossed(total_xp, total_xp + score.xp_earned)
+                    .map(|threshold| (MilestoneKind::XpEarned, threshold)),
+            )
+            .collect::<Vec<_>>();
+        for (kind, threshold) in crossed {
+            let milestone = Milestone {
+                kind,
+                threshold,
+                player: score.player,
+                score_id: score.id,
+                reached_at: score.timestamp,
+            };
+            self.state.milestones.push(milestone.clone());
+            self.publish_event(ArcadeEvent::MilestoneReached(milestone))
+                .await;
+        }
     }
 
-    /// Handle XP update sync from another chain (hub only).
-    async fn handle_sync_xp_update(
+    /// On the hub, place a new account's early run in the new-player score
+    /// distribution, flagging the account for review once enough of its
+    /// early runs are far better than that distribution's median.
+    async fn screen_early_run(&mut self, score: &GameScore) {
+        if !self.is_hub_chain() || score.category != ScoringCategory::Standard {
+            return;
+        }
+        let config = self.state.smurf_detection.get().clone();
+        let mut runs = self
+            .state
+            .early_runs
+            .get(&score.player)
+            .await
+            .expect("Failed to load early runs")
+            .unwrap_or_default();
+        if !runs.is_early(&config) {
+            return;
+        }
+
+        let mut histogram = self
+            .state
+            .early_score_histograms
+            .get(&score.game_type)
+            .await
+            .expect("Failed to load early score histogram")
+            .unwrap_or_else(|| ScoreHistogram::new(score.game_type));
+        let suspicious = histogram.total() >= config.min_samples
+            && histogram.percentile(50.0).is_some_and(|median| {
+                config.is_suspicious(score.score, median, score.game_type.lower_score_is_better())
+            });
+        histogram.record(score.score);
+        self.state
+            .early_score_histograms
+            .insert(&score.game_type, histogram)
+            .expect("Failed to update early score histogram");
+
+        let flag = runs.record(suspicious, &config);
+        if flag {
+            let flag_id = *self.state.moderation_flag_counter.get() + 1;
+            self.state.moderation_flag_counter.set(flag_id);
+            log::info!(
+                "Flagged {} as a suspected smurf after {} suspicious early runs",
+                score.player,
+                runs.suspicious(),
+            );
+            self.state
+                .moderation_queue
+                .insert(
+                    &flag_id,
+                    ModerationFlag {
+                        id: flag_id,
+                        player: score.player,
+                        reason: FlagReason::SuspectedSmurf,
+                        suspicious_runs: runs.suspicious(),
+                        games_screened: runs.games(),
+                        flagged_at: score.timestamp,
+                    },
+                )
+                .expect("Failed to update moderation queue");
+            if self.state.humanity_checks.get().challenge_flagged {
+                self.issue_humanity_challenge(score.player, Some(flag_id), score.timestamp)
+                    .await;
+            }
+        }
+        self.state
+            .early_runs
+            .insert(&score.player, runs)
+            .expect("Failed to update early runs");
+    }
+
+    /// Publish an event on [`ARCADE_EVENT_STREAM`] and forward it to every
+    /// bridged application.
+    async fn publish_event(&mut self, event: ArcadeEvent) {
+        self.runtime.emit(ARCADE_EVENT_STREAM.into(), &event);
+
+        let mut deliveries = Vec::new();
+        self.state
+            .bridge_subscriptions
+            .for_each_index_value(|application_id, subscription| {
+                deliveries.extend(
+                    subscription
+                        .chains()
+                        .map(|chain_id| (application_id, chain_id)),
+                );
+                Ok(())
+            })
+            .await
+            .expect("Failed to read bridge subscriptions");
+        for (application_id, chain_id) in deliveries {
+            self.runtime
+                .prepare_message(Message::BridgeEvent {
+                    application_id,
+                    event: event.clone(),
+                })
+                .send_to(chain_id);
+        }
+    }
+
+    /// Publish a new player's registration (hub only).
+    async fn publish_registration(&mut self, player: &Player) {
+        if self.is_hub_chain() {
+            self.publish_event(ArcadeEvent::PlayerRegistered {
+                player: player.owner,
+                username: player.username.clone(),
+            })
+            .await;
+        }
+    }
+
+    /// Publish a score counted on the hub and, if `new_record` holds the
+    /// record it beat, the new hall of fame record, leaving out what the
+    /// player keeps private.
+    async fn publish_score(&mut self, score: &GameScore, new_record: Option<Option<u64>>) {
+        let history_public = !self
+            .state
+            .history_opt_outs
+            .contains(&score.player)
+            .await
+            .unwrap_or(false);
+        if history_public {
+            self.publish_event(ArcadeEvent::ScoreSubmitted {
+                player: score.player,
+                score_id: score.id,
+                game_type: score.game_type,
+                score: score.score,
+                xp_earned: score.xp_earned,
+            })
+            .await;
+        }
+        let listed = !self
+            .state
+            .leaderboard_opt_outs
+            .contains(&score.player)
+            .await
+            .unwrap_or(false);
+        if let Some(previous) = new_record.filter(|_| listed) {
+            self.publish_event(ArcadeEvent::NewHighScore {
+                player: score.player,
+                score_id: score.id,
+                game_type: score.game_type,
+                score: score.score,
+                previous,
+            })
+            .await;
+        }
+    }
+
+    /// Publish a level-up if a player's level rose (hub only).
+    async fn publish_level_up(&mut self, player: AccountOwner, before: u32, after: u32) {
+        if after > before && self.is_hub_chain() {
+            self.publish_event(ArcadeEvent::LevelUp {
+                player,
+                level: after,
+            })
+            .await;
+        }
+    }
+
+    /// Count a run towards the player's achievements, returning the ones it
+    /// unlocked. The hub is only sent progress that changed.
+    async fn unlock_achievements(
         &mut self,
-        wallet_address: AccountOwner,
-        total_xp: u64,
-        level: u32,
-        games_played: u64,
-    ) {
-        // Update leaderboard entry if exists
-        if let Ok(Some(mut entry)) = self.state.leaderboard.get(&wallet_address).await {
-            // Only update if the new values are higher
-            if total_xp >= entry.total_xp {
-                entry.total_xp = total_xp;
-                entry.level = level;
-                self.state
+        player: &Player,
+        score: &GameScore,
+        full_circuit: bool,
+    ) -> Vec<Achievement> {
+        let mut progress = self
+            .state
+            .achievements
+            .get(&player.owner)
+            .await
+            .expect("Failed to load achievements")
+            .unwrap_or_default();
+        let recorded = progress.record_run(score.game_type, full_circuit);
+        let unlocked = progress.unlock_reached(player, score.timestamp);
+        if !recorded && unlocked.is_empty() {
+            return unlocked;
+        }
+        self.state
+            .achievements
+            .insert(&player.owner, progress.clone())
+            .expect("Failed to update achievements");
+
+        self.send_to_hub_if_needed(Message::SyncAchievements {
+            wallet_address: player.owner,
+            progress,
+        });
+        unlocked
+    }
+
+    /// Count a run towards the achievements of the crossover events running
+    /// when it was played, saving and syncing the progress if any unlocked.
+    async fn unlock_event_achievements(&mut self, score: &GameScore) {
+        let schedule = self.state.crossover_schedule.get().clone();
+        if schedule.active(score.timestamp).is_empty() {
+            return;
+        }
+        let mut progress = self
+            .state
+            .event_achievements
+            .get(&score.player)
+            .await
+            .expect("Failed to load event achievements")
+            .unwrap_or_default();
+        let unlocked = progress.record_run(&schedule, score, score.timestamp);
+        for unlock in &unlocked {
+            log::info!(
+                "{} unlocked {} in {}",
+                score.player,
+                unlock.achievement.name,
+                unlock.event_name
+            );
+        }
+        self.state
+            .event_achievements
+            .insert(&score.player, progress.clone())
+            .expect("Failed to update event achievements");
+        if !unlocked.is_empty() {
+            self.send_to_hub_if_needed(Message::SyncEventAchievements {
+                wallet_address: score.player,
+                progress,
+            });
+        }
+    }
+
+    /// Mark the goals a player met with their latest run as completed and
+    /// celebrate each one.
+    async fn complete_goals(&mut self, player: &Player, score: &GameScore) {
+        let Some(mut goals) = self
+            .state
+            .goals
+            .get(&player.owner)
+            .await
+            .expect("Failed to load goals")
+        else {
+            return;
+        };
+        let completed = goals.complete(player, score, score.timestamp);
+        if completed.is_empty() {
+            return;
+        }
+        self.state
+            .goals
+            .insert(&player.owner, goals)
+            .expect("Failed to update goals");
+        for goal in completed {
+            self.publish_goal_completed(player.owner, goal).await;
+        }
+    }
+
+    /// Publish a completed goal from the hub, or tell the hub about it.
+    async fn publish_goal_completed(&mut self, player: AccountOwner, goal: Goal) {
+        if self.is_hub_chain() {
+            self.publish_event(ArcadeEvent::GoalCompleted { player, goal })
+                .await;
+        } else {
+            self.send_to_hub_if_needed(Message::SyncGoalCompleted {
+                wallet_address: player,
+                goal,
+            });
+        }
+    }
+
+    /// Publish a completed full circuit from the hub, or tell the hub about
+    /// it.
+    async fn publish_full_circuit(&mut self, player: AccountOwner, day: u64) {
+        if self.is_hub_chain() {
+            self.publish_event(ArcadeEvent::FullCircuit { player, day })
+                .await;
+        } else {
+            self.send_to_hub_if_needed(Message::SyncFullCircuit {
+                wallet_address: player,
+                day,
+            });
+        }
+    }
+
+    /// Validate one relayed submission: its attestation, nonce, player,
+    /// device and the player's play limits.
+    async fn check_relayed_submission(
+        &self,
+        chain_id: ChainId,
+        timestamp: u64,
+        item: &GatewaySubmission,
+        nonces: &mut BTreeMap<AccountOwner, u64>,
+        pending: &mut PendingSubmissions,
+    ) -> Result<(), ArcadeError> {
+        item.verify(chain_id)?;
+
+        let last_nonce = match nonces.get(&item.player) {
+            Some(nonce) => Some(*nonce),
+            None => self
+                .state
+                .gateway_nonces
+                .get(&item.player)
+                .await
+                .ok()
+                .flatten(),
+        };
+        if last_nonce.is_some_and(|last| item.nonce <= last) {
+            return Err(ArcadeError::StaleNonce);
+        }
+        nonces.insert(item.player, item.nonce);
+
+        let Some(player) = self.state.players.get(&item.player).await.ok().flatten() else {
+            return Err(ArcadeError::PlayerNotRegistered);
+        };
+
+        self.state
+            .check_submission(&player, &item.submission, timestamp, pending)
+            .await
+    }
+
+    /// Persist device activity and daily play staged by
+    /// [`ArcadeHubState::check_submission`].
+    fn commit_pending(&mut self, pending: PendingSubmissions) {
+        for (device_id, activity) in pending.devices {
+            self.state
+                .device_activity
+                .insert(&device_id, activity)
+                .expect("Failed to update device activity");
+        }
+        for (owner, play) in pending.plays {
+            self.state
+                .daily_play
+                .insert(&owner, play)
+                .expect("Failed to update daily play");
+        }
+        for (owner, nonce) in pending.score_nonces {
+            self.state
+                .score_nonces
+                .insert(&owner, nonce)
+                .expect("Failed to update score nonce");
+        }
+    }
+
+    /// Check the next leaderboard entries, then the next rookie leaderboard
+    /// ones, belong to registered players. Returns the violations found and
+    /// whether the pass is complete.
+    async fn check_leaderboard(&self, check: &mut InvariantCheck) -> (Vec<String>, bool) {
+        let start = check.checked as usize;
+        let leaderboard_len = self
+            .state
+            .leaderboard
+            .count()
+            .await
+            .expect("Failed to count leaderboard");
+        let mut entries = Vec::new();
+        if start < leaderboard_len {
+            for (owner, _) in map_page(&self.state.leaderboard, start, INVARIANT_CHUNK_SIZE).await {
+                entries.push((owner, false));
+            }
+        }
+        let remaining = INVARIANT_CHUNK_SIZE - entries.len();
+        let rookie_start = start.saturating_sub(leaderboard_len);
+        for (owner, _) in map_page(&self.state.rookie_leaderboard, rookie_start, remaining).await {
+            entries.push((owner, true));
+        }
+
+        let mut violations = Vec::new();
+        for (owner, rookie) in &entries {
+            let board = if *rookie {
+                "Rookie leaderboard"
+            } else {
+                "Leaderboard"
+            };
+            if !self
+                .state
+                .players
+                .contains_key(owner)
+                .await
+                .unwrap_or(false)
+            {
+                violations.push(format!("{board} entry for {owner} has no player"));
+            }
+            if *rookie
+                && !self
+                    .state
                     .leaderboard
-                    .insert(&wallet_address, entry)
-                    .expect("Failed to update leaderboard");
+                    .contains_key(owner)
+                    .await
+                    .unwrap_or(false)
+            {
+                violations.push(format!(
+                    "{board} entry for {owner} is missing from the leaderboard"
+                ));
             }
         }
+        check.checked += entries.len() as u64;
+        (violations, entries.len() < INVARIANT_CHUNK_SIZE)
+    }
 
-        // Update player if exists
-        if let Ok(Some(mut player)) = self.state.players.get(&wallet_address).await {
-            if total_xp >= player.total_xp {
-                player.total_xp = total_xp;
-                player.level = level;
-                player.games_played = games_played;
-                self.state
-                    .players
-                    .insert(&wallet_address, player)
-                    .expect("Failed to update player");
+    /// Sum the next logged scores of players who aren't shadow-banned,
+    /// comparing the sums with the arcade totals once the whole log is
+    /// summed.
+    async fn check_score_totals(&self, check: &mut InvariantCheck) -> (Vec<String>, bool) {
+        let count = self.state.score_log.count();
+        let start = (check.checked as usize).min(count);
+        let end = (start + INVARIANT_CHUNK_SIZE).min(count);
+        let scores = self
+            .state
+            .score_log
+            .read(start..end)
+            .await
+            .expect("Failed to read score log");
+        for (position, score) in (start as u64..).zip(&scores) {
+            if !self.is_score_removed(position).await && !self.is_shadow_banned(&score.player).await
+            {
+                check.games += 1;
+                check.xp = check.xp.saturating_add(score.xp_earned);
+            }
+        }
+        check.checked += scores.len() as u64;
+        if end < count {
+            return (Vec::new(), false);
+        }
+
+        let mut violations = Vec::new();
+        let total_games = *self.state.total_games_played.get();
+        if check.games != total_games {
+            violations.push(format!(
+                "Logged scores add up to {} games, but {total_games} were counted",
+                check.games
+            ));
+        }
+        let total_xp = *self.state.total_xp_earned.get();
+        if check.xp != total_xp {
+            violations.push(format!(
+                "Logged scores add up to {} XP, but {total_xp} was counted",
+                check.xp
+            ));
+        }
+        (violations, true)
+    }
+
+    /// Check the next logged scores can be looked up by their chains and
+    /// IDs.
+    async fn check_score_positions(&self, check: &mut InvariantCheck) -> (Vec<String>, bool) {
+        let count = self.state.score_log.count();
+        let start = (check.checked as usize).min(count);
+        let end = (start + INVARIANT_CHUNK_SIZE).min(count);
+        let scores = self
+            .state
+            .score_log
+            .read(start..end)
+            .await
+            .expect("Failed to read score log");
+
+        let mut violations = Vec::new();
+        for (position, score) in (start..).zip(&scores) {
+            if self.is_score_removed(position as u64).await {
+                continue;
+            }
+            let indexed = self
+                .state
+                .score_positions
+                .get(&(score.chain_id, score.id))
+                .await
+                .expect("Failed to load score position");
+            if indexed != Some(position as u64) {
+                violations.push(format!(
+                    "Score {} from chain {} at log position {position} is indexed at {indexed:?}",
+                    score.id, score.chain_id
+                ));
+            }
+        }
+        check.checked += scores.len() as u64;
+        (violations, end == count)
+    }
+
+    /// Check the next players' score logs only point at their own scores.
+    async fn check_player_scores(&self, check: &mut InvariantCheck) -> (Vec<String>, bool) {
+        let owners = self
+            .state
+            .player_scores
+            .indices()
+            .await
+            .expect("Failed to load player score logs")
+            .into_iter()
+            .skip(check.checked as usize)
+            .take(INVARIANT_CHUNK_SIZE)
+            .collect::<Vec<_>>();
+
+        let mut violations = Vec::new();
+        for owner in &owners {
+            let positions = match self
+                .state
+                .player_scores
+                .try_load_entry(owner)
+                .await
+                .expect("Failed to load player scores")
+            {
+                Some(log) => log.read(..).await.expect("Failed to load player scores"),
+                None => Vec::new(),
+            };
+            let scores = self
+                .state
+                .score_log
+                .multi_get(
+                    positions
+                        .iter()
+                        .map(|position| *position as usize)
+                        .collect(),
+                )
+                .await
+                .expect("Failed to load scores");
+            for (index, (position, score)) in positions.iter().zip(scores).enumerate() {
+                if score.is_none_or(|score| score.player != *owner) {
+                    violations.push(format!(
+                        "Score {index} of {owner} points at log position {position}, which \
+                         isn't theirs"
+                    ));
+                }
             }
         }
+        check.checked += owners.len() as u64;
+        (violations, owners.len() < INVARIANT_CHUNK_SIZE)
+    }
+
+    /// The application's own account on this chain, used to hold bonds,
+    /// streak shield payments and raffle prizes.
+    fn escrow_account(&mut self) -> Account {
+        let owner = AccountOwner::from(self.runtime.application_id());
+        Account::new(self.runtime.chain(), owner)
+    }
+
+    /// Whether the application is a developer sandbox, which syncs nothing.
+    fn is_sandbox(&mut self) -> bool {
+        self.runtime
+            .application_parameters()
+            .is_some_and(|parameters| parameters.sandbox)
+    }
+
+    /// The hub chain, if it is known and this is another chain that syncs
+    /// to it.
+    fn remote_hub(&mut self) -> Option<ChainId> {
+        let hub_chain_id = (*self.state.hub_chain_id.get())?;
+        (hub_chain_id != self.runtime.chain() && !self.is_sandbox()).then_some(hub_chain_id)
+    }
+
+    /// Send a request straight to `destination`, on behalf of the signer.
+    fn send_request(&mut self, destination: ChainId, message: Message) {
+        self.runtime
+            .prepare_message(message)
+            .with_authentication()
+            .send_to(destination);
+    }
+
+    /// Whether this is the hub chain.
+    fn is_hub_chain(&mut self) -> bool {
+        self.state.hub_chain_id.get() == &Some(self.runtime.chain())
     }
 
-    /// Send a message to the hub chain if we're not already on it.
+    /// Sync a change to the hub: publish it on this chain's sync stream and,
+    /// if direct syncs are enabled, send it to the hub chain if it is known.
+    /// The hub has no one to sync to, syncs applied from other chains go no
+    /// further, and sandboxes sync nothing.
     fn send_to_hub_if_needed(&mut self, message: Message) {
-        let hub_chain_id = match self.state.hub_chain_id.get() {
-            Some(id) => *id,
-            None => return,
+        if self.applying_sync || self.is_sandbox() || self.is_hub_chain() {
+            return;
+        }
+        if let Some(batched) = &mut self.batched_progress {
+            if message.is_run_progress() {
+                batched.push(message);
+                return;
+            }
+        }
+        let index = self.runtime.emit(
+            SYNC_EVENT_STREAM.into(),
+            &ArcadeEvent::Sync(Box::new(message.clone())),
+        );
+        if !*self.state.direct_syncs.get() {
+            return;
+        }
+        let hub_chain_id = *self.state.hub_chain_id.get();
+        let message = Message::SequencedSync {
+            index,
+            message: Box::new(message),
         };
+        runtime::send_to_hub_if_needed(&mut self.runtime, hub_chain_id, message);
+    }
+}
 
-        // Only send if not on hub chain
-        if self.runtime.chain_id() != hub_chain_id {
-            self.runtime
-                .prepare_message(message)
-                .with_authentication()
-                .send_to(hub_chain_id);
-        }
+/// Up to `take` entries of `map` in storage order, skipping the first `skip`.
+async fn map_page<I, V>(map: &MapView<I, V>, skip: usize, take: usize) -> Vec<(I, V)>
+where
+    I: Send + DeserializeOwned,
+    V: Clone + Sync + Send + Serialize + DeserializeOwned + 'static,
+{
+    let mut seen = 0;
+    let mut entries = Vec::new();
+    if take == 0 {
+        return entries;
     }
+    map.for_each_index_value_while(|index, value| {
+        if seen >= skip {
+            entries.push((index, value.into_owned()));
+        }
+        seen += 1;
+        Ok(entries.len() < take)
+    })
+    .await
+    .expect("Failed to read map");
+    entries
 }
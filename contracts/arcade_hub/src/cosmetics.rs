@@ -0,0 +1,75 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Cosmetics: purely visual items players collect.
+//!
+//! Cosmetics carry no gameplay effect. They are granted to players' hub
+//! inventories, currently by season rollovers, and are never taken away.
+
+use async_graphql::{InputObject, SimpleObject};
+use serde::{Deserialize, Serialize};
+
+use crate::seasons::SeasonTier;
+
+/// Longest a cosmetic name can be, in characters.
+pub const MAX_COSMETIC_NAME_LENGTH: usize = 32;
+
+/// What a cosmetic decorates.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, async_graphql::Enum)]
+pub enum CosmeticKind {
+    Avatar,
+    Frame,
+    Banner,
+    Title,
+}
+
+/// A cosmetic item.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, SimpleObject, InputObject)]
+#[graphql(input_name = "CosmeticInput")]
+pub struct Cosmetic {
+    pub name: String,
+    pub kind: CosmeticKind,
+}
+
+impl Cosmetic {
+    /// Whether the cosmetic's name is acceptable.
+    pub fn is_valid(&self) -> bool {
+        !self.name.trim().is_empty() && self.name.chars().count() <= MAX_COSMETIC_NAME_LENGTH
+    }
+}
+
+/// A cosmetic a player owns and how they earned it.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct OwnedCosmetic {
+    pub cosmetic: Cosmetic,
+    /// The season whose rollover granted it.
+    pub season: u32,
+    /// The name of that season's theme.
+    pub theme: String,
+    /// The tier the player finished the season in.
+    pub tier: SeasonTier,
+    pub granted_at: u64,
+}
+
+/// The cosmetics a player owns.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CosmeticInventory {
+    items: Vec<OwnedCosmetic>,
+}
+
+impl CosmeticInventory {
+    /// Add a cosmetic, unless the player already got one from the same
+    /// season.
+    pub fn grant(&mut self, item: OwnedCosmetic) -> bool {
+        if self.items.iter().any(|owned| owned.season == item.season) {
+            return false;
+        }
+        self.items.push(item);
+        true
+    }
+
+    /// Every cosmetic owned, oldest first.
+    pub fn items(&self) -> &[OwnedCosmetic] {
+        &self.items
+    }
+}
@@ -0,0 +1,254 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Limited-time crossover events, scheduled by the admin.
+//!
+//! While an event runs, between its start and end timestamps, it replaces
+//! the XP multiplier of the games it overrides and offers achievements that
+//! can only be unlocked during it. Nothing is switched on or off: every
+//! lookup resolves the events running at the block's timestamp. When events
+//! overlap on a game, the one that started last wins, and the one scheduled
+//! last if they started together.
+
+use std::collections::BTreeMap;
+
+use async_graphql::{InputObject, SimpleObject};
+use serde::{Deserialize, Serialize};
+
+use crate::{ArcadeError, GameScore, GameType};
+
+/// Events that can be scheduled or running at once.
+pub const MAX_SCHEDULED_CROSSOVERS: usize = 10;
+
+/// Achievements one event can offer.
+pub const MAX_EVENT_ACHIEVEMENTS: usize = 5;
+
+/// Highest XP multiplier an event can set, in percent.
+pub const MAX_EVENT_MULTIPLIER_PERCENT: u32 = 500;
+
+/// Longest an event name or achievement text can be, in characters.
+pub const MAX_EVENT_TEXT_LENGTH: usize = 64;
+
+/// An event's XP multiplier for one game, replacing the underplayed-game
+/// multiplier while the event runs.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, SimpleObject, InputObject)]
+#[graphql(input_name = "XpOverrideInput")]
+pub struct XpOverride {
+    pub game_type: GameType,
+    /// XP is scaled by this percentage; 100 leaves it unchanged.
+    pub multiplier_percent: u32,
+}
+
+/// An achievement only unlockable during its event: `runs` runs, of
+/// `game_type` if set, each scoring at least `min_score`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, SimpleObject, InputObject)]
+#[graphql(input_name = "EventAchievementInput")]
+pub struct EventAchievement {
+    pub name: String,
+    pub description: String,
+    pub game_type: Option<GameType>,
+    pub min_score: u64,
+    pub runs: u32,
+}
+
+impl EventAchievement {
+    /// Whether `score` counts towards the achievement.
+    fn counts(&self, score: &GameScore) -> bool {
+        self.game_type
+            .is_none_or(|game_type| game_type == score.game_type)
+            && score.score >= self.min_score
+    }
+}
+
+/// A crossover event as the admin describes it.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject, InputObject)]
+#[graphql(input_name = "CrossoverEventSpecInput")]
+pub struct CrossoverEventSpec {
+    pub name: String,
+    pub starts_at: u64,
+    pub ends_at: u64,
+    pub xp_overrides: Vec<XpOverride>,
+    pub achievements: Vec<EventAchievement>,
+}
+
+impl CrossoverEventSpec {
+    /// Check the event is well formed and hasn't already ended at `now`.
+    pub fn validate(&self, now: u64) -> Result<(), ArcadeError> {
+        let text_ok =
+            |text: &str| !text.trim().is_empty() && text.chars().count() <= MAX_EVENT_TEXT_LENGTH;
+        let mut games = Vec::new();
+        let overrides_ok = self.xp_overrides.iter().all(|xp_override| {
+            let unique = !games.contains(&xp_override.game_type);
+            games.push(xp_override.game_type);
+            unique && (1..=MAX_EVENT_MULTIPLIER_PERCENT).contains(&xp_override.multiplier_percent)
+        });
+        let mut names = Vec::new();
+        let achievements_ok = self.achievements.len() <= MAX_EVENT_ACHIEVEMENTS
+            && self.achievements.iter().all(|achievement| {
+                let unique = !names.contains(&achievement.name);
+                names.push(achievement.name.clone());
+                unique
+                    && text_ok(&achievement.name)
+                    && text_ok(&achievement.description)
+                    && achievement.runs > 0
+            });
+        let timing_ok = self.starts_at < self.ends_at && self.ends_at > now;
+        if !text_ok(&self.name) || !timing_ok || !overrides_ok || !achievements_ok {
+            return Err(ArcadeError::InvalidCrossover);
+        }
+        Ok(())
+    }
+}
+
+/// A scheduled crossover event.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct CrossoverEvent {
+    pub id: u64,
+    pub spec: CrossoverEventSpec,
+}
+
+impl CrossoverEvent {
+    /// Whether the event is running at `now`.
+    pub fn is_active(&self, now: u64) -> bool {
+        (self.spec.starts_at..self.spec.ends_at).contains(&now)
+    }
+}
+
+/// The events that haven't ended yet.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CrossoverSchedule {
+    events: Vec<CrossoverEvent>,
+    next_id: u64,
+}
+
+impl CrossoverSchedule {
+    /// Schedule an event, dropping those that ended before `now`. Returns
+    /// its ID.
+    pub fn add(&mut self, spec: CrossoverEventSpec, now: u64) -> Result<u64, ArcadeError> {
+        spec.validate(now)?;
+        let ended = |event: &CrossoverEvent| event.spec.ends_at <= now;
+        let pending = self.events.iter().filter(|event| !ended(event)).count();
+        if pending >= MAX_SCHEDULED_CROSSOVERS {
+            return Err(ArcadeError::TooManyCrossovers);
+        }
+        self.events.retain(|event| !ended(event));
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.events.push(CrossoverEvent { id, spec });
+        Ok(id)
+    }
+
+    /// Cancel an event, whether or not it has started.
+    pub fn cancel(&mut self, event_id: u64) -> Result<(), ArcadeError> {
+        let Some(position) = self.events.iter().position(|event| event.id == event_id) else {
+            return Err(ArcadeError::CrossoverNotFound);
+        };
+        self.events.remove(position);
+        Ok(())
+    }
+
+    /// The events running at `now`, in precedence order: the latest
+    /// started first, ties going to the latest scheduled.
+    pub fn active(&self, now: u64) -> Vec<&CrossoverEvent> {
+        let mut active: Vec<&CrossoverEvent> = self
+            .events
+            .iter()
+            .filter(|event| event.is_active(now))
+            .collect();
+        active.sort_by_key(|event| std::cmp::Reverse((event.spec.starts_at, event.id)));
+        active
+    }
+
+    /// The events running or yet to start at `now`, soonest first.
+    pub fn upcoming(&self, now: u64) -> Vec<CrossoverEvent> {
+        let mut upcoming: Vec<CrossoverEvent> = self
+            .events
+            .iter()
+            .filter(|event| event.spec.ends_at > now)
+            .cloned()
+            .collect();
+        upcoming.sort_by_key(|event| (event.spec.starts_at, event.id));
+        upcoming
+    }
+
+    /// The XP multiplier a running event sets for `game_type` at `now`, in
+    /// percent, if any.
+    pub fn xp_multiplier(&self, game_type: GameType, now: u64) -> Option<u32> {
+        self.active(now).into_iter().find_map(|event| {
+            event
+                .spec
+                .xp_overrides
+                .iter()
+                .find(|xp_override| xp_override.game_type == game_type)
+                .map(|xp_override| xp_override.multiplier_percent)
+        })
+    }
+}
+
+/// An event achievement a player unlocked. Keeps the event's name, as the
+/// event itself is dropped from the schedule once over.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct EventAchievementUnlock {
+    pub event_id: u64,
+    pub event_name: String,
+    pub achievement: EventAchievement,
+    pub unlocked_at: u64,
+}
+
+/// A player's runs towards event achievements and those they unlocked.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct EventAchievementProgress {
+    /// Qualifying runs, keyed by event ID and achievement index.
+    runs: BTreeMap<(u64, usize), u32>,
+    unlocked: Vec<EventAchievementUnlock>,
+}
+
+impl EventAchievementProgress {
+    /// Count `score` towards the achievements of the events running at
+    /// `now`, returning those newly unlocked. Progress on events no longer
+    /// scheduled is forgotten.
+    pub fn record_run(
+        &mut self,
+        schedule: &CrossoverSchedule,
+        score: &GameScore,
+        now: u64,
+    ) -> Vec<EventAchievementUnlock> {
+        let scheduled: Vec<u64> = schedule.events.iter().map(|event| event.id).collect();
+        self.runs
+            .retain(|(event_id, _), _| scheduled.contains(event_id));
+
+        let mut newly_unlocked = Vec::new();
+        for event in schedule.active(now) {
+            for (index, achievement) in event.spec.achievements.iter().enumerate() {
+                if !achievement.counts(score) || self.is_unlocked(event.id, &achievement.name) {
+                    continue;
+                }
+                let runs = self.runs.entry((event.id, index)).or_insert(0);
+                *runs += 1;
+                if *runs >= achievement.runs {
+                    self.runs.remove(&(event.id, index));
+                    newly_unlocked.push(EventAchievementUnlock {
+                        event_id: event.id,
+                        event_name: event.spec.name.clone(),
+                        achievement: achievement.clone(),
+                        unlocked_at: now,
+                    });
+                }
+            }
+        }
+        self.unlocked.extend(newly_unlocked.iter().cloned());
+        newly_unlocked
+    }
+
+    fn is_unlocked(&self, event_id: u64, name: &str) -> bool {
+        self.unlocked
+            .iter()
+            .any(|unlock| unlock.event_id == event_id && unlock.achievement.name == name)
+    }
+
+    /// Every event achievement unlocked, oldest first.
+    pub fn unlocked(&self) -> &[EventAchievementUnlock] {
+        &self.unlocked
+    }
+}
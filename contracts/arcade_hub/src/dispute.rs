@@ -0,0 +1,136 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Score appeals and the bond accounting that backs them.
+
+use async_graphql::{Enum, SimpleObject};
+use linera_sdk::linera_base_types::{Account, AccountOwner, Amount, ChainId, CryptoHash};
+use serde::{Deserialize, Serialize};
+
+use crate::ArcadeError;
+
+/// Bond charged for filing an appeal when none is configured at instantiation.
+pub const DEFAULT_APPEAL_BOND: Amount = Amount::from_millis(100);
+
+/// Maximum length of an appeal reason, in characters.
+pub const MAX_APPEAL_REASON_LENGTH: usize = 280;
+
+/// Validate the free-text reason attached to an appeal.
+pub fn validate_appeal_reason(reason: &str) -> Result<(), ArcadeError> {
+    let len = reason.trim().chars().count();
+    if len == 0 || len > MAX_APPEAL_REASON_LENGTH {
+        return Err(ArcadeError::InvalidAppealReason);
+    }
+    Ok(())
+}
+
+/// The lifecycle state of an appeal.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Enum)]
+pub enum AppealStatus {
+    /// Waiting for a moderator decision.
+    Pending,
+    /// The appeal succeeded and the bond was refunded.
+    Upheld,
+    /// The appeal failed and the bond was forfeited.
+    Rejected,
+}
+
+/// An appeal filed against a recorded score.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct Appeal {
+    pub id: u64,
+    /// The appealed score's [`GameScore::receipt`](crate::GameScore::receipt).
+    pub receipt: CryptoHash,
+    pub appellant: AccountOwner,
+    /// The chain the appeal was filed on, where a refunded bond is paid.
+    pub chain_id: ChainId,
+    pub reason: String,
+    pub bond: Amount,
+    pub status: AppealStatus,
+    pub filed_at: u64,
+    pub resolved_at: Option<u64>,
+}
+
+impl Appeal {
+    /// Create a new pending appeal.
+    pub fn new(
+        id: u64,
+        receipt: CryptoHash,
+        appellant: AccountOwner,
+        chain_id: ChainId,
+        reason: String,
+        bond: Amount,
+        timestamp: u64,
+    ) -> Self {
+        Self {
+            id,
+            receipt,
+            appellant,
+            chain_id,
+            reason,
+            bond,
+            status: AppealStatus::Pending,
+            filed_at: timestamp,
+            resolved_at: None,
+        }
+    }
+
+    /// Resolve the appeal, returning how its bond must be settled.
+    pub fn resolve(&mut self, upheld: bool, timestamp: u64) -> Result<BondSettlement, ArcadeError> {
+        if self.status != AppealStatus::Pending {
+            return Err(ArcadeError::AppealAlreadyResolved);
+        }
+        self.resolved_at = Some(timestamp);
+        if upheld {
+            self.status = AppealStatus::Upheld;
+            Ok(BondSettlement::Refund {
+                to: Account::new(self.chain_id, self.appellant),
+                amount: self.bond,
+            })
+        } else {
+            self.status = AppealStatus::Rejected;
+            Ok(BondSettlement::Forfeit { amount: self.bond })
+        }
+    }
+}
+
+/// What happens to an appeal bond once the appeal is resolved.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BondSettlement {
+    /// Return the bond to the appellant on the chain they filed from.
+    Refund { to: Account, amount: Amount },
+    /// Keep the bond in the application's account.
+    Forfeit { amount: Amount },
+}
+
+/// Running totals of appeal bonds handled by the application.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, SimpleObject)]
+pub struct BondAccounts {
+    /// Bonds currently held for pending appeals.
+    pub held: Amount,
+    /// Bonds returned to appellants.
+    pub refunded: Amount,
+    /// Bonds kept after rejected appeals.
+    pub forfeited: Amount,
+}
+
+impl BondAccounts {
+    /// Record a bond deposited with a new appeal.
+    pub fn deposit(&mut self, amount: Amount) {
+        self.held.saturating_add_assign(amount);
+    }
+
+    /// Record the settlement of a bond.
+    pub fn settle(&mut self, settlement: &BondSettlement) {
+        match settlement {
+            BondSettlement::Refund { amount, .. } => {
+                self.held = self.held.saturating_sub(*amount);
+                self.refunded.saturating_add_assign(*amount);
+            }
+            BondSettlement::Forfeit { amount } => {
+                self.held = self.held.saturating_sub(*amount);
+                self.forfeited.saturating_add_assign(*amount);
+            }
+        }
+    }
+}
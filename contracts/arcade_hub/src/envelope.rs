@@ -0,0 +1,384 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Versioned operations, so the ABI can grow without breaking wallets built
+//! against an older one.
+//!
+//! A version 1 operation is a bare [`Operation`]. The variants wallets were
+//! first built against keep their tags and fields, and new operations are
+//! only ever appended, so those payloads still decode. Fields added to an
+//! existing operation go in [`OpV2`] instead, which is sent as an extra
+//! enum variant with a reserved tag far past the end of [`Operation`],
+//! keeping the tag free however many operations are added.
+
+use std::fmt;
+
+use serde::{
+    de::{self, DeserializeSeed, EnumAccess, IntoDeserializer, VariantAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{score_signing::ScoreSignature, usernames::RegistrationVoucher, Operation};
+
+/// The enum tag reserved for [`OpV2`].
+const V2_TAG: u32 = 1 << 16;
+/// The variant name of [`OpV2`] in self-describing formats.
+const V2_NAME: &str = "V2";
+
+/// The longest idempotency key accepted, in bytes.
+pub const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 64;
+
+/// An operation as originally encoded.
+pub type OpV1 = Operation;
+
+/// An operation with the metadata introduced in version 2.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OpV2 {
+    pub operation: Operation,
+    /// Set by wallets that may retry the same operation. A key the signer
+    /// has used before is not executed again; the response it got the
+    /// first time is returned instead.
+    pub idempotency_key: Option<String>,
+    /// The game session a [`Operation::SubmitScore`] closes.
+    pub session_id: Option<u64>,
+    /// The voucher presented with a [`Operation::RegisterPlayer`].
+    pub voucher: Option<RegistrationVoucher>,
+    /// The kiosk device a [`Operation::SubmitScore`] was played on.
+    pub device_id: Option<String>,
+    /// The game server's signature of a [`Operation::SubmitScore`].
+    pub signature: Option<ScoreSignature>,
+}
+
+/// Any version of an operation, as accepted by the contract.
+#[derive(Clone, Debug)]
+pub enum VersionedOperation {
+    V1(OpV1),
+    V2(OpV2),
+}
+
+impl VersionedOperation {
+    /// Upgrade to the latest version, leaving new fields unset.
+    pub fn into_latest(self) -> OpV2 {
+        match self {
+            VersionedOperation::V1(operation) => OpV2 {
+                operation,
+                idempotency_key: None,
+                session_id: None,
+                voucher: None,
+                device_id: None,
+                signature: None,
+            },
+            VersionedOperation::V2(operation) => operation,
+        }
+    }
+}
+
+impl From<Operation> for VersionedOperation {
+    fn from(operation: Operation) -> Self {
+        VersionedOperation::V1(operation)
+    }
+}
+
+impl From<OpV2> for VersionedOperation {
+    fn from(operation: OpV2) -> Self {
+        VersionedOperation::V2(operation)
+    }
+}
+
+impl Serialize for VersionedOperation {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            VersionedOperation::V1(operation) => operation.serialize(serializer),
+            VersionedOperation::V2(operation) => {
+                serializer.serialize_newtype_variant("Operation", V2_TAG, V2_NAME, operation)
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for VersionedOperation {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_enum("Operation", &[V2_NAME], EnvelopeVisitor)
+    }
+}
+
+/// An enum tag, by index in binary formats or by name in self-describing
+/// ones.
+enum Tag {
+    Index(u32),
+    Name(String),
+}
+
+impl Tag {
+    fn is_v2(&self) -> bool {
+        match self {
+            Tag::Index(index) => *index == V2_TAG,
+            Tag::Name(name) => name == V2_NAME,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Tag {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_identifier(TagVisitor)
+    }
+}
+
+struct TagVisitor;
+
+impl Visitor<'_> for TagVisitor {
+    type Value = Tag;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an operation variant")
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Tag, E> {
+        u32::try_from(value)
+            .map(Tag::Index)
+            .map_err(|_| E::custom(format!("Operation variant {value} is out of range")))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Tag, E> {
+        Ok(Tag::Name(value.to_string()))
+    }
+}
+
+struct EnvelopeVisitor;
+
+impl<'de> Visitor<'de> for EnvelopeVisitor {
+    type Value = VersionedOperation;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an arcade operation")
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        let (tag, variant) = data.variant::<Tag>()?;
+        if tag.is_v2() {
+            return variant.newtype_variant().map(VersionedOperation::V2);
+        }
+        // The tag has already been read, so hand it back to `Operation`
+        // along with the rest of the variant
+        Operation::deserialize(Replay { tag, variant }).map(VersionedOperation::V1)
+    }
+}
+
+/// An enum whose tag has already been read, replayed to another
+/// deserializer.
+struct Replay<A> {
+    tag: Tag,
+    variant: A,
+}
+
+impl<'de, A: VariantAccess<'de>> Deserializer<'de> for Replay<A> {
+    type Error = A::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, A::Error> {
+        visitor.visit_enum(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de, A: VariantAccess<'de>> EnumAccess<'de> for Replay<A> {
+    type Error = A::Error;
+    type Variant = A;
+
+    fn variant_seed<S: DeserializeSeed<'de>>(self, seed: S) -> Result<(S::Value, A), A::Error> {
+        let value = match self.tag {
+            Tag::Index(index) => seed.deserialize(index.into_deserializer())?,
+            Tag::Name(name) => seed.deserialize(name.into_deserializer())?,
+        };
+        Ok((value, self.variant))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use linera_sdk::bcs;
+
+    use super::*;
+    use crate::GameType;
+
+    fn operations() -> Vec<Operation> {
+        vec![
+            Operation::VoteInGuildPoll {
+                poll_id: 3,
+                option: 1,
+            },
+            Operation::ExportProgress,
+        ]
+    }
+
+    fn op_v2() -> OpV2 {
+        OpV2 {
+            operation: Operation::ClaimGuildPrize { prize_id: 7 },
+            idempotency_key: Some("retry-1".to_string()),
+            session_id: Some(42),
+            voucher: None,
+            device_id: Some("kiosk-1".to_string()),
+            signature: None,
+        }
+    }
+
+    /// The operation inside `versioned`, if it is a version 1 operation.
+    fn v1(versioned: VersionedOperation) -> Operation {
+        match versioned {
+            VersionedOperation::V1(operation) => operation,
+            VersionedOperation::V2(operation) => panic!("Decoded as V2: {operation:?}"),
+        }
+    }
+
+    /// The envelope inside `versioned`, if it is a version 2 operation.
+    fn v2(versioned: VersionedOperation) -> OpV2 {
+        match versioned {
+            VersionedOperation::V2(operation) => operation,
+            VersionedOperation::V1(operation) => panic!("Decoded as V1: {operation:?}"),
+        }
+    }
+
+    /// An enum tag as BCS encodes it, in ULEB128.
+    fn bcs_tag(mut tag: u32) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        loop {
+            let byte = (tag & 0x7f) as u8;
+            tag >>= 7;
+            if tag == 0 {
+                bytes.push(byte);
+                return bytes;
+            }
+            bytes.push(byte | 0x80);
+        }
+    }
+
+    #[test]
+    fn bare_operation_bcs_decodes_as_v1() {
+        for operation in operations() {
+            let bytes = bcs::to_bytes(&operation).unwrap();
+            let decoded = v1(bcs::from_bytes(&bytes).unwrap());
+            assert_eq!(bcs::to_bytes(&decoded).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn bare_operation_json_decodes_as_v1() {
+        for operation in operations() {
+            let json = serde_json::to_string(&operation).unwrap();
+            let decoded = v1(serde_json::from_str(&json).unwrap());
+            assert_eq!(serde_json::to_string(&decoded).unwrap(), json);
+        }
+    }
+
+    #[test]
+    fn v1_reencodes_as_bare_operation() {
+        for operation in operations() {
+            let bytes = bcs::to_bytes(&operation).unwrap();
+            let versioned = VersionedOperation::from(operation);
+            assert_eq!(bcs::to_bytes(&versioned).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn op_v2_round_trips_in_bcs() {
+        let bytes = bcs::to_bytes(&VersionedOperation::from(op_v2())).unwrap();
+        assert_eq!(bytes[..3], bcs_tag(V2_TAG)[..]);
+        let decoded = v2(bcs::from_bytes(&bytes).unwrap());
+        assert_eq!(
+            bcs::to_bytes(&decoded).unwrap(),
+            bcs::to_bytes(&op_v2()).unwrap()
+        );
+    }
+
+    #[test]
+    fn op_v2_round_trips_in_json() {
+        let json = serde_json::to_string(&VersionedOperation::from(op_v2())).unwrap();
+        let decoded = v2(serde_json::from_str(&json).unwrap());
+        assert_eq!(
+            serde_json::to_string(&decoded).unwrap(),
+            serde_json::to_string(&op_v2()).unwrap()
+        );
+    }
+
+    #[test]
+    fn baseline_register_player_bcs_decodes() {
+        // Tag 0, then the username "ada"
+        let bytes = [0, 3, b'a', b'd', b'a'];
+        match v1(bcs::from_bytes(&bytes).unwrap()) {
+            Operation::RegisterPlayer { username } => assert_eq!(username, "ada"),
+            operation => panic!("Decoded as {operation:?}"),
+        }
+    }
+
+    #[test]
+    fn baseline_submit_score_bcs_decodes() {
+        // Tag 1, then `ReactionStrike`, a score of 1000 and a bonus of 7
+        let bytes = [
+            1, 2, 0xe8, 0x03, 0, 0, 0, 0, 0, 0, 1, 7, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        match v1(bcs::from_bytes(&bytes).unwrap()) {
+            Operation::SubmitScore {
+                game_type,
+                score,
+                bonus_data,
+            } => {
+                assert_eq!(game_type, GameType::ReactionStrike);
+                assert_eq!(score, 1000);
+                assert_eq!(bonus_data, Some(7));
+            }
+            operation => panic!("Decoded as {operation:?}"),
+        }
+        // The same without a bonus
+        let bytes = [1, 2, 0xe8, 0x03, 0, 0, 0, 0, 0, 0, 0];
+        assert!(matches!(
+            v1(bcs::from_bytes(&bytes).unwrap()),
+            Operation::SubmitScore {
+                score: 1000,
+                bonus_data: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn baseline_update_username_bcs_decodes() {
+        // Tag 2, then the new username "grace"
+        let bytes = [2, 5, b'g', b'r', b'a', b'c', b'e'];
+        match v1(bcs::from_bytes(&bytes).unwrap()) {
+            Operation::UpdateUsername { new_username } => assert_eq!(new_username, "grace"),
+            operation => panic!("Decoded as {operation:?}"),
+        }
+    }
+
+    #[test]
+    fn baseline_json_decodes() {
+        let json = r#"{"UpdateUsername":{"new_username":"grace"}}"#;
+        assert!(matches!(
+            v1(serde_json::from_str(json).unwrap()),
+            Operation::UpdateUsername { new_username } if new_username == "grace"
+        ));
+    }
+
+    #[test]
+    fn unknown_bcs_tag_is_rejected() {
+        for tag in [V2_TAG - 1, V2_TAG + 1] {
+            let mut bytes = bcs_tag(tag);
+            bytes.extend(bcs::to_bytes(&7u64).unwrap());
+            assert!(bcs::from_bytes::<VersionedOperation>(&bytes).is_err());
+        }
+    }
+
+    #[test]
+    fn unknown_json_tag_is_rejected() {
+        for json in [
+            r#"{"NoSuchOperation":{"poll_id":3}}"#,
+            r#""NoSuchOperation""#,
+        ] {
+            assert!(serde_json::from_str::<VersionedOperation>(json).is_err());
+        }
+    }
+}
@@ -0,0 +1,360 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reproducible players, scores and seasons for tests, benchmarks and
+//! demos.
+//!
+//! Everything is derived from a seed, so the same [`FixtureConfig`] always
+//! yields the same [`Fixture`]. An application instantiated with
+//! [`InstantiationArgument::demo_fixture`](crate::InstantiationArgument::demo_fixture)
+//! loads one into its state.
+
+use linera_sdk::linera_base_types::{AccountOwner, ChainId};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    seasons::{self, Season, SeasonArchive, SeasonalXp},
+    GameScore, GameType, Player, ScoreSubmission, ScoringCategory, MICROS_PER_DAY,
+};
+
+/// A small deterministic generator (xorshift64*).
+#[derive(Clone, Debug)]
+pub struct FixtureRng(u64);
+
+impl FixtureRng {
+    pub fn new(seed: u64) -> Self {
+        // The state must never be zero
+        Self(seed ^ 0x9E37_79B9_7F4A_7C15)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// A value in `min..=max`.
+    pub fn range(&mut self, min: u64, max: u64) -> u64 {
+        min + self.next_u64() % (max - min + 1)
+    }
+
+    /// A random game.
+    pub fn game_type(&mut self) -> GameType {
+        GameType::ALL[self.next_u64() as usize % GameType::ALL.len()]
+    }
+
+    /// A random 20-byte account owner.
+    pub fn owner(&mut self) -> AccountOwner {
+        let mut bytes = [0u8; 20];
+        for chunk in bytes.chunks_mut(8) {
+            let random = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&random[..chunk.len()]);
+        }
+        AccountOwner::Address20(bytes)
+    }
+
+    /// A run of `game_type` within the range real players score.
+    pub fn submission(
+        &mut self,
+        game_type: GameType,
+        device_id: Option<String>,
+    ) -> ScoreSubmission {
+        let (score, bonus_data) = match game_type {
+            // Clicks in 10 seconds
+            GameType::SpeedClicker => (self.range(30, 90), None),
+            // Level reached, perfect rounds
+            GameType::MemoryMatrix => {
+                let level = self.range(1, 12);
+                (level, Some(self.range(0, level)))
+            }
+            // Average reaction in ms, targets hit
+            GameType::ReactionStrike => (self.range(180, 600), Some(self.range(5, 20))),
+            // Correct answers, best streak
+            GameType::MathBlitz => {
+                let correct = self.range(5, 40);
+                (correct, Some(self.range(0, correct)))
+            }
+            // Snake length, apples eaten
+            GameType::SnakeSprint => {
+                let length = self.range(3, 60);
+                (length, Some(length - 3))
+            }
+        };
+        ScoreSubmission {
+            game_type,
+            score,
+            bonus_data,
+            device_id,
+        }
+    }
+}
+
+/// What to generate.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FixtureConfig {
+    pub seed: u64,
+    pub players: u32,
+    pub scores_per_player: u32,
+    /// Scores are spread over this many days before the fixture's end time.
+    pub days: u64,
+    /// Length of back-to-back seasons starting with the first score, in
+    /// days; `None` leaves seasons off.
+    #[serde(default)]
+    pub season_length_days: Option<u32>,
+}
+
+impl Default for FixtureConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            players: 20,
+            scores_per_player: 10,
+            days: 7,
+            season_length_days: Some(3),
+        }
+    }
+}
+
+/// A generated set of players and their scores.
+#[derive(Clone, Debug)]
+pub struct Fixture {
+    /// Players with XP and game counts matching their scores.
+    pub players: Vec<Player>,
+    /// Scores in timestamp order, with IDs numbered from 0.
+    pub scores: Vec<GameScore>,
+    /// Seasons that ended before the fixture's end time, oldest first, with
+    /// standings ranked by the scores played in them.
+    pub archived_seasons: Vec<SeasonArchive>,
+    /// The season in progress at the fixture's end time, if seasons are on.
+    pub current_season: Option<Season>,
+    /// XP earned in the current season, by players who earned any.
+    pub seasonal_xp: Vec<(AccountOwner, SeasonalXp)>,
+}
+
+impl Fixture {
+    /// Generate players registered before `end` and scores played before it
+    /// on `chain_id`.
+    pub fn generate(config: &FixtureConfig, chain_id: ChainId, end: u64) -> Self {
+        let mut rng = FixtureRng::new(config.seed);
+        let window = config.days.max(1) * MICROS_PER_DAY;
+        let start = end.saturating_sub(window);
+
+        let mut players = (0..config.players)
+            .map(|i| Player::new(rng.owner(), format!("player{}", i + 1), start))
+            .collect::<Vec<_>>();
+
+        let mut scores = Vec::new();
+        for player in &mut players {
+            for _ in 0..config.scores_per_player {
+                let game_type = rng.game_type();
+                let submission = rng.submission(game_type, None);
+                let xp_earned = game_type.calculate_xp(submission.score, submission.bonus_data);
+                player.add_xp(xp_earned);
+                player.increment_games();
+                scores.push(GameScore {
+                    id: 0,
+                    game_type,
+                    player: player.owner,
+                    score: submission.score,
+                    xp_earned,
+                    bonus_data: submission.bonus_data,
+                    timestamp: start + rng.range(0, window - 1),
+                    device_id: None,
+                    category: ScoringCategory::Standard,
+                    chain_id,
+                });
+            }
+        }
+
+        scores.sort_by_key(|score| score.timestamp);
+        for (id, score) in scores.iter_mut().enumerate() {
+            score.id = id as u64;
+        }
+
+        let mut archived_seasons = Vec::new();
+        let mut seasonal_xp = Vec::new();
+        let current_season = config.season_length_days.map(|days| {
+            let days = days.clamp(
+                seasons::MIN_SEASON_LENGTH_DAYS,
+                seasons::MAX_SEASON_LENGTH_DAYS,
+            );
+            let mut season = Season::first(days, start);
+            while season.has_ended(end) {
+                let mut archive = SeasonArchive {
+                    season,
+                    archived_at: season.ends_at,
+                    standings: seasons::rank(xp_in_season(&players, &scores, &season)),
+                };
+                archive.retain_top(seasons::MAX_ARCHIVED_STANDINGS as u32);
+                archived_seasons.push(archive);
+                season = season.next(season.ends_at);
+            }
+            seasonal_xp = xp_in_season(&players, &scores, &season)
+                .into_iter()
+                .map(|(owner, xp)| {
+                    let seasonal = SeasonalXp {
+                        season: season.number,
+                        xp,
+                    };
+                    (owner, seasonal)
+                })
+                .collect();
+            season
+        });
+
+        Fixture {
+            players,
+            scores,
+            archived_seasons,
+            current_season,
+            seasonal_xp,
+        }
+    }
+}
+
+/// Each player's XP from the `scores` played during `season`, for players
+/// who earned any.
+fn xp_in_season(
+    players: &[Player],
+    scores: &[GameScore],
+    season: &Season,
+) -> Vec<(AccountOwner, u64)> {
+    players
+        .iter()
+        .filter_map(|player| {
+            let xp = scores
+                .iter()
+                .filter(|score| {
+                    score.player == player.owner
+                        && (season.started_at..season.ends_at).contains(&score.timestamp)
+                })
+                .map(|score| score.xp_earned)
+                .sum::<u64>();
+            (xp > 0).then_some((player.owner, xp))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use linera_sdk::{bcs, linera_base_types::CryptoHash};
+
+    use super::*;
+    use crate::calculate_level;
+
+    const END: u64 = 1_700_000_000_000_000;
+
+    fn chain() -> ChainId {
+        ChainId(CryptoHash::test_hash("fixtures"))
+    }
+
+    fn encoded(fixture: &Fixture) -> Vec<u8> {
+        bcs::to_bytes(&(
+            &fixture.players,
+            &fixture.scores,
+            &fixture.archived_seasons,
+            &fixture.current_season,
+            &fixture.seasonal_xp,
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn same_seed_generates_same_fixture() {
+        let config = FixtureConfig {
+            seed: 7,
+            ..FixtureConfig::default()
+        };
+        let fixture = Fixture::generate(&config, chain(), END);
+        assert_eq!(encoded(&fixture), encoded(&Fixture::generate(&config, chain(), END)));
+
+        let other = FixtureConfig {
+            seed: 8,
+            ..FixtureConfig::default()
+        };
+        assert_ne!(encoded(&fixture), encoded(&Fixture::generate(&other, chain(), END)));
+    }
+
+    #[test]
+    fn players_match_their_scores() {
+        let config = FixtureConfig::default();
+        let fixture = Fixture::generate(&config, chain(), END);
+        assert_eq!(fixture.players.len(), config.players as usize);
+        for player in &fixture.players {
+            let runs = fixture
+                .scores
+                .iter()
+                .filter(|score| score.player == player.owner)
+                .collect::<Vec<_>>();
+            let xp = runs.iter().map(|score| score.xp_earned).sum::<u64>();
+            assert_eq!(runs.len() as u64, player.games_played);
+            assert_eq!(u64::from(config.scores_per_player), player.games_played);
+            assert_eq!(xp, player.total_xp);
+            assert_eq!(calculate_level(xp), player.level);
+        }
+    }
+
+    #[test]
+    fn scores_are_ordered_and_within_the_window() {
+        let config = FixtureConfig::default();
+        let fixture = Fixture::generate(&config, chain(), END);
+        let start = END - config.days * MICROS_PER_DAY;
+        for (id, score) in fixture.scores.iter().enumerate() {
+            assert_eq!(score.id, id as u64);
+            assert!((start..END).contains(&score.timestamp));
+        }
+        assert!(fixture
+            .scores
+            .windows(2)
+            .all(|pair| pair[0].timestamp <= pair[1].timestamp));
+    }
+
+    #[test]
+    fn seasons_account_for_every_score() {
+        let fixture = Fixture::generate(&FixtureConfig::default(), chain(), END);
+        let current = fixture.current_season.unwrap();
+        assert!(!current.has_ended(END));
+        assert_eq!(current.number as usize, fixture.archived_seasons.len() + 1);
+
+        let mut ends_at = None;
+        for (i, archive) in fixture.archived_seasons.iter().enumerate() {
+            assert_eq!(archive.season.number as usize, i + 1);
+            if let Some(ends_at) = ends_at {
+                assert_eq!(archive.season.started_at, ends_at);
+            }
+            ends_at = Some(archive.season.ends_at);
+        }
+        assert_eq!(ends_at, Some(current.started_at));
+
+        let archived = fixture
+            .archived_seasons
+            .iter()
+            .flat_map(|archive| &archive.standings)
+            .map(|standing| standing.seasonal_xp)
+            .sum::<u64>();
+        let seasonal = fixture
+            .seasonal_xp
+            .iter()
+            .map(|(_, seasonal)| seasonal.in_season(current.number))
+            .sum::<u64>();
+        let total = fixture
+            .scores
+            .iter()
+            .map(|score| score.xp_earned)
+            .sum::<u64>();
+        assert_eq!(archived + seasonal, total);
+    }
+
+    #[test]
+    fn seasons_can_be_left_off() {
+        let config = FixtureConfig {
+            season_length_days: None,
+            ..FixtureConfig::default()
+        };
+        let fixture = Fixture::generate(&config, chain(), END);
+        assert!(fixture.current_season.is_none());
+        assert!(fixture.archived_seasons.is_empty());
+        assert!(fixture.seasonal_xp.is_empty());
+    }
+}
@@ -0,0 +1,189 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Personal goals players set for themselves, such as "reach level 15 by
+//! the end of the month". The contract checks them after every run and
+//! marks them completed once met.
+
+use async_graphql::{Enum, InputObject, SimpleObject};
+use serde::{Deserialize, Serialize};
+
+use crate::{ArcadeError, GameScore, GameType, Player};
+
+/// Goals a player can have in progress at once.
+pub const MAX_ACTIVE_GOALS: usize = 5;
+
+/// Goals kept per player, counting finished ones. The oldest finished goals
+/// are dropped first.
+pub const MAX_GOALS_KEPT: usize = 20;
+
+/// What a goal measures.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Enum)]
+pub enum GoalKind {
+    /// Reach a level.
+    Level,
+    /// Reach a lifetime XP total.
+    TotalXp,
+    /// Play a number of games in total.
+    GamesPlayed,
+    /// Score at least the target in one run of a game.
+    GameScore,
+}
+
+/// A goal as the player describes it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, SimpleObject, InputObject)]
+#[graphql(input_name = "GoalSpecInput")]
+pub struct GoalSpec {
+    pub kind: GoalKind,
+    pub target: u64,
+    /// The game a [`GoalKind::GameScore`] goal is for; unset otherwise.
+    pub game_type: Option<GameType>,
+    /// The goal must be met before this timestamp, if set.
+    pub deadline: Option<u64>,
+}
+
+impl GoalSpec {
+    /// Check the goal is well formed and its deadline is after `now`.
+    pub fn validate(&self, now: u64) -> Result<(), ArcadeError> {
+        let game_ok = match self.kind {
+            GoalKind::GameScore => self.game_type.is_some(),
+            _ => self.game_type.is_none(),
+        };
+        if self.target == 0 || !game_ok {
+            return Err(ArcadeError::InvalidGoal);
+        }
+        if self.deadline.is_some_and(|deadline| deadline <= now) {
+            return Err(ArcadeError::InvalidGoalDeadline);
+        }
+        Ok(())
+    }
+
+    /// Whether `player`'s stats, or `score` if given, meet the goal.
+    pub fn is_met(&self, player: &Player, score: Option<&GameScore>) -> bool {
+        match self.kind {
+            GoalKind::Level => u64::from(player.level) >= self.target,
+            GoalKind::TotalXp => player.total_xp >= self.target,
+            GoalKind::GamesPlayed => player.games_played >= self.target,
+            GoalKind::GameScore => score.is_some_and(|score| {
+                Some(score.game_type) == self.game_type && score.score >= self.target
+            }),
+        }
+    }
+}
+
+/// Where a goal stands.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Enum)]
+pub enum GoalStatus {
+    Active,
+    Completed,
+    /// The deadline passed before the goal was met.
+    Expired,
+}
+
+/// A player's goal and its progress.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct Goal {
+    pub id: u64,
+    pub spec: GoalSpec,
+    pub created_at: u64,
+    pub completed_at: Option<u64>,
+}
+
+impl Goal {
+    /// Where the goal stands at `now`.
+    pub fn status(&self, now: u64) -> GoalStatus {
+        if self.completed_at.is_some() {
+            GoalStatus::Completed
+        } else if self.spec.deadline.is_some_and(|deadline| now >= deadline) {
+            GoalStatus::Expired
+        } else {
+            GoalStatus::Active
+        }
+    }
+}
+
+/// A goal with its status at the time of the query.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct GoalProgress {
+    pub goal: Goal,
+    pub status: GoalStatus,
+}
+
+/// Every goal a player has set.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PlayerGoals {
+    goals: Vec<Goal>,
+    next_id: u64,
+}
+
+impl PlayerGoals {
+    /// Add a goal for `player`, returning its ID. Goals already met are
+    /// rejected rather than completed on the spot.
+    pub fn add(&mut self, spec: GoalSpec, player: &Player, now: u64) -> Result<u64, ArcadeError> {
+        spec.validate(now)?;
+        if spec.is_met(player, None) {
+            return Err(ArcadeError::GoalAlreadyMet);
+        }
+        let active = self
+            .goals
+            .iter()
+            .filter(|goal| goal.status(now) == GoalStatus::Active)
+            .count();
+        if active >= MAX_ACTIVE_GOALS {
+            return Err(ArcadeError::TooManyGoals);
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.goals.push(Goal {
+            id,
+            spec,
+            created_at: now,
+            completed_at: None,
+        });
+        while self.goals.len() > MAX_GOALS_KEPT {
+            let Some(oldest_finished) = self
+                .goals
+                .iter()
+                .position(|goal| goal.status(now) != GoalStatus::Active)
+            else {
+                break;
+            };
+            self.goals.remove(oldest_finished);
+        }
+        Ok(id)
+    }
+
+    /// Remove a goal.
+    pub fn remove(&mut self, goal_id: u64) -> Result<(), ArcadeError> {
+        let Some(position) = self.goals.iter().position(|goal| goal.id == goal_id) else {
+            return Err(ArcadeError::GoalNotFound);
+        };
+        self.goals.remove(position);
+        Ok(())
+    }
+
+    /// Mark the active goals that `player`'s stats or latest `score` meet as
+    /// completed at `now`, returning them.
+    pub fn complete(&mut self, player: &Player, score: &GameScore, now: u64) -> Vec<Goal> {
+        let mut completed = Vec::new();
+        for goal in &mut self.goals {
+            if goal.status(now) == GoalStatus::Active && goal.spec.is_met(player, Some(score)) {
+                goal.completed_at = Some(now);
+                completed.push(goal.clone());
+            }
+        }
+        completed
+    }
+
+    /// Every goal with its status at `now`, oldest first.
+    pub fn progress(&self, now: u64) -> Vec<GoalProgress> {
+        self.goals
+            .iter()
+            .map(|goal| GoalProgress {
+                goal: goal.clone(),
+                status: goal.status(now),
+            })
+            .collect()
+    }
+}
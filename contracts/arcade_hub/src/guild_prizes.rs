@@ -0,0 +1,266 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Guild prizes and how they are split between members.
+//!
+//! When a guild event pays out, the admin awards the prize to the guild on
+//! the hub, moving it from their balance into escrow. The prize is split at
+//! once between the guild's members by the payout rule their leader chose:
+//! an equal split, a split weighted by the XP each member contributed since
+//! joining, or shares the leader decides, none above
+//! [`MAX_LEADER_SHARE_PERCENT`]. Each member's share is recorded as a claim
+//! they collect from escrow on the hub whenever they like, even after
+//! leaving the guild.
+
+use async_graphql::{Enum, InputObject, SimpleObject};
+use linera_sdk::linera_base_types::{AccountOwner, Amount};
+use serde::{Deserialize, Serialize};
+
+use crate::{guilds::Guild, ArcadeError};
+
+/// Largest share of a prize a leader can give one member, in percent,
+/// unless the guild has a single member.
+pub const MAX_LEADER_SHARE_PERCENT: u32 = 50;
+
+/// Longest a prize's note can be, in characters.
+pub const MAX_GUILD_PRIZE_NOTE_LENGTH: usize = 140;
+
+/// How a guild's prizes are split between its members.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Enum)]
+pub enum PayoutSplit {
+    #[default]
+    Equal,
+    /// In proportion to the XP each member contributed since joining.
+    ContributionWeighted,
+    /// By shares the leader decides.
+    LeaderDecided,
+}
+
+/// A member's share of the guild's prizes, decided by the leader.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, SimpleObject, InputObject)]
+#[graphql(input_name = "PayoutShareInput")]
+pub struct PayoutShare {
+    pub member: AccountOwner,
+    pub percent: u32,
+}
+
+/// A guild's payout rule, chosen by its leader.
+#[derive(
+    Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, SimpleObject, InputObject,
+)]
+#[graphql(input_name = "GuildPayoutRuleInput")]
+pub struct GuildPayoutRule {
+    pub split: PayoutSplit,
+    /// Each member's share, adding up to 100, for
+    /// [`PayoutSplit::LeaderDecided`] only. Members left out get nothing,
+    /// and the shares of members who leave are split between the rest in
+    /// proportion.
+    pub shares: Vec<PayoutShare>,
+}
+
+impl GuildPayoutRule {
+    /// Check the rule can be applied to `guild`.
+    pub fn validate(&self, guild: &Guild) -> Result<(), ArcadeError> {
+        if self.split != PayoutSplit::LeaderDecided {
+            if !self.shares.is_empty() {
+                return Err(ArcadeError::InvalidGuildPayoutRule);
+            }
+            return Ok(());
+        }
+        let cap = if guild.members.len() > 1 {
+            MAX_LEADER_SHARE_PERCENT
+        } else {
+            100
+        };
+        let mut members = Vec::new();
+        let shares_ok = self.shares.iter().all(|share| {
+            let unique = !members.contains(&share.member);
+            members.push(share.member);
+            unique && guild.role_of(&share.member).is_some() && share.percent <= cap
+        });
+        let total: u32 = self.shares.iter().map(|share| share.percent).sum();
+        if !shares_ok || total != 100 {
+            return Err(ArcadeError::InvalidGuildPayoutRule);
+        }
+        Ok(())
+    }
+
+    /// Split `amount` between `members`, given with the XP each contributed,
+    /// leaving out members whose share is nothing. Rounding leftovers go to
+    /// the member with the largest share, the earliest to join on a tie.
+    pub fn shares_of(
+        &self,
+        amount: Amount,
+        members: &[(AccountOwner, u64)],
+    ) -> Vec<(AccountOwner, Amount)> {
+        let mut weights = members
+            .iter()
+            .map(|(member, xp)| match self.split {
+                PayoutSplit::Equal => 1,
+                PayoutSplit::ContributionWeighted => u128::from(*xp),
+                PayoutSplit::LeaderDecided => self
+                    .shares
+                    .iter()
+                    .find(|share| &share.member == member)
+                    .map_or(0, |share| u128::from(share.percent)),
+            })
+            .collect::<Vec<_>>();
+        // Nobody to weight by, e.g. no one has played yet: split equally
+        if weights.iter().all(|weight| *weight == 0) {
+            weights.fill(1);
+        }
+        // Scale the weights down until they add up to at most 64 bits, so
+        // the products below fit in 128
+        let total: u128 = weights.iter().sum();
+        let shift = (u128::BITS - total.leading_zeros()).saturating_sub(u64::BITS);
+        if shift > 0 {
+            for weight in &mut weights {
+                *weight >>= shift;
+            }
+        }
+        let total: u128 = weights.iter().sum();
+        let attos = u128::from(amount);
+        let mut amounts = weights
+            .iter()
+            .map(|weight| attos / total * weight + attos % total * weight / total)
+            .collect::<Vec<_>>();
+        let leftover = attos - amounts.iter().sum::<u128>();
+        if let Some(largest) =
+            (0..weights.len()).max_by_key(|i| (weights[*i], std::cmp::Reverse(*i)))
+        {
+            amounts[largest] += leftover;
+        }
+        members
+            .iter()
+            .zip(amounts)
+            .filter(|(_, attos)| *attos > 0)
+            .map(|((member, _), attos)| (*member, Amount::from_attos(attos)))
+            .collect()
+    }
+}
+
+/// Check a prize's amount and note.
+pub fn validate_prize(amount: Amount, note: &str) -> Result<(), ArcadeError> {
+    if amount.is_zero()
+        || note.trim().is_empty()
+        || note.chars().count() > MAX_GUILD_PRIZE_NOTE_LENGTH
+        || note.chars().any(char::is_control)
+    {
+        return Err(ArcadeError::InvalidGuildPrize);
+    }
+    Ok(())
+}
+
+/// A prize awarded to a guild.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct GuildPrize {
+    pub id: u64,
+    pub guild_id: u64,
+    pub amount: Amount,
+    /// What the prize was won for.
+    pub note: String,
+    /// The split the prize was paid out by.
+    pub split: PayoutSplit,
+    pub awarded_by: AccountOwner,
+    pub awarded_at: u64,
+}
+
+/// A member's share of a guild prize.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct PrizeClaim {
+    pub prize_id: u64,
+    pub guild_id: u64,
+    pub member: AccountOwner,
+    pub amount: Amount,
+    /// When the member collected their share; `None` until they do.
+    pub claimed_at: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use linera_sdk::linera_base_types::CryptoHash;
+
+    use super::*;
+
+    fn member(name: &str) -> AccountOwner {
+        AccountOwner::Address32(CryptoHash::test_hash(name))
+    }
+
+    fn rule(split: PayoutSplit) -> GuildPayoutRule {
+        GuildPayoutRule {
+            split,
+            shares: Vec::new(),
+        }
+    }
+
+    fn attos_of(shares: &[(AccountOwner, Amount)]) -> Vec<u128> {
+        shares
+            .iter()
+            .map(|(_, amount)| u128::from(*amount))
+            .collect()
+    }
+
+    #[test]
+    fn equal_split_gives_the_leftover_to_the_earliest_member() {
+        let members = [(member("a"), 0), (member("b"), 0), (member("c"), 0)];
+        let shares = rule(PayoutSplit::Equal).shares_of(Amount::from_attos(100), &members);
+        assert_eq!(attos_of(&shares), [34, 33, 33]);
+    }
+
+    #[test]
+    fn weighted_split_gives_the_leftover_to_the_largest_share() {
+        let members = [(member("a"), 1), (member("b"), 2), (member("c"), 2)];
+        let shares =
+            rule(PayoutSplit::ContributionWeighted).shares_of(Amount::from_attos(7), &members);
+        // 7/5, 14/5 and 14/5 round down to 1, 2 and 2
+        assert_eq!(attos_of(&shares), [1, 4, 2]);
+    }
+
+    #[test]
+    fn weighted_split_without_contributions_is_equal() {
+        let members = [(member("a"), 0), (member("b"), 0)];
+        let shares =
+            rule(PayoutSplit::ContributionWeighted).shares_of(Amount::from_attos(10), &members);
+        assert_eq!(attos_of(&shares), [5, 5]);
+    }
+
+    #[test]
+    fn leader_split_leaves_out_members_without_a_share() {
+        let members = [(member("a"), 0), (member("b"), 0), (member("c"), 0)];
+        let rule = GuildPayoutRule {
+            split: PayoutSplit::LeaderDecided,
+            shares: vec![
+                PayoutShare {
+                    member: member("a"),
+                    percent: 50,
+                },
+                PayoutShare {
+                    member: member("c"),
+                    percent: 50,
+                },
+            ],
+        };
+        let shares = rule.shares_of(Amount::from_attos(101), &members);
+        assert_eq!(
+            shares,
+            [
+                (member("a"), Amount::from_attos(51)),
+                (member("c"), Amount::from_attos(50)),
+            ]
+        );
+    }
+
+    #[test]
+    fn weighted_split_of_huge_amounts_and_weights_adds_up() {
+        let members = [
+            (member("a"), u64::MAX),
+            (member("b"), u64::MAX - 1),
+            (member("c"), 3),
+        ];
+        let amount = Amount::from_attos(u128::MAX);
+        let shares = rule(PayoutSplit::ContributionWeighted).shares_of(amount, &members);
+        let total: u128 = attos_of(&shares).iter().sum();
+        assert_eq!(total, u128::MAX);
+        assert_eq!(shares[0].0, member("a"));
+    }
+}
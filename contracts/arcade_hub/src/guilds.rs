@@ -0,0 +1,450 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Guilds: named groups of players with a leader, officers and members, and
+//! an audit trail of how each guild was managed.
+//!
+//! Guilds level up through [`GUILD_TIERS`] as their members earn XP, and
+//! each tier raises the member cap.
+
+use std::collections::VecDeque;
+
+use async_graphql::{Enum, SimpleObject};
+use linera_sdk::linera_base_types::AccountOwner;
+use serde::{Deserialize, Serialize};
+
+use crate::ArcadeError;
+
+/// Maximum number of invites a guild can have outstanding.
+pub const MAX_GUILD_INVITES: usize = 50;
+
+/// Audit entries kept per guild; older entries are dropped.
+pub const MAX_GUILD_AUDIT_ENTRIES: usize = 200;
+
+/// Maximum length of a guild announcement, in characters.
+pub const MAX_ANNOUNCEMENT_LENGTH: usize = 280;
+
+/// A guild level, reached once the guild's members have earned `min_xp`
+/// between them.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, SimpleObject)]
+pub struct GuildTier {
+    pub level: u32,
+    pub min_xp: u64,
+    /// Maximum number of members, leader included.
+    pub member_cap: u32,
+}
+
+/// Every guild level, lowest first.
+pub const GUILD_TIERS: [GuildTier; 5] = [
+    GuildTier {
+        level: 1,
+        min_xp: 0,
+        member_cap: 10,
+    },
+    GuildTier {
+        level: 2,
+        min_xp: 10_000,
+        member_cap: 20,
+    },
+    GuildTier {
+        level: 3,
+        min_xp: 50_000,
+        member_cap: 30,
+    },
+    GuildTier {
+        level: 4,
+        min_xp: 150_000,
+        member_cap: 40,
+    },
+    GuildTier {
+        level: 5,
+        min_xp: 400_000,
+        member_cap: 50,
+    },
+];
+
+/// The tier a guild with `xp` has reached.
+pub fn guild_tier(xp: u64) -> GuildTier {
+    GUILD_TIERS
+        .into_iter()
+        .rev()
+        .find(|tier| xp >= tier.min_xp)
+        .unwrap_or(GUILD_TIERS[0])
+}
+
+/// The tier after the one a guild with `xp` has reached, if any.
+pub fn next_guild_tier(xp: u64) -> Option<GuildTier> {
+    GUILD_TIERS.into_iter().find(|tier| xp < tier.min_xp)
+}
+
+/// A guild's accumulated XP and level.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct GuildProgress {
+    pub guild_id: u64,
+    /// XP earned by members while in the guild.
+    pub xp: u64,
+    pub tier: GuildTier,
+    pub next_tier: Option<GuildTier>,
+}
+
+impl GuildProgress {
+    pub fn new(guild_id: u64, xp: u64) -> Self {
+        Self {
+            guild_id,
+            xp,
+            tier: guild_tier(xp),
+            next_tier: next_guild_tier(xp),
+        }
+    }
+}
+
+/// A member's standing within their guild.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize, Enum)]
+pub enum GuildRole {
+    Member,
+    Officer,
+    Leader,
+}
+
+/// Actions that require more than plain membership.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GuildPermission {
+    Invite,
+    Kick,
+    Rename,
+    Announce,
+    CreatePoll,
+    AssignRoles,
+    SetPayoutRule,
+}
+
+impl GuildRole {
+    /// Whether the role grants `permission`.
+    pub fn allows(self, permission: GuildPermission) -> bool {
+        match permission {
+            GuildPermission::Invite
+            | GuildPermission::Kick
+            | GuildPermission::Announce
+            | GuildPermission::CreatePoll => self >= GuildRole::Officer,
+            GuildPermission::Rename
+            | GuildPermission::AssignRoles
+            | GuildPermission::SetPayoutRule => self == GuildRole::Leader,
+        }
+    }
+}
+
+/// A guild's place on the guild leaderboard.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct GuildStanding {
+    pub rank: u32,
+    pub guild_id: u64,
+    pub name: String,
+    pub member_count: u32,
+    pub progress: GuildProgress,
+}
+
+/// Rank `guilds`, given with their XP, by XP descending, breaking ties in
+/// favor of the older guild.
+pub fn rank_guilds(mut guilds: Vec<(Guild, u64)>) -> Vec<GuildStanding> {
+    guilds.sort_by_key(|(guild, xp)| (std::cmp::Reverse(*xp), guild.created_at, guild.id));
+    guilds
+        .into_iter()
+        .enumerate()
+        .map(|(i, (guild, xp))| GuildStanding {
+            rank: (i + 1) as u32,
+            guild_id: guild.id,
+            name: guild.name,
+            member_count: guild.members.len() as u32,
+            progress: GuildProgress::new(guild.id, xp),
+        })
+        .collect()
+}
+
+/// A guild member and their role.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct GuildMember {
+    pub owner: AccountOwner,
+    pub role: GuildRole,
+    pub joined_at: u64,
+}
+
+/// A guild's message of the day, shown at the top of its page.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct GuildAnnouncement {
+    pub text: String,
+    pub author: AccountOwner,
+    pub posted_at: u64,
+}
+
+/// A guild and its roster.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct Guild {
+    /// The guild's ID on this chain. The hub numbers guilds synced from
+    /// other chains itself.
+    pub id: u64,
+    pub name: String,
+    /// Members in the order they joined, the leader among them.
+    pub members: Vec<GuildMember>,
+    /// Players invited to join who haven't yet.
+    pub invites: Vec<AccountOwner>,
+    /// The current announcement, if any.
+    pub announcement: Option<GuildAnnouncement>,
+    pub created_at: u64,
+}
+
+impl Guild {
+    /// Create a guild led by `leader`.
+    pub fn new(id: u64, name: String, leader: AccountOwner, timestamp: u64) -> Self {
+        Self {
+            id,
+            name,
+            members: vec![GuildMember {
+                owner: leader,
+                role: GuildRole::Leader,
+                joined_at: timestamp,
+            }],
+            invites: Vec::new(),
+            announcement: None,
+            created_at: timestamp,
+        }
+    }
+
+    /// The role of `owner`, if they are a member.
+    pub fn role_of(&self, owner: &AccountOwner) -> Option<GuildRole> {
+        self.members
+            .iter()
+            .find(|member| &member.owner == owner)
+            .map(|member| member.role)
+    }
+
+    /// The guild's leader.
+    pub fn leader(&self) -> Option<AccountOwner> {
+        self.members
+            .iter()
+            .find(|member| member.role == GuildRole::Leader)
+            .map(|member| member.owner)
+    }
+
+    /// Check that `actor` is a member whose role grants `permission`,
+    /// returning their role.
+    pub fn ensure_permitted(
+        &self,
+        actor: &AccountOwner,
+        permission: GuildPermission,
+    ) -> Result<GuildRole, ArcadeError> {
+        match self.role_of(actor) {
+            Some(role) if role.allows(permission) => Ok(role),
+            Some(_) => Err(ArcadeError::GuildPermissionDenied),
+            None => Err(ArcadeError::NotInGuild),
+        }
+    }
+
+    /// Invite `player`. Inviting an already invited player is a no-op.
+    pub fn invite(&mut self, player: AccountOwner) -> Result<(), ArcadeError> {
+        if self.role_of(&player).is_some() {
+            return Err(ArcadeError::AlreadyInGuild);
+        }
+        if self.invites.contains(&player) {
+            return Ok(());
+        }
+        if self.invites.len() >= MAX_GUILD_INVITES {
+            return Err(ArcadeError::TooManyGuildInvites);
+        }
+        self.invites.push(player);
+        Ok(())
+    }
+
+    /// Admit `player` on their pending invite, if the guild's `tier` has
+    /// room for them.
+    pub fn join(
+        &mut self,
+        player: AccountOwner,
+        timestamp: u64,
+        tier: &GuildTier,
+    ) -> Result<(), ArcadeError> {
+        if !self.invites.contains(&player) {
+            return Err(ArcadeError::GuildInviteNotFound);
+        }
+        self.admit(player, timestamp, tier)
+    }
+
+    /// Admit `player`, invited or not, if the guild's `tier` has room for
+    /// them.
+    pub fn admit(
+        &mut self,
+        player: AccountOwner,
+        timestamp: u64,
+        tier: &GuildTier,
+    ) -> Result<(), ArcadeError> {
+        if self.members.len() >= tier.member_cap as usize {
+            return Err(ArcadeError::GuildFull);
+        }
+        self.invites.retain(|invited| invited != &player);
+        self.members.push(GuildMember {
+            owner: player,
+            role: GuildRole::Member,
+            joined_at: timestamp,
+        });
+        Ok(())
+    }
+
+    /// Remove `member` from the roster.
+    pub fn remove(&mut self, member: &AccountOwner) {
+        self.members.retain(|candidate| &candidate.owner != member);
+    }
+
+    /// Give `member` a new role. Making someone leader hands over the
+    /// leadership, and the previous leader becomes an officer.
+    pub fn set_role(&mut self, member: &AccountOwner, role: GuildRole) -> Result<(), ArcadeError> {
+        if self.role_of(member).is_none() {
+            return Err(ArcadeError::NotInGuild);
+        }
+        for candidate in &mut self.members {
+            if &candidate.owner == member {
+                candidate.role = role;
+            } else if role == GuildRole::Leader && candidate.role == GuildRole::Leader {
+                candidate.role = GuildRole::Officer;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A member's play since joining their current guild.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GuildContribution {
+    pub guild_id: u64,
+    pub xp_contributed: u64,
+    pub games_played: u64,
+    pub last_played_at: Option<u64>,
+}
+
+impl GuildContribution {
+    pub fn new(guild_id: u64) -> Self {
+        Self {
+            guild_id,
+            xp_contributed: 0,
+            games_played: 0,
+            last_played_at: None,
+        }
+    }
+
+    /// Count a run that earned `xp` at `timestamp`.
+    pub fn record(&mut self, xp: u64, timestamp: u64) {
+        self.xp_contributed = self.xp_contributed.saturating_add(xp);
+        self.games_played = self.games_played.saturating_add(1);
+        self.last_played_at = Some(self.last_played_at.unwrap_or(0).max(timestamp));
+    }
+
+    /// Take back a removed run that earned `xp`.
+    pub fn forget(&mut self, xp: u64) {
+        self.xp_contributed = self.xp_contributed.saturating_sub(xp);
+        self.games_played = self.games_played.saturating_sub(1);
+    }
+}
+
+/// A guild member with their contribution, for ranking members within a
+/// guild.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct GuildContributor {
+    pub owner: AccountOwner,
+    pub role: GuildRole,
+    pub joined_at: u64,
+    /// XP earned since joining the guild.
+    pub xp_contributed: u64,
+    /// Games played since joining the guild.
+    pub games_played: u64,
+    /// When the member last played since joining, if they have.
+    pub last_played_at: Option<u64>,
+}
+
+impl GuildContributor {
+    /// Combine a member with their contribution, if they've made one.
+    pub fn new(member: &GuildMember, contribution: Option<&GuildContribution>) -> Self {
+        Self {
+            owner: member.owner,
+            role: member.role,
+            joined_at: member.joined_at,
+            xp_contributed: contribution.map_or(0, |c| c.xp_contributed),
+            games_played: contribution.map_or(0, |c| c.games_played),
+            last_played_at: contribution.and_then(|c| c.last_played_at),
+        }
+    }
+}
+
+/// Kinds of guild management actions recorded in the audit trail.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Enum)]
+pub enum GuildAction {
+    Created,
+    Invited,
+    Joined,
+    Left,
+    Kicked,
+    Renamed,
+    RoleChanged,
+    Announced,
+    LevelUp,
+    PayoutRuleChanged,
+}
+
+/// One guild management action.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct GuildAuditEntry {
+    pub action: GuildAction,
+    /// The member who took the action.
+    pub actor: AccountOwner,
+    /// The player acted on, if any.
+    pub target: Option<AccountOwner>,
+    /// The new name, role, announcement or level, where there is one.
+    pub detail: Option<String>,
+    pub timestamp: u64,
+}
+
+/// A guild's most recent management actions, oldest first.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GuildAuditTrail {
+    entries: VecDeque<GuildAuditEntry>,
+}
+
+impl GuildAuditTrail {
+    /// Append an entry, dropping the oldest once the trail is full.
+    pub fn record(&mut self, entry: GuildAuditEntry) {
+        if self.entries.len() >= MAX_GUILD_AUDIT_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    /// Up to `limit` entries, newest first.
+    pub fn latest(&self, limit: usize) -> Vec<GuildAuditEntry> {
+        self.entries.iter().rev().take(limit).cloned().collect()
+    }
+}
+
+/// Validate a guild name: 3-24 characters of letters, digits, spaces, '_'
+/// or '-', not starting or ending with a space.
+pub fn validate_guild_name(name: &str) -> Result<(), ArcadeError> {
+    let len = name.chars().count();
+    if !(3..=24).contains(&len)
+        || name.trim() != name
+        || !name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == ' ' || c == '_' || c == '-')
+    {
+        return Err(ArcadeError::InvalidGuildName);
+    }
+    Ok(())
+}
+
+/// Validate an announcement: 1-280 characters, not all whitespace.
+pub fn validate_announcement(text: &str) -> Result<(), ArcadeError> {
+    if text.trim().is_empty() || text.chars().count() > MAX_ANNOUNCEMENT_LENGTH {
+        return Err(ArcadeError::InvalidGuildAnnouncement);
+    }
+    Ok(())
+}
+
+/// Normalize a guild name for uniqueness checks (case-insensitive).
+pub fn normalize_guild_name(name: &str) -> String {
+    name.to_lowercase()
+}
@@ -0,0 +1,129 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Proof-of-humanity challenges for suspicious accounts.
+//!
+//! The admin can challenge any account, and accounts flagged into the
+//! moderation queue can be challenged as soon as they are flagged. A
+//! challenged account's submissions are rejected until it completes the
+//! challenge: a second wallet the player controls signs the challenge's
+//! nonce, and the player submits that signature. Challenges issued on the
+//! hub are taken up by every chain, and the hub drops the player's synced
+//! runs meanwhile; the player completes one on their own chain, which sends
+//! the proof to the hub to clear it everywhere.
+//! Each wallet can only vouch for a limited number of players, so a bot
+//! farm can't clear all its accounts with one key. Resolving the moderation
+//! flag a challenge was issued for withdraws the challenge.
+
+use async_graphql::{InputObject, SimpleObject};
+use linera_sdk::linera_base_types::{AccountOwner, BcsSignable, ChainId, CryptoHash};
+use serde::{Deserialize, Serialize};
+
+use crate::{check_account_signature, ArcadeError};
+
+/// Most players a single wallet can be allowed to vouch for.
+pub const MAX_VOUCHES_PER_VERIFIER: u32 = 10;
+
+/// Humanity check settings, managed by the admin.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject, InputObject)]
+#[graphql(input_name = "HumanityCheckConfigInput")]
+pub struct HumanityCheckConfig {
+    /// Challenge accounts as soon as they are flagged for moderation.
+    pub challenge_flagged: bool,
+    /// How many players one wallet can vouch for.
+    pub vouches_per_verifier: u32,
+}
+
+impl Default for HumanityCheckConfig {
+    fn default() -> Self {
+        Self {
+            challenge_flagged: false,
+            vouches_per_verifier: 1,
+        }
+    }
+}
+
+impl HumanityCheckConfig {
+    /// Check every wallet can vouch for someone, up to the limit.
+    pub fn validate(&self) -> Result<(), ArcadeError> {
+        if !(1..=MAX_VOUCHES_PER_VERIFIER).contains(&self.vouches_per_verifier) {
+            return Err(ArcadeError::InvalidHumanityCheckConfig);
+        }
+        Ok(())
+    }
+}
+
+/// An open challenge an account must complete before it can submit again.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct HumanityChallenge {
+    pub id: u64,
+    pub player: AccountOwner,
+    /// The value the player's second wallet must sign.
+    pub nonce: CryptoHash,
+    /// The moderation flag the challenge was issued for, if any.
+    pub flag_id: Option<u64>,
+    /// The chain that issued the challenge and clears it.
+    pub chain_id: ChainId,
+    pub issued_at: u64,
+}
+
+impl HumanityChallenge {
+    /// A challenge for `player` issued by `chain_id` at `timestamp`.
+    pub fn new(
+        id: u64,
+        player: AccountOwner,
+        flag_id: Option<u64>,
+        chain_id: ChainId,
+        timestamp: u64,
+    ) -> Self {
+        let nonce = CryptoHash::new(&HumanityNoncePreimage {
+            chain_id,
+            challenge_id: id,
+            player,
+            timestamp,
+        });
+        Self {
+            id,
+            player,
+            nonce,
+            flag_id,
+            chain_id,
+            issued_at: timestamp,
+        }
+    }
+
+    /// Check `signature` is `verifier`'s signature over the challenge, from
+    /// a wallet other than the player's own.
+    pub fn verify(&self, verifier: &AccountOwner, signature: &str) -> Result<(), ArcadeError> {
+        let proof = HumanityProof {
+            challenge_id: self.id,
+            player: self.player,
+            nonce: self.nonce,
+        };
+        if verifier == &self.player || !check_account_signature(signature, verifier, &proof) {
+            return Err(ArcadeError::InvalidHumanityProof);
+        }
+        Ok(())
+    }
+}
+
+/// The value a challenge's nonce is computed over.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct HumanityNoncePreimage {
+    chain_id: ChainId,
+    challenge_id: u64,
+    player: AccountOwner,
+    timestamp: u64,
+}
+
+impl BcsSignable<'_> for HumanityNoncePreimage {}
+
+/// The payload the player's second wallet signs to complete a challenge.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HumanityProof {
+    pub challenge_id: u64,
+    pub player: AccountOwner,
+    pub nonce: CryptoHash,
+}
+
+impl BcsSignable<'_> for HumanityProof {}
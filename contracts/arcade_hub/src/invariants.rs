@@ -0,0 +1,61 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Consistency checks an operator can run over the state, a chunk at a time.
+//!
+//! Each section walks one view in order, checking up to
+//! [`INVARIANT_CHUNK_SIZE`] entries per operation and picking up where the
+//! previous one stopped. Violations are published as events as they are
+//! found, and the section's pass ends with an event summing them up.
+
+use async_graphql::{Enum, SimpleObject};
+use serde::{Deserialize, Serialize};
+
+/// Entries one invariant check operation looks at.
+pub const INVARIANT_CHUNK_SIZE: usize = 100;
+
+/// A group of related invariants, checked over one view.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Enum)]
+pub enum InvariantSection {
+    /// Every leaderboard and rookie leaderboard entry belongs to a
+    /// registered player.
+    Leaderboard,
+    /// The arcade totals match the sum of the logged scores. Scores by
+    /// players shadow-banned since they were recorded are left out of the
+    /// sum, though they did count towards the totals, so a ban can show up
+    /// as a mismatch.
+    ScoreTotals,
+    /// Every score's ID looks up a logged score with that ID.
+    ScorePositions,
+    /// Every position in a player's score log points at one of the
+    /// player's scores. Checked a player at a time.
+    PlayerScores,
+}
+
+/// How far a section's current pass has got.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct InvariantCheck {
+    pub section: InvariantSection,
+    /// Entries checked so far.
+    pub checked: u64,
+    pub violations: u32,
+    pub started_at: u64,
+    /// Games summed so far, for [`InvariantSection::ScoreTotals`].
+    pub games: u64,
+    /// XP summed so far, for [`InvariantSection::ScoreTotals`].
+    pub xp: u64,
+}
+
+impl InvariantCheck {
+    /// A pass over `section` starting at `timestamp`.
+    pub fn new(section: InvariantSection, timestamp: u64) -> Self {
+        Self {
+            section,
+            checked: 0,
+            violations: 0,
+            started_at: timestamp,
+            games: 0,
+            xp: 0,
+        }
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Shareable invite codes for guilds.
+//!
+//! The creator picks a random code and registers only its hash, so the codes
+//! themselves never appear in state. Whoever redeems a code joins the guild
+//! without needing a personal invite, until the code runs out of uses,
+//! expires or is revoked.
+
+use async_graphql::SimpleObject;
+use linera_sdk::linera_base_types::{AccountOwner, BcsSignable, CryptoHash};
+use serde::{Deserialize, Serialize};
+
+use crate::ArcadeError;
+
+/// Maximum number of times one code can be redeemed.
+pub const MAX_INVITE_CODE_USES: u32 = 100;
+
+/// Longest an invite code can stay valid, in hours (30 days).
+pub const MAX_INVITE_CODE_HOURS: u32 = 30 * 24;
+
+/// Microseconds in an hour.
+const MICROS_PER_HOUR: u64 = 60 * 60 * 1_000_000;
+
+/// A registered invite code, stored under its hash.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct InviteCode {
+    pub code_hash: CryptoHash,
+    pub guild_id: u64,
+    pub creator: AccountOwner,
+    /// Redemptions allowed in total (1 for a single-use code).
+    pub max_uses: u32,
+    pub uses: u32,
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
+impl InviteCode {
+    /// Register a code for `guild_id`, valid for `duration_hours` from
+    /// `timestamp`.
+    pub fn new(
+        code_hash: CryptoHash,
+        guild_id: u64,
+        creator: AccountOwner,
+        max_uses: u32,
+        duration_hours: u32,
+        timestamp: u64,
+    ) -> Result<Self, ArcadeError> {
+        if !(1..=MAX_INVITE_CODE_USES).contains(&max_uses)
+            || !(1..=MAX_INVITE_CODE_HOURS).contains(&duration_hours)
+        {
+            return Err(ArcadeError::InvalidInviteCodeLimits);
+        }
+        Ok(Self {
+            code_hash,
+            guild_id,
+            creator,
+            max_uses,
+            uses: 0,
+            created_at: timestamp,
+            expires_at: timestamp
+                .saturating_add(u64::from(duration_hours).saturating_mul(MICROS_PER_HOUR)),
+        })
+    }
+
+    /// Whether the code can still be redeemed at `timestamp`.
+    pub fn is_active(&self, timestamp: u64) -> bool {
+        timestamp < self.expires_at && self.uses < self.max_uses
+    }
+}
+
+/// The value an invite code's hash is computed over.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InviteCodePreimage {
+    pub code: String,
+}
+
+impl BcsSignable<'_> for InviteCodePreimage {}
+
+/// The hash an invite code is registered and looked up under.
+pub fn hash_code(code: &str) -> CryptoHash {
+    CryptoHash::new(&InviteCodePreimage {
+        code: code.to_string(),
+    })
+}
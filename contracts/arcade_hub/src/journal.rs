@@ -0,0 +1,116 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! An append-only journal of what changed the state, for disaster recovery.
+//!
+//! Every accepted operation and every cross-chain sync the hub applies is
+//! journaled in its BCS encoding, so the state's history can be inspected
+//! and replayed off-chain. Every [`JOURNAL_CHECKPOINT_INTERVAL`] entries the
+//! journal takes a checkpoint of the arcade totals and how long the score
+//! log was.
+//!
+//! The views derived from the players and the score log (leaderboards,
+//! score indexes, totals) can be rebuilt on-chain if a bug corrupts them.
+//! A replay walks its source a chunk at a time, like an invariant check,
+//! and is only allowed in maintenance mode so nothing reads or writes the
+//! views while they are half rebuilt. Totals replay from a checkpoint,
+//! adding only the scores logged after it.
+
+use async_graphql::{Enum, SimpleObject};
+use linera_sdk::linera_base_types::{AccountOwner, ChainId};
+use serde::{Deserialize, Serialize};
+
+/// Journal entries between checkpoints.
+pub const JOURNAL_CHECKPOINT_INTERVAL: u64 = 1000;
+
+/// Entries one replay operation reads from its source.
+pub const REPLAY_CHUNK_SIZE: usize = 100;
+
+/// What a journal entry records.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Enum)]
+pub enum JournalKind {
+    /// An operation signed on this chain.
+    Operation,
+    /// A sync from another chain, received in a message or from its sync
+    /// stream.
+    Message,
+}
+
+/// One state-changing operation or message.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct JournalEntry {
+    pub sequence: u64,
+    pub kind: JournalKind,
+    /// The operation's name, as in [`crate::Operation::name`]; `None` for
+    /// messages.
+    pub name: Option<String>,
+    /// Who signed the operation.
+    pub signer: Option<AccountOwner>,
+    /// The chain the message came from.
+    pub origin: Option<ChainId>,
+    /// The operation or message in BCS.
+    pub encoded: Vec<u8>,
+    pub recorded_at: u64,
+}
+
+/// The arcade totals as they were after a journal entry.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct JournalCheckpoint {
+    /// Journal entries before the checkpoint.
+    pub journal_length: u64,
+    /// Scores logged before the checkpoint.
+    pub score_log_length: u64,
+    pub total_games_played: u64,
+    pub total_xp_earned: u64,
+    pub taken_at: u64,
+}
+
+/// A group of derived views rebuilt together.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Enum)]
+pub enum ReplayTarget {
+    /// The leaderboard and rookie leaderboard, from the players.
+    Leaderboard,
+    /// Score lookups by ID, by receipt and per player, from the score log.
+    ScoreIndexes,
+    /// The games played and XP earned totals, from a checkpoint and the
+    /// scores logged after it. Scores by shadow-banned players are left
+    /// out.
+    Totals,
+}
+
+/// How far a target's current replay has got.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct ReplayProgress {
+    pub target: ReplayTarget,
+    /// The checkpoint the replay started from, for [`ReplayTarget::Totals`].
+    pub checkpoint: Option<u64>,
+    /// How far into its source the replay has got.
+    pub replayed: u64,
+    pub started_at: u64,
+    /// Games counted so far, for [`ReplayTarget::Totals`].
+    pub games: u64,
+    /// XP counted so far, for [`ReplayTarget::Totals`].
+    pub xp: u64,
+}
+
+impl ReplayProgress {
+    /// A replay of `target` starting at `timestamp`, from `checkpoint` if
+    /// it is one.
+    pub fn new(
+        target: ReplayTarget,
+        checkpoint: Option<&(u64, JournalCheckpoint)>,
+        timestamp: u64,
+    ) -> Self {
+        let (games, xp) = checkpoint.map_or((0, 0), |(_, checkpoint)| {
+            (checkpoint.total_games_played, checkpoint.total_xp_earned)
+        });
+        Self {
+            target,
+            checkpoint: checkpoint.map(|(index, _)| *index),
+            replayed: checkpoint.map_or(0, |(_, checkpoint)| checkpoint.score_log_length),
+            started_at: timestamp,
+            games,
+            xp,
+        }
+    }
+}
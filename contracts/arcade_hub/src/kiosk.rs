@@ -0,0 +1,170 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Device binding and per-device limits for kiosk deployments.
+//!
+//! Kiosk cabinets identify themselves with an opaque, operator-assigned
+//! `device_id` (never an IP address) that is attached to each submission.
+
+use async_graphql::{InputObject, SimpleObject};
+use linera_sdk::linera_base_types::{AccountOwner, BcsSignable, ChainId};
+use serde::{Deserialize, Serialize};
+
+use crate::{check_account_signature, ArcadeError, ScoreSubmission};
+
+/// Length of a device rate-limit window, in microseconds (one hour).
+pub const DEVICE_WINDOW_MICROS: u64 = 60 * 60 * 1_000_000;
+
+/// Maximum length of a device ID.
+pub const MAX_DEVICE_ID_LENGTH: usize = 64;
+
+/// Maximum number of submissions a gateway may relay in one operation.
+pub const MAX_RELAY_BATCH_SIZE: usize = 50;
+
+/// Kiosk-mode settings, managed by the admin.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, SimpleObject, InputObject)]
+#[graphql(input_name = "KioskConfigInput")]
+pub struct KioskConfig {
+    /// Reject submissions that don't carry a device ID.
+    pub require_device_id: bool,
+    /// Maximum submissions per device per hour (0 = unlimited).
+    pub max_submissions_per_hour: u32,
+}
+
+/// Submission activity of a single device within the current window.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, SimpleObject)]
+pub struct DeviceActivity {
+    pub device_id: String,
+    pub window_start: u64,
+    pub submissions_in_window: u32,
+    pub total_submissions: u64,
+    pub last_seen: u64,
+}
+
+impl DeviceActivity {
+    /// Create an empty activity record for a device.
+    pub fn new(device_id: String) -> Self {
+        Self {
+            device_id,
+            ..Self::default()
+        }
+    }
+
+    /// Record a submission at `timestamp`, enforcing the hourly limit.
+    pub fn record(&mut self, timestamp: u64, limit: u32) -> Result<(), ArcadeError> {
+        if timestamp.saturating_sub(self.window_start) >= DEVICE_WINDOW_MICROS {
+            self.window_start = timestamp;
+            self.submissions_in_window = 0;
+        }
+        if limit > 0 && self.submissions_in_window >= limit {
+            return Err(ArcadeError::DeviceRateLimited);
+        }
+        self.submissions_in_window += 1;
+        self.total_submissions = self.total_submissions.saturating_add(1);
+        self.last_seen = timestamp;
+        Ok(())
+    }
+}
+
+/// Validate a device ID.
+pub fn validate_device_id(device_id: &str) -> Result<(), ArcadeError> {
+    let len = device_id.len();
+    if len == 0 || len > MAX_DEVICE_ID_LENGTH {
+        return Err(ArcadeError::InvalidDeviceId);
+    }
+    if !device_id
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == ':')
+    {
+        return Err(ArcadeError::InvalidDeviceId);
+    }
+    Ok(())
+}
+
+/// A submission relayed by a gateway on behalf of a player.
+#[derive(Clone, Debug, Serialize, Deserialize, InputObject)]
+pub struct GatewaySubmission {
+    pub player: AccountOwner,
+    /// Per-player counter; must exceed the player's last relayed nonce.
+    pub nonce: u64,
+    pub submission: ScoreSubmission,
+    /// Hex-encoded BCS `AccountSignature` by the player over a
+    /// [`SubmissionAttestation`].
+    pub signature: String,
+}
+
+impl GatewaySubmission {
+    /// Check that the player signed this submission for `chain_id`.
+    pub fn verify(&self, chain_id: ChainId) -> Result<(), ArcadeError> {
+        let attestation = SubmissionAttestation {
+            chain_id,
+            player: self.player,
+            nonce: self.nonce,
+            submission: self.submission.clone(),
+        };
+        if !check_account_signature(&self.signature, &self.player, &attestation) {
+            return Err(ArcadeError::InvalidAttestation);
+        }
+        Ok(())
+    }
+}
+
+/// The payload a player signs to let a gateway relay their submission.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SubmissionAttestation {
+    pub chain_id: ChainId,
+    pub player: AccountOwner,
+    pub nonce: u64,
+    pub submission: ScoreSubmission,
+}
+
+impl BcsSignable<'_> for SubmissionAttestation {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::{MockRuntime, RuntimeFacade};
+
+    #[test]
+    fn device_limit_applies_within_a_window() {
+        let mut runtime = MockRuntime::at(DEVICE_WINDOW_MICROS);
+        let mut activity = DeviceActivity::new("cabinet-1".to_string());
+
+        for _ in 0..3 {
+            assert!(activity.record(runtime.now_micros(), 3).is_ok());
+            runtime.advance(60 * 1_000_000);
+        }
+        assert!(matches!(
+            activity.record(runtime.now_micros(), 3),
+            Err(ArcadeError::DeviceRateLimited)
+        ));
+        assert_eq!(activity.total_submissions, 3);
+    }
+
+    #[test]
+    fn device_limit_resets_with_the_next_window() {
+        let start = DEVICE_WINDOW_MICROS;
+        let mut runtime = MockRuntime::at(start);
+        let mut activity = DeviceActivity::new("cabinet-1".to_string());
+
+        assert!(activity.record(runtime.now_micros(), 1).is_ok());
+        runtime.advance(DEVICE_WINDOW_MICROS - 1);
+        assert!(activity.record(runtime.now_micros(), 1).is_err());
+
+        runtime.advance(1);
+        assert!(activity.record(runtime.now_micros(), 1).is_ok());
+        assert_eq!(activity.window_start, start + DEVICE_WINDOW_MICROS);
+        assert_eq!(activity.submissions_in_window, 1);
+    }
+
+    #[test]
+    fn unlimited_device_is_never_limited() {
+        let mut runtime = MockRuntime::at(DEVICE_WINDOW_MICROS);
+        let mut activity = DeviceActivity::new("cabinet-1".to_string());
+
+        for _ in 0..100 {
+            assert!(activity.record(runtime.now_micros(), 0).is_ok());
+            runtime.advance(1);
+        }
+    }
+}
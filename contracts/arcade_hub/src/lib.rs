@@ -3,10 +3,59 @@
 
 //! ABI and shared types for the Arcade Hub application.
 
+pub mod achievements;
+pub mod analytics;
+pub mod balance;
+pub mod bosses;
+pub mod bridge;
+pub mod challenges;
+pub mod circuit;
+pub mod community_games;
+pub mod cosmetics;
+pub mod crossovers;
+pub mod dispute;
+pub mod envelope;
+pub mod fixtures;
+pub mod goals;
+pub mod guild_prizes;
+pub mod guilds;
+pub mod humanity;
+pub mod invariants;
+pub mod invites;
+pub mod journal;
+pub mod kiosk;
+pub mod limits;
+pub mod locale;
+pub mod mentorship;
+pub mod migration;
+pub mod milestones;
+pub mod name_auctions;
+pub mod plausibility;
+pub mod polls;
+pub mod raffles;
+pub mod rankings;
+pub mod reactions;
+pub mod runtime;
+pub mod score_signing;
+pub mod scoring;
+pub mod seasons;
+pub mod sessions;
+pub mod smurfing;
+pub mod snapshots;
+pub mod social;
+pub mod storage;
+pub mod streaks;
+pub mod submission_windows;
+pub mod usage;
+pub mod usernames;
+
 use async_graphql::{InputObject, Request, Response, SimpleObject};
 use linera_sdk::{
     graphql::GraphQLMutationRoot,
-    linera_base_types::{AccountOwner, ChainId, ContractAbi, ServiceAbi},
+    linera_base_types::{
+        AccountOwner, AccountSignature, Amount, ApplicationId, BcsSignable, ChainId, ContractAbi,
+        CryptoHash, ServiceAbi,
+    },
 };
 use serde::{Deserialize, Serialize};
 
@@ -14,7 +63,7 @@ use serde::{Deserialize, Serialize};
 pub struct ArcadeHubAbi;
 
 impl ContractAbi for ArcadeHubAbi {
-    type Operation = Operation;
+    type Operation = envelope::VersionedOperation;
     type Response = ArcadeResponse;
 }
 
@@ -28,7 +77,17 @@ pub type GameId = u16;
 
 /// The supported game types in the arcade.
 #[derive(
-    Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, async_graphql::Enum,
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize,
+    async_graphql::Enum,
 )]
 pub enum GameType {
     SpeedClicker,
@@ -39,6 +98,15 @@ pub enum GameType {
 }
 
 impl GameType {
+    /// Every game in the arcade.
+    pub const ALL: [GameType; 5] = [
+        GameType::SpeedClicker,
+        GameType::MemoryMatrix,
+        GameType::ReactionStrike,
+        GameType::MathBlitz,
+        GameType::SnakeSprint,
+    ];
+
     /// Calculate XP earned based on game type, score, and optional bonus data.
     pub fn calculate_xp(&self, score: u64, bonus_data: Option<u64>) -> u64 {
         match self {
@@ -80,6 +148,28 @@ impl GameType {
         }
     }
 
+    /// Calculate XP for a submission in the given scoring category.
+    ///
+    /// Assisted runs use relaxed timing where a game has a reaction window:
+    /// ReactionStrike measures reactions against a 2000ms window instead of
+    /// 1000ms. Other games score the same in both categories.
+    pub fn calculate_category_xp(
+        &self,
+        score: u64,
+        bonus_data: Option<u64>,
+        category: ScoringCategory,
+    ) -> u64 {
+        match (self, category) {
+            (GameType::ReactionStrike, ScoringCategory::Assisted) => {
+                let avg_ms = score as i64;
+                let targets_hit = bonus_data.unwrap_or(0);
+                let base = ASSISTED_REACTION_WINDOW_MS.saturating_sub(avg_ms).max(0);
+                (base as u64).saturating_mul(targets_hit)
+            }
+            _ => self.calculate_xp(score, bonus_data),
+        }
+    }
+
     /// Get the game ID for this game type.
     pub fn id(&self) -> GameId {
         match self {
@@ -91,6 +181,43 @@ impl GameType {
         }
     }
 
+    /// Typical length of one round, in seconds, used to estimate play time.
+    pub fn typical_duration_secs(&self) -> u64 {
+        match self {
+            GameType::SpeedClicker => 10,
+            GameType::MemoryMatrix => 90,
+            GameType::ReactionStrike => 45,
+            GameType::MathBlitz => 60,
+            GameType::SnakeSprint => 120,
+        }
+    }
+
+    /// Whether a lower raw score is the better run (ReactionStrike scores
+    /// average reaction time).
+    pub fn lower_score_is_better(&self) -> bool {
+        matches!(self, GameType::ReactionStrike)
+    }
+
+    /// Whether raw score `score` is a better run of this game than `other`.
+    pub fn beats(&self, score: u64, other: u64) -> bool {
+        if self.lower_score_is_better() {
+            score < other
+        } else {
+            score > other
+        }
+    }
+
+    /// Width of one bucket in this game's score histogram.
+    pub fn histogram_bucket_width(&self) -> u64 {
+        match self {
+            GameType::SpeedClicker => 5,
+            GameType::MemoryMatrix => 1,
+            GameType::ReactionStrike => 25,
+            GameType::MathBlitz => 2,
+            GameType::SnakeSprint => 5,
+        }
+    }
+
     /// Get the display name for this game type.
     pub fn name(&self) -> &'static str {
         match self {
@@ -103,6 +230,22 @@ impl GameType {
     }
 }
 
+/// Reaction window for assisted ReactionStrike runs, in milliseconds.
+pub const ASSISTED_REACTION_WINDOW_MS: i64 = 2000;
+
+/// The scoring category a run competes in.
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize, async_graphql::Enum,
+)]
+pub enum ScoringCategory {
+    /// Standard rules.
+    #[default]
+    Standard,
+    /// Accessibility-adjusted rules (e.g. longer reaction windows), ranked
+    /// on separate leaderboards.
+    Assisted,
+}
+
 /// Calculate level from total XP.
 pub fn calculate_level(total_xp: u64) -> u32 {
     // Level formula: level = sqrt(xp / 100) + 1
@@ -110,16 +253,90 @@ pub fn calculate_level(total_xp: u64) -> u32 {
     ((total_xp as f64 / 100.0).sqrt() as u32).saturating_add(1)
 }
 
+/// Microseconds in a day.
+pub const MICROS_PER_DAY: u64 = 24 * 60 * 60 * 1_000_000;
+
+/// The UTC day (days since the Unix epoch) containing `timestamp`.
+pub fn day_index(timestamp: u64) -> u64 {
+    timestamp / MICROS_PER_DAY
+}
+
+/// Earliest UTC offset a player can choose, in minutes (UTC-12:00).
+pub const MIN_UTC_OFFSET_MINUTES: i32 = -12 * 60;
+
+/// Latest UTC offset a player can choose, in minutes (UTC+14:00).
+pub const MAX_UTC_OFFSET_MINUTES: i32 = 14 * 60;
+
+/// How long a player must wait between timezone changes, in microseconds
+/// (30 days).
+pub const TIMEZONE_COOLDOWN_MICROS: u64 = 30 * MICROS_PER_DAY;
+
+/// Longest the arcade can be put in maintenance mode at once, in
+/// microseconds (7 days).
+pub const MAX_MAINTENANCE_MICROS: u64 = 7 * MICROS_PER_DAY;
+
+/// A period during which the arcade is read-only, e.g. while it is being
+/// upgraded.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct MaintenanceWindow {
+    pub started_at: u64,
+    /// When the arcade leaves maintenance mode on its own.
+    pub ends_at: u64,
+}
+
+impl MaintenanceWindow {
+    /// Whether the arcade is read-only at `timestamp`.
+    pub fn is_active(&self, timestamp: u64) -> bool {
+        timestamp < self.ends_at
+    }
+}
+
+/// Per-player privacy flags.
+#[derive(
+    Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, SimpleObject, InputObject,
+)]
+#[graphql(input_name = "PrivacySettingsInput")]
+pub struct PrivacySettings {
+    /// Exclude the player from public leaderboards.
+    pub hide_from_leaderboard: bool,
+    /// Exclude the player's scores from public score feeds.
+    pub hide_score_history: bool,
+    /// Only show the profile to the player themselves and their friends.
+    /// Queries can't authenticate who is viewing, so this is a hint for
+    /// clients rather than access control.
+    pub friends_only_profile: bool,
+}
+
 /// A registered player in the arcade.
 #[derive(Clone, Debug, Serialize, Deserialize, SimpleObject, InputObject)]
 #[graphql(input_name = "PlayerInput")]
 pub struct Player {
     pub owner: AccountOwner,
+    /// The unique handle chosen at registration, changed only with
+    /// [`Operation::UpdateUsername`], or by the hub with
+    /// [`Message::ForceRename`] if another chain's player already held it.
     pub username: String,
+    /// Freely editable name shown in the UI.
+    pub display_name: String,
     pub total_xp: u64,
     pub level: u32,
     pub games_played: u64,
     pub registered_at: u64,
+    pub privacy: PrivacySettings,
+    /// Self-imposed daily play limits.
+    pub play_limits: limits::PlayLimits,
+    /// When the play limits were last changed, if ever.
+    pub play_limits_updated_at: Option<u64>,
+    /// The category the player's runs are scored in.
+    pub scoring_category: ScoringCategory,
+    /// The player's timezone, as minutes ahead of UTC. Daily limits,
+    /// circuits, streaks and kudos reset at the player's local midnight.
+    pub utc_offset_minutes: i32,
+    /// When the timezone was last changed, if ever.
+    pub utc_offset_updated_at: Option<u64>,
+    /// How the player's numbers and dates are presented, and which day
+    /// their week starts on.
+    pub locale: locale::LocaleSettings,
 }
 
 impl Player {
@@ -127,14 +344,29 @@ impl Player {
     pub fn new(owner: AccountOwner, username: String, timestamp: u64) -> Self {
         Self {
             owner,
+            display_name: username.clone(),
             username,
             total_xp: 0,
             level: 1,
             games_played: 0,
             registered_at: timestamp,
+            privacy: PrivacySettings::default(),
+            play_limits: limits::PlayLimits::default(),
+            play_limits_updated_at: None,
+            scoring_category: ScoringCategory::Standard,
+            utc_offset_minutes: 0,
+            utc_offset_updated_at: None,
+            locale: locale::LocaleSettings::default(),
         }
     }
 
+    /// The day (days since the Unix epoch) containing `timestamp` in the
+    /// player's timezone.
+    pub fn local_day(&self, timestamp: u64) -> u64 {
+        let offset = i64::from(self.utc_offset_minutes) * 60 * 1_000_000;
+        day_index(timestamp.saturating_add_signed(offset))
+    }
+
     /// Add XP to the player and update level.
     pub fn add_xp(&mut self, xp: u64) {
         self.total_xp = self.total_xp.saturating_add(xp);
@@ -145,6 +377,13 @@ impl Player {
     pub fn increment_games(&mut self) {
         self.games_played = self.games_played.saturating_add(1);
     }
+
+    /// Take back the XP and game of a removed run, and update level.
+    pub fn remove_run(&mut self, xp: u64) {
+        self.total_xp = self.total_xp.saturating_sub(xp);
+        self.level = calculate_level(self.total_xp);
+        self.games_played = self.games_played.saturating_sub(1);
+    }
 }
 
 /// A leaderboard entry for global rankings.
@@ -153,9 +392,13 @@ impl Player {
 pub struct LeaderboardEntry {
     pub wallet_address: AccountOwner,
     pub username: String,
+    pub display_name: String,
     pub total_xp: u64,
     pub level: u32,
     pub rank: u32,
+    pub scoring_category: ScoringCategory,
+    /// The value of the metric the leaderboard was ordered by.
+    pub metric_value: u64,
 }
 
 impl LeaderboardEntry {
@@ -164,9 +407,12 @@ impl LeaderboardEntry {
         Self {
             wallet_address: player.owner.clone(),
             username: player.username.clone(),
+            display_name: player.display_name.clone(),
             total_xp: player.total_xp,
             level: player.level,
             rank,
+            scoring_category: player.scoring_category,
+            metric_value: player.total_xp,
         }
     }
 }
@@ -175,6 +421,8 @@ impl LeaderboardEntry {
 #[derive(Clone, Debug, Serialize, Deserialize, SimpleObject, InputObject)]
 #[graphql(input_name = "GameScoreInput")]
 pub struct GameScore {
+    /// The score's ID on the chain it was played on. IDs are numbered per
+    /// chain, so a score is identified by this and its `chain_id`.
     pub id: u64,
     pub game_type: GameType,
     pub player: AccountOwner,
@@ -182,6 +430,61 @@ pub struct GameScore {
     pub xp_earned: u64,
     pub bonus_data: Option<u64>,
     pub timestamp: u64,
+    /// The kiosk device the score was submitted from, if any.
+    pub device_id: Option<String>,
+    /// The category the run was scored in.
+    pub category: ScoringCategory,
+    /// The chain the run was played on.
+    pub chain_id: ChainId,
+}
+
+impl GameScore {
+    /// The score's receipt: a hash of the whole record, which a player can
+    /// share to prove this exact run is theirs.
+    pub fn receipt(&self) -> CryptoHash {
+        CryptoHash::new(self)
+    }
+}
+
+impl BcsSignable<'_> for GameScore {}
+
+/// The player-supplied part of a score submission.
+#[derive(Clone, Debug, Serialize, Deserialize, InputObject)]
+pub struct ScoreSubmission {
+    pub game_type: GameType,
+    pub score: u64,
+    pub bonus_data: Option<u64>,
+    /// The kiosk device the score is submitted from, if any.
+    pub device_id: Option<String>,
+}
+
+/// Most scores one [`Operation::SubmitScores`] batch may carry.
+pub const MAX_SCORE_BATCH_SIZE: usize = 20;
+
+/// Latest scores kept for the recent activity feed.
+pub const RECENT_SCORES_CAPACITY: usize = 500;
+
+/// What one accepted run earned.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct ScoreResult {
+    /// XP earned, including any bonus for completing the day's full
+    /// circuit.
+    pub xp_earned: u64,
+    pub full_circuit_bonus: Option<u64>,
+    pub achievements_unlocked: Vec<achievements::Achievement>,
+    /// The accepted score's [`GameScore::receipt`].
+    pub receipt: CryptoHash,
+    /// The player's XP after the run.
+    pub total_xp: u64,
+    /// The player's level after the run.
+    pub level: u32,
+    /// Whether the run took the player up a level.
+    pub leveled_up: bool,
+    /// Whether the run beat the player's best in its game, by its scoring direction.
+    pub personal_best: bool,
+    /// The player's rank on the chain's leaderboard after the run, counting
+    /// only players with more XP ahead of them.
+    pub rank: u32,
 }
 
 /// A high score entry for a specific game.
@@ -189,6 +492,7 @@ pub struct GameScore {
 pub struct GameHighScoreEntry {
     pub player: AccountOwner,
     pub username: String,
+    pub display_name: String,
     pub score: u64,
     pub xp_earned: u64,
     pub timestamp: u64,
@@ -202,19 +506,666 @@ pub struct ArcadeStats {
     pub total_xp_earned: u64,
 }
 
-/// Operations that can be executed on the arcade hub.
+/// A player's profile together with their current rank.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct PlayerSnapshot {
+    pub player: Player,
+    pub rank: Option<i32>,
+    /// The player's XP this local day, week and month.
+    pub xp: locale::XpBuckets,
+}
+
+/// Everything the home screen shows, in one response.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct ArcadeSummary {
+    pub stats: ArcadeStats,
+    pub top_players: Vec<LeaderboardEntry>,
+    pub latest_milestone: Option<milestones::Milestone>,
+    /// The crossover events running now, highest precedence first.
+    pub current_events: Vec<crossovers::CrossoverEvent>,
+    /// Today's daily challenge, in the requesting player's timezone if a
+    /// wallet was supplied and UTC otherwise.
+    pub daily_challenge: challenges::DailyChallenge,
+    /// The requesting player's snapshot, if a wallet was supplied.
+    pub me: Option<PlayerSnapshot>,
+}
+
+/// The outcome of a submission dry run.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct SubmissionCheck {
+    /// Whether the submission would currently be accepted.
+    pub accepted: bool,
+    /// Why it would be rejected.
+    pub error: Option<String>,
+    /// The XP it would earn, if accepted.
+    pub xp_earned: Option<u64>,
+}
+
+/// Chain time, for correcting client clock skew.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct ChainTime {
+    /// The current system time of the chain, in microseconds.
+    pub now: u64,
+    /// When the hub's state last changed, if this chain is the hub.
+    pub hub_last_known: Option<u64>,
+}
+
+/// The GraphQL API version this service exposes by default.
+///
+/// Version 1 is the original query set; version 2 adds every query field
+/// introduced since. Clients pick a version with the `apiVersion` request
+/// extension.
+pub const API_VERSION: u32 = 2;
+
+/// The oldest GraphQL API version this service still answers.
+pub const MIN_API_VERSION: u32 = 1;
+
+/// The API versions a query was answered with.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct ApiVersion {
+    /// The newest version the service supports.
+    pub current: u32,
+    /// The oldest version the service supports.
+    pub minimum: u32,
+    /// The version this request was answered with.
+    pub requested: u32,
+}
+
+/// One page of the leaderboard, read from a stored snapshot.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct LeaderboardPage {
+    pub entries: Vec<LeaderboardEntry>,
+    /// The snapshot the page was read from.
+    pub snapshot_id: u64,
+    pub taken_at: u64,
+    /// Pass this to fetch the next page; `None` on the last page.
+    pub next_page_token: Option<String>,
+    /// Whether the snapshot stops short of the full leaderboard, keeping
+    /// only the top [`snapshots::MAX_SNAPSHOT_ENTRIES`] players, so its last
+    /// page isn't the end of the leaderboard.
+    pub truncated: bool,
+}
+
+/// One page of players in storage order, for indexers syncing every player.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct PlayersPage {
+    pub players: Vec<Player>,
+    /// Pass this as `after_owner` to fetch the next page; `None` on the last
+    /// page.
+    pub next_after_owner: Option<AccountOwner>,
+}
+
+/// One page of scores in ID order, for indexers syncing every score.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct ScoresPage {
+    pub scores: Vec<GameScore>,
+    /// Pass this as `after_id` to fetch the next page; `None` on the last
+    /// page.
+    pub next_after_id: Option<u64>,
+}
+
+/// Where a page of a paginated list ends.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, SimpleObject)]
+pub struct PageInfo {
+    /// Pass this as `after` to fetch the next page; `None` on the last page.
+    pub end_cursor: Option<String>,
+    pub has_next_page: bool,
+}
+
+impl PageInfo {
+    /// The page info for a page ending at `cursor`, if more follow.
+    pub fn new(cursor: Option<String>, has_next_page: bool) -> Self {
+        Self {
+            end_cursor: cursor.filter(|_| has_next_page),
+            has_next_page,
+        }
+    }
+}
+
+/// One page of players.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct PlayerConnection {
+    pub nodes: Vec<Player>,
+    pub page_info: PageInfo,
+}
+
+/// One page of scores.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct ScoreConnection {
+    pub nodes: Vec<GameScore>,
+    pub page_info: PageInfo,
+}
+
+/// One page of leaderboard entries.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct LeaderboardConnection {
+    pub nodes: Vec<LeaderboardEntry>,
+    pub page_info: PageInfo,
+}
+
+/// A range of the score log, for replaying history.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct ScoreLogRange {
+    /// The log position of the first score.
+    pub start: u64,
+    /// Scores in log order; `None` where a score is hidden.
+    pub scores: Vec<Option<GameScore>>,
+    /// The number of scores in the whole log.
+    pub length: u64,
+}
+
+/// Operations that can be executed on the arcade hub. Wallets encode them
+/// by variant index, so new operations are only ever appended.
 #[derive(Debug, Clone, Serialize, Deserialize, GraphQLMutationRoot)]
 pub enum Operation {
-    /// Register a new player with a username.
+    /// Register a new player with a username. A version 2 operation may
+    /// also present an operator-signed voucher that reserved it. If a
+    /// player on another chain already holds the name on the hub, the hub
+    /// renames the new player to a fallback handle.
     RegisterPlayer { username: String },
-    /// Submit a game score.
+    /// Submit a game score. A version 2 operation may also carry the kiosk
+    /// device, the game server's signature, required if a score signer is
+    /// registered, and the game session the score closes;
+    /// [`Operation::EndGame`] names the session itself.
     SubmitScore {
         game_type: GameType,
         score: u64,
         bonus_data: Option<u64>,
     },
-    /// Update a player's username.
+    /// Change the signer's username handle to one no other player holds,
+    /// freeing the old one. If the hub already holds the name for a player
+    /// on another chain, it changes the handle back.
     UpdateUsername { new_username: String },
+    /// Update a player's privacy settings.
+    UpdatePrivacy { privacy: PrivacySettings },
+    /// Shadow-ban a suspected bot (admin only). Submissions are still
+    /// accepted but hidden from public leaderboards and aggregates, as are
+    /// the ones made before the ban.
+    ShadowBanPlayer { wallet_address: AccountOwner },
+    /// Lift a shadow-ban (admin only).
+    ClearShadowBan { wallet_address: AccountOwner },
+    /// Ban a player (admin only). Their submissions are rejected and they
+    /// are left off leaderboards on the chain the ban is set on. Bans set
+    /// on the hub are published for every other chain to take up.
+    BanPlayer { wallet_address: AccountOwner },
+    /// Lift a player's ban (admin only).
+    UnbanPlayer { wallet_address: AccountOwner },
+    /// Appeal the recorded score with the given receipt, depositing the
+    /// configured bond. Appeals are taken up and resolved on the hub, so
+    /// off the hub the bond is moved to the hub's escrow.
+    FileAppeal { receipt: CryptoHash, reason: String },
+    /// Resolve a pending appeal (admin only), refunding or forfeiting its bond.
+    ResolveAppeal { appeal_id: u64, upheld: bool },
+    /// Remove a fraudulent score, taking its XP and game back from the
+    /// player and the arcade totals, along with what it earned towards
+    /// guilds, seasons, challenges, raffles and boss battles (admin only).
+    /// The hub refuses to remove a score that counted towards a drawn
+    /// raffle or a defeated boss, as it is paid out. On the chain the score
+    /// was played on the hub is sent the correction; on the hub, the
+    /// removal of another chain's score is sent to that chain, so the
+    /// player's own record loses it too.
+    RemoveScore { receipt: CryptoHash },
+    /// Update kiosk-mode settings (admin only). Settings made on the hub
+    /// apply on every chain.
+    ConfigureKiosk { config: kiosk::KioskConfig },
+    /// Update the underplayed-game bonus settings (admin only).
+    ConfigureBalance { config: balance::BalanceConfig },
+    /// Replace the live scoring config (admin only).
+    ConfigureScoring { config: scoring::ScoringConfig },
+    /// Try out a scoring config alongside the live one, tallying the XP
+    /// runs would earn under it without awarding it (admin only).
+    StartScoringCanary { config: scoring::ScoringConfig },
+    /// End the running scoring canary, making its config live if
+    /// `promote` (admin only).
+    EndScoringCanary { promote: bool },
+    /// Replace the daily windows of UTC time when `game_type` is closed for
+    /// submissions; none keeps it open around the clock (admin only).
+    SetSubmissionWindows {
+        game_type: GameType,
+        closed_windows: Vec<submission_windows::ClosedWindow>,
+    },
+    /// Update the daily soft quotas on operations (admin only).
+    SetUsageQuota { quota: usage::UsageQuota },
+    /// Update the smurf detection thresholds (admin only).
+    ConfigureSmurfDetection {
+        config: smurfing::SmurfDetectionConfig,
+    },
+    /// Take a reviewed account off the moderation queue (admin only),
+    /// withdrawing the humanity challenge issued for the flag.
+    ResolveModerationFlag { flag_id: u64 },
+    /// Update the humanity check settings (admin only).
+    ConfigureHumanityChecks {
+        config: humanity::HumanityCheckConfig,
+    },
+    /// Challenge an account to prove it is human (admin only). Its
+    /// submissions on this chain are rejected until it completes the
+    /// challenge; a challenge it already had is replaced. Challenges issued
+    /// on the hub are taken up by every chain, and the hub drops the
+    /// player's synced runs until the challenge is cleared.
+    IssueHumanityChallenge { wallet_address: AccountOwner },
+    /// Complete the signer's humanity challenge with `verifier`'s
+    /// hex-encoded BCS `AccountSignature` over a
+    /// [`humanity::HumanityProof`]. The verifier must be a second wallet of
+    /// the player's. A challenge the hub issued is completed on the
+    /// player's own chain, which sends the proof to the hub.
+    CompleteHumanityChallenge {
+        verifier: AccountOwner,
+        signature: String,
+    },
+    /// Schedule a limited-time crossover event (admin only).
+    ScheduleCrossover {
+        event: crossovers::CrossoverEventSpec,
+    },
+    /// Cancel a crossover event, whether or not it has started (admin only).
+    CancelCrossover { event_id: u64 },
+    /// Reject all further submissions from a device (admin only). A ban set
+    /// on the hub applies on every chain.
+    BanDevice { device_id: String },
+    /// Lift a device ban (admin only).
+    UnbanDevice { device_id: String },
+    /// Allow an account to relay attested submissions (admin only).
+    AuthorizeGateway { gateway: AccountOwner },
+    /// Revoke a gateway's relay permission (admin only).
+    RevokeGateway { gateway: AccountOwner },
+    /// Relay a batch of player-attested submissions from a kiosk gateway.
+    /// The batch is applied atomically.
+    RelayScores {
+        submissions: Vec<kiosk::GatewaySubmission>,
+    },
+    /// Set the operator key that signs registration vouchers (admin only).
+    SetVoucherSigner { signer: Option<AccountOwner> },
+    /// Make the arcade read-only until `until`, or leave maintenance mode
+    /// early with `None` (admin only). Every other operation is rejected
+    /// while it lasts; queries and cross-chain syncs carry on.
+    SetMaintenanceMode { until: Option<u64> },
+    /// Hold a username ahead of registration. Only the admin may reserve on
+    /// behalf of another owner. Each unregistered owner holds at most one
+    /// reservation, and can't take another until it expires.
+    ReserveUsername {
+        username: String,
+        reserved_for: Option<AccountOwner>,
+    },
+    /// Block usernames containing `username` (admin only), such as
+    /// profanity or impersonations like "admin". Blocked names can still be
+    /// reserved by the admin, and registered by whoever holds the
+    /// reservation.
+    AddBlockedUsername { username: String },
+    /// Unblock a term on the username blocklist (admin only).
+    RemoveBlockedUsername { username: String },
+    /// Put a premium username up for a sealed-bid auction (admin only).
+    StartUsernameAuction {
+        auction: name_auctions::UsernameAuctionSpec,
+    },
+    /// Place a sealed bid on a username auction, moving `deposit` from the
+    /// signer's balance into escrow. The deposit must cover the bid.
+    BidOnUsername {
+        auction_id: u64,
+        commitment: CryptoHash,
+        deposit: Amount,
+    },
+    /// Reveal a sealed bid once bidding has closed.
+    RevealUsernameBid {
+        auction_id: u64,
+        amount: Amount,
+        salt: String,
+    },
+    /// Settle a username auction once reveals have closed, paying out
+    /// deposits and reserving the username for the winner.
+    SettleUsernameAuction { auction_id: u64 },
+    /// Delete the signer's account, releasing their username once the grace
+    /// period has passed. The signer must leave their guild first.
+    DeleteAccount,
+    /// Flag an account that hasn't played for the configured inactivity
+    /// period, releasing its username once the grace period has passed
+    /// unless it plays again (admin only).
+    FlagInactiveAccount { player: AccountOwner },
+    /// Update the inactivity and grace periods for username recycling
+    /// (admin only).
+    ConfigureUsernameRecycling {
+        config: usernames::UsernameRecyclingConfig,
+    },
+    /// Set self-imposed daily play limits. Limits can be tightened at any
+    /// time but only loosened or removed after a cooldown.
+    SetPlayLimits { limits: limits::PlayLimits },
+    /// Set the timezone daily resets follow, as minutes ahead of UTC. Can
+    /// be changed once every 30 days.
+    SetTimezone { utc_offset_minutes: i32 },
+    /// Set the locale clients format the player's numbers and dates for,
+    /// and the day their week starts on.
+    SetLocale { locale: locale::LocaleSettings },
+    /// Opt in to or out of the assisted scoring category for future runs.
+    SetScoringCategory { category: ScoringCategory },
+    /// Store the current public leaderboard as a snapshot (admin only).
+    SnapshotLeaderboard,
+    /// Check the next chunk of one section of the state's invariants,
+    /// publishing any violations found as events (admin only).
+    CheckInvariants {
+        section: invariants::InvariantSection,
+    },
+    /// Rebuild the next chunk of a group of derived views, totals from
+    /// journal checkpoint `checkpoint` or from scratch (admin only, in
+    /// maintenance mode). The checkpoint is only read when a replay starts.
+    ReplayJournal {
+        target: journal::ReplayTarget,
+        checkpoint: Option<u64>,
+    },
+    /// Keep only the top `top_k` entries of archived snapshots and season
+    /// standings, or all of them if `None` (admin only). Older snapshots are
+    /// trimmed gradually.
+    SetHistoryRetention { top_k: Option<u32> },
+    /// Start the first season, lasting `length_days`; each season rolls over
+    /// into the next of the same length when it ends (admin only).
+    StartSeason { length_days: u32 },
+    /// End the current season now, archiving its standings, and start the
+    /// next (admin only).
+    RollOverSeason,
+    /// Give a season yet to end a theme and cosmetic reward set, granted
+    /// by final tier when it rolls over (admin only).
+    SetSeasonTheme {
+        season: u32,
+        theme: seasons::SeasonTheme,
+    },
+    /// Schedule a raffle on the hub, moving its prize from the signer's
+    /// balance into escrow (admin only).
+    ScheduleRaffle { raffle: raffles::RaffleSpec },
+    /// Draw a raffle once it has ended, revealing its committed secret, and
+    /// pay out the prize. Anyone can draw; without the secret only once
+    /// [`raffles::RAFFLE_REVEAL_WINDOW_MICROS`] have passed since the end.
+    /// Scores synced to the hub after the draw no longer earn tickets.
+    DrawRaffle {
+        raffle_id: u64,
+        secret: Option<String>,
+    },
+    /// Schedule a cooperative boss battle on the hub (admin only).
+    ScheduleBossBattle { battle: bosses::BossBattleSpec },
+    /// Refuse follows, friend requests, kudos, mentorship offers and guild
+    /// invites from a player.
+    BlockPlayer { target: AccountOwner },
+    /// Lift a block.
+    UnblockPlayer { target: AccountOwner },
+    /// Buy a shield that covers one missed day of the signer's daily streak,
+    /// for [`streaks::SHIELD_PRICE`].
+    BuyStreakShield,
+    /// Set a personal goal, checked after every run.
+    AddGoal { goal: goals::GoalSpec },
+    /// Remove a goal, whatever its status.
+    RemoveGoal { goal_id: u64 },
+    /// Follow a player to see their personal bests in the following feed.
+    Follow { target: AccountOwner },
+    /// Stop following a player.
+    Unfollow { target: AccountOwner },
+    /// Add a player as a friend, or accept their friend request. Players
+    /// are friends once each has added the other.
+    AddFriend { target: AccountOwner },
+    /// Remove a friend or withdraw a friend request.
+    RemoveFriend { target: AccountOwner },
+    /// Send kudos to another player, e.g. after a duel. Players can send
+    /// [`social::DAILY_KUDOS`] per UTC day, at most one to any one player.
+    GiveKudos { recipient: AccountOwner },
+    /// React with an emote to a hall-of-fame record or a top leaderboard
+    /// entry. Each emote can be left once per target, up to
+    /// [`reactions::MAX_EMOTES_PER_TARGET`] per target.
+    React {
+        target: reactions::ReactionTarget,
+        emote: reactions::Emote,
+    },
+    /// Take back an emote left on a target.
+    RemoveReaction {
+        target: reactions::ReactionTarget,
+        emote: reactions::Emote,
+    },
+    /// Offer to mentor a newcomer, who must accept before the mentorship
+    /// starts.
+    OfferMentorship { apprentice: AccountOwner },
+    /// Accept a pending mentorship offer.
+    AcceptMentorship { mentor: AccountOwner },
+    /// End the mentorship with `counterpart`, or withdraw or decline a
+    /// pending offer between the two, from either side.
+    EndMentorship { counterpart: AccountOwner },
+    /// Found a guild led by the signer.
+    CreateGuild { name: String },
+    /// Invite a player to the signer's guild (officers and the leader).
+    InviteToGuild { player: AccountOwner },
+    /// Join a guild that has invited the signer.
+    JoinGuild { guild_id: u64 },
+    /// Leave the signer's guild. The leader can only leave as the last
+    /// member, which disbands the guild.
+    LeaveGuild,
+    /// Remove a lower-ranked member from the signer's guild (officers and
+    /// the leader).
+    KickFromGuild { member: AccountOwner },
+    /// Rename the signer's guild (leader only).
+    RenameGuild { name: String },
+    /// Change a member's role (leader only). Making a member leader hands
+    /// over the leadership.
+    SetGuildRole {
+        member: AccountOwner,
+        role: guilds::GuildRole,
+    },
+    /// Set or clear the signer's guild announcement (officers and the
+    /// leader).
+    SetGuildAnnouncement { announcement: Option<String> },
+    /// Register a shareable code that lets up to `max_uses` players join the
+    /// signer's guild within `duration_hours` (officers and the leader).
+    /// Only the code's hash, from `inviteCodeHash`, is sent.
+    CreateInviteCode {
+        code_hash: CryptoHash,
+        max_uses: u32,
+        duration_hours: u32,
+    },
+    /// Join a guild with an invite code.
+    RedeemInviteCode { code: String },
+    /// Revoke an invite code the signer created.
+    RevokeInviteCode { code_hash: CryptoHash },
+    /// Put a question to the signer's guild, open for `duration_hours`
+    /// (officers and the leader).
+    CreateGuildPoll {
+        question: String,
+        options: Vec<String>,
+        duration_hours: u32,
+    },
+    /// Vote in one of the signer's guild's open polls, replacing any earlier
+    /// vote. `option` indexes the poll's options.
+    VoteInGuildPoll { poll_id: u64, option: u32 },
+    /// Choose how the signer's guild's prizes are split (leader only).
+    SetGuildPayoutRule { rule: guild_prizes::GuildPayoutRule },
+    /// Award a prize to a guild on the hub (admin only), moving it from the
+    /// signer's balance into escrow and splitting it between the guild's
+    /// members by its payout rule.
+    AwardGuildPrize {
+        guild_id: u64,
+        amount: Amount,
+        note: String,
+    },
+    /// Collect the signer's share of a guild prize on the hub.
+    ClaimGuildPrize { prize_id: u64 },
+    /// Lock the signer's record and commit to a snapshot of it, so the old
+    /// deployment's admin can sign it over for import elsewhere.
+    ExportProgress,
+    /// Set the key of the deployment admin whose signed exports are accepted
+    /// by [`Operation::ImportProgress`] (admin only).
+    SetMigrationSource { admin: Option<AccountOwner> },
+    /// Add the XP and games of a record exported from another deployment to
+    /// the signer's, with the old admin's signature over the snapshot. Off
+    /// the hub, the import is credited once the hub confirms the snapshot
+    /// wasn't imported on any chain before.
+    ImportProgress {
+        snapshot: migration::ProgressSnapshot,
+        signature: String,
+    },
+    /// Forward arcade events to the calling application on `chain_id`. Only
+    /// callable by another application.
+    RegisterEventBridge { chain_id: ChainId },
+    /// Stop forwarding events to the calling application on `chain_id`.
+    UnregisterEventBridge { chain_id: ChainId },
+    /// Take the events forwarded to the calling application on this chain.
+    DrainBridgedEvents,
+    /// Apply the syncs `chain_id` publishes on its [`SYNC_EVENT_STREAM`],
+    /// as the hub does with sync messages (admin only on the hub). A sync
+    /// that also arrives in a message is applied once, by whichever comes
+    /// first.
+    SubscribeToSyncs { chain_id: ChainId },
+    /// Stop applying the syncs `chain_id` publishes (admin only on the
+    /// hub).
+    UnsubscribeFromSyncs { chain_id: ChainId },
+    /// Submit a community game for the admin's review on the hub, as its
+    /// developer.
+    SubmitCommunityGame {
+        game: community_games::CommunityGameSpec,
+    },
+    /// Approve or reject a community game waiting for review (admin only).
+    ReviewCommunityGame { game_id: u64, approved: bool },
+    /// Submit a run of an approved community game, checked and ranked on
+    /// the hub.
+    SubmitCommunityScore { game_id: u64, score: u64 },
+    /// Update a player's display name. The username handle is unaffected.
+    UpdateDisplayName { display_name: String },
+    /// Start a game, opening a session whose score must be submitted before
+    /// it times out. Any session the player already had open is set aside,
+    /// where [`Operation::SubmitScores`] can still close it. A game started
+    /// on a kiosk device must be submitted from it.
+    StartGame {
+        game_type: GameType,
+        device_id: Option<String>,
+    },
+    /// Submit the score of an open game session, closing it.
+    EndGame {
+        session_id: u64,
+        score: u64,
+        bonus_data: Option<u64>,
+        device_id: Option<String>,
+        signature: Option<score_signing::ScoreSignature>,
+    },
+    /// Submit the player's one run of today's daily challenge, closing its
+    /// game session. The run also counts as a regular score.
+    SubmitDailyChallenge {
+        session_id: u64,
+        submission: ScoreSubmission,
+        signature: Option<score_signing::ScoreSignature>,
+    },
+    /// Submit several finished runs at once, such as games played in a row
+    /// without submitting each. Each run closes the game session at the
+    /// same index of `session_ids`, which may have been set aside by
+    /// starting another game. If a score signer is registered, each run
+    /// needs the game server's signature at the same index of `signatures`
+    /// instead, and a session is optional. The batch is applied only if
+    /// every run is accepted.
+    SubmitScores {
+        submissions: Vec<ScoreSubmission>,
+        signatures: Vec<score_signing::ScoreSignature>,
+        session_ids: Vec<u64>,
+    },
+}
+
+impl Operation {
+    /// The operation's name, for logs.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Operation::RegisterPlayer { .. } => "RegisterPlayer",
+            Operation::SubmitScore { .. } => "SubmitScore",
+            Operation::SubmitScores { .. } => "SubmitScores",
+            Operation::StartGame { .. } => "StartGame",
+            Operation::EndGame { .. } => "EndGame",
+            Operation::SubmitDailyChallenge { .. } => "SubmitDailyChallenge",
+            Operation::UpdateDisplayName { .. } => "UpdateDisplayName",
+            Operation::UpdateUsername { .. } => "UpdateUsername",
+            Operation::UpdatePrivacy { .. } => "UpdatePrivacy",
+            Operation::ShadowBanPlayer { .. } => "ShadowBanPlayer",
+            Operation::ClearShadowBan { .. } => "ClearShadowBan",
+            Operation::BanPlayer { .. } => "BanPlayer",
+            Operation::UnbanPlayer { .. } => "UnbanPlayer",
+            Operation::FileAppeal { .. } => "FileAppeal",
+            Operation::ResolveAppeal { .. } => "ResolveAppeal",
+            Operation::RemoveScore { .. } => "RemoveScore",
+            Operation::ConfigureKiosk { .. } => "ConfigureKiosk",
+            Operation::ConfigureBalance { .. } => "ConfigureBalance",
+            Operation::ConfigureScoring { .. } => "ConfigureScoring",
+            Operation::StartScoringCanary { .. } => "StartScoringCanary",
+            Operation::EndScoringCanary { .. } => "EndScoringCanary",
+            Operation::SetSubmissionWindows { .. } => "SetSubmissionWindows",
+            Operation::SetUsageQuota { .. } => "SetUsageQuota",
+            Operation::ConfigureSmurfDetection { .. } => "ConfigureSmurfDetection",
+            Operation::ResolveModerationFlag { .. } => "ResolveModerationFlag",
+            Operation::ConfigureHumanityChecks { .. } => "ConfigureHumanityChecks",
+            Operation::IssueHumanityChallenge { .. } => "IssueHumanityChallenge",
+            Operation::CompleteHumanityChallenge { .. } => "CompleteHumanityChallenge",
+            Operation::ScheduleCrossover { .. } => "ScheduleCrossover",
+            Operation::CancelCrossover { .. } => "CancelCrossover",
+            Operation::BanDevice { .. } => "BanDevice",
+            Operation::UnbanDevice { .. } => "UnbanDevice",
+            Operation::AuthorizeGateway { .. } => "AuthorizeGateway",
+            Operation::RevokeGateway { .. } => "RevokeGateway",
+            Operation::RelayScores { .. } => "RelayScores",
+            Operation::SetVoucherSigner { .. } => "SetVoucherSigner",
+            Operation::SetMaintenanceMode { .. } => "SetMaintenanceMode",
+            Operation::ReserveUsername { .. } => "ReserveUsername",
+            Operation::AddBlockedUsername { .. } => "AddBlockedUsername",
+            Operation::RemoveBlockedUsername { .. } => "RemoveBlockedUsername",
+            Operation::StartUsernameAuction { .. } => "StartUsernameAuction",
+            Operation::BidOnUsername { .. } => "BidOnUsername",
+            Operation::RevealUsernameBid { .. } => "RevealUsernameBid",
+            Operation::SettleUsernameAuction { .. } => "SettleUsernameAuction",
+            Operation::DeleteAccount => "DeleteAccount",
+            Operation::FlagInactiveAccount { .. } => "FlagInactiveAccount",
+            Operation::ConfigureUsernameRecycling { .. } => "ConfigureUsernameRecycling",
+            Operation::SetPlayLimits { .. } => "SetPlayLimits",
+            Operation::SetScoringCategory { .. } => "SetScoringCategory",
+            Operation::SetTimezone { .. } => "SetTimezone",
+            Operation::SetLocale { .. } => "SetLocale",
+            Operation::SnapshotLeaderboard => "SnapshotLeaderboard",
+            Operation::CheckInvariants { .. } => "CheckInvariants",
+            Operation::ReplayJournal { .. } => "ReplayJournal",
+            Operation::SetHistoryRetention { .. } => "SetHistoryRetention",
+            Operation::StartSeason { .. } => "StartSeason",
+            Operation::RollOverSeason => "RollOverSeason",
+            Operation::SetSeasonTheme { .. } => "SetSeasonTheme",
+            Operation::ScheduleRaffle { .. } => "ScheduleRaffle",
+            Operation::DrawRaffle { .. } => "DrawRaffle",
+            Operation::ScheduleBossBattle { .. } => "ScheduleBossBattle",
+            Operation::BlockPlayer { .. } => "BlockPlayer",
+            Operation::UnblockPlayer { .. } => "UnblockPlayer",
+            Operation::BuyStreakShield => "BuyStreakShield",
+            Operation::AddGoal { .. } => "AddGoal",
+            Operation::RemoveGoal { .. } => "RemoveGoal",
+            Operation::Follow { .. } => "Follow",
+            Operation::Unfollow { .. } => "Unfollow",
+            Operation::AddFriend { .. } => "AddFriend",
+            Operation::RemoveFriend { .. } => "RemoveFriend",
+            Operation::GiveKudos { .. } => "GiveKudos",
+            Operation::React { .. } => "React",
+            Operation::RemoveReaction { .. } => "RemoveReaction",
+            Operation::OfferMentorship { .. } => "OfferMentorship",
+            Operation::AcceptMentorship { .. } => "AcceptMentorship",
+            Operation::EndMentorship { .. } => "EndMentorship",
+            Operation::CreateGuild { .. } => "CreateGuild",
+            Operation::InviteToGuild { .. } => "InviteToGuild",
+            Operation::JoinGuild { .. } => "JoinGuild",
+            Operation::LeaveGuild => "LeaveGuild",
+            Operation::KickFromGuild { .. } => "KickFromGuild",
+            Operation::RenameGuild { .. } => "RenameGuild",
+            Operation::SetGuildRole { .. } => "SetGuildRole",
+            Operation::SetGuildAnnouncement { .. } => "SetGuildAnnouncement",
+            Operation::CreateInviteCode { .. } => "CreateInviteCode",
+            Operation::RedeemInviteCode { .. } => "RedeemInviteCode",
+            Operation::RevokeInviteCode { .. } => "RevokeInviteCode",
+            Operation::CreateGuildPoll { .. } => "CreateGuildPoll",
+            Operation::VoteInGuildPoll { .. } => "VoteInGuildPoll",
+            Operation::SetGuildPayoutRule { .. } => "SetGuildPayoutRule",
+            Operation::AwardGuildPrize { .. } => "AwardGuildPrize",
+            Operation::ClaimGuildPrize { .. } => "ClaimGuildPrize",
+            Operation::ExportProgress => "ExportProgress",
+            Operation::SetMigrationSource { .. } => "SetMigrationSource",
+            Operation::ImportProgress { .. } => "ImportProgress",
+            Operation::RegisterEventBridge { .. } => "RegisterEventBridge",
+            Operation::UnregisterEventBridge { .. } => "UnregisterEventBridge",
+            Operation::DrainBridgedEvents => "DrainBridgedEvents",
+            Operation::SubscribeToSyncs { .. } => "SubscribeToSyncs",
+            Operation::UnsubscribeFromSyncs { .. } => "UnsubscribeFromSyncs",
+            Operation::SubmitCommunityGame { .. } => "SubmitCommunityGame",
+            Operation::ReviewCommunityGame { .. } => "ReviewCommunityGame",
+            Operation::SubmitCommunityScore { .. } => "SubmitCommunityScore",
+        }
+    }
 }
 
 /// Response from contract operations.
@@ -222,12 +1173,206 @@ pub enum Operation {
 pub enum ArcadeResponse {
     /// Player was registered successfully.
     PlayerRegistered,
-    /// Score was submitted successfully with XP earned.
-    ScoreSubmitted { xp_earned: u64 },
-    /// Username was updated successfully.
+    /// Score was submitted successfully with XP earned, including any bonus
+    /// for completing the day's full circuit, and the achievements the run
+    /// unlocked, and the accepted score's receipt. The rest describes the
+    /// player after the run, as in [`ScoreResult`].
+    ScoreSubmitted {
+        xp_earned: u64,
+        full_circuit_bonus: Option<u64>,
+        achievements_unlocked: Vec<achievements::Achievement>,
+        receipt: CryptoHash,
+        total_xp: u64,
+        level: u32,
+        leveled_up: bool,
+        personal_best: bool,
+        rank: u32,
+    },
+    /// A batch of scores was submitted, with what each run earned in
+    /// submission order.
+    ScoresSubmitted { results: Vec<ScoreResult> },
+    /// A game session was opened with the given ID and seed.
+    GameStarted { session_id: u64, seed: u64 },
+    /// Display name was updated successfully.
+    DisplayNameUpdated,
+    /// Username handle was changed successfully.
     UsernameUpdated,
-    /// Operation failed with an error.
-    Error(String),
+    /// Privacy settings were updated successfully.
+    PrivacyUpdated,
+    /// A player's shadow-ban flag was changed.
+    ShadowBanUpdated,
+    /// A player's ban flag was changed.
+    PlayerBanUpdated,
+    /// An appeal was filed with the given ID, or sent to the hub, which
+    /// numbers it, if `None`.
+    AppealFiled { appeal_id: Option<u64> },
+    /// An appeal was resolved.
+    AppealResolved,
+    /// A score was removed.
+    ScoreRemoved,
+    /// The removal of a score was sent to the chain it was played on,
+    /// which syncs the correction back to the hub.
+    ScoreRemovalForwarded { chain_id: ChainId },
+    /// Kiosk-mode settings were updated.
+    KioskConfigured,
+    /// Underplayed-game bonus settings were updated.
+    BalanceConfigured,
+    /// The live scoring config was replaced.
+    ScoringConfigured,
+    /// A scoring canary was started.
+    ScoringCanaryStarted,
+    /// The scoring canary was ended.
+    ScoringCanaryEnded,
+    /// A game's closed windows were replaced.
+    SubmissionWindowsUpdated,
+    /// The daily soft quotas were updated.
+    UsageQuotaUpdated,
+    /// Smurf detection thresholds were updated.
+    SmurfDetectionConfigured,
+    /// A moderation flag was taken off the queue.
+    ModerationFlagResolved,
+    /// The humanity check settings were updated.
+    HumanityChecksConfigured,
+    /// A humanity challenge was issued with the given ID.
+    HumanityChallengeIssued { challenge_id: u64 },
+    /// The signer's humanity challenge was completed.
+    HumanityChallengeCompleted,
+    /// The proof for a humanity challenge the hub issued was sent there;
+    /// the challenge is lifted once the hub accepts it.
+    HumanityProofForwarded,
+    /// A crossover event was scheduled with the given ID.
+    CrossoverScheduled { event_id: u64 },
+    /// A crossover event was cancelled.
+    CrossoverCancelled,
+    /// A device's ban flag was changed.
+    DeviceBanUpdated,
+    /// A gateway was authorized or revoked.
+    GatewayUpdated,
+    /// A relayed batch was applied, with the XP earned by each item.
+    ScoresRelayed { xp_earned: Vec<u64> },
+    /// The voucher signer was updated.
+    VoucherSignerUpdated,
+    /// Maintenance mode was entered or left.
+    MaintenanceModeUpdated,
+    /// A username was reserved until the given timestamp.
+    ReservationCreated { expires_at: u64 },
+    /// The username blocklist was updated.
+    UsernameBlocklistUpdated,
+    /// A username auction was started with the given ID.
+    UsernameAuctionStarted { auction_id: u64 },
+    /// A sealed bid was placed.
+    UsernameBidPlaced,
+    /// A sealed bid was revealed.
+    UsernameBidRevealed,
+    /// A username auction was settled; `winner` is `None` if no bid at or
+    /// above the reserve price was revealed.
+    UsernameAuctionSettled { winner: Option<AccountOwner> },
+    /// The account was deleted; its username is recyclable from the given
+    /// timestamp.
+    AccountDeleted { recyclable_at: u64 },
+    /// The account was flagged as inactive; its username is recyclable from
+    /// the given timestamp unless it plays again.
+    AccountFlaggedInactive { recyclable_at: u64 },
+    /// The username recycling periods were updated.
+    UsernameRecyclingConfigured,
+    /// A leaderboard snapshot was stored with the given ID.
+    SnapshotTaken { snapshot_id: u64 },
+    /// A chunk of an invariant section was checked; `checked` and
+    /// `violations` count the section's pass so far, which is `complete`
+    /// once it reached the end.
+    InvariantsChecked {
+        checked: u64,
+        violations: u32,
+        complete: bool,
+    },
+    /// A chunk of derived views was rebuilt; `replayed` is how far into its
+    /// source the replay has got, which is `complete` once it reached the
+    /// end.
+    JournalReplayed { replayed: u64, complete: bool },
+    /// The leaderboard history retention limit was changed.
+    HistoryRetentionUpdated,
+    /// A season started, by rollover or for the first time.
+    SeasonStarted { season: u32 },
+    /// A season's theme was set.
+    SeasonThemeSet,
+    /// A raffle was scheduled with the given ID.
+    RaffleScheduled { raffle_id: u64 },
+    /// A raffle was drawn; `winner` is `None` if nobody earned a ticket.
+    RaffleDrawn { winner: Option<AccountOwner> },
+    /// A boss battle was scheduled with the given ID.
+    BossBattleScheduled { battle_id: u64 },
+    /// The player's blocklist was changed.
+    BlocklistUpdated,
+    /// A streak shield was bought, leaving the player with `shields`.
+    StreakShieldBought { shields: u32 },
+    /// A goal was set with the given ID.
+    GoalAdded { goal_id: u64 },
+    /// A goal was removed.
+    GoalRemoved,
+    /// The player's follows were changed.
+    FollowsUpdated,
+    /// The player's friends were changed.
+    FriendsUpdated,
+    /// Kudos were sent.
+    KudosSent,
+    /// An emote was left on or taken back from a target.
+    ReactionsUpdated,
+    /// A mentorship offer was made.
+    MentorshipOffered,
+    /// A mentorship started.
+    MentorshipStarted,
+    /// A mentorship or pending offer was ended.
+    MentorshipEnded,
+    /// A guild was created with the given ID.
+    GuildCreated { guild_id: u64 },
+    /// A guild's roster, invites, name or announcement were changed.
+    GuildUpdated,
+    /// An invite code was registered or revoked.
+    InviteCodeUpdated,
+    /// A guild poll was opened with the given ID.
+    GuildPollCreated { poll_id: u64 },
+    /// A vote was cast in a guild poll.
+    GuildVoteCast,
+    /// A guild's payout rule was changed.
+    GuildPayoutRuleUpdated,
+    /// A guild prize was awarded with the given ID.
+    GuildPrizeAwarded { prize_id: u64 },
+    /// A share of a guild prize was collected.
+    GuildPrizeClaimed { amount: Amount },
+    /// The player's progress was exported, committing to the given hash.
+    ProgressExported { record_hash: CryptoHash },
+    /// The migration source key was changed.
+    MigrationSourceUpdated,
+    /// Progress from another deployment was imported, adding the given XP,
+    /// or sent to the hub to confirm first if `None`.
+    ProgressImported { xp: Option<u64> },
+    /// The player's play limits were changed.
+    PlayLimitsUpdated,
+    /// The player's scoring category was changed.
+    ScoringCategoryUpdated,
+    /// The player's timezone was changed.
+    TimezoneUpdated,
+    /// The player's locale was changed.
+    LocaleUpdated,
+    /// The calling application's event bridge was changed.
+    EventBridgeUpdated,
+    /// Events forwarded to the calling application, oldest first.
+    BridgedEvents { events: Vec<bridge::BridgedEvent> },
+    /// The chains whose syncs are applied here were changed.
+    SyncSourcesUpdated,
+    /// A community game was submitted for review with the given ID.
+    CommunityGameSubmitted { game_id: u64 },
+    /// A community game was approved or rejected.
+    CommunityGameReviewed,
+    /// A community run was accepted. `xp_earned` is the community XP it
+    /// earned, or `None` if it was sent to the hub to be checked there.
+    CommunityScoreSubmitted { xp_earned: Option<u64> },
+    /// Operation failed with an error, identified by `code` and described
+    /// by `message`.
+    Error {
+        code: ArcadeErrorCode,
+        message: String,
+    },
 }
 
 /// Messages sent between chains for hub aggregation.
@@ -237,6 +1382,17 @@ pub enum Message {
     SyncPlayer(Player),
     /// Sync a game score to the hub.
     SyncScore(GameScore),
+    /// Sync a batch of a player's scores to the hub, with their XP after
+    /// the last one and the progress syncs the runs made (see
+    /// [`Message::is_run_progress`]), in order.
+    SyncScoreBatch {
+        wallet_address: AccountOwner,
+        scores: Vec<GameScore>,
+        total_xp: u64,
+        level: u32,
+        games_played: u64,
+        progress: Vec<Message>,
+    },
     /// Sync an XP update to the hub.
     SyncXpUpdate {
         wallet_address: AccountOwner,
@@ -244,6 +1400,268 @@ pub enum Message {
         level: u32,
         games_played: u64,
     },
+    /// Sync a display name change to the hub.
+    SyncDisplayName {
+        wallet_address: AccountOwner,
+        display_name: String,
+    },
+    /// Sync a username change to the hub.
+    SyncUsername {
+        wallet_address: AccountOwner,
+        username: String,
+    },
+    /// Sync a privacy settings change to the hub.
+    SyncPrivacy {
+        wallet_address: AccountOwner,
+        privacy: PrivacySettings,
+    },
+    /// Sync a block or unblock to the hub.
+    SyncBlock {
+        wallet_address: AccountOwner,
+        target: AccountOwner,
+        blocked: bool,
+    },
+    /// Sync a daily challenge run to the hub.
+    SyncDailyChallenge { entry: challenges::ChallengeEntry },
+    /// Sync a player's monthly challenge progress to the hub.
+    SyncMonthlyProgress {
+        progress: challenges::MonthlyProgress,
+    },
+    /// Sync a player's event achievements to the hub.
+    SyncEventAchievements {
+        wallet_address: AccountOwner,
+        progress: crossovers::EventAchievementProgress,
+    },
+    /// Sync a player's achievement progress to the hub.
+    SyncAchievements {
+        wallet_address: AccountOwner,
+        progress: achievements::AchievementProgress,
+    },
+    /// Tell the hub a player completed one of their goals.
+    SyncGoalCompleted {
+        wallet_address: AccountOwner,
+        goal: goals::Goal,
+    },
+    /// Sync a follow or unfollow to the hub.
+    SyncFollow {
+        wallet_address: AccountOwner,
+        target: AccountOwner,
+        followed: bool,
+    },
+    /// Tell the hub a player deleted their account.
+    SyncAccountDeleted { wallet_address: AccountOwner },
+    /// Tell the hub a score was removed by moderation.
+    SyncScoreRemoved(GameScore),
+    /// Sync a friend addition or removal to the hub.
+    SyncFriend {
+        wallet_address: AccountOwner,
+        target: AccountOwner,
+        added: bool,
+    },
+    /// Sync kudos sent by a player to the hub.
+    SyncKudos {
+        wallet_address: AccountOwner,
+        recipient: AccountOwner,
+        timestamp: u64,
+    },
+    /// Sync an emote left or taken back by a player to the hub.
+    SyncReaction {
+        wallet_address: AccountOwner,
+        target: reactions::ReactionTarget,
+        emote: reactions::Emote,
+        added: bool,
+    },
+    /// Deliver an arcade event to a bridged application.
+    BridgeEvent {
+        application_id: ApplicationId,
+        event: ArcadeEvent,
+    },
+    /// Tell the hub a player completed the full circuit on `day`.
+    SyncFullCircuit {
+        wallet_address: AccountOwner,
+        day: u64,
+    },
+    /// Sync a guild's roster, name and announcement to the hub.
+    SyncGuild(guilds::Guild),
+    /// Tell the hub a guild was disbanded.
+    SyncGuildDisbanded { guild_id: u64 },
+    /// Tell the hub a guild's payout rule changed.
+    SyncGuildPayoutRule {
+        guild_id: u64,
+        rule: guild_prizes::GuildPayoutRule,
+    },
+    /// Tell the hub a player's progress was exported and locked.
+    SyncProgressExported(migration::ProgressExport),
+    /// Tell the hub a player imported progress from another deployment.
+    SyncProgressImported(migration::ProgressImport),
+    /// Send a run of a community game to the hub.
+    SyncCommunityScore(community_games::CommunityScore),
+    /// A sync sent straight to the hub, if the deployment enabled
+    /// [`InstantiationArgument::direct_syncs`], numbered by its index on
+    /// the sender's [`SYNC_EVENT_STREAM`], so a hub also subscribed to that
+    /// stream applies it only once.
+    SequencedSync { index: u32, message: Box<Message> },
+    /// Ask the hub to take up an appeal filed on another chain, whose bond
+    /// was moved into the hub's escrow.
+    FileAppeal {
+        appellant: AccountOwner,
+        receipt: CryptoHash,
+        reason: String,
+        bond: Amount,
+    },
+    /// Ask the hub to subscribe to the sender's [`SYNC_EVENT_STREAM`].
+    AnnounceSyncSource,
+    /// Ask the hub to confirm a progress import, which only the first
+    /// chain to import a snapshot is allowed.
+    ImportProgress(migration::ProgressImport),
+    /// Tell the chain that asked whether the hub accepted its progress
+    /// import.
+    ProgressImportSettled {
+        import: migration::ProgressImport,
+        accepted: bool,
+    },
+    /// Tell the chain a score was played on that the admin removed it on
+    /// the hub.
+    RemoveScore { receipt: CryptoHash },
+    /// Tell a player's chain the hub holds them under `username`, because
+    /// the name they registered or changed to was already taken there.
+    ForceRename {
+        wallet_address: AccountOwner,
+        username: String,
+    },
+    /// Ask the hub to clear the humanity challenge it issued `player`, with
+    /// `verifier`'s signature as completed on the player's chain.
+    CompleteHumanityChallenge {
+        player: AccountOwner,
+        verifier: AccountOwner,
+        signature: String,
+    },
+}
+
+impl Message {
+    /// Whether the message syncs progress a player's run made, which a
+    /// batch of runs carries inside its [`Message::SyncScoreBatch`].
+    pub fn is_run_progress(&self) -> bool {
+        matches!(
+            self,
+            Message::SyncMonthlyProgress { .. }
+                | Message::SyncEventAchievements { .. }
+                | Message::SyncAchievements { .. }
+                | Message::SyncGoalCompleted { .. }
+                | Message::SyncFullCircuit { .. }
+        )
+    }
+
+    /// Whether the message is a request sent straight between the hub and
+    /// another chain, which is never synced.
+    pub fn is_request(&self) -> bool {
+        matches!(
+            self,
+            Message::FileAppeal { .. }
+                | Message::AnnounceSyncSource
+                | Message::ImportProgress(_)
+                | Message::ProgressImportSettled { .. }
+                | Message::RemoveScore { .. }
+                | Message::ForceRename { .. }
+                | Message::CompleteHumanityChallenge { .. }
+        )
+    }
+}
+
+/// Name of the event stream the hub publishes arcade-wide events on.
+pub const ARCADE_EVENT_STREAM: &str = "arcade";
+
+/// Name of the event stream every chain but the hub publishes its syncs on,
+/// as [`ArcadeEvent::Sync`]. Each chain announces itself to the hub with
+/// [`Message::AnnounceSyncSource`] and the hub subscribes to its stream. A
+/// chain that doesn't know the hub's ID can still be aggregated by having
+/// the admin subscribe the hub to it with [`Operation::SubscribeToSyncs`],
+/// and mirror or analytics chains can subscribe to any chain themselves.
+pub const SYNC_EVENT_STREAM: &str = "sync";
+
+/// Events published on [`ARCADE_EVENT_STREAM`] and [`SYNC_EVENT_STREAM`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ArcadeEvent {
+    /// A change synced to the hub, published on [`SYNC_EVENT_STREAM`] by
+    /// the chain it was made on.
+    Sync(Box<Message>),
+    /// A player joined the arcade.
+    PlayerRegistered {
+        player: AccountOwner,
+        username: String,
+    },
+    /// A score was counted towards the arcade, unless its player keeps
+    /// their score history private.
+    ScoreSubmitted {
+        player: AccountOwner,
+        score_id: u64,
+        game_type: GameType,
+        score: u64,
+        xp_earned: u64,
+    },
+    /// A score became its game's hall of fame record, unless its player
+    /// opted out of leaderboards.
+    NewHighScore {
+        player: AccountOwner,
+        score_id: u64,
+        game_type: GameType,
+        score: u64,
+        /// The record it beat, if the game had one.
+        previous: Option<u64>,
+    },
+    /// An arcade-wide milestone was reached.
+    MilestoneReached(milestones::Milestone),
+    /// A player reached a new level.
+    LevelUp { player: AccountOwner, level: u32 },
+    /// A player played every game on one UTC day.
+    FullCircuit { player: AccountOwner, day: u64 },
+    /// A player completed one of their personal goals.
+    GoalCompleted {
+        player: AccountOwner,
+        goal: goals::Goal,
+    },
+    /// An invariant check found the state inconsistent.
+    InvariantViolated {
+        section: invariants::InvariantSection,
+        detail: String,
+    },
+    /// A pass over an invariant section finished.
+    InvariantCheckCompleted {
+        section: invariants::InvariantSection,
+        checked: u64,
+        violations: u32,
+    },
+    /// The hub's kiosk-mode settings changed; every chain takes them up.
+    KioskConfigured(kiosk::KioskConfig),
+    /// A kiosk device was banned or unbanned on the hub; every chain takes
+    /// the ban up.
+    DeviceBanUpdated { device_id: String, banned: bool },
+    /// A player was banned or unbanned on the hub; every chain takes the
+    /// ban up.
+    PlayerBanUpdated {
+        wallet_address: AccountOwner,
+        banned: bool,
+    },
+    /// The hub challenged a player to prove they are human; every chain
+    /// takes the challenge up.
+    HumanityChallengeIssued(humanity::HumanityChallenge),
+    /// The hub cleared the humanity challenge with the given nonce, as
+    /// completed or withdrawn.
+    HumanityChallengeCleared {
+        player: AccountOwner,
+        nonce: CryptoHash,
+    },
+}
+
+/// Application parameters, the same on every chain. An application
+/// created without parameters (`null`) is a production deployment.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArcadeParameters {
+    /// A developer sandbox, for testing scoring flows against a real
+    /// deployment: no chain syncs anything to the hub or applies syncs
+    /// from other chains, so every chain's players and scores stay on it.
+    #[serde(default)]
+    pub sandbox: bool,
 }
 
 /// Instantiation argument for the arcade hub application.
@@ -251,6 +1669,36 @@ pub enum Message {
 pub struct InstantiationArgument {
     /// The chain ID of the hub chain that aggregates all data.
     pub hub_chain_id: ChainId,
+    /// The account allowed to run moderation operations.
+    #[serde(default)]
+    pub admin: Option<AccountOwner>,
+    /// The bond required to file an appeal (defaults to
+    /// [`dispute::DEFAULT_APPEAL_BOND`]).
+    #[serde(default)]
+    pub appeal_bond: Option<Amount>,
+    /// The operator key that signs registration vouchers.
+    #[serde(default)]
+    pub voucher_signer: Option<AccountOwner>,
+    /// Plausibility bounds replacing the defaults of the games they name.
+    #[serde(default)]
+    pub score_bounds: Vec<plausibility::ScoreBounds>,
+    /// The game server key whose signature every submission must carry.
+    /// Unset, submissions need no signature.
+    #[serde(default)]
+    pub score_signer: Option<AccountOwner>,
+    /// Who is placed on the rookie leaderboard (defaults to
+    /// [`rankings::RookieCriteria::default`]).
+    #[serde(default)]
+    pub rookie_criteria: Option<rankings::RookieCriteria>,
+    /// Populate the new application with generated players and scores, for
+    /// demo environments.
+    #[serde(default)]
+    pub demo_fixture: Option<fixtures::FixtureConfig>,
+    /// Also send every sync straight to the hub as a message. Off, the hub
+    /// only reads each chain's [`SYNC_EVENT_STREAM`], subscribing to it
+    /// when the chain announces itself.
+    #[serde(default)]
+    pub direct_syncs: bool,
 }
 
 /// Errors that can occur in the arcade hub.
@@ -260,20 +1708,943 @@ pub enum ArcadeError {
     PlayerAlreadyRegistered,
     #[error("Player is not registered")]
     PlayerNotRegistered,
+    #[error("Score or bonus data is outside what a real run can reach")]
+    ImplausibleScore,
+    #[error("Scores must be submitted by closing a game session")]
+    SessionRequired,
+    #[error("No open game session with this ID")]
+    SessionNotFound,
+    #[error("The game session is for a different game")]
+    SessionGameMismatch,
+    #[error("The game session has timed out")]
+    SessionExpired,
     #[error("Username must be between 3 and 20 characters")]
     InvalidUsernameLength,
     #[error("Username contains invalid characters")]
     InvalidUsernameCharacters,
+    #[error("Display name must be between 1 and 32 characters without control characters")]
+    InvalidDisplayName,
     #[error("Operation requires authentication")]
     NotAuthenticated,
+    #[error("Operation requires admin privileges")]
+    NotAdmin,
+    #[error("Player is banned")]
+    PlayerBanned,
+    #[error("The arcade is read-only for maintenance")]
+    MaintenanceMode,
+    #[error("Maintenance must end in the future and within 7 days")]
+    InvalidMaintenanceWindow,
+    #[error("Operation requires maintenance mode")]
+    MaintenanceRequired,
+    #[error("{game_type:?} is closed for submissions at this hour")]
+    GameClosed { game_type: GameType },
+    #[error("Closed windows must be distinct minutes of the day, at most 4 per game")]
+    InvalidSubmissionWindows,
+    #[error(
+        "Game weights must be 1-500% with each game weighted once, and the p90 bonus at most 100%"
+    )]
+    InvalidScoringConfig,
+    #[error("A scoring canary is already running")]
+    ScoringCanaryRunning,
+    #[error("No scoring canary is running")]
+    ScoringCanaryNotRunning,
+    #[error("Journal checkpoint not found")]
+    CheckpointNotFound,
+    #[error("Score not found")]
+    ScoreNotFound,
+    #[error("Appeal not found")]
+    AppealNotFound,
+    #[error("Appeal has already been resolved")]
+    AppealAlreadyResolved,
+    #[error("Appeal reason must be between 1 and 280 characters")]
+    InvalidAppealReason,
+    #[error("Insufficient balance to cover the appeal bond")]
+    InsufficientBond,
+    #[error("Smurf detection must flag runs above the median after 1 to all early games")]
+    InvalidSmurfDetectionConfig,
+    #[error("Moderation flag not found")]
+    ModerationFlagNotFound,
+    #[error("Complete your humanity challenge before submitting again")]
+    HumanityCheckRequired,
+    #[error("Each wallet must be able to vouch for 1 to 10 players")]
+    InvalidHumanityCheckConfig,
+    #[error("No open humanity challenge for this player")]
+    HumanityChallengeNotFound,
+    #[error("Humanity proof is invalid or the verifier can't vouch for this player")]
+    InvalidHumanityProof,
+    #[error("Submissions must include a device ID")]
+    DeviceIdRequired,
+    #[error("Device ID must be 1-64 alphanumeric, '_', '-' or ':' characters")]
+    InvalidDeviceId,
+    #[error("Device is banned")]
+    DeviceBanned,
+    #[error("Device has reached its hourly submission limit")]
+    DeviceRateLimited,
+    #[error("Signer is not an authorized gateway")]
+    NotGateway,
+    #[error("Batch must contain at least one submission")]
+    EmptyBatch,
+    #[error("Batch exceeds the maximum number of submissions")]
+    BatchTooLarge,
+    #[error("Submission attestation is missing or invalid")]
+    InvalidAttestation,
+    #[error("Submission nonce has already been used")]
+    StaleNonce,
+    #[error("Submission must be signed by the game server")]
+    ScoreSignatureRequired,
+    #[error("Game server signature is invalid")]
+    InvalidScoreSignature,
+    #[error("Registration voucher is invalid")]
+    InvalidVoucher,
+    #[error("Registration voucher has expired")]
+    VoucherExpired,
+    #[error("Registration voucher has already been used")]
+    VoucherAlreadyUsed,
+    #[error("Username is reserved by another player")]
+    UsernameReserved,
+    #[error("Player already holds a username reservation that hasn't expired")]
+    ReservationActive,
+    #[error("Username is taken by another player")]
+    UsernameTaken,
+    #[error("Player has played within the inactivity period")]
+    AccountNotInactive,
+    #[error("Username grace period cannot exceed the inactivity period")]
+    InvalidUsernameRecyclingConfig,
+    #[error("Leave your guild before deleting the account")]
+    AccountInGuild,
+    #[error("Username is being auctioned")]
+    UsernameInAuction,
+    #[error("Username is not allowed")]
+    UsernameBlocked,
+    #[error("Username blocklist is full")]
+    UsernameBlocklistFull,
+    #[error("Username auction is malformed or bidding has already closed")]
+    InvalidUsernameAuction,
+    #[error("Too many username auctions are open")]
+    TooManyUsernameAuctions,
+    #[error("Username auction not found")]
+    UsernameAuctionNotFound,
+    #[error("Username auction is not accepting that right now")]
+    UsernameAuctionPhase,
+    #[error("Username auction has already been settled")]
+    UsernameAuctionAlreadySettled,
+    #[error("Player has already bid on this username")]
+    AlreadyBidOnUsername,
+    #[error("Username auction has the maximum number of bids")]
+    TooManyUsernameBids,
+    #[error("No bid from this player on the username auction")]
+    UsernameBidNotFound,
+    #[error("Bid does not match its commitment or exceeds its deposit")]
+    InvalidUsernameBid,
+    #[error("Insufficient balance to cover the bid deposit")]
+    InsufficientBidDeposit,
+    #[error("Today's daily challenge is a different game")]
+    WrongChallengeGame,
+    #[error("Today's daily challenge has already been submitted")]
+    ChallengeAlreadySubmitted,
+    #[error("Players cannot block themselves")]
+    CannotBlockSelf,
+    #[error("Blocklist is full")]
+    BlocklistFull,
+    #[error("The recipient has blocked this player")]
+    BlockedByPlayer,
+    #[error("Batch item {index} rejected: {reason}")]
+    BatchItemRejected {
+        index: usize,
+        reason: Box<ArcadeError>,
+    },
+    #[error("You've reached your daily play limit. Take a break and come back tomorrow!")]
+    DailyPlayLimitReached,
+    #[error("Play limits can only be loosened once the cooldown after the last change has passed")]
+    PlayLimitCooldown,
+    #[error("UTC offset must be between -12:00 and +14:00")]
+    InvalidUtcOffset,
+    #[error("Timezone can only be changed once every 30 days")]
+    TimezoneCooldown,
+    #[error("Locale must be a BCP 47 language tag of at most 35 characters")]
+    InvalidLocale,
+    #[error("Operation can only be called by another application")]
+    NotApplicationCaller,
+    #[error("Application has registered the maximum number of bridge chains")]
+    TooManyBridgeChains,
+    #[error("Event bridges are registered on the hub chain")]
+    BridgeRequiresHub,
+    #[error("A chain can't subscribe to its own syncs")]
+    InvalidSyncSource,
+    #[error("History retention must keep at least one entry")]
+    InvalidHistoryRetention,
+    #[error(
+        "Season length must be between {} and {} days",
+        seasons::MIN_SEASON_LENGTH_DAYS,
+        seasons::MAX_SEASON_LENGTH_DAYS
+    )]
+    InvalidSeasonLength,
+    #[error("Seasons run on the hub chain")]
+    SeasonsRequireHub,
+    #[error("Seasons have already started")]
+    SeasonAlreadyRunning,
+    #[error("No season is running")]
+    NoActiveSeason,
+    #[error("Season theme must be named and reward distinct cosmetics for distinct tiers")]
+    InvalidSeasonTheme,
+    #[error("Season has already ended")]
+    SeasonAlreadyEnded,
+    #[error("Raffle is malformed or has already ended")]
+    InvalidRaffle,
+    #[error("Raffles run on the hub chain")]
+    RafflesRequireHub,
+    #[error(
+        "At most {} raffles can be awaiting their draw at once",
+        raffles::MAX_OPEN_RAFFLES
+    )]
+    TooManyRaffles,
+    #[error("Insufficient balance to fund the raffle prize")]
+    InsufficientRafflePrize,
+    #[error("Raffle not found")]
+    RaffleNotFound,
+    #[error("Raffle has not ended yet")]
+    RaffleNotEnded,
+    #[error("Raffle has already been drawn")]
+    RaffleAlreadyDrawn,
+    #[error("Secret does not match the raffle's commitment")]
+    RaffleSecretMismatch,
+    #[error("Boss battle is malformed or has already ended")]
+    InvalidBossBattle,
+    #[error("Boss battles run on the hub chain")]
+    BossBattlesRequireHub,
+    #[error(
+        "At most {} boss battles can be scheduled at once",
+        bosses::MAX_OPEN_BOSS_BATTLES
+    )]
+    TooManyBossBattles,
+    #[error("Community game definition is malformed")]
+    InvalidCommunityGame,
+    #[error("Community games are listed on the hub chain")]
+    CommunityGamesRequireHub,
+    #[error(
+        "At most {} community games can wait for review at once",
+        community_games::MAX_PENDING_COMMUNITY_GAMES
+    )]
+    TooManyPendingCommunityGames,
+    #[error("Community game not found")]
+    CommunityGameNotFound,
+    #[error("Community game has already been reviewed")]
+    CommunityGameAlreadyReviewed,
+    #[error("Community game isn't approved")]
+    CommunityGameNotApproved,
+    #[error("Score is above the community game's maximum")]
+    InvalidCommunityScore,
+    #[error(
+        "Player already holds the maximum of {} streak shields",
+        streaks::MAX_SHIELDS
+    )]
+    TooManyStreakShields,
+    #[error(
+        "Player has gained the maximum of {} streak shields this month",
+        streaks::SHIELDS_PER_MONTH
+    )]
+    StreakShieldLimitReached,
+    #[error("Insufficient balance to buy a streak shield")]
+    InsufficientShieldPayment,
+    #[error("Crossover event is malformed or has already ended")]
+    InvalidCrossover,
+    #[error(
+        "At most {} crossover events can be scheduled at once",
+        crossovers::MAX_SCHEDULED_CROSSOVERS
+    )]
+    TooManyCrossovers,
+    #[error("Crossover event not found")]
+    CrossoverNotFound,
+    #[error("Goal target must be positive, with a game set for exactly the game score goals")]
+    InvalidGoal,
+    #[error("Goal deadline must be in the future")]
+    InvalidGoalDeadline,
+    #[error("Goal is already met")]
+    GoalAlreadyMet,
+    #[error("Player has the maximum number of active goals")]
+    TooManyGoals,
+    #[error("Goal not found")]
+    GoalNotFound,
+    #[error("Players cannot follow themselves")]
+    CannotFollowSelf,
+    #[error("Player follows the maximum number of players")]
+    FollowingListFull,
+    #[error("Players cannot add themselves as friends")]
+    CannotFriendSelf,
+    #[error("Player has added the maximum number of friends")]
+    FriendListFull,
+    #[error("Player has too many pending friend requests")]
+    TooManyFriendRequests,
+    #[error("Players cannot send kudos to themselves")]
+    CannotKudosSelf,
+    #[error("Kudos were already sent to this player today")]
+    KudosAlreadySent,
+    #[error("You've sent all {} of today's kudos", social::DAILY_KUDOS)]
+    DailyKudosLimitReached,
+    #[error("Reactions can only be left on hall-of-fame records and top leaderboard entries")]
+    InvalidReactionTarget,
+    #[error("This emote was already left on this target")]
+    AlreadyReacted,
+    #[error(
+        "At most {} different emotes can be left on one target",
+        reactions::MAX_EMOTES_PER_TARGET
+    )]
+    TooManyReactions,
+    #[error("This emote was not left on this target")]
+    ReactionNotFound,
+    #[error("Players cannot mentor themselves")]
+    CannotMentorSelf,
+    #[error("Mentors must have reached level {}", mentorship::MIN_MENTOR_LEVEL)]
+    MentorLevelTooLow,
+    #[error("Apprentices must be below level {}", mentorship::GRADUATION_LEVEL)]
+    ApprenticeLevelTooHigh,
+    #[error("Player already has a mentor")]
+    AlreadyMentored,
+    #[error("Mentor has the maximum number of apprentices")]
+    TooManyApprentices,
+    #[error("Player has the maximum number of pending mentorship offers")]
+    TooManyMentorshipOffers,
+    #[error("No pending mentorship offer from this player")]
+    MentorshipOfferNotFound,
+    #[error("No mentorship or pending offer with this player")]
+    MentorshipNotFound,
+    #[error(
+        "Guild name must be 3-24 letters, digits, spaces, '_' or '-', without surrounding spaces"
+    )]
+    InvalidGuildName,
+    #[error("Guild name is already taken")]
+    GuildNameTaken,
+    #[error("Guild not found")]
+    GuildNotFound,
+    #[error("Player is already in a guild")]
+    AlreadyInGuild,
+    #[error("Player is not in this guild")]
+    NotInGuild,
+    #[error("Your guild role does not allow this")]
+    GuildPermissionDenied,
+    #[error("No pending invite from this guild")]
+    GuildInviteNotFound,
+    #[error("Guild has the maximum number of pending invites")]
+    TooManyGuildInvites,
+    #[error("Guild is full")]
+    GuildFull,
+    #[error("The guild leader must hand over the leadership before leaving")]
+    GuildLeaderCannotLeave,
+    #[error("Guild announcement must be between 1 and 280 characters")]
+    InvalidGuildAnnouncement,
+    #[error("Leader-decided payouts need shares for members adding up to 100, each at most 50")]
+    InvalidGuildPayoutRule,
+    #[error("Guild prizes need an amount and a note of 1 to 140 characters")]
+    InvalidGuildPrize,
+    #[error("Guild prizes are awarded and claimed on the hub chain")]
+    GuildPrizesRequireHub,
+    #[error("Insufficient balance to fund the guild prize")]
+    InsufficientGuildPrize,
+    #[error("No share of this guild prize for this player")]
+    GuildPrizeClaimNotFound,
+    #[error("Guild prize share has already been claimed")]
+    GuildPrizeAlreadyClaimed,
+    #[error("Player's progress has been exported and is locked")]
+    ProgressLocked,
+    #[error("Migration proof is not signed by the migration source")]
+    InvalidMigrationProof,
+    #[error("Exported progress has already been imported")]
+    ProgressAlreadyImported,
+    #[error("Invite codes allow 1-100 uses and last between 1 hour and 30 days")]
+    InvalidInviteCodeLimits,
+    #[error("An invite code with this hash is already registered")]
+    InviteCodeExists,
+    #[error("Invite code is invalid or has been revoked")]
+    InvalidInviteCode,
+    #[error("Invite code has expired or run out of uses")]
+    InviteCodeExpired,
+    #[error("Only the creator of an invite code can revoke it")]
+    NotInviteCodeCreator,
+    #[error("Polls need a question of 1-140 characters and 2-10 options of 1-60 characters")]
+    InvalidPoll,
+    #[error("Polls must stay open between 1 hour and 7 days")]
+    InvalidPollDuration,
+    #[error("Poll not found")]
+    PollNotFound,
+    #[error("Poll has closed")]
+    PollClosed,
+    #[error("Poll has no such option")]
+    InvalidPollOption,
+    #[error("The game session was started on a different device")]
+    SessionDeviceMismatch,
+    #[error("The raffle's secret can still be revealed")]
+    RaffleRevealPending,
+    #[error("Idempotency key must be between 1 and 64 bytes")]
+    InvalidIdempotencyKey,
+    #[error("The score already counted towards a drawn raffle or a defeated boss")]
+    ScorePaidOut,
     #[error("Internal error: {0}")]
     Internal(String),
 }
 
 impl ArcadeError {
-    /// Convert to an ArcadeResponse::Error.
+    /// A stable identifier for the error, for logs and clients that match
+    /// on errors rather than their messages.
+    pub fn code(&self) -> ArcadeErrorCode {
+        match self {
+            ArcadeError::PlayerAlreadyRegistered => ArcadeErrorCode::PlayerAlreadyRegistered,
+            ArcadeError::PlayerNotRegistered => ArcadeErrorCode::PlayerNotRegistered,
+            ArcadeError::ImplausibleScore => ArcadeErrorCode::ImplausibleScore,
+            ArcadeError::SessionRequired => ArcadeErrorCode::SessionRequired,
+            ArcadeError::SessionNotFound => ArcadeErrorCode::SessionNotFound,
+            ArcadeError::SessionGameMismatch => ArcadeErrorCode::SessionGameMismatch,
+            ArcadeError::SessionExpired => ArcadeErrorCode::SessionExpired,
+            ArcadeError::InvalidUsernameLength => ArcadeErrorCode::InvalidUsernameLength,
+            ArcadeError::InvalidUsernameCharacters => ArcadeErrorCode::InvalidUsernameCharacters,
+            ArcadeError::InvalidDisplayName => ArcadeErrorCode::InvalidDisplayName,
+            ArcadeError::NotAuthenticated => ArcadeErrorCode::NotAuthenticated,
+            ArcadeError::NotAdmin => ArcadeErrorCode::NotAdmin,
+            ArcadeError::PlayerBanned => ArcadeErrorCode::PlayerBanned,
+            ArcadeError::MaintenanceMode => ArcadeErrorCode::MaintenanceMode,
+            ArcadeError::InvalidMaintenanceWindow => ArcadeErrorCode::InvalidMaintenanceWindow,
+            ArcadeError::MaintenanceRequired => ArcadeErrorCode::MaintenanceRequired,
+            ArcadeError::GameClosed { .. } => ArcadeErrorCode::GameClosed,
+            ArcadeError::InvalidSubmissionWindows => ArcadeErrorCode::InvalidSubmissionWindows,
+            ArcadeError::InvalidScoringConfig => ArcadeErrorCode::InvalidScoringConfig,
+            ArcadeError::ScoringCanaryRunning => ArcadeErrorCode::ScoringCanaryRunning,
+            ArcadeError::ScoringCanaryNotRunning => ArcadeErrorCode::ScoringCanaryNotRunning,
+            ArcadeError::CheckpointNotFound => ArcadeErrorCode::CheckpointNotFound,
+            ArcadeError::ScoreNotFound => ArcadeErrorCode::ScoreNotFound,
+            ArcadeError::AppealNotFound => ArcadeErrorCode::AppealNotFound,
+            ArcadeError::AppealAlreadyResolved => ArcadeErrorCode::AppealAlreadyResolved,
+            ArcadeError::InvalidAppealReason => ArcadeErrorCode::InvalidAppealReason,
+            ArcadeError::InsufficientBond => ArcadeErrorCode::InsufficientBond,
+            ArcadeError::InvalidSmurfDetectionConfig => {
+                ArcadeErrorCode::InvalidSmurfDetectionConfig
+            }
+            ArcadeError::ModerationFlagNotFound => ArcadeErrorCode::ModerationFlagNotFound,
+            ArcadeError::HumanityCheckRequired => ArcadeErrorCode::HumanityCheckRequired,
+            ArcadeError::InvalidHumanityCheckConfig => ArcadeErrorCode::InvalidHumanityCheckConfig,
+            ArcadeError::HumanityChallengeNotFound => ArcadeErrorCode::HumanityChallengeNotFound,
+            ArcadeError::InvalidHumanityProof => ArcadeErrorCode::InvalidHumanityProof,
+            ArcadeError::DeviceIdRequired => ArcadeErrorCode::DeviceIdRequired,
+            ArcadeError::InvalidDeviceId => ArcadeErrorCode::InvalidDeviceId,
+            ArcadeError::DeviceBanned => ArcadeErrorCode::DeviceBanned,
+            ArcadeError::DeviceRateLimited => ArcadeErrorCode::DeviceRateLimited,
+            ArcadeError::NotGateway => ArcadeErrorCode::NotGateway,
+            ArcadeError::EmptyBatch => ArcadeErrorCode::EmptyBatch,
+            ArcadeError::BatchTooLarge => ArcadeErrorCode::BatchTooLarge,
+            ArcadeError::InvalidAttestation => ArcadeErrorCode::InvalidAttestation,
+            ArcadeError::StaleNonce => ArcadeErrorCode::StaleNonce,
+            ArcadeError::ScoreSignatureRequired => ArcadeErrorCode::ScoreSignatureRequired,
+            ArcadeError::InvalidScoreSignature => ArcadeErrorCode::InvalidScoreSignature,
+            ArcadeError::InvalidVoucher => ArcadeErrorCode::InvalidVoucher,
+            ArcadeError::VoucherExpired => ArcadeErrorCode::VoucherExpired,
+            ArcadeError::VoucherAlreadyUsed => ArcadeErrorCode::VoucherAlreadyUsed,
+            ArcadeError::UsernameReserved => ArcadeErrorCode::UsernameReserved,
+            ArcadeError::ReservationActive => ArcadeErrorCode::ReservationActive,
+            ArcadeError::UsernameTaken => ArcadeErrorCode::UsernameTaken,
+            ArcadeError::AccountNotInactive => ArcadeErrorCode::AccountNotInactive,
+            ArcadeError::InvalidUsernameRecyclingConfig => {
+                ArcadeErrorCode::InvalidUsernameRecyclingConfig
+            }
+            ArcadeError::AccountInGuild => ArcadeErrorCode::AccountInGuild,
+            ArcadeError::UsernameInAuction => ArcadeErrorCode::UsernameInAuction,
+            ArcadeError::UsernameBlocked => ArcadeErrorCode::UsernameBlocked,
+            ArcadeError::UsernameBlocklistFull => ArcadeErrorCode::UsernameBlocklistFull,
+            ArcadeError::InvalidUsernameAuction => ArcadeErrorCode::InvalidUsernameAuction,
+            ArcadeError::TooManyUsernameAuctions => ArcadeErrorCode::TooManyUsernameAuctions,
+            ArcadeError::UsernameAuctionNotFound => ArcadeErrorCode::UsernameAuctionNotFound,
+            ArcadeError::UsernameAuctionPhase => ArcadeErrorCode::UsernameAuctionPhase,
+            ArcadeError::UsernameAuctionAlreadySettled => {
+                ArcadeErrorCode::UsernameAuctionAlreadySettled
+            }
+            ArcadeError::AlreadyBidOnUsername => ArcadeErrorCode::AlreadyBidOnUsername,
+            ArcadeError::TooManyUsernameBids => ArcadeErrorCode::TooManyUsernameBids,
+            ArcadeError::UsernameBidNotFound => ArcadeErrorCode::UsernameBidNotFound,
+            ArcadeError::InvalidUsernameBid => ArcadeErrorCode::InvalidUsernameBid,
+            ArcadeError::InsufficientBidDeposit => ArcadeErrorCode::InsufficientBidDeposit,
+            ArcadeError::WrongChallengeGame => ArcadeErrorCode::WrongChallengeGame,
+            ArcadeError::ChallengeAlreadySubmitted => ArcadeErrorCode::ChallengeAlreadySubmitted,
+            ArcadeError::CannotBlockSelf => ArcadeErrorCode::CannotBlockSelf,
+            ArcadeError::BlocklistFull => ArcadeErrorCode::BlocklistFull,
+            ArcadeError::BlockedByPlayer => ArcadeErrorCode::BlockedByPlayer,
+            ArcadeError::BatchItemRejected { .. } => ArcadeErrorCode::BatchItemRejected,
+            ArcadeError::DailyPlayLimitReached => ArcadeErrorCode::DailyPlayLimitReached,
+            ArcadeError::PlayLimitCooldown => ArcadeErrorCode::PlayLimitCooldown,
+            ArcadeError::InvalidUtcOffset => ArcadeErrorCode::InvalidUtcOffset,
+            ArcadeError::TimezoneCooldown => ArcadeErrorCode::TimezoneCooldown,
+            ArcadeError::InvalidLocale => ArcadeErrorCode::InvalidLocale,
+            ArcadeError::NotApplicationCaller => ArcadeErrorCode::NotApplicationCaller,
+            ArcadeError::TooManyBridgeChains => ArcadeErrorCode::TooManyBridgeChains,
+            ArcadeError::BridgeRequiresHub => ArcadeErrorCode::BridgeRequiresHub,
+            ArcadeError::InvalidSyncSource => ArcadeErrorCode::InvalidSyncSource,
+            ArcadeError::InvalidHistoryRetention => ArcadeErrorCode::InvalidHistoryRetention,
+            ArcadeError::InvalidSeasonLength => ArcadeErrorCode::InvalidSeasonLength,
+            ArcadeError::SeasonsRequireHub => ArcadeErrorCode::SeasonsRequireHub,
+            ArcadeError::SeasonAlreadyRunning => ArcadeErrorCode::SeasonAlreadyRunning,
+            ArcadeError::NoActiveSeason => ArcadeErrorCode::NoActiveSeason,
+            ArcadeError::InvalidSeasonTheme => ArcadeErrorCode::InvalidSeasonTheme,
+            ArcadeError::SeasonAlreadyEnded => ArcadeErrorCode::SeasonAlreadyEnded,
+            ArcadeError::InvalidRaffle => ArcadeErrorCode::InvalidRaffle,
+            ArcadeError::RafflesRequireHub => ArcadeErrorCode::RafflesRequireHub,
+            ArcadeError::TooManyRaffles => ArcadeErrorCode::TooManyRaffles,
+            ArcadeError::InsufficientRafflePrize => ArcadeErrorCode::InsufficientRafflePrize,
+            ArcadeError::RaffleNotFound => ArcadeErrorCode::RaffleNotFound,
+            ArcadeError::RaffleNotEnded => ArcadeErrorCode::RaffleNotEnded,
+            ArcadeError::RaffleAlreadyDrawn => ArcadeErrorCode::RaffleAlreadyDrawn,
+            ArcadeError::RaffleSecretMismatch => ArcadeErrorCode::RaffleSecretMismatch,
+            ArcadeError::InvalidBossBattle => ArcadeErrorCode::InvalidBossBattle,
+            ArcadeError::BossBattlesRequireHub => ArcadeErrorCode::BossBattlesRequireHub,
+            ArcadeError::TooManyBossBattles => ArcadeErrorCode::TooManyBossBattles,
+            ArcadeError::InvalidCommunityGame => ArcadeErrorCode::InvalidCommunityGame,
+            ArcadeError::CommunityGamesRequireHub => ArcadeErrorCode::CommunityGamesRequireHub,
+            ArcadeError::TooManyPendingCommunityGames => {
+                ArcadeErrorCode::TooManyPendingCommunityGames
+            }
+            ArcadeError::CommunityGameNotFound => ArcadeErrorCode::CommunityGameNotFound,
+            ArcadeError::CommunityGameAlreadyReviewed => {
+                ArcadeErrorCode::CommunityGameAlreadyReviewed
+            }
+            ArcadeError::CommunityGameNotApproved => ArcadeErrorCode::CommunityGameNotApproved,
+            ArcadeError::InvalidCommunityScore => ArcadeErrorCode::InvalidCommunityScore,
+            ArcadeError::TooManyStreakShields => ArcadeErrorCode::TooManyStreakShields,
+            ArcadeError::StreakShieldLimitReached => ArcadeErrorCode::StreakShieldLimitReached,
+            ArcadeError::InsufficientShieldPayment => ArcadeErrorCode::InsufficientShieldPayment,
+            ArcadeError::InvalidCrossover => ArcadeErrorCode::InvalidCrossover,
+            ArcadeError::TooManyCrossovers => ArcadeErrorCode::TooManyCrossovers,
+            ArcadeError::CrossoverNotFound => ArcadeErrorCode::CrossoverNotFound,
+            ArcadeError::InvalidGoal => ArcadeErrorCode::InvalidGoal,
+            ArcadeError::InvalidGoalDeadline => ArcadeErrorCode::InvalidGoalDeadline,
+            ArcadeError::GoalAlreadyMet => ArcadeErrorCode::GoalAlreadyMet,
+            ArcadeError::TooManyGoals => ArcadeErrorCode::TooManyGoals,
+            ArcadeError::GoalNotFound => ArcadeErrorCode::GoalNotFound,
+            ArcadeError::CannotFollowSelf => ArcadeErrorCode::CannotFollowSelf,
+            ArcadeError::FollowingListFull => ArcadeErrorCode::FollowingListFull,
+            ArcadeError::CannotFriendSelf => ArcadeErrorCode::CannotFriendSelf,
+            ArcadeError::FriendListFull => ArcadeErrorCode::FriendListFull,
+            ArcadeError::TooManyFriendRequests => ArcadeErrorCode::TooManyFriendRequests,
+            ArcadeError::CannotKudosSelf => ArcadeErrorCode::CannotKudosSelf,
+            ArcadeError::KudosAlreadySent => ArcadeErrorCode::KudosAlreadySent,
+            ArcadeError::DailyKudosLimitReached => ArcadeErrorCode::DailyKudosLimitReached,
+            ArcadeError::InvalidReactionTarget => ArcadeErrorCode::InvalidReactionTarget,
+            ArcadeError::AlreadyReacted => ArcadeErrorCode::AlreadyReacted,
+            ArcadeError::TooManyReactions => ArcadeErrorCode::TooManyReactions,
+            ArcadeError::ReactionNotFound => ArcadeErrorCode::ReactionNotFound,
+            ArcadeError::CannotMentorSelf => ArcadeErrorCode::CannotMentorSelf,
+            ArcadeError::MentorLevelTooLow => ArcadeErrorCode::MentorLevelTooLow,
+            ArcadeError::ApprenticeLevelTooHigh => ArcadeErrorCode::ApprenticeLevelTooHigh,
+            ArcadeError::AlreadyMentored => ArcadeErrorCode::AlreadyMentored,
+            ArcadeError::TooManyApprentices => ArcadeErrorCode::TooManyApprentices,
+            ArcadeError::TooManyMentorshipOffers => ArcadeErrorCode::TooManyMentorshipOffers,
+            ArcadeError::MentorshipOfferNotFound => ArcadeErrorCode::MentorshipOfferNotFound,
+            ArcadeError::MentorshipNotFound => ArcadeErrorCode::MentorshipNotFound,
+            ArcadeError::InvalidGuildName => ArcadeErrorCode::InvalidGuildName,
+            ArcadeError::GuildNameTaken => ArcadeErrorCode::GuildNameTaken,
+            ArcadeError::GuildNotFound => ArcadeErrorCode::GuildNotFound,
+            ArcadeError::AlreadyInGuild => ArcadeErrorCode::AlreadyInGuild,
+            ArcadeError::NotInGuild => ArcadeErrorCode::NotInGuild,
+            ArcadeError::GuildPermissionDenied => ArcadeErrorCode::GuildPermissionDenied,
+            ArcadeError::GuildInviteNotFound => ArcadeErrorCode::GuildInviteNotFound,
+            ArcadeError::TooManyGuildInvites => ArcadeErrorCode::TooManyGuildInvites,
+            ArcadeError::GuildFull => ArcadeErrorCode::GuildFull,
+            ArcadeError::GuildLeaderCannotLeave => ArcadeErrorCode::GuildLeaderCannotLeave,
+            ArcadeError::InvalidGuildAnnouncement => ArcadeErrorCode::InvalidGuildAnnouncement,
+            ArcadeError::InvalidGuildPayoutRule => ArcadeErrorCode::InvalidGuildPayoutRule,
+            ArcadeError::InvalidGuildPrize => ArcadeErrorCode::InvalidGuildPrize,
+            ArcadeError::GuildPrizesRequireHub => ArcadeErrorCode::GuildPrizesRequireHub,
+            ArcadeError::InsufficientGuildPrize => ArcadeErrorCode::InsufficientGuildPrize,
+            ArcadeError::GuildPrizeClaimNotFound => ArcadeErrorCode::GuildPrizeClaimNotFound,
+            ArcadeError::GuildPrizeAlreadyClaimed => ArcadeErrorCode::GuildPrizeAlreadyClaimed,
+            ArcadeError::ProgressLocked => ArcadeErrorCode::ProgressLocked,
+            ArcadeError::InvalidMigrationProof => ArcadeErrorCode::InvalidMigrationProof,
+            ArcadeError::ProgressAlreadyImported => ArcadeErrorCode::ProgressAlreadyImported,
+            ArcadeError::InvalidInviteCodeLimits => ArcadeErrorCode::InvalidInviteCodeLimits,
+            ArcadeError::InviteCodeExists => ArcadeErrorCode::InviteCodeExists,
+            ArcadeError::InvalidInviteCode => ArcadeErrorCode::InvalidInviteCode,
+            ArcadeError::InviteCodeExpired => ArcadeErrorCode::InviteCodeExpired,
+            ArcadeError::NotInviteCodeCreator => ArcadeErrorCode::NotInviteCodeCreator,
+            ArcadeError::InvalidPoll => ArcadeErrorCode::InvalidPoll,
+            ArcadeError::InvalidPollDuration => ArcadeErrorCode::InvalidPollDuration,
+            ArcadeError::PollNotFound => ArcadeErrorCode::PollNotFound,
+            ArcadeError::PollClosed => ArcadeErrorCode::PollClosed,
+            ArcadeError::InvalidPollOption => ArcadeErrorCode::InvalidPollOption,
+            ArcadeError::SessionDeviceMismatch => ArcadeErrorCode::SessionDeviceMismatch,
+            ArcadeError::RaffleRevealPending => ArcadeErrorCode::RaffleRevealPending,
+            ArcadeError::InvalidIdempotencyKey => ArcadeErrorCode::InvalidIdempotencyKey,
+            ArcadeError::ScorePaidOut => ArcadeErrorCode::ScorePaidOut,
+            ArcadeError::Internal(_) => ArcadeErrorCode::Internal,
+        }
+    }
+
+    /// Convert to an [`ArcadeResponse::Error`], logging the rejection.
     pub fn into_response(self) -> ArcadeResponse {
-        ArcadeResponse::Error(self.to_string())
+        let code = self.code();
+        log::warn!("Rejected with {code}: {self}");
+        ArcadeResponse::Error {
+            code,
+            message: self.to_string(),
+        }
+    }
+}
+
+/// A machine-readable kind of [`ArcadeError`], one per variant, so clients
+/// can branch on errors without parsing their messages.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ArcadeErrorCode {
+    PlayerAlreadyRegistered,
+    PlayerNotRegistered,
+    ImplausibleScore,
+    SessionRequired,
+    SessionNotFound,
+    SessionGameMismatch,
+    SessionExpired,
+    InvalidUsernameLength,
+    InvalidUsernameCharacters,
+    InvalidDisplayName,
+    NotAuthenticated,
+    NotAdmin,
+    PlayerBanned,
+    MaintenanceMode,
+    InvalidMaintenanceWindow,
+    MaintenanceRequired,
+    GameClosed,
+    InvalidSubmissionWindows,
+    InvalidScoringConfig,
+    ScoringCanaryRunning,
+    ScoringCanaryNotRunning,
+    CheckpointNotFound,
+    ScoreNotFound,
+    AppealNotFound,
+    AppealAlreadyResolved,
+    InvalidAppealReason,
+    InsufficientBond,
+    InvalidSmurfDetectionConfig,
+    ModerationFlagNotFound,
+    HumanityCheckRequired,
+    InvalidHumanityCheckConfig,
+    HumanityChallengeNotFound,
+    InvalidHumanityProof,
+    DeviceIdRequired,
+    InvalidDeviceId,
+    DeviceBanned,
+    DeviceRateLimited,
+    NotGateway,
+    EmptyBatch,
+    BatchTooLarge,
+    InvalidAttestation,
+    StaleNonce,
+    ScoreSignatureRequired,
+    InvalidScoreSignature,
+    InvalidVoucher,
+    VoucherExpired,
+    VoucherAlreadyUsed,
+    UsernameReserved,
+    ReservationActive,
+    UsernameTaken,
+    AccountNotInactive,
+    InvalidUsernameRecyclingConfig,
+    AccountInGuild,
+    UsernameInAuction,
+    UsernameBlocked,
+    UsernameBlocklistFull,
+    InvalidUsernameAuction,
+    TooManyUsernameAuctions,
+    UsernameAuctionNotFound,
+    UsernameAuctionPhase,
+    UsernameAuctionAlreadySettled,
+    AlreadyBidOnUsername,
+    TooManyUsernameBids,
+    UsernameBidNotFound,
+    InvalidUsernameBid,
+    InsufficientBidDeposit,
+    WrongChallengeGame,
+    ChallengeAlreadySubmitted,
+    CannotBlockSelf,
+    BlocklistFull,
+    BlockedByPlayer,
+    BatchItemRejected,
+    DailyPlayLimitReached,
+    PlayLimitCooldown,
+    InvalidUtcOffset,
+    TimezoneCooldown,
+    InvalidLocale,
+    NotApplicationCaller,
+    TooManyBridgeChains,
+    BridgeRequiresHub,
+    InvalidSyncSource,
+    InvalidHistoryRetention,
+    InvalidSeasonLength,
+    SeasonsRequireHub,
+    SeasonAlreadyRunning,
+    NoActiveSeason,
+    InvalidSeasonTheme,
+    SeasonAlreadyEnded,
+    InvalidRaffle,
+    RafflesRequireHub,
+    TooManyRaffles,
+    InsufficientRafflePrize,
+    RaffleNotFound,
+    RaffleNotEnded,
+    RaffleAlreadyDrawn,
+    RaffleSecretMismatch,
+    InvalidBossBattle,
+    BossBattlesRequireHub,
+    TooManyBossBattles,
+    InvalidCommunityGame,
+    CommunityGamesRequireHub,
+    TooManyPendingCommunityGames,
+    CommunityGameNotFound,
+    CommunityGameAlreadyReviewed,
+    CommunityGameNotApproved,
+    InvalidCommunityScore,
+    TooManyStreakShields,
+    StreakShieldLimitReached,
+    InsufficientShieldPayment,
+    InvalidCrossover,
+    TooManyCrossovers,
+    CrossoverNotFound,
+    InvalidGoal,
+    InvalidGoalDeadline,
+    GoalAlreadyMet,
+    TooManyGoals,
+    GoalNotFound,
+    CannotFollowSelf,
+    FollowingListFull,
+    CannotFriendSelf,
+    FriendListFull,
+    TooManyFriendRequests,
+    CannotKudosSelf,
+    KudosAlreadySent,
+    DailyKudosLimitReached,
+    InvalidReactionTarget,
+    AlreadyReacted,
+    TooManyReactions,
+    ReactionNotFound,
+    CannotMentorSelf,
+    MentorLevelTooLow,
+    ApprenticeLevelTooHigh,
+    AlreadyMentored,
+    TooManyApprentices,
+    TooManyMentorshipOffers,
+    MentorshipOfferNotFound,
+    MentorshipNotFound,
+    InvalidGuildName,
+    GuildNameTaken,
+    GuildNotFound,
+    AlreadyInGuild,
+    NotInGuild,
+    GuildPermissionDenied,
+    GuildInviteNotFound,
+    TooManyGuildInvites,
+    GuildFull,
+    GuildLeaderCannotLeave,
+    InvalidGuildAnnouncement,
+    InvalidGuildPayoutRule,
+    InvalidGuildPrize,
+    GuildPrizesRequireHub,
+    InsufficientGuildPrize,
+    GuildPrizeClaimNotFound,
+    GuildPrizeAlreadyClaimed,
+    ProgressLocked,
+    InvalidMigrationProof,
+    ProgressAlreadyImported,
+    InvalidInviteCodeLimits,
+    InviteCodeExists,
+    InvalidInviteCode,
+    InviteCodeExpired,
+    NotInviteCodeCreator,
+    InvalidPoll,
+    InvalidPollDuration,
+    PollNotFound,
+    PollClosed,
+    InvalidPollOption,
+    SessionDeviceMismatch,
+    RaffleRevealPending,
+    InvalidIdempotencyKey,
+    ScorePaidOut,
+    Internal,
+}
+
+impl ArcadeErrorCode {
+    /// The code as it appears in logs and JSON.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ArcadeErrorCode::PlayerAlreadyRegistered => "PLAYER_ALREADY_REGISTERED",
+            ArcadeErrorCode::PlayerNotRegistered => "PLAYER_NOT_REGISTERED",
+            ArcadeErrorCode::ImplausibleScore => "IMPLAUSIBLE_SCORE",
+            ArcadeErrorCode::SessionRequired => "SESSION_REQUIRED",
+            ArcadeErrorCode::SessionNotFound => "SESSION_NOT_FOUND",
+            ArcadeErrorCode::SessionGameMismatch => "SESSION_GAME_MISMATCH",
+            ArcadeErrorCode::SessionExpired => "SESSION_EXPIRED",
+            ArcadeErrorCode::InvalidUsernameLength => "INVALID_USERNAME_LENGTH",
+            ArcadeErrorCode::InvalidUsernameCharacters => "INVALID_USERNAME_CHARACTERS",
+            ArcadeErrorCode::InvalidDisplayName => "INVALID_DISPLAY_NAME",
+            ArcadeErrorCode::NotAuthenticated => "NOT_AUTHENTICATED",
+            ArcadeErrorCode::NotAdmin => "NOT_ADMIN",
+            ArcadeErrorCode::PlayerBanned => "PLAYER_BANNED",
+            ArcadeErrorCode::MaintenanceMode => "MAINTENANCE_MODE",
+            ArcadeErrorCode::InvalidMaintenanceWindow => "INVALID_MAINTENANCE_WINDOW",
+            ArcadeErrorCode::MaintenanceRequired => "MAINTENANCE_REQUIRED",
+            ArcadeErrorCode::GameClosed => "GAME_CLOSED",
+            ArcadeErrorCode::InvalidSubmissionWindows => "INVALID_SUBMISSION_WINDOWS",
+            ArcadeErrorCode::InvalidScoringConfig => "INVALID_SCORING_CONFIG",
+            ArcadeErrorCode::ScoringCanaryRunning => "SCORING_CANARY_RUNNING",
+            ArcadeErrorCode::ScoringCanaryNotRunning => "SCORING_CANARY_NOT_RUNNING",
+            ArcadeErrorCode::CheckpointNotFound => "CHECKPOINT_NOT_FOUND",
+            ArcadeErrorCode::ScoreNotFound => "SCORE_NOT_FOUND",
+            ArcadeErrorCode::AppealNotFound => "APPEAL_NOT_FOUND",
+            ArcadeErrorCode::AppealAlreadyResolved => "APPEAL_ALREADY_RESOLVED",
+            ArcadeErrorCode::InvalidAppealReason => "INVALID_APPEAL_REASON",
+            ArcadeErrorCode::InsufficientBond => "INSUFFICIENT_BOND",
+            ArcadeErrorCode::InvalidSmurfDetectionConfig => "INVALID_SMURF_DETECTION_CONFIG",
+            ArcadeErrorCode::ModerationFlagNotFound => "MODERATION_FLAG_NOT_FOUND",
+            ArcadeErrorCode::HumanityCheckRequired => "HUMANITY_CHECK_REQUIRED",
+            ArcadeErrorCode::InvalidHumanityCheckConfig => "INVALID_HUMANITY_CHECK_CONFIG",
+            ArcadeErrorCode::HumanityChallengeNotFound => "HUMANITY_CHALLENGE_NOT_FOUND",
+            ArcadeErrorCode::InvalidHumanityProof => "INVALID_HUMANITY_PROOF",
+            ArcadeErrorCode::DeviceIdRequired => "DEVICE_ID_REQUIRED",
+            ArcadeErrorCode::InvalidDeviceId => "INVALID_DEVICE_ID",
+            ArcadeErrorCode::DeviceBanned => "DEVICE_BANNED",
+            ArcadeErrorCode::DeviceRateLimited => "DEVICE_RATE_LIMITED",
+            ArcadeErrorCode::NotGateway => "NOT_GATEWAY",
+            ArcadeErrorCode::EmptyBatch => "EMPTY_BATCH",
+            ArcadeErrorCode::BatchTooLarge => "BATCH_TOO_LARGE",
+            ArcadeErrorCode::InvalidAttestation => "INVALID_ATTESTATION",
+            ArcadeErrorCode::StaleNonce => "STALE_NONCE",
+            ArcadeErrorCode::ScoreSignatureRequired => "SCORE_SIGNATURE_REQUIRED",
+            ArcadeErrorCode::InvalidScoreSignature => "INVALID_SCORE_SIGNATURE",
+            ArcadeErrorCode::InvalidVoucher => "INVALID_VOUCHER",
+            ArcadeErrorCode::VoucherExpired => "VOUCHER_EXPIRED",
+            ArcadeErrorCode::VoucherAlreadyUsed => "VOUCHER_ALREADY_USED",
+            ArcadeErrorCode::UsernameReserved => "USERNAME_RESERVED",
+            ArcadeErrorCode::ReservationActive => "RESERVATION_ACTIVE",
+            ArcadeErrorCode::UsernameTaken => "USERNAME_TAKEN",
+            ArcadeErrorCode::AccountNotInactive => "ACCOUNT_NOT_INACTIVE",
+            ArcadeErrorCode::InvalidUsernameRecyclingConfig => "INVALID_USERNAME_RECYCLING_CONFIG",
+            ArcadeErrorCode::AccountInGuild => "ACCOUNT_IN_GUILD",
+            ArcadeErrorCode::UsernameInAuction => "USERNAME_IN_AUCTION",
+            ArcadeErrorCode::UsernameBlocked => "USERNAME_BLOCKED",
+            ArcadeErrorCode::UsernameBlocklistFull => "USERNAME_BLOCKLIST_FULL",
+            ArcadeErrorCode::InvalidUsernameAuction => "INVALID_USERNAME_AUCTION",
+            ArcadeErrorCode::TooManyUsernameAuctions => "TOO_MANY_USERNAME_AUCTIONS",
+            ArcadeErrorCode::UsernameAuctionNotFound => "USERNAME_AUCTION_NOT_FOUND",
+            ArcadeErrorCode::UsernameAuctionPhase => "USERNAME_AUCTION_PHASE",
+            ArcadeErrorCode::UsernameAuctionAlreadySettled => "USERNAME_AUCTION_ALREADY_SETTLED",
+            ArcadeErrorCode::AlreadyBidOnUsername => "ALREADY_BID_ON_USERNAME",
+            ArcadeErrorCode::TooManyUsernameBids => "TOO_MANY_USERNAME_BIDS",
+            ArcadeErrorCode::UsernameBidNotFound => "USERNAME_BID_NOT_FOUND",
+            ArcadeErrorCode::InvalidUsernameBid => "INVALID_USERNAME_BID",
+            ArcadeErrorCode::InsufficientBidDeposit => "INSUFFICIENT_BID_DEPOSIT",
+            ArcadeErrorCode::WrongChallengeGame => "WRONG_CHALLENGE_GAME",
+            ArcadeErrorCode::ChallengeAlreadySubmitted => "CHALLENGE_ALREADY_SUBMITTED",
+            ArcadeErrorCode::CannotBlockSelf => "CANNOT_BLOCK_SELF",
+            ArcadeErrorCode::BlocklistFull => "BLOCKLIST_FULL",
+            ArcadeErrorCode::BlockedByPlayer => "BLOCKED_BY_PLAYER",
+            ArcadeErrorCode::BatchItemRejected => "BATCH_ITEM_REJECTED",
+            ArcadeErrorCode::DailyPlayLimitReached => "DAILY_PLAY_LIMIT_REACHED",
+            ArcadeErrorCode::PlayLimitCooldown => "PLAY_LIMIT_COOLDOWN",
+            ArcadeErrorCode::InvalidUtcOffset => "INVALID_UTC_OFFSET",
+            ArcadeErrorCode::TimezoneCooldown => "TIMEZONE_COOLDOWN",
+            ArcadeErrorCode::InvalidLocale => "INVALID_LOCALE",
+            ArcadeErrorCode::NotApplicationCaller => "NOT_APPLICATION_CALLER",
+            ArcadeErrorCode::TooManyBridgeChains => "TOO_MANY_BRIDGE_CHAINS",
+            ArcadeErrorCode::BridgeRequiresHub => "BRIDGE_REQUIRES_HUB",
+            ArcadeErrorCode::InvalidSyncSource => "INVALID_SYNC_SOURCE",
+            ArcadeErrorCode::InvalidHistoryRetention => "INVALID_HISTORY_RETENTION",
+            ArcadeErrorCode::InvalidSeasonLength => "INVALID_SEASON_LENGTH",
+            ArcadeErrorCode::SeasonsRequireHub => "SEASONS_REQUIRE_HUB",
+            ArcadeErrorCode::SeasonAlreadyRunning => "SEASON_ALREADY_RUNNING",
+            ArcadeErrorCode::NoActiveSeason => "NO_ACTIVE_SEASON",
+            ArcadeErrorCode::InvalidSeasonTheme => "INVALID_SEASON_THEME",
+            ArcadeErrorCode::SeasonAlreadyEnded => "SEASON_ALREADY_ENDED",
+            ArcadeErrorCode::InvalidRaffle => "INVALID_RAFFLE",
+            ArcadeErrorCode::RafflesRequireHub => "RAFFLES_REQUIRE_HUB",
+            ArcadeErrorCode::TooManyRaffles => "TOO_MANY_RAFFLES",
+            ArcadeErrorCode::InsufficientRafflePrize => "INSUFFICIENT_RAFFLE_PRIZE",
+            ArcadeErrorCode::RaffleNotFound => "RAFFLE_NOT_FOUND",
+            ArcadeErrorCode::RaffleNotEnded => "RAFFLE_NOT_ENDED",
+            ArcadeErrorCode::RaffleAlreadyDrawn => "RAFFLE_ALREADY_DRAWN",
+            ArcadeErrorCode::RaffleSecretMismatch => "RAFFLE_SECRET_MISMATCH",
+            ArcadeErrorCode::InvalidBossBattle => "INVALID_BOSS_BATTLE",
+            ArcadeErrorCode::BossBattlesRequireHub => "BOSS_BATTLES_REQUIRE_HUB",
+            ArcadeErrorCode::TooManyBossBattles => "TOO_MANY_BOSS_BATTLES",
+            ArcadeErrorCode::InvalidCommunityGame => "INVALID_COMMUNITY_GAME",
+            ArcadeErrorCode::CommunityGamesRequireHub => "COMMUNITY_GAMES_REQUIRE_HUB",
+            ArcadeErrorCode::TooManyPendingCommunityGames => "TOO_MANY_PENDING_COMMUNITY_GAMES",
+            ArcadeErrorCode::CommunityGameNotFound => "COMMUNITY_GAME_NOT_FOUND",
+            ArcadeErrorCode::CommunityGameAlreadyReviewed => "COMMUNITY_GAME_ALREADY_REVIEWED",
+            ArcadeErrorCode::CommunityGameNotApproved => "COMMUNITY_GAME_NOT_APPROVED",
+            ArcadeErrorCode::InvalidCommunityScore => "INVALID_COMMUNITY_SCORE",
+            ArcadeErrorCode::TooManyStreakShields => "TOO_MANY_STREAK_SHIELDS",
+            ArcadeErrorCode::StreakShieldLimitReached => "STREAK_SHIELD_LIMIT_REACHED",
+            ArcadeErrorCode::InsufficientShieldPayment => "INSUFFICIENT_SHIELD_PAYMENT",
+            ArcadeErrorCode::InvalidCrossover => "INVALID_CROSSOVER",
+            ArcadeErrorCode::TooManyCrossovers => "TOO_MANY_CROSSOVERS",
+            ArcadeErrorCode::CrossoverNotFound => "CROSSOVER_NOT_FOUND",
+            ArcadeErrorCode::InvalidGoal => "INVALID_GOAL",
+            ArcadeErrorCode::InvalidGoalDeadline => "INVALID_GOAL_DEADLINE",
+            ArcadeErrorCode::GoalAlreadyMet => "GOAL_ALREADY_MET",
+            ArcadeErrorCode::TooManyGoals => "TOO_MANY_GOALS",
+            ArcadeErrorCode::GoalNotFound => "GOAL_NOT_FOUND",
+            ArcadeErrorCode::CannotFollowSelf => "CANNOT_FOLLOW_SELF",
+            ArcadeErrorCode::FollowingListFull => "FOLLOWING_LIST_FULL",
+            ArcadeErrorCode::CannotFriendSelf => "CANNOT_FRIEND_SELF",
+            ArcadeErrorCode::FriendListFull => "FRIEND_LIST_FULL",
+            ArcadeErrorCode::TooManyFriendRequests => "TOO_MANY_FRIEND_REQUESTS",
+            ArcadeErrorCode::CannotKudosSelf => "CANNOT_KUDOS_SELF",
+            ArcadeErrorCode::KudosAlreadySent => "KUDOS_ALREADY_SENT",
+            ArcadeErrorCode::DailyKudosLimitReached => "DAILY_KUDOS_LIMIT_REACHED",
+            ArcadeErrorCode::InvalidReactionTarget => "INVALID_REACTION_TARGET",
+            ArcadeErrorCode::AlreadyReacted => "ALREADY_REACTED",
+            ArcadeErrorCode::TooManyReactions => "TOO_MANY_REACTIONS",
+            ArcadeErrorCode::ReactionNotFound => "REACTION_NOT_FOUND",
+            ArcadeErrorCode::CannotMentorSelf => "CANNOT_MENTOR_SELF",
+            ArcadeErrorCode::MentorLevelTooLow => "MENTOR_LEVEL_TOO_LOW",
+            ArcadeErrorCode::ApprenticeLevelTooHigh => "APPRENTICE_LEVEL_TOO_HIGH",
+            ArcadeErrorCode::AlreadyMentored => "ALREADY_MENTORED",
+            ArcadeErrorCode::TooManyApprentices => "TOO_MANY_APPRENTICES",
+            ArcadeErrorCode::TooManyMentorshipOffers => "TOO_MANY_MENTORSHIP_OFFERS",
+            ArcadeErrorCode::MentorshipOfferNotFound => "MENTORSHIP_OFFER_NOT_FOUND",
+            ArcadeErrorCode::MentorshipNotFound => "MENTORSHIP_NOT_FOUND",
+            ArcadeErrorCode::InvalidGuildName => "INVALID_GUILD_NAME",
+            ArcadeErrorCode::GuildNameTaken => "GUILD_NAME_TAKEN",
+            ArcadeErrorCode::GuildNotFound => "GUILD_NOT_FOUND",
+            ArcadeErrorCode::AlreadyInGuild => "ALREADY_IN_GUILD",
+            ArcadeErrorCode::NotInGuild => "NOT_IN_GUILD",
+            ArcadeErrorCode::GuildPermissionDenied => "GUILD_PERMISSION_DENIED",
+            ArcadeErrorCode::GuildInviteNotFound => "GUILD_INVITE_NOT_FOUND",
+            ArcadeErrorCode::TooManyGuildInvites => "TOO_MANY_GUILD_INVITES",
+            ArcadeErrorCode::GuildFull => "GUILD_FULL",
+            ArcadeErrorCode::GuildLeaderCannotLeave => "GUILD_LEADER_CANNOT_LEAVE",
+            ArcadeErrorCode::InvalidGuildAnnouncement => "INVALID_GUILD_ANNOUNCEMENT",
+            ArcadeErrorCode::InvalidGuildPayoutRule => "INVALID_GUILD_PAYOUT_RULE",
+            ArcadeErrorCode::InvalidGuildPrize => "INVALID_GUILD_PRIZE",
+            ArcadeErrorCode::GuildPrizesRequireHub => "GUILD_PRIZES_REQUIRE_HUB",
+            ArcadeErrorCode::InsufficientGuildPrize => "INSUFFICIENT_GUILD_PRIZE",
+            ArcadeErrorCode::GuildPrizeClaimNotFound => "GUILD_PRIZE_CLAIM_NOT_FOUND",
+            ArcadeErrorCode::GuildPrizeAlreadyClaimed => "GUILD_PRIZE_ALREADY_CLAIMED",
+            ArcadeErrorCode::ProgressLocked => "PROGRESS_LOCKED",
+            ArcadeErrorCode::InvalidMigrationProof => "INVALID_MIGRATION_PROOF",
+            ArcadeErrorCode::ProgressAlreadyImported => "PROGRESS_ALREADY_IMPORTED",
+            ArcadeErrorCode::InvalidInviteCodeLimits => "INVALID_INVITE_CODE_LIMITS",
+            ArcadeErrorCode::InviteCodeExists => "INVITE_CODE_EXISTS",
+            ArcadeErrorCode::InvalidInviteCode => "INVALID_INVITE_CODE",
+            ArcadeErrorCode::InviteCodeExpired => "INVITE_CODE_EXPIRED",
+            ArcadeErrorCode::NotInviteCodeCreator => "NOT_INVITE_CODE_CREATOR",
+            ArcadeErrorCode::InvalidPoll => "INVALID_POLL",
+            ArcadeErrorCode::InvalidPollDuration => "INVALID_POLL_DURATION",
+            ArcadeErrorCode::PollNotFound => "POLL_NOT_FOUND",
+            ArcadeErrorCode::PollClosed => "POLL_CLOSED",
+            ArcadeErrorCode::InvalidPollOption => "INVALID_POLL_OPTION",
+            ArcadeErrorCode::SessionDeviceMismatch => "SESSION_DEVICE_MISMATCH",
+            ArcadeErrorCode::RaffleRevealPending => "RAFFLE_REVEAL_PENDING",
+            ArcadeErrorCode::InvalidIdempotencyKey => "INVALID_IDEMPOTENCY_KEY",
+            ArcadeErrorCode::ScorePaidOut => "SCORE_PAID_OUT",
+            ArcadeErrorCode::Internal => "INTERNAL",
+        }
+    }
+}
+
+impl std::fmt::Display for ArcadeErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
     }
 }
 
@@ -291,3 +2662,27 @@ pub fn validate_username(username: &str) -> Result<(), ArcadeError> {
     }
     Ok(())
 }
+
+/// Validate a display name. Looser than [`validate_username`]: any printable
+/// characters are allowed.
+pub fn validate_display_name(display_name: &str) -> Result<(), ArcadeError> {
+    let len = display_name.trim().chars().count();
+    if !(1..=32).contains(&len) || display_name.chars().any(char::is_control) {
+        return Err(ArcadeError::InvalidDisplayName);
+    }
+    Ok(())
+}
+
+/// Check a hex-encoded BCS `AccountSignature` by `signer` over `payload`.
+pub fn check_account_signature<'de, T>(signature: &str, signer: &AccountOwner, payload: &T) -> bool
+where
+    T: BcsSignable<'de> + std::fmt::Debug,
+{
+    let Ok(bytes) = hex::decode(signature) else {
+        return false;
+    };
+    let Ok(signature) = AccountSignature::from_slice(&bytes) else {
+        return false;
+    };
+    &signature.owner() == signer && signature.verify(payload).is_ok()
+}
@@ -0,0 +1,82 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Self-imposed daily play limits.
+
+use async_graphql::{InputObject, SimpleObject};
+use serde::{Deserialize, Serialize};
+
+use crate::{ArcadeError, GameType};
+
+/// How long a player must wait after setting limits before loosening or
+/// removing them, in microseconds (7 days).
+pub const PLAY_LIMIT_COOLDOWN_MICROS: u64 = 7 * 24 * 60 * 60 * 1_000_000;
+
+/// Daily play limits a player sets on their own account.
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize, SimpleObject, InputObject,
+)]
+#[graphql(input_name = "PlayLimitsInput")]
+pub struct PlayLimits {
+    /// Maximum games per UTC day (0 = unlimited).
+    pub max_games_per_day: u32,
+    /// Maximum minutes of play per UTC day (0 = unlimited), estimated from
+    /// each game's typical round length.
+    pub max_minutes_per_day: u32,
+}
+
+impl PlayLimits {
+    /// Whether any limit is set.
+    pub fn is_limited(&self) -> bool {
+        self.max_games_per_day > 0 || self.max_minutes_per_day > 0
+    }
+
+    /// Whether `other` allows more play than `self` in any dimension.
+    pub fn is_loosened_by(&self, other: &PlayLimits) -> bool {
+        let loosened = |current: u32, new: u32| current > 0 && (new == 0 || new > current);
+        loosened(self.max_games_per_day, other.max_games_per_day)
+            || loosened(self.max_minutes_per_day, other.max_minutes_per_day)
+    }
+}
+
+/// A player's play on a single day in their timezone.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, SimpleObject)]
+pub struct DailyPlay {
+    /// Days since the Unix epoch, in the player's timezone.
+    pub day: u64,
+    pub games: u32,
+    pub seconds: u64,
+}
+
+impl DailyPlay {
+    /// Count a game of `game_type` played on `today`, enforcing `limits`.
+    pub fn record(
+        &mut self,
+        limits: &PlayLimits,
+        game_type: GameType,
+        today: u64,
+    ) -> Result<(), ArcadeError> {
+        if self.day != today {
+            *self = DailyPlay {
+                day: today,
+                ..DailyPlay::default()
+            };
+        }
+
+        // A game may start as long as some allowance is left for the day
+        if limits.max_games_per_day > 0 && self.games >= limits.max_games_per_day {
+            return Err(ArcadeError::DailyPlayLimitReached);
+        }
+        if limits.max_minutes_per_day > 0
+            && self.seconds >= u64::from(limits.max_minutes_per_day) * 60
+        {
+            return Err(ArcadeError::DailyPlayLimitReached);
+        }
+
+        self.games = self.games.saturating_add(1);
+        self.seconds = self
+            .seconds
+            .saturating_add(game_type.typical_duration_secs());
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Locale preferences and the local calendar periods summaries bucket XP
+//! into.
+//!
+//! A player's locale tells clients how to format numbers and dates, and
+//! which day the player's week starts on. Summary queries bucket the
+//! player's XP by local day, week and month on the chain, following their
+//! timezone and first day of the week, so clients get the totals and the
+//! period boundaries without doing any date math themselves.
+
+use async_graphql::{Enum, InputObject, SimpleObject};
+use serde::{Deserialize, Serialize};
+
+use crate::{day_index, streaks, ArcadeError, MICROS_PER_DAY};
+
+/// Longest a language tag can be, in characters.
+pub const MAX_LANGUAGE_TAG_LENGTH: usize = 35;
+
+/// The day a week starts on.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Enum)]
+pub enum FirstDayOfWeek {
+    #[default]
+    Monday,
+    Sunday,
+    Saturday,
+}
+
+impl FirstDayOfWeek {
+    /// Days from the start of the week to `day` (days since the Unix epoch).
+    fn days_into_week(self, day: u64) -> u64 {
+        // The Unix epoch was a Thursday
+        let days_after_monday = (day + 3) % 7;
+        match self {
+            FirstDayOfWeek::Monday => days_after_monday,
+            FirstDayOfWeek::Sunday => (days_after_monday + 1) % 7,
+            FirstDayOfWeek::Saturday => (days_after_monday + 2) % 7,
+        }
+    }
+}
+
+/// How a player's numbers and dates are presented.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, SimpleObject, InputObject)]
+#[graphql(input_name = "LocaleSettingsInput")]
+pub struct LocaleSettings {
+    /// BCP 47 language tag clients format numbers and dates for, such as
+    /// `en-US` or `pt-BR`.
+    pub language_tag: String,
+    pub first_day_of_week: FirstDayOfWeek,
+}
+
+impl Default for LocaleSettings {
+    fn default() -> Self {
+        Self {
+            language_tag: "en".to_string(),
+            first_day_of_week: FirstDayOfWeek::Monday,
+        }
+    }
+}
+
+impl LocaleSettings {
+    /// Check the language tag is shaped like a BCP 47 tag: a 2 or 3 letter
+    /// language followed by `-`-separated alphanumeric subtags.
+    pub fn validate(&self) -> Result<(), ArcadeError> {
+        let mut subtags = self.language_tag.split('-');
+        let language_ok = subtags.next().is_some_and(|language| {
+            (2..=3).contains(&language.len()) && language.chars().all(|c| c.is_ascii_alphabetic())
+        });
+        let subtags_ok = subtags.all(|subtag| {
+            (1..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        });
+        if !language_ok || !subtags_ok || self.language_tag.len() > MAX_LANGUAGE_TAG_LENGTH {
+            return Err(ArcadeError::InvalidLocale);
+        }
+        Ok(())
+    }
+}
+
+/// A player's XP in the local day, week and month containing the time of
+/// the query, along with when each period started.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct XpBuckets {
+    pub xp_today: u64,
+    pub xp_this_week: u64,
+    pub xp_this_month: u64,
+    pub day_starts_at: u64,
+    pub week_starts_at: u64,
+    pub month_starts_at: u64,
+}
+
+impl XpBuckets {
+    /// Empty buckets for the periods containing `now`, for a player
+    /// `utc_offset_minutes` ahead of UTC whose week starts on
+    /// `first_day_of_week`.
+    pub fn new(now: u64, utc_offset_minutes: i32, first_day_of_week: FirstDayOfWeek) -> Self {
+        let offset = i64::from(utc_offset_minutes) * 60 * 1_000_000;
+        let today = day_index(now.saturating_add_signed(offset));
+        let week_start = today.saturating_sub(first_day_of_week.days_into_week(today));
+        let month = streaks::month_index(today);
+        let mut month_start = today;
+        while month_start > 0 && streaks::month_index(month_start - 1) == month {
+            month_start -= 1;
+        }
+        let starts_at = |day: u64| (day * MICROS_PER_DAY).saturating_add_signed(-offset);
+        Self {
+            xp_today: 0,
+            xp_this_week: 0,
+            xp_this_month: 0,
+            day_starts_at: starts_at(today),
+            week_starts_at: starts_at(week_start),
+            month_starts_at: starts_at(month_start),
+        }
+    }
+
+    /// When the earliest of the periods started; older runs fall in none.
+    pub fn earliest_start(&self) -> u64 {
+        self.week_starts_at.min(self.month_starts_at)
+    }
+
+    /// Add `xp` earned at `timestamp` to the periods it falls in.
+    pub fn record(&mut self, timestamp: u64, xp: u64) {
+        if timestamp >= self.day_starts_at {
+            self.xp_today = self.xp_today.saturating_add(xp);
+        }
+        if timestamp >= self.week_starts_at {
+            self.xp_this_week = self.xp_this_week.saturating_add(xp);
+        }
+        if timestamp >= self.month_starts_at {
+            self.xp_this_month = self.xp_this_month.saturating_add(xp);
+        }
+    }
+}
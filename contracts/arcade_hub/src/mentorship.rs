@@ -0,0 +1,139 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Mentorships: an experienced player sponsoring a newcomer, with both
+//! earning bonus XP from the apprentice's runs until the apprentice reaches
+//! [`GRADUATION_LEVEL`].
+//!
+//! A mentor offers, the apprentice accepts. Pairings live on the chain the
+//! apprentice plays on, so both players must be registered there.
+
+use std::collections::BTreeSet;
+
+use async_graphql::SimpleObject;
+use linera_sdk::linera_base_types::AccountOwner;
+use serde::{Deserialize, Serialize};
+
+use crate::{ArcadeError, Player};
+
+/// Level a player must have reached to take on apprentices.
+pub const MIN_MENTOR_LEVEL: u32 = 10;
+
+/// Level at which an apprentice graduates and the mentorship ends.
+pub const GRADUATION_LEVEL: u32 = 5;
+
+/// Apprentices a mentor can have at once.
+pub const MAX_APPRENTICES: usize = 3;
+
+/// Pending offers an apprentice can hold at once.
+pub const MAX_MENTORSHIP_OFFERS: usize = 10;
+
+/// Bonus XP, in percent of each of the apprentice's runs, earned by the
+/// apprentice and by the mentor.
+pub const MENTORSHIP_BONUS_PERCENT: u64 = 10;
+
+/// An active pairing, keyed by the apprentice.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct Mentorship {
+    pub mentor: AccountOwner,
+    pub apprentice: AccountOwner,
+    pub started_at: u64,
+    /// Bonus XP the mentor has earned from this apprentice so far.
+    pub mentor_xp_earned: u64,
+}
+
+/// A mentor's current apprentices and track record.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct MentorRecord {
+    pub mentor: AccountOwner,
+    /// Current apprentices, in the order they were taken on.
+    pub apprentices: Vec<AccountOwner>,
+    /// Apprentices who reached [`GRADUATION_LEVEL`] under this mentor.
+    pub graduates: u32,
+    /// Bonus XP earned from apprentices, over every mentorship.
+    pub bonus_xp: u64,
+}
+
+impl MentorRecord {
+    pub fn new(mentor: AccountOwner) -> Self {
+        Self {
+            mentor,
+            apprentices: Vec::new(),
+            graduates: 0,
+            bonus_xp: 0,
+        }
+    }
+
+    /// Whether the mentor has as many apprentices as they can take on.
+    pub fn is_full(&self) -> bool {
+        self.apprentices.len() >= MAX_APPRENTICES
+    }
+
+    /// Take on `apprentice`, if there is room for another.
+    pub fn add_apprentice(&mut self, apprentice: AccountOwner) -> Result<(), ArcadeError> {
+        if self.is_full() {
+            return Err(ArcadeError::TooManyApprentices);
+        }
+        self.apprentices.push(apprentice);
+        Ok(())
+    }
+
+    /// Let `apprentice` go, counting them as a graduate if they graduated.
+    pub fn remove_apprentice(&mut self, apprentice: &AccountOwner, graduated: bool) {
+        self.apprentices.retain(|candidate| candidate != apprentice);
+        if graduated {
+            self.graduates = self.graduates.saturating_add(1);
+        }
+    }
+}
+
+/// The mentors who have offered to take a player on.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MentorshipOffers {
+    mentors: BTreeSet<AccountOwner>,
+}
+
+impl MentorshipOffers {
+    /// Record an offer from `mentor`. Repeating an offer is a no-op.
+    pub fn offer(&mut self, mentor: AccountOwner) -> Result<(), ArcadeError> {
+        if !self.mentors.contains(&mentor) && self.mentors.len() >= MAX_MENTORSHIP_OFFERS {
+            return Err(ArcadeError::TooManyMentorshipOffers);
+        }
+        self.mentors.insert(mentor);
+        Ok(())
+    }
+
+    /// Drop the offer from `mentor`, returning whether there was one.
+    pub fn withdraw(&mut self, mentor: &AccountOwner) -> bool {
+        self.mentors.remove(mentor)
+    }
+
+    /// Whether no offers are pending.
+    pub fn is_empty(&self) -> bool {
+        self.mentors.is_empty()
+    }
+
+    /// The mentors with pending offers, in a stable order.
+    pub fn mentors(&self) -> Vec<AccountOwner> {
+        self.mentors.iter().copied().collect()
+    }
+}
+
+/// Check that `mentor` may offer to mentor `apprentice`.
+pub fn check_pairing(mentor: &Player, apprentice: &Player) -> Result<(), ArcadeError> {
+    if mentor.owner == apprentice.owner {
+        return Err(ArcadeError::CannotMentorSelf);
+    }
+    if mentor.level < MIN_MENTOR_LEVEL {
+        return Err(ArcadeError::MentorLevelTooLow);
+    }
+    if apprentice.level >= GRADUATION_LEVEL {
+        return Err(ArcadeError::ApprenticeLevelTooHigh);
+    }
+    Ok(())
+}
+
+/// The mentorship bonus on a run that earned `xp`.
+pub fn bonus_xp(xp: u64) -> u64 {
+    xp.saturating_mul(MENTORSHIP_BONUS_PERCENT) / 100
+}
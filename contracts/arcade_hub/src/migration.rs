@@ -0,0 +1,117 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Moving a player's progression between arcade deployments.
+//!
+//! A player leaving a deployment exports their progress: the chain takes a
+//! snapshot of their record, commits to its hash, and locks the record so
+//! it can't earn anything more there, holding the progress in escrow. The
+//! old deployment's admin signs a [`MigrationProof`] for the snapshot with
+//! their key, naming the deployment it may be imported into. The player then
+//! presents the snapshot and signature on the new deployment, whose admin
+//! has named the old admin key as its migration source, and the snapshot's
+//! XP and games are added to their record there. Each snapshot can only be
+//! imported once: off the hub, the hub confirms no chain imported it before
+//! it is credited.
+
+use async_graphql::{InputObject, SimpleObject};
+use linera_sdk::linera_base_types::{
+    AccountOwner, ApplicationId, BcsSignable, ChainId, CryptoHash,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{check_account_signature, ArcadeError, Player};
+
+/// A player's record as exported from a deployment.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject, InputObject)]
+#[graphql(input_name = "ProgressSnapshotInput")]
+pub struct ProgressSnapshot {
+    pub player: AccountOwner,
+    pub username: String,
+    pub total_xp: u64,
+    pub level: u32,
+    pub games_played: u64,
+    pub registered_at: u64,
+    /// The deployment and chain the record was exported from.
+    pub source_application: ApplicationId,
+    pub source_chain: ChainId,
+    pub exported_at: u64,
+}
+
+impl ProgressSnapshot {
+    /// Snapshot `player` as exported from `source_application` on
+    /// `source_chain` at `timestamp`.
+    pub fn new(
+        player: &Player,
+        source_application: ApplicationId,
+        source_chain: ChainId,
+        timestamp: u64,
+    ) -> Self {
+        Self {
+            player: player.owner,
+            username: player.username.clone(),
+            total_xp: player.total_xp,
+            level: player.level,
+            games_played: player.games_played,
+            registered_at: player.registered_at,
+            source_application,
+            source_chain,
+            exported_at: timestamp,
+        }
+    }
+
+    /// The hash the export commits to.
+    pub fn hash(&self) -> CryptoHash {
+        CryptoHash::new(self)
+    }
+
+    /// Check `signature` is `source_admin`'s proof that the snapshot may be
+    /// imported into `destination`, returning the snapshot's hash.
+    pub fn verify(
+        &self,
+        source_admin: &AccountOwner,
+        destination: ApplicationId,
+        signature: &str,
+    ) -> Result<CryptoHash, ArcadeError> {
+        let record_hash = self.hash();
+        let proof = MigrationProof {
+            record_hash,
+            destination,
+        };
+        if !check_account_signature(signature, source_admin, &proof) {
+            return Err(ArcadeError::InvalidMigrationProof);
+        }
+        Ok(record_hash)
+    }
+}
+
+impl BcsSignable<'_> for ProgressSnapshot {}
+
+/// A player's exported progress, locked on the deployment they left.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct ProgressExport {
+    pub snapshot: ProgressSnapshot,
+    /// The hash the old deployment's admin signs.
+    pub record_hash: CryptoHash,
+}
+
+/// The payload the old deployment's admin signs to release an export.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MigrationProof {
+    pub record_hash: CryptoHash,
+    /// The deployment the export may be imported into.
+    pub destination: ApplicationId,
+}
+
+impl BcsSignable<'_> for MigrationProof {}
+
+/// Progress imported from another deployment.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct ProgressImport {
+    pub player: AccountOwner,
+    pub record_hash: CryptoHash,
+    pub source_application: ApplicationId,
+    pub xp: u64,
+    pub games_played: u64,
+    pub imported_at: u64,
+}
@@ -0,0 +1,58 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Arcade-wide milestones and the players who reached them.
+
+use async_graphql::{Enum, SimpleObject};
+use linera_sdk::linera_base_types::AccountOwner;
+use serde::{Deserialize, Serialize};
+
+/// Game counts celebrated as milestones.
+pub const GAMES_PLAYED_MILESTONES: [u64; 5] = [1_000, 10_000, 100_000, 1_000_000, 10_000_000];
+
+/// XP totals celebrated as milestones.
+pub const XP_EARNED_MILESTONES: [u64; 5] = [
+    1_000_000,
+    10_000_000,
+    100_000_000,
+    1_000_000_000,
+    10_000_000_000,
+];
+
+/// The arcade-wide counter a milestone is measured on.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Enum)]
+pub enum MilestoneKind {
+    /// Total games played.
+    GamesPlayed,
+    /// Total XP earned.
+    XpEarned,
+}
+
+impl MilestoneKind {
+    /// The thresholds celebrated for this counter.
+    pub fn thresholds(&self) -> &'static [u64] {
+        match self {
+            MilestoneKind::GamesPlayed => &GAMES_PLAYED_MILESTONES,
+            MilestoneKind::XpEarned => &XP_EARNED_MILESTONES,
+        }
+    }
+
+    /// The thresholds crossed when the counter moves from `before` to `after`.
+    pub fn crossed(&self, before: u64, after: u64) -> impl Iterator<Item = u64> {
+        self.thresholds()
+            .iter()
+            .copied()
+            .filter(move |threshold| before < *threshold && *threshold <= after)
+    }
+}
+
+/// A commemorative record of the player who pushed a counter past a milestone.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct Milestone {
+    pub kind: MilestoneKind,
+    pub threshold: u64,
+    pub player: AccountOwner,
+    /// The score that crossed the threshold.
+    pub score_id: u64,
+    pub reached_at: u64,
+}
@@ -0,0 +1,266 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sealed-bid auctions for premium usernames.
+//!
+//! The admin puts a handle up for auction, which stops anyone from
+//! reserving or registering it until the auction is settled. Bidding is
+//! two-phase: while bidding is open, players commit to the hash of their
+//! bid and escrow a deposit of at least that amount, so the deposit is all
+//! anyone sees; once bidding closes they reveal the bid. The highest
+//! revealed bid at or above the reserve price wins and pays its own bid to
+//! the admin who started the auction, and the handle is reserved for the
+//! winner for good. Revealed losing bids are refunded in full, while the
+//! deposits of bids never revealed go to the admin, so committing to bids
+//! with no intention of revealing them costs something.
+
+use async_graphql::{Enum, InputObject, SimpleObject};
+use linera_sdk::linera_base_types::{AccountOwner, Amount, BcsSignable, CryptoHash};
+use serde::{Deserialize, Serialize};
+
+use crate::{validate_username, ArcadeError};
+
+/// Username auctions that can be running without having been settled.
+pub const MAX_OPEN_USERNAME_AUCTIONS: u64 = 10;
+
+/// Most bids one auction accepts.
+pub const MAX_BIDS_PER_AUCTION: usize = 100;
+
+/// A username auction as the admin describes it.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject, InputObject)]
+#[graphql(input_name = "UsernameAuctionSpecInput")]
+pub struct UsernameAuctionSpec {
+    pub username: String,
+    /// When sealed bids stop being accepted and reveals start.
+    pub bidding_ends_at: u64,
+    /// When reveals stop being accepted and the auction can be settled.
+    pub reveal_ends_at: u64,
+    /// The lowest bid that can win.
+    pub reserve_price: Amount,
+}
+
+impl UsernameAuctionSpec {
+    /// Check the auction is well formed and bidding is still open at `now`.
+    pub fn validate(&self, now: u64) -> Result<(), ArcadeError> {
+        validate_username(&self.username)?;
+        if self.bidding_ends_at <= now || self.reveal_ends_at <= self.bidding_ends_at {
+            return Err(ArcadeError::InvalidUsernameAuction);
+        }
+        Ok(())
+    }
+}
+
+/// Where an auction is in its lifecycle.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Enum)]
+pub enum UsernameAuctionPhase {
+    /// Accepting sealed bids.
+    Bidding,
+    /// Accepting reveals of the sealed bids.
+    Revealing,
+    /// Waiting to be settled.
+    Ended,
+    Settled,
+}
+
+/// A sealed bid and, once revealed, its amount.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct SealedBid {
+    pub bidder: AccountOwner,
+    /// The [`bid_commitment`] to the bid.
+    pub commitment: CryptoHash,
+    /// The amount escrowed with the bid, at least the bid itself.
+    pub deposit: Amount,
+    pub placed_at: u64,
+    pub revealed_amount: Option<Amount>,
+}
+
+/// The outcome of a settled auction.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct UsernameAuctionResult {
+    /// The player the username is now reserved for, if any bid at or
+    /// above the reserve price was revealed.
+    pub winner: Option<AccountOwner>,
+    /// What the winner paid.
+    pub price: Amount,
+    /// Deposits of bids never revealed, paid to the admin.
+    pub forfeited: Amount,
+    pub settled_at: u64,
+}
+
+/// The transfers that settle an auction.
+#[derive(Clone, Debug)]
+pub struct UsernameAuctionPayouts {
+    pub result: UsernameAuctionResult,
+    /// Deposits returned to bidders, less the winner's price.
+    pub refunds: Vec<(AccountOwner, Amount)>,
+    /// The winning price and forfeited deposits.
+    pub to_beneficiary: Amount,
+}
+
+/// An auction for one premium username.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct UsernameAuction {
+    pub id: u64,
+    pub spec: UsernameAuctionSpec,
+    /// The admin who started the auction and is paid its proceeds.
+    pub beneficiary: AccountOwner,
+    pub bids: Vec<SealedBid>,
+    pub result: Option<UsernameAuctionResult>,
+}
+
+impl UsernameAuction {
+    /// An auction yet to receive bids.
+    pub fn new(id: u64, spec: UsernameAuctionSpec, beneficiary: AccountOwner) -> Self {
+        Self {
+            id,
+            spec,
+            beneficiary,
+            bids: Vec::new(),
+            result: None,
+        }
+    }
+
+    /// The auction's phase at `now`.
+    pub fn phase(&self, now: u64) -> UsernameAuctionPhase {
+        if self.result.is_some() {
+            UsernameAuctionPhase::Settled
+        } else if now < self.spec.bidding_ends_at {
+            UsernameAuctionPhase::Bidding
+        } else if now < self.spec.reveal_ends_at {
+            UsernameAuctionPhase::Revealing
+        } else {
+            UsernameAuctionPhase::Ended
+        }
+    }
+
+    /// Check `bidder` can place a bid with `deposit` at `now`.
+    pub fn check_bid(
+        &self,
+        bidder: &AccountOwner,
+        deposit: Amount,
+        now: u64,
+    ) -> Result<(), ArcadeError> {
+        if self.phase(now) != UsernameAuctionPhase::Bidding {
+            return Err(ArcadeError::UsernameAuctionPhase);
+        }
+        if deposit.is_zero() {
+            return Err(ArcadeError::InvalidUsernameBid);
+        }
+        if self.bids.iter().any(|bid| bid.bidder == *bidder) {
+            return Err(ArcadeError::AlreadyBidOnUsername);
+        }
+        if self.bids.len() >= MAX_BIDS_PER_AUCTION {
+            return Err(ArcadeError::TooManyUsernameBids);
+        }
+        Ok(())
+    }
+
+    /// Reveal `bidder`'s bid of `amount`, committed to with `salt`.
+    pub fn reveal(
+        &mut self,
+        bidder: &AccountOwner,
+        amount: Amount,
+        salt: &str,
+        now: u64,
+    ) -> Result<(), ArcadeError> {
+        if self.phase(now) != UsernameAuctionPhase::Revealing {
+            return Err(ArcadeError::UsernameAuctionPhase);
+        }
+        let commitment = bid_commitment(self.id, *bidder, amount, salt);
+        let Some(bid) = self.bids.iter_mut().find(|bid| bid.bidder == *bidder) else {
+            return Err(ArcadeError::UsernameBidNotFound);
+        };
+        if bid.revealed_amount.is_some() || bid.commitment != commitment || amount > bid.deposit {
+            return Err(ArcadeError::InvalidUsernameBid);
+        }
+        bid.revealed_amount = Some(amount);
+        Ok(())
+    }
+
+    /// Work out the transfers that settle the auction at `now`, recording
+    /// its result.
+    pub fn settle(&mut self, now: u64) -> Result<UsernameAuctionPayouts, ArcadeError> {
+        match self.phase(now) {
+            UsernameAuctionPhase::Ended => {}
+            UsernameAuctionPhase::Settled => {
+                return Err(ArcadeError::UsernameAuctionAlreadySettled);
+            }
+            _ => return Err(ArcadeError::UsernameAuctionPhase),
+        }
+
+        // The highest revealed bid wins, the earliest of equal bids first
+        let winning_bid = self
+            .bids
+            .iter()
+            .enumerate()
+            .filter_map(|(index, bid)| Some((index, bid.bidder, bid.revealed_amount?)))
+            .filter(|(_, _, amount)| *amount >= self.spec.reserve_price)
+            .max_by_key(|(index, _, amount)| (*amount, std::cmp::Reverse(*index)))
+            .map(|(_, bidder, amount)| (bidder, amount));
+
+        let mut refunds = Vec::new();
+        let mut forfeited = Amount::ZERO;
+        for bid in &self.bids {
+            if bid.revealed_amount.is_none() {
+                forfeited = forfeited.saturating_add(bid.deposit);
+                continue;
+            }
+            let refund = match winning_bid {
+                Some((winner, price)) if winner == bid.bidder => bid.deposit.saturating_sub(price),
+                _ => bid.deposit,
+            };
+            if !refund.is_zero() {
+                refunds.push((bid.bidder, refund));
+            }
+        }
+
+        let price = winning_bid.map_or(Amount::ZERO, |(_, price)| price);
+        let result = UsernameAuctionResult {
+            winner: winning_bid.map(|(winner, _)| winner),
+            price,
+            forfeited,
+            settled_at: now,
+        };
+        self.result = Some(result.clone());
+        Ok(UsernameAuctionPayouts {
+            result,
+            refunds,
+            to_beneficiary: price.saturating_add(forfeited),
+        })
+    }
+}
+
+/// A username auction and its phase when queried.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct UsernameAuctionStatus {
+    pub auction: UsernameAuction,
+    pub phase: UsernameAuctionPhase,
+}
+
+/// The commitment a bidder places for a bid of `amount` on auction
+/// `auction_id`, salted with a secret `salt` so the amount can't be
+/// guessed from it.
+pub fn bid_commitment(
+    auction_id: u64,
+    bidder: AccountOwner,
+    amount: Amount,
+    salt: &str,
+) -> CryptoHash {
+    CryptoHash::new(&BidPreimage {
+        auction_id,
+        bidder,
+        amount,
+        salt: salt.to_string(),
+    })
+}
+
+/// The value a bid's commitment is hashed from.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct BidPreimage {
+    auction_id: u64,
+    bidder: AccountOwner,
+    amount: Amount,
+    salt: String,
+}
+
+impl BcsSignable<'_> for BidPreimage {}
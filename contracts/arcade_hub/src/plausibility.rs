@@ -0,0 +1,98 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Plausibility bounds on submitted scores.
+//!
+//! Scores are reported by the client, so nothing stops a submission
+//! claiming a million clicks in ten seconds. Each game has bounds on its
+//! raw score and bonus data that no human run can exceed, and submissions
+//! outside them are rejected before any XP is awarded. The defaults can be
+//! overridden per game when the application is instantiated.
+
+use async_graphql::SimpleObject;
+use serde::{Deserialize, Serialize};
+
+use crate::{ArcadeError, GameType, ScoreSubmission};
+
+/// The range of plausible scores and bonus data for one game.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, SimpleObject)]
+pub struct ScoreBounds {
+    pub game_type: GameType,
+    pub min_score: u64,
+    pub max_score: u64,
+    /// Highest plausible bonus data.
+    pub max_bonus: u64,
+    /// Whether the bonus data counts a subset of what the score counts
+    /// (perfect rounds of the levels reached, the best streak of correct
+    /// answers), and so can't exceed it.
+    pub bonus_within_score: bool,
+}
+
+impl ScoreBounds {
+    /// The default bounds for `game_type`.
+    pub fn default_for(game_type: GameType) -> Self {
+        let (min_score, max_score, max_bonus, bonus_within_score) = match game_type {
+            // score = clicks in 10s, at most 20 clicks a second
+            GameType::SpeedClicker => (0, 200, 0, false),
+            // score = level reached, bonus = perfect rounds
+            GameType::MemoryMatrix => (0, 50, 50, true),
+            // score = average reaction in ms, no faster than 100ms;
+            // bonus = targets hit in a 45s round
+            GameType::ReactionStrike => (100, 5_000, 100, false),
+            // score = correct answers in 60s, bonus = best streak
+            GameType::MathBlitz => (0, 120, 120, true),
+            // score = snake length, bonus = apples eaten in a 120s round
+            GameType::SnakeSprint => (0, 400, 400, false),
+        };
+        Self {
+            game_type,
+            min_score,
+            max_score,
+            max_bonus,
+            bonus_within_score,
+        }
+    }
+
+    /// Check a submission falls within the bounds.
+    pub fn check(&self, submission: &ScoreSubmission) -> Result<(), ArcadeError> {
+        let bonus = submission.bonus_data.unwrap_or(0);
+        let score_ok = (self.min_score..=self.max_score).contains(&submission.score);
+        let bonus_ok =
+            bonus <= self.max_bonus && (!self.bonus_within_score || bonus <= submission.score);
+        if !score_ok || !bonus_ok {
+            return Err(ArcadeError::ImplausibleScore);
+        }
+        Ok(())
+    }
+}
+
+/// The bounds set at instantiation, replacing the defaults of the games
+/// they name.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PlausibilityConfig {
+    overrides: Vec<ScoreBounds>,
+}
+
+impl PlausibilityConfig {
+    /// A configuration with the given overrides. Later overrides of a game
+    /// replace earlier ones.
+    pub fn new(overrides: Vec<ScoreBounds>) -> Self {
+        let mut config = Self::default();
+        for bounds in overrides {
+            config
+                .overrides
+                .retain(|existing| existing.game_type != bounds.game_type);
+            config.overrides.push(bounds);
+        }
+        config
+    }
+
+    /// The bounds in force for `game_type`.
+    pub fn bounds_for(&self, game_type: GameType) -> ScoreBounds {
+        self.overrides
+            .iter()
+            .find(|bounds| bounds.game_type == game_type)
+            .copied()
+            .unwrap_or_else(|| ScoreBounds::default_for(game_type))
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Polls within a guild: officers ask a question, members vote until the
+//! deadline, and the contract keeps the tally.
+
+use async_graphql::SimpleObject;
+use linera_sdk::linera_base_types::AccountOwner;
+use serde::{Deserialize, Serialize};
+
+use crate::ArcadeError;
+
+/// Maximum length of a poll question, in characters.
+pub const MAX_QUESTION_LENGTH: usize = 140;
+
+/// Maximum length of a poll option, in characters.
+pub const MAX_OPTION_LENGTH: usize = 60;
+
+/// Maximum number of options in a poll.
+pub const MAX_POLL_OPTIONS: usize = 10;
+
+/// Longest a poll can stay open, in hours (7 days).
+pub const MAX_POLL_DURATION_HOURS: u32 = 7 * 24;
+
+/// Microseconds in an hour.
+const MICROS_PER_HOUR: u64 = 60 * 60 * 1_000_000;
+
+/// One member's vote.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct PollVote {
+    pub voter: AccountOwner,
+    /// Index into the poll's options.
+    pub option: u32,
+}
+
+/// A question put to a guild's members.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct GuildPoll {
+    pub id: u64,
+    pub guild_id: u64,
+    pub question: String,
+    pub options: Vec<String>,
+    /// Votes per option, indexed like `options`.
+    pub tallies: Vec<u32>,
+    /// Every vote cast, one per member.
+    pub votes: Vec<PollVote>,
+    pub created_by: AccountOwner,
+    pub created_at: u64,
+    /// Votes are accepted until this timestamp.
+    pub closes_at: u64,
+}
+
+/// A poll with its standing at the time of the query.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct GuildPollResult {
+    pub poll: GuildPoll,
+    /// Whether votes are still accepted.
+    pub open: bool,
+    /// The winning option so far, or `None` if there are no votes or the
+    /// lead is tied.
+    pub winner: Option<u32>,
+}
+
+impl GuildPoll {
+    /// Create a poll open for `duration_hours` from `timestamp`, validating
+    /// its question, options and duration.
+    pub fn new(
+        id: u64,
+        guild_id: u64,
+        question: String,
+        options: Vec<String>,
+        duration_hours: u32,
+        created_by: AccountOwner,
+        timestamp: u64,
+    ) -> Result<Self, ArcadeError> {
+        let text_ok =
+            |text: &str, max: usize| !text.trim().is_empty() && text.chars().count() <= max;
+        if !text_ok(&question, MAX_QUESTION_LENGTH)
+            || !(2..=MAX_POLL_OPTIONS).contains(&options.len())
+            || !options
+                .iter()
+                .all(|option| text_ok(option, MAX_OPTION_LENGTH))
+        {
+            return Err(ArcadeError::InvalidPoll);
+        }
+        if !(1..=MAX_POLL_DURATION_HOURS).contains(&duration_hours) {
+            return Err(ArcadeError::InvalidPollDuration);
+        }
+
+        Ok(Self {
+            id,
+            guild_id,
+            question,
+            tallies: vec![0; options.len()],
+            options,
+            votes: Vec::new(),
+            created_by,
+            created_at: timestamp,
+            closes_at: timestamp
+                .saturating_add(u64::from(duration_hours).saturating_mul(MICROS_PER_HOUR)),
+        })
+    }
+
+    /// Whether votes are still accepted at `timestamp`.
+    pub fn is_open(&self, timestamp: u64) -> bool {
+        timestamp < self.closes_at
+    }
+
+    /// Cast `voter`'s vote, replacing any earlier vote of theirs.
+    pub fn vote(
+        &mut self,
+        voter: AccountOwner,
+        option: u32,
+        timestamp: u64,
+    ) -> Result<(), ArcadeError> {
+        if !self.is_open(timestamp) {
+            return Err(ArcadeError::PollClosed);
+        }
+        if option as usize >= self.options.len() {
+            return Err(ArcadeError::InvalidPollOption);
+        }
+        match self.votes.iter_mut().find(|vote| vote.voter == voter) {
+            Some(vote) => {
+                self.tallies[vote.option as usize] -= 1;
+                vote.option = option;
+            }
+            None => self.votes.push(PollVote { voter, option }),
+        }
+        self.tallies[option as usize] += 1;
+        Ok(())
+    }
+
+    /// The option with the most votes, or `None` if there are no votes or
+    /// the lead is tied.
+    pub fn winner(&self) -> Option<u32> {
+        let most = *self.tallies.iter().max()?;
+        let mut leaders = self
+            .tallies
+            .iter()
+            .enumerate()
+            .filter(|(_, tally)| **tally == most);
+        match (leaders.next(), leaders.next()) {
+            (Some((index, _)), None) if most > 0 => Some(index as u32),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,226 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Raffles: admin-funded events where every game played during the window
+//! earns tickets and one ticket wins the prize pool.
+//!
+//! The draw is commit-reveal. When scheduling a raffle the admin moves the
+//! prize into escrow and commits to the hash of a secret. Every ticket award
+//! is chained into a digest, which is fixed once the raffle ends; the
+//! winning ticket is drawn from the secret together with that digest, so
+//! players can't predict it before the reveal. The admin knows the secret,
+//! so they can't hold tickets themselves, but the draw is only as fair as
+//! the admin is: anyone else playing for them near the end could still aim
+//! the digest at a ticket of theirs.
+//!
+//! Anyone holding the secret can draw once the raffle has ended. If it
+//! hasn't been revealed within [`RAFFLE_REVEAL_WINDOW_MICROS`] of the end,
+//! anyone can draw without it, from the digest alone, so withholding the
+//! secret doesn't keep the prize from the players.
+
+use async_graphql::{InputObject, SimpleObject};
+use linera_sdk::linera_base_types::{AccountOwner, Amount, BcsSignable, CryptoHash};
+use serde::{Deserialize, Serialize};
+
+use crate::{ArcadeError, MICROS_PER_DAY};
+
+/// Raffles that can be scheduled or running without having been drawn.
+pub const MAX_OPEN_RAFFLES: u64 = 5;
+
+/// Most tickets a raffle can award per game.
+pub const MAX_TICKETS_PER_GAME: u32 = 10;
+
+/// Longest a raffle name can be, in characters.
+pub const MAX_RAFFLE_NAME_LENGTH: usize = 64;
+
+/// How long after a raffle ends its secret can be revealed before anyone
+/// can draw it without the secret.
+pub const RAFFLE_REVEAL_WINDOW_MICROS: u64 = 3 * MICROS_PER_DAY;
+
+/// A raffle as the admin describes it.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject, InputObject)]
+#[graphql(input_name = "RaffleSpecInput")]
+pub struct RaffleSpec {
+    pub name: String,
+    pub starts_at: u64,
+    pub ends_at: u64,
+    /// Tickets earned by each game played between `starts_at` and
+    /// `ends_at`.
+    pub tickets_per_game: u32,
+    /// The prize pool, taken from the admin's balance when the raffle is
+    /// scheduled.
+    pub prize: Amount,
+    /// The [`commitment`] to the secret revealed to draw the winner.
+    pub seed_commitment: CryptoHash,
+}
+
+impl RaffleSpec {
+    /// Check the raffle is well formed and hasn't already ended at `now`.
+    pub fn validate(&self, now: u64) -> Result<(), ArcadeError> {
+        let name_ok =
+            !self.name.trim().is_empty() && self.name.chars().count() <= MAX_RAFFLE_NAME_LENGTH;
+        let timing_ok = self.starts_at < self.ends_at && self.ends_at > now;
+        let tickets_ok = (1..=MAX_TICKETS_PER_GAME).contains(&self.tickets_per_game);
+        if !name_ok || !timing_ok || !tickets_ok || self.prize.is_zero() {
+            return Err(ArcadeError::InvalidRaffle);
+        }
+        Ok(())
+    }
+}
+
+/// The outcome of a raffle's draw.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct RaffleDraw {
+    /// The player holding the winning ticket, who was paid the prize. If
+    /// nobody played, there is no winner and the prize went back to the
+    /// admin who funded it.
+    pub winner: Option<AccountOwner>,
+    /// The winning ticket, numbered from 0 in holder address order.
+    pub winning_ticket: Option<u64>,
+    /// The revealed secret, so anyone can check the draw, or `None` if it
+    /// was drawn from the ticket digest alone after the reveal window.
+    pub secret: Option<String>,
+    pub drawn_at: u64,
+}
+
+/// A scheduled raffle.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct Raffle {
+    pub id: u64,
+    pub spec: RaffleSpec,
+    /// The admin whose balance funded the prize.
+    pub funded_by: AccountOwner,
+    pub total_tickets: u64,
+    /// The hash chained over every ticket award, `None` until the first.
+    pub ticket_digest: Option<CryptoHash>,
+    pub draw: Option<RaffleDraw>,
+}
+
+impl Raffle {
+    /// A raffle yet to be drawn.
+    pub fn new(id: u64, spec: RaffleSpec, funded_by: AccountOwner) -> Self {
+        Self {
+            id,
+            spec,
+            funded_by,
+            total_tickets: 0,
+            ticket_digest: None,
+            draw: None,
+        }
+    }
+
+    /// Award the tickets for the game with `receipt`, played at
+    /// `played_at`, returning how many, if the game falls in the window and
+    /// the raffle is undrawn.
+    pub fn award_tickets(&mut self, receipt: CryptoHash, played_at: u64) -> Option<u64> {
+        if self.draw.is_some() || !(self.spec.starts_at..self.spec.ends_at).contains(&played_at) {
+            return None;
+        }
+        let tickets = u64::from(self.spec.tickets_per_game);
+        self.total_tickets += tickets;
+        self.ticket_digest = Some(CryptoHash::new(&TicketAward {
+            previous: self.ticket_digest,
+            receipt,
+            tickets,
+        }));
+        Some(tickets)
+    }
+
+    /// Take back the tickets a removed game played at `played_at` was
+    /// awarded, returning how many, if the raffle is still undrawn. The
+    /// ticket digest keeps the award, which only seeds the draw.
+    pub fn take_back_tickets(&mut self, played_at: u64) -> Option<u64> {
+        if self.draw.is_some() || !(self.spec.starts_at..self.spec.ends_at).contains(&played_at) {
+            return None;
+        }
+        let tickets = u64::from(self.spec.tickets_per_game);
+        self.total_tickets = self.total_tickets.checked_sub(tickets)?;
+        Some(tickets)
+    }
+
+    /// Check the raffle can be drawn at `now`, with `secret` or, once the
+    /// reveal window has passed, without it, and pick the winning ticket,
+    /// if any were awarded.
+    pub fn winning_ticket(
+        &self,
+        secret: Option<&str>,
+        now: u64,
+    ) -> Result<Option<u64>, ArcadeError> {
+        if self.draw.is_some() {
+            return Err(ArcadeError::RaffleAlreadyDrawn);
+        }
+        if now < self.spec.ends_at {
+            return Err(ArcadeError::RaffleNotEnded);
+        }
+        let reveal_deadline = self
+            .spec
+            .ends_at
+            .saturating_add(RAFFLE_REVEAL_WINDOW_MICROS);
+        match secret {
+            Some(secret) if commitment(secret) != self.spec.seed_commitment => {
+                return Err(ArcadeError::RaffleSecretMismatch);
+            }
+            None if now < reveal_deadline => return Err(ArcadeError::RaffleRevealPending),
+            _ => {}
+        }
+        let Some(ticket_digest) = self.ticket_digest.filter(|_| self.total_tickets > 0) else {
+            return Ok(None);
+        };
+        let hash = CryptoHash::new(&RaffleDrawPreimage {
+            raffle_id: self.id,
+            secret: secret.map(str::to_string),
+            ticket_digest,
+        });
+        Ok(Some(<[u64; 4]>::from(hash)[0] % self.total_tickets))
+    }
+}
+
+/// The player holding `ticket` among `holders`, given as `(player, tickets)`
+/// pairs. Tickets are numbered from 0 through the holders in address order.
+pub fn holder_of(mut holders: Vec<(AccountOwner, u64)>, ticket: u64) -> Option<AccountOwner> {
+    holders.sort_by_key(|(owner, _)| *owner);
+    let mut end = 0;
+    for (owner, tickets) in holders {
+        end += tickets;
+        if ticket < end {
+            return Some(owner);
+        }
+    }
+    None
+}
+
+/// The commitment the admin publishes for `secret` when scheduling a
+/// raffle.
+pub fn commitment(secret: &str) -> CryptoHash {
+    CryptoHash::new(&RaffleSecret {
+        secret: secret.to_string(),
+    })
+}
+
+/// The value a raffle's commitment is hashed from.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct RaffleSecret {
+    secret: String,
+}
+
+impl BcsSignable<'_> for RaffleSecret {}
+
+/// One link of a raffle's ticket digest.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct TicketAward {
+    previous: Option<CryptoHash>,
+    receipt: CryptoHash,
+    tickets: u64,
+}
+
+impl BcsSignable<'_> for TicketAward {}
+
+/// The value a raffle's winning ticket is drawn from.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct RaffleDrawPreimage {
+    raffle_id: u64,
+    secret: Option<String>,
+    ticket_digest: CryptoHash,
+}
+
+impl BcsSignable<'_> for RaffleDrawPreimage {}
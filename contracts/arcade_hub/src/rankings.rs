@@ -0,0 +1,174 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Alternative leaderboard metrics and the indexes that back them.
+
+use std::collections::BTreeMap;
+
+use async_graphql::{Enum, SimpleObject};
+use serde::{Deserialize, Serialize};
+
+use crate::{day_index, GameScore, GameType, MICROS_PER_DAY};
+
+/// Default age below which an account counts as a rookie, in days.
+pub const DEFAULT_ROOKIE_MAX_AGE_DAYS: u32 = 14;
+
+/// Default level below which a player counts as a rookie.
+pub const DEFAULT_ROOKIE_MAX_LEVEL: u32 = 10;
+
+/// The metric a leaderboard is ordered by.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Enum)]
+pub enum LeaderboardMetric {
+    /// Lifetime XP.
+    #[default]
+    TotalXp,
+    /// Player level (ties broken by XP).
+    Level,
+    /// Number of games played.
+    GamesPlayed,
+    /// Sum of the XP of the player's best run in each game.
+    ChampionScore,
+    /// XP earned in the current season.
+    SeasonalXp,
+    /// Recent XP, each day's weighing less the older it is, so active
+    /// players rank above dormant ones.
+    CurrentForm,
+}
+
+/// Days of XP counted towards a player's current form.
+pub const FORM_WINDOW_DAYS: u64 = 28;
+
+/// Share of a day's XP still counted towards current form for each day
+/// that has passed since, in percent.
+pub const FORM_DAILY_RETENTION_PERCENT: u64 = 90;
+
+/// A player's XP per UTC day over the form window, maintained on
+/// submission so current form doesn't require scanning every score.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RecentForm {
+    daily_xp: BTreeMap<u64, u64>,
+}
+
+impl RecentForm {
+    /// Count a run's XP on the day it was played, dropping days that have
+    /// left the window.
+    pub fn record(&mut self, xp_earned: u64, timestamp: u64) {
+        let day = day_index(timestamp);
+        let xp = self.daily_xp.entry(day).or_insert(0);
+        *xp = xp.saturating_add(xp_earned);
+        let first_day = day.saturating_sub(FORM_WINDOW_DAYS - 1);
+        self.daily_xp = self.daily_xp.split_off(&first_day);
+    }
+
+    /// The player's current form at `now`: the XP of each day in the
+    /// window, decayed by its age.
+    pub fn score(&self, now: u64) -> u64 {
+        let today = day_index(now);
+        let mut total = 0u64;
+        for (&day, &xp) in &self.daily_xp {
+            let age = today.saturating_sub(day);
+            if age >= FORM_WINDOW_DAYS {
+                continue;
+            }
+            let mut weighted = u128::from(xp);
+            for _ in 0..age {
+                weighted = weighted * u128::from(FORM_DAILY_RETENTION_PERCENT) / 100;
+            }
+            total = total.saturating_add(weighted as u64);
+        }
+        total
+    }
+}
+
+/// The XP of a player's best run in each game, maintained on submission so
+/// the champion score doesn't require scanning every score.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ChampionScore {
+    best_run_xp: BTreeMap<GameType, u64>,
+    total: u64,
+}
+
+impl ChampionScore {
+    /// Record a run, keeping it if it's the player's best in that game.
+    pub fn record(&mut self, game_type: GameType, xp_earned: u64) {
+        let best = self.best_run_xp.entry(game_type).or_insert(0);
+        if xp_earned > *best {
+            self.total = self.total - *best + xp_earned;
+            *best = xp_earned;
+        }
+    }
+
+    /// The champion score: the sum of the best runs.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+}
+
+/// Who is placed on the rookie leaderboard, so new players compete with
+/// each other rather than with veterans. A player is a rookie while they
+/// are within every limit set, and graduates once they pass any of them.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, SimpleObject)]
+pub struct RookieCriteria {
+    /// Accounts registered at most this many days ago.
+    pub max_account_age_days: Option<u32>,
+    /// Players below this level.
+    pub max_level: Option<u32>,
+}
+
+impl Default for RookieCriteria {
+    fn default() -> Self {
+        Self {
+            max_account_age_days: Some(DEFAULT_ROOKIE_MAX_AGE_DAYS),
+            max_level: Some(DEFAULT_ROOKIE_MAX_LEVEL),
+        }
+    }
+}
+
+impl RookieCriteria {
+    /// Whether a player registered at `registered_at` and now at `level` is
+    /// a rookie at `now`.
+    pub fn is_rookie(&self, registered_at: u64, level: u32, now: u64) -> bool {
+        let age_ok = self.max_account_age_days.is_none_or(|days| {
+            now.saturating_sub(registered_at) < u64::from(days) * MICROS_PER_DAY
+        });
+        let level_ok = self.max_level.is_none_or(|max_level| level < max_level);
+        age_ok && level_ok
+    }
+}
+
+/// A player's best run in each game, by that game's scoring direction.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PersonalBests {
+    best_runs: BTreeMap<GameType, GameScore>,
+}
+
+impl PersonalBests {
+    /// Record a run, returning whether it set a new personal best.
+    pub fn record(&mut self, score: &GameScore) -> bool {
+        match self.best_runs.get(&score.game_type) {
+            Some(best) if !score.game_type.beats(score.score, best.score) => false,
+            _ => {
+                self.best_runs.insert(score.game_type, score.clone());
+                true
+            }
+        }
+    }
+
+    /// Forget `score` if it is the best run of its game, returning whether
+    /// it was.
+    pub fn forget(&mut self, score: &GameScore) -> bool {
+        let is_best = self
+            .best_runs
+            .get(&score.game_type)
+            .is_some_and(|best| best.receipt() == score.receipt());
+        if is_best {
+            self.best_runs.remove(&score.game_type);
+        }
+        is_best
+    }
+
+    /// The best runs, one per game played.
+    pub fn runs(&self) -> impl Iterator<Item = &GameScore> {
+        self.best_runs.values()
+    }
+}
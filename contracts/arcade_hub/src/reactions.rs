@@ -0,0 +1,156 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Emote reactions on hall-of-fame records and top leaderboard entries.
+//!
+//! Each player can leave each emote once on a target, and at most
+//! [`MAX_EMOTES_PER_TARGET`] different emotes. Only the hub sees the
+//! arcade-wide standings, so it is the hub that checks a reaction's target
+//! when the reaction syncs.
+
+use std::collections::BTreeMap;
+
+use async_graphql::{Enum, OneofObject, SimpleObject};
+use linera_sdk::linera_base_types::AccountOwner;
+use serde::{Deserialize, Serialize};
+
+use crate::{ArcadeError, GameScore};
+
+/// Different emotes one player can leave on one target.
+pub const MAX_EMOTES_PER_TARGET: usize = 3;
+
+/// Top leaderboard ranks that can be reacted to.
+pub const REACTABLE_RANKS: u32 = 10;
+
+/// The fixed set of emotes players can react with.
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize, Enum,
+)]
+pub enum Emote {
+    Fire,
+    Clap,
+    Trophy,
+    Heart,
+    Laugh,
+    Wow,
+}
+
+impl Emote {
+    /// The emoji the emote is shown as.
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Emote::Fire => "🔥",
+            Emote::Clap => "👏",
+            Emote::Trophy => "🏆",
+            Emote::Heart => "❤️",
+            Emote::Laugh => "😂",
+            Emote::Wow => "😮",
+        }
+    }
+}
+
+/// What a reaction is attached to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, OneofObject)]
+pub enum ReactionTarget {
+    /// A hall-of-fame record, by score ID.
+    Record(u64),
+    /// A player's entry in the top [`REACTABLE_RANKS`] of the leaderboard.
+    LeaderboardEntry(AccountOwner),
+}
+
+/// How many players left one emote on a target.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct EmoteCount {
+    pub emote: Emote,
+    pub symbol: String,
+    pub count: u64,
+}
+
+/// Reaction counts for one target.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ReactionTally {
+    counts: BTreeMap<Emote, u64>,
+}
+
+impl ReactionTally {
+    /// Count one more `emote`.
+    pub fn add(&mut self, emote: Emote) {
+        *self.counts.entry(emote).or_insert(0) += 1;
+    }
+
+    /// Count one fewer `emote`.
+    pub fn remove(&mut self, emote: Emote) {
+        if let Some(count) = self.counts.get_mut(&emote) {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                self.counts.remove(&emote);
+            }
+        }
+    }
+
+    /// Whether no reactions are counted.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// The emotes left on the target, most used first.
+    pub fn counts(&self) -> Vec<EmoteCount> {
+        let mut counts: Vec<EmoteCount> = self
+            .counts
+            .iter()
+            .map(|(emote, count)| EmoteCount {
+                emote: *emote,
+                symbol: emote.symbol().to_string(),
+                count: *count,
+            })
+            .collect();
+        counts.sort_by_key(|count| std::cmp::Reverse(count.count));
+        counts
+    }
+}
+
+/// The emotes one player has left on one target.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PlayerReactions {
+    emotes: Vec<Emote>,
+}
+
+impl PlayerReactions {
+    /// Leave `emote`, within the per-target limits.
+    pub fn add(&mut self, emote: Emote) -> Result<(), ArcadeError> {
+        if self.emotes.contains(&emote) {
+            return Err(ArcadeError::AlreadyReacted);
+        }
+        if self.emotes.len() >= MAX_EMOTES_PER_TARGET {
+            return Err(ArcadeError::TooManyReactions);
+        }
+        self.emotes.push(emote);
+        Ok(())
+    }
+
+    /// Take back `emote`.
+    pub fn remove(&mut self, emote: Emote) -> Result<(), ArcadeError> {
+        let Some(position) = self.emotes.iter().position(|left| *left == emote) else {
+            return Err(ArcadeError::ReactionNotFound);
+        };
+        self.emotes.remove(position);
+        Ok(())
+    }
+
+    /// Whether no emotes are left.
+    pub fn is_empty(&self) -> bool {
+        self.emotes.is_empty()
+    }
+
+    /// The emotes left, in the order they were added.
+    pub fn emotes(&self) -> Vec<Emote> {
+        self.emotes.clone()
+    }
+}
+
+/// A game's standing record with the reactions left on it.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct HallOfFameRecord {
+    pub score: GameScore,
+    pub reactions: Vec<EmoteCount>,
+}
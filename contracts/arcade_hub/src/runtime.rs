@@ -0,0 +1,172 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! The few runtime calls the contract's logic depends on, behind a trait so
+//! time- and signer-dependent code can run natively against a mock runtime
+//! in tests.
+
+#[cfg(test)]
+use linera_sdk::linera_base_types::CryptoHash;
+use linera_sdk::{
+    linera_base_types::{AccountOwner, ChainId},
+    Contract, ContractRuntime,
+};
+
+use crate::Message;
+
+/// Runtime calls used by the arcade's logic.
+pub trait RuntimeFacade {
+    /// The current system time, in microseconds.
+    fn now_micros(&mut self) -> u64;
+    /// The account that signed the current operation, if any.
+    fn signer(&mut self) -> Option<AccountOwner>;
+    /// The chain the application is running on.
+    fn chain(&mut self) -> ChainId;
+    /// Send an authenticated message to another chain.
+    fn send_message(&mut self, destination: ChainId, message: Message);
+}
+
+impl<Application> RuntimeFacade for ContractRuntime<Application>
+where
+    Application: Contract<Message = Message>,
+{
+    fn now_micros(&mut self) -> u64 {
+        self.system_time().micros()
+    }
+
+    fn signer(&mut self) -> Option<AccountOwner> {
+        self.authenticated_signer()
+    }
+
+    fn chain(&mut self) -> ChainId {
+        self.chain_id()
+    }
+
+    fn send_message(&mut self, destination: ChainId, message: Message) {
+        self.prepare_message(message)
+            .with_authentication()
+            .send_to(destination);
+    }
+}
+
+/// An in-memory runtime with a settable clock and signer that records the
+/// messages sent through it.
+#[cfg(test)]
+#[derive(Clone, Debug)]
+pub struct MockRuntime {
+    pub now: u64,
+    pub signer: Option<AccountOwner>,
+    pub chain_id: ChainId,
+    /// Messages sent so far, in order, with their destinations.
+    pub sent_messages: Vec<(ChainId, Message)>,
+}
+
+#[cfg(test)]
+impl MockRuntime {
+    pub fn new(chain_id: ChainId, now: u64) -> Self {
+        Self {
+            now,
+            signer: None,
+            chain_id,
+            sent_messages: Vec::new(),
+        }
+    }
+
+    /// A runtime on a test chain, with its clock at `now`.
+    pub fn at(now: u64) -> Self {
+        Self::new(ChainId(CryptoHash::test_hash("arcade")), now)
+    }
+
+    /// Move the clock forward.
+    pub fn advance(&mut self, micros: u64) {
+        self.now += micros;
+    }
+}
+
+#[cfg(test)]
+impl RuntimeFacade for MockRuntime {
+    fn now_micros(&mut self) -> u64 {
+        self.now
+    }
+
+    fn signer(&mut self) -> Option<AccountOwner> {
+        self.signer
+    }
+
+    fn chain(&mut self) -> ChainId {
+        self.chain_id
+    }
+
+    fn send_message(&mut self, destination: ChainId, message: Message) {
+        self.sent_messages.push((destination, message));
+    }
+}
+
+/// Send `message` to the hub, unless this already is the hub or the hub is
+/// not yet known.
+pub fn send_to_hub_if_needed(
+    runtime: &mut impl RuntimeFacade,
+    hub_chain_id: Option<ChainId>,
+    message: Message,
+) {
+    let Some(hub_chain_id) = hub_chain_id else {
+        return;
+    };
+    if runtime.chain() != hub_chain_id {
+        runtime.send_message(hub_chain_id, message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signer_is_whoever_was_set() {
+        let mut runtime = MockRuntime::at(0);
+        assert_eq!(runtime.signer(), None);
+
+        let owner = AccountOwner::Address32(CryptoHash::test_hash("player"));
+        runtime.signer = Some(owner);
+        assert_eq!(runtime.signer(), Some(owner));
+    }
+
+    #[test]
+    fn messages_are_recorded_in_order() {
+        let mut runtime = MockRuntime::at(0);
+        let hub = ChainId(CryptoHash::test_hash("hub"));
+        let other = ChainId(CryptoHash::test_hash("other"));
+
+        runtime.send_message(hub, Message::AnnounceSyncSource);
+        runtime.send_message(
+            other,
+            Message::RemoveScore {
+                receipt: CryptoHash::test_hash("receipt"),
+            },
+        );
+        assert_eq!(runtime.sent_messages.len(), 2);
+        assert!(matches!(
+            runtime.sent_messages[0],
+            (chain_id, Message::AnnounceSyncSource) if chain_id == hub
+        ));
+        assert!(matches!(
+            runtime.sent_messages[1],
+            (chain_id, Message::RemoveScore { .. }) if chain_id == other
+        ));
+    }
+
+    #[test]
+    fn only_chains_other_than_the_hub_send_to_it() {
+        let mut runtime = MockRuntime::at(0);
+        let hub = ChainId(CryptoHash::test_hash("hub"));
+
+        send_to_hub_if_needed(&mut runtime, None, Message::AnnounceSyncSource);
+        assert!(runtime.sent_messages.is_empty());
+        send_to_hub_if_needed(&mut runtime, Some(hub), Message::AnnounceSyncSource);
+        assert_eq!(runtime.sent_messages.len(), 1);
+
+        runtime.chain_id = hub;
+        send_to_hub_if_needed(&mut runtime, Some(hub), Message::AnnounceSyncSource);
+        assert_eq!(runtime.sent_messages.len(), 1);
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Anti-cheat mode: scores signed by a trusted game server.
+//!
+//! When a score signer is registered at instantiation, every submission
+//! must carry the signer's signature over the run, so only scores the game
+//! server witnessed earn XP. Each player's signatures carry increasing
+//! nonces, so a signed run can't be submitted twice. Runs relayed by kiosk
+//! gateways are attested by the players themselves and need no server
+//! signature.
+
+use async_graphql::InputObject;
+use linera_sdk::linera_base_types::{AccountOwner, BcsSignable};
+use serde::{Deserialize, Serialize};
+
+use crate::{check_account_signature, ArcadeError, GameType, ScoreSubmission};
+
+/// The game server's signature over a run.
+#[derive(Clone, Debug, Serialize, Deserialize, InputObject)]
+pub struct ScoreSignature {
+    /// Per-player counter; must exceed the nonce of the player's last
+    /// signed run.
+    pub nonce: u64,
+    /// Hex-encoded BCS `AccountSignature` by the score signer over a
+    /// [`SignedScore`].
+    pub signature: String,
+}
+
+impl ScoreSignature {
+    /// Check that `signer` signed `player`'s `submission`.
+    pub fn verify(
+        &self,
+        signer: &AccountOwner,
+        player: AccountOwner,
+        submission: &ScoreSubmission,
+    ) -> Result<(), ArcadeError> {
+        let payload = SignedScore {
+            player,
+            game_type: submission.game_type,
+            score: submission.score,
+            bonus_data: submission.bonus_data,
+            nonce: self.nonce,
+        };
+        if !check_account_signature(&self.signature, signer, &payload) {
+            return Err(ArcadeError::InvalidScoreSignature);
+        }
+        Ok(())
+    }
+}
+
+/// The payload the game server signs for a run.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignedScore {
+    pub player: AccountOwner,
+    pub game_type: GameType,
+    pub score: u64,
+    pub bonus_data: Option<u64>,
+    pub nonce: u64,
+}
+
+impl BcsSignable<'_> for SignedScore {}
@@ -0,0 +1,184 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tunable weights on the XP a run earns, and canary rollouts of new ones.
+//!
+//! The live scoring config weights each game's base XP and sets the bonus
+//! for beating a game's p90 score. A new config can be tried out as a
+//! canary first: while the canary runs, the XP of every run accepted on
+//! this chain is worked out under both configs and the two are tallied per
+//! game, but only the live config's XP is awarded. Once the tallies look
+//! right the admin promotes the canary to live, or drops it. Tallies cover
+//! the XP scoring itself, before mentorship, circuit and monthly challenge
+//! rewards are added.
+
+use async_graphql::{InputObject, SimpleObject};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    analytics,
+    balance::{self, NEUTRAL_MULTIPLIER_PERCENT},
+    ArcadeError, GameType,
+};
+
+/// Highest weight a game's XP can be given, in percent.
+pub const MAX_GAME_WEIGHT_PERCENT: u32 = 500;
+
+/// Highest bonus for beating a game's p90 score, in percent.
+pub const MAX_TOP_DECILE_BONUS_PERCENT: u64 = 100;
+
+/// The weight of one game's base XP.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, SimpleObject, InputObject)]
+#[graphql(input_name = "GameWeightInput")]
+pub struct GameWeight {
+    pub game_type: GameType,
+    /// Base XP is scaled by this percentage; 100 leaves it unchanged.
+    pub weight_percent: u32,
+}
+
+/// How a run's XP is worked out, managed by the admin.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, SimpleObject, InputObject)]
+#[graphql(input_name = "ScoringConfigInput")]
+pub struct ScoringConfig {
+    /// Weights of the games that aren't weighted 100%.
+    pub game_weights: Vec<GameWeight>,
+    /// Bonus XP for beating the game's p90 score, as a percentage of the
+    /// run's weighted base XP.
+    pub top_decile_bonus_percent: u64,
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        Self {
+            game_weights: Vec::new(),
+            top_decile_bonus_percent: analytics::TOP_DECILE_BONUS_PERCENT,
+        }
+    }
+}
+
+impl ScoringConfig {
+    /// Check every weight and the bonus are in range, weighting each game
+    /// at most once.
+    pub fn validate(&self) -> Result<(), ArcadeError> {
+        let mut games = Vec::new();
+        let weights_ok = self.game_weights.iter().all(|weight| {
+            let unique = !games.contains(&weight.game_type);
+            games.push(weight.game_type);
+            unique && (1..=MAX_GAME_WEIGHT_PERCENT).contains(&weight.weight_percent)
+        });
+        if !weights_ok || self.top_decile_bonus_percent > MAX_TOP_DECILE_BONUS_PERCENT {
+            return Err(ArcadeError::InvalidScoringConfig);
+        }
+        Ok(())
+    }
+
+    /// `game_type`'s weight, in percent.
+    pub fn weight_percent(&self, game_type: GameType) -> u32 {
+        self.game_weights
+            .iter()
+            .find(|weight| weight.game_type == game_type)
+            .map_or(NEUTRAL_MULTIPLIER_PERCENT, |weight| weight.weight_percent)
+    }
+
+    /// The XP a run with `factors` earns under this config.
+    pub fn xp(&self, factors: &XpFactors) -> u64 {
+        let base =
+            balance::apply_multiplier(factors.base_xp, self.weight_percent(factors.game_type));
+        let bonus = if factors.top_decile {
+            base.saturating_mul(self.top_decile_bonus_percent) / 100
+        } else {
+            0
+        };
+        balance::apply_multiplier(base, factors.multiplier_percent).saturating_add(bonus)
+    }
+}
+
+/// What a run's XP is worked out from, whatever the scoring config.
+#[derive(Clone, Copy, Debug)]
+pub struct XpFactors {
+    pub game_type: GameType,
+    /// The game's XP for the score in the player's scoring category.
+    pub base_xp: u64,
+    /// The crossover event or underplayed-game multiplier, in percent.
+    pub multiplier_percent: u32,
+    /// Whether the score beats the game's p90 on this chain.
+    pub top_decile: bool,
+}
+
+/// One game's XP under the live and canary configs.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct CanaryTally {
+    pub game_type: GameType,
+    pub runs: u64,
+    /// XP awarded under the live config.
+    pub live_xp: u64,
+    /// XP the runs would have earned under the canary config.
+    pub canary_xp: u64,
+    /// Runs whose XP differs between the two.
+    pub changed_runs: u64,
+}
+
+/// A scoring config being tried out alongside the live one.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct ScoringCanary {
+    pub config: ScoringConfig,
+    pub started_at: u64,
+    /// When the canary was promoted or dropped; `None` while it runs.
+    pub ended_at: Option<u64>,
+    pub promoted: bool,
+    /// Tallies of the games played since the canary started.
+    pub tallies: Vec<CanaryTally>,
+}
+
+impl ScoringCanary {
+    /// A canary of `config` starting at `now`.
+    pub fn new(config: ScoringConfig, now: u64) -> Self {
+        Self {
+            config,
+            started_at: now,
+            ended_at: None,
+            promoted: false,
+            tallies: Vec::new(),
+        }
+    }
+
+    /// Whether the canary is still running.
+    pub fn is_running(&self) -> bool {
+        self.ended_at.is_none()
+    }
+
+    /// Tally a run with `factors` that was awarded `live_xp`.
+    pub fn record(&mut self, factors: &XpFactors, live_xp: u64) {
+        let canary_xp = self.config.xp(factors);
+        let index = match self
+            .tallies
+            .iter()
+            .position(|tally| tally.game_type == factors.game_type)
+        {
+            Some(index) => index,
+            None => {
+                self.tallies.push(CanaryTally {
+                    game_type: factors.game_type,
+                    runs: 0,
+                    live_xp: 0,
+                    canary_xp: 0,
+                    changed_runs: 0,
+                });
+                self.tallies.len() - 1
+            }
+        };
+        let tally = &mut self.tallies[index];
+        tally.runs += 1;
+        tally.live_xp = tally.live_xp.saturating_add(live_xp);
+        tally.canary_xp = tally.canary_xp.saturating_add(canary_xp);
+        if canary_xp != live_xp {
+            tally.changed_runs += 1;
+        }
+    }
+
+    /// End the canary at `now`, promoted to live or dropped.
+    pub fn end(&mut self, promoted: bool, now: u64) {
+        self.ended_at = Some(now);
+        self.promoted = promoted;
+    }
+}
@@ -0,0 +1,300 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Seasons: fixed-length stretches with their own XP leaderboard, so new
+//! players get a fresh race to win.
+//!
+//! The admin starts the first season; each rolls over into the next when
+//! it ends, or early on admin request, archiving its final standings.
+//! Seasonal XP is kept next to lifetime XP and restarts from zero every
+//! season.
+//!
+//! A season can be given a theme with its own cosmetic rewards, one per
+//! final tier, granted to its ranked players when it rolls over.
+
+use async_graphql::{InputObject, SimpleObject};
+use linera_sdk::linera_base_types::AccountOwner;
+use serde::{Deserialize, Serialize};
+
+use crate::{cosmetics::Cosmetic, runtime::RuntimeFacade, ArcadeError, MICROS_PER_DAY};
+
+/// Shortest season the admin can set, in days.
+pub const MIN_SEASON_LENGTH_DAYS: u32 = 1;
+
+/// Longest season the admin can set, in days.
+pub const MAX_SEASON_LENGTH_DAYS: u32 = 365;
+
+/// Maximum number of standings archived per season.
+pub const MAX_ARCHIVED_STANDINGS: usize = 1000;
+
+/// Longest a season theme name can be, in characters.
+pub const MAX_THEME_NAME_LENGTH: usize = 32;
+
+/// A season, running from `started_at` until `ends_at`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, SimpleObject)]
+pub struct Season {
+    /// Seasons are numbered from 1.
+    pub number: u32,
+    pub started_at: u64,
+    pub ends_at: u64,
+    /// Length of this season and those that follow it, in microseconds.
+    pub length_micros: u64,
+}
+
+impl Season {
+    /// The first season, starting at `now` and lasting `length_days`.
+    pub fn first(length_days: u32, now: u64) -> Self {
+        let length_micros = u64::from(length_days) * MICROS_PER_DAY;
+        Self {
+            number: 1,
+            started_at: now,
+            ends_at: now + length_micros,
+            length_micros,
+        }
+    }
+
+    /// Whether the season is over at `now`.
+    pub fn has_ended(&self, now: u64) -> bool {
+        now >= self.ends_at
+    }
+
+    /// The season that follows this one, as of `now`. Rolled over early, it
+    /// starts at `now`; rolled over late, it starts on the schedule of
+    /// back-to-back seasons, skipping any that would have ended already.
+    pub fn next(&self, now: u64) -> Self {
+        let started_at = if now < self.ends_at {
+            now
+        } else {
+            let missed = (now - self.ends_at) / self.length_micros;
+            self.ends_at + missed * self.length_micros
+        };
+        Self {
+            number: self.number + 1,
+            started_at,
+            ends_at: started_at + self.length_micros,
+            length_micros: self.length_micros,
+        }
+    }
+}
+
+/// A player's XP in one season.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, SimpleObject)]
+pub struct SeasonalXp {
+    pub season: u32,
+    pub xp: u64,
+}
+
+impl SeasonalXp {
+    /// Add XP earned in `season`, starting over if it's a new season.
+    pub fn add(&mut self, season: u32, xp: u64) {
+        if self.season != season {
+            *self = SeasonalXp { season, xp: 0 };
+        }
+        self.xp = self.xp.saturating_add(xp);
+    }
+
+    /// Take back XP earned in `season`, if it is still the one counted.
+    pub fn remove(&mut self, season: u32, xp: u64) {
+        if self.season == season {
+            self.xp = self.xp.saturating_sub(xp);
+        }
+    }
+
+    /// The XP earned in `season`.
+    pub fn in_season(&self, season: u32) -> u64 {
+        if self.season == season {
+            self.xp
+        } else {
+            0
+        }
+    }
+}
+
+/// One ranked player in a season's standings.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct SeasonStanding {
+    pub wallet_address: AccountOwner,
+    pub rank: u32,
+    pub seasonal_xp: u64,
+}
+
+/// Whether `season` has ended by the runtime's clock.
+pub fn is_due(runtime: &mut impl RuntimeFacade, season: &Season) -> bool {
+    season.has_ended(runtime.now_micros())
+}
+
+/// The season that follows `season`, rolled over now.
+pub fn start_next(runtime: &mut impl RuntimeFacade, season: &Season) -> Season {
+    season.next(runtime.now_micros())
+}
+
+/// Rank `(player, seasonal XP)` pairs, most XP first, ties going to the
+/// lower address so the order is the same on every validator.
+pub fn rank(mut entries: Vec<(AccountOwner, u64)>) -> Vec<SeasonStanding> {
+    entries.sort_by(|(a_owner, a_xp), (b_owner, b_xp)| b_xp.cmp(a_xp).then(a_owner.cmp(b_owner)));
+    entries
+        .into_iter()
+        .enumerate()
+        .map(|(i, (wallet_address, seasonal_xp))| SeasonStanding {
+            wallet_address,
+            rank: (i + 1) as u32,
+            seasonal_xp,
+        })
+        .collect()
+}
+
+/// A finished season and its final standings.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct SeasonArchive {
+    pub season: Season,
+    /// When the season was rolled over, which is later than its scheduled
+    /// end if no activity triggered the rollover on time.
+    pub archived_at: u64,
+    /// Players in rank order, truncated to [`MAX_ARCHIVED_STANDINGS`] and to
+    /// the leaderboard history retention limit.
+    pub standings: Vec<SeasonStanding>,
+}
+
+impl SeasonArchive {
+    /// Drop every standing ranked below `top_k`, returning whether any were
+    /// dropped.
+    pub fn retain_top(&mut self, top_k: u32) -> bool {
+        let before = self.standings.len();
+        self.standings.truncate(top_k as usize);
+        self.standings.len() < before
+    }
+}
+
+/// The tier a player finished a season in, by final rank.
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize, async_graphql::Enum,
+)]
+pub enum SeasonTier {
+    /// Top 10.
+    Legend,
+    /// Top 100.
+    Gold,
+    /// Top 1000.
+    Silver,
+    /// Everyone else who earned seasonal XP.
+    Bronze,
+}
+
+impl SeasonTier {
+    /// The tier of final rank `rank`.
+    pub fn for_rank(rank: u32) -> Self {
+        match rank {
+            0..=10 => SeasonTier::Legend,
+            11..=100 => SeasonTier::Gold,
+            101..=1000 => SeasonTier::Silver,
+            _ => SeasonTier::Bronze,
+        }
+    }
+}
+
+/// The cosmetic a season's theme grants for finishing in `tier`.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject, InputObject)]
+#[graphql(input_name = "TierRewardInput")]
+pub struct TierReward {
+    pub tier: SeasonTier,
+    pub cosmetic: Cosmetic,
+}
+
+/// A season's theme and its cosmetic reward set.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject, InputObject)]
+#[graphql(input_name = "SeasonThemeInput")]
+pub struct SeasonTheme {
+    pub name: String,
+    /// At most one reward per tier; tiers left out grant nothing.
+    pub rewards: Vec<TierReward>,
+}
+
+impl SeasonTheme {
+    /// Check the theme is named and its rewards are distinct cosmetics for
+    /// distinct tiers.
+    pub fn validate(&self) -> Result<(), ArcadeError> {
+        let name_ok =
+            !self.name.trim().is_empty() && self.name.chars().count() <= MAX_THEME_NAME_LENGTH;
+        let mut tiers = Vec::new();
+        let mut cosmetics = Vec::new();
+        let rewards_ok = self.rewards.iter().all(|reward| {
+            let distinct = !tiers.contains(&reward.tier) && !cosmetics.contains(&&reward.cosmetic);
+            tiers.push(reward.tier);
+            cosmetics.push(&reward.cosmetic);
+            distinct && reward.cosmetic.is_valid()
+        });
+        if !name_ok || !rewards_ok {
+            return Err(ArcadeError::InvalidSeasonTheme);
+        }
+        Ok(())
+    }
+
+    /// The cosmetic granted for finishing in `tier`, if any.
+    pub fn reward_for(&self, tier: SeasonTier) -> Option<&Cosmetic> {
+        self.rewards
+            .iter()
+            .find(|reward| reward.tier == tier)
+            .map(|reward| &reward.cosmetic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::MockRuntime;
+
+    const START: u64 = 20_000 * MICROS_PER_DAY;
+
+    #[test]
+    fn season_is_due_once_it_ends() {
+        let season = Season::first(7, START);
+        let mut runtime = MockRuntime::at(START);
+
+        runtime.advance(7 * MICROS_PER_DAY - 1);
+        assert!(!is_due(&mut runtime, &season));
+        runtime.advance(1);
+        assert!(is_due(&mut runtime, &season));
+    }
+
+    #[test]
+    fn early_rollover_starts_the_next_season_now() {
+        let season = Season::first(7, START);
+        let mut runtime = MockRuntime::at(START + 2 * MICROS_PER_DAY);
+
+        let next = start_next(&mut runtime, &season);
+        assert_eq!(next.number, 2);
+        assert_eq!(next.started_at, START + 2 * MICROS_PER_DAY);
+        assert_eq!(next.ends_at, START + 9 * MICROS_PER_DAY);
+    }
+
+    #[test]
+    fn late_rollover_keeps_the_schedule() {
+        let season = Season::first(7, START);
+        let mut runtime = MockRuntime::at(START + 7 * MICROS_PER_DAY + 1);
+
+        let next = start_next(&mut runtime, &season);
+        assert_eq!(next.number, 2);
+        assert_eq!(next.started_at, season.ends_at);
+
+        // Seasons that would have ended already are skipped
+        runtime.advance(15 * MICROS_PER_DAY);
+        let next = start_next(&mut runtime, &season);
+        assert_eq!(next.started_at, START + 21 * MICROS_PER_DAY);
+        assert!(!is_due(&mut runtime, &next));
+        assert_eq!(next.length_micros, season.length_micros);
+    }
+
+    #[test]
+    fn removed_xp_only_comes_out_of_its_season() {
+        let mut seasonal = SeasonalXp::default();
+        seasonal.add(1, 300);
+        seasonal.remove(1, 100);
+        assert_eq!(seasonal.in_season(1), 200);
+
+        seasonal.add(2, 50);
+        seasonal.remove(1, 100);
+        assert_eq!(seasonal.in_season(2), 50);
+        seasonal.remove(2, 80);
+        assert_eq!(seasonal.in_season(2), 0);
+    }
+}
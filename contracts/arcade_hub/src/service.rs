@@ -5,21 +5,76 @@
 
 mod state;
 
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 use arcade_hub::{
-    ArcadeHubAbi, ArcadeStats, GameHighScoreEntry, GameScore, GameType, LeaderboardEntry,
-    Operation, Player,
+    achievements::{Achievement, AchievementDefinition, AchievementStatus},
+    analytics::{GameStats, HeatmapCell, ScoreDistribution, ScoreHistogram},
+    balance::{BalanceConfig, GameMultiplier},
+    bosses::{BossBattle, BossRewards},
+    bridge::BridgeSubscriber,
+    calculate_level,
+    challenges::{
+        self, ChallengeDay, ChallengeEntry, DailyChallenge, MonthlyChallenge, MonthlyProgress,
+    },
+    circuit::{self, DailyCircuit},
+    community_games::{CommunityGame, CommunityGameStatus, CommunityLeaderboardEntry},
+    cosmetics::OwnedCosmetic,
+    crossovers::{CrossoverEvent, EventAchievementUnlock},
+    day_index,
+    dispute::{Appeal, AppealStatus, BondAccounts},
+    goals::GoalProgress,
+    guild_prizes::{GuildPayoutRule, GuildPrize, PrizeClaim},
+    guilds::{
+        self, Guild, GuildAuditEntry, GuildContributor, GuildProgress, GuildStanding, GuildTier,
+    },
+    humanity::{HumanityChallenge, HumanityCheckConfig},
+    invariants::{InvariantCheck, InvariantSection},
+    invites::{self, InviteCode},
+    journal::{JournalCheckpoint, JournalEntry, ReplayProgress, ReplayTarget},
+    kiosk::{DeviceActivity, KioskConfig},
+    limits::DailyPlay,
+    locale::XpBuckets,
+    mentorship::{self, MentorRecord, Mentorship},
+    migration::{ProgressExport, ProgressImport},
+    milestones::Milestone,
+    name_auctions::{self, UsernameAuction, UsernameAuctionStatus},
+    plausibility::ScoreBounds,
+    polls::GuildPollResult,
+    raffles::{self, Raffle},
+    rankings::{LeaderboardMetric, RookieCriteria},
+    reactions::{Emote, EmoteCount, HallOfFameRecord, ReactionTarget},
+    score_signing::ScoreSignature,
+    scoring::{ScoringCanary, ScoringConfig},
+    seasons::{self, Season, SeasonArchive, SeasonStanding, SeasonTheme},
+    sessions::GameSession,
+    smurfing::{ModerationFlag, SmurfDetectionConfig},
+    snapshots::{LeaderboardDiff, LeaderboardSnapshot, PageToken},
+    social::{self, FollowCounts, KudosRecord, PlayerPresence},
+    storage::{StorageReport, ViewUsage, LOG_SAMPLE_SIZE},
+    streaks::{self, DailyStreak},
+    submission_windows::GameInfo,
+    usage::{UsageQuota, UsageReport},
+    usernames::{self, UsernameClaim, UsernameRecyclingConfig, UsernameReservation},
+    validate_username, ApiVersion, ArcadeError, ArcadeHubAbi, ArcadeParameters, ArcadeStats,
+    ArcadeSummary, ChainTime, GameHighScoreEntry, GameScore, GameType, LeaderboardConnection,
+    LeaderboardEntry, LeaderboardPage, MaintenanceWindow, Operation, PageInfo, Player,
+    PlayerConnection, PlayerSnapshot, PlayersPage, ScoreConnection, ScoreLogRange, ScoreSubmission,
+    ScoresPage, ScoringCategory, SubmissionCheck, API_VERSION, MICROS_PER_DAY, MIN_API_VERSION,
+};
+use async_graphql::{
+    Context, EmptySubscription, Object, Request, Response, Schema, ServerError, Value,
 };
-use async_graphql::{EmptySubscription, Object, Schema};
 use linera_sdk::{
+    bcs,
     graphql::GraphQLMutationRoot as _,
-    linera_base_types::{AccountOwner, WithServiceAbi},
-    views::View,
+    linera_base_types::{AccountOwner, Amount, ChainId, CryptoHash, WithServiceAbi},
+    views::{CollectionView, LogView, MapView, View, ViewError},
     Service, ServiceRuntime,
 };
+use serde::{de::DeserializeOwned, Serialize};
 
-use self::state::ArcadeHubState;
+use self::state::{ArcadeHubState, PendingSubmissions};
 
 /// The Arcade Hub service for GraphQL queries.
 #[derive(Clone)]
@@ -35,7 +90,7 @@ impl WithServiceAbi for ArcadeHubService {
 }
 
 impl Service for ArcadeHubService {
-    type Parameters = ();
+    type Parameters = Option<ArcadeParameters>;
 
     async fn new(runtime: ServiceRuntime<Self>) -> Self {
         let state = ArcadeHubState::load(runtime.root_view_storage_context())
@@ -48,38 +103,103 @@ impl Service for ArcadeHubService {
     }
 
     async fn handle_query(&self, request: Self::Query) -> Self::QueryResponse {
+        let version = match requested_api_version(&request) {
+            Ok(version) => version,
+            Err(error) => return Response::from_errors(vec![error]),
+        };
         let schema = Schema::build(
             QueryRoot {
                 state: self.state.clone(),
+                runtime: self.runtime.clone(),
             },
             Operation::mutation_root(self.runtime.clone()),
             EmptySubscription,
         )
         .finish();
-        schema.execute(request).await
+        schema
+            .execute(request.data(RequestedApiVersion(version)))
+            .await
+    }
+}
+
+/// The API version a request is answered with.
+#[derive(Clone, Copy)]
+struct RequestedApiVersion(u32);
+
+/// Read the `apiVersion` request extension, defaulting to the latest
+/// version.
+fn requested_api_version(request: &Request) -> Result<u32, ServerError> {
+    let Some(value) = request.extensions.get("apiVersion") else {
+        return Ok(API_VERSION);
+    };
+    let version = match value {
+        Value::Number(number) => number.as_u64().and_then(|n| u32::try_from(n).ok()),
+        _ => None,
+    };
+    match version {
+        Some(version) if (MIN_API_VERSION..=API_VERSION).contains(&version) => Ok(version),
+        _ => Err(ServerError::new(
+            format!(
+                "Unsupported apiVersion {value}; this service supports \
+                 {MIN_API_VERSION} to {API_VERSION}"
+            ),
+            None,
+        )),
     }
 }
 
+/// Whether a field introduced in API version 2 is visible to the request.
+fn since_v2(ctx: &Context<'_>) -> bool {
+    ctx.data_opt::<RequestedApiVersion>()
+        .is_none_or(|version| version.0 >= 2)
+}
+
+/// The largest page `players_page`, `scores_page` and the connection
+/// queries return.
+const MAX_INDEXER_PAGE: i32 = 1000;
+
+/// The page size of connection queries when `first` isn't given.
+const DEFAULT_CONNECTION_PAGE: i32 = 50;
+
 /// The root query type for GraphQL.
+#[derive(Clone)]
 struct QueryRoot {
     state: Arc<ArcadeHubState>,
+    runtime: Arc<ServiceRuntime<ArcadeHubService>>,
 }
 
 #[Object]
 impl QueryRoot {
-    /// Get a player by their wallet address.
-    async fn player(&self, wallet: String) -> Option<Player> {
+    /// Get a player by their wallet address. Friends-only profiles are only
+    /// returned when `viewer` is the player themselves or one of their
+    /// friends. The viewer is taken on trust, so this only keeps clients
+    /// from showing the profile to others.
+    async fn player(
+        &self,
+        wallet: String,
+        #[graphql(visible = "since_v2")] viewer: Option<String>,
+    ) -> Option<Player> {
         let owner = parse_account_owner(&wallet)?;
-        self.state.players.get(&owner).await.ok().flatten()
+        let player = self.state.players.get(&owner).await.ok().flatten()?;
+        if player.privacy.friends_only_profile {
+            let viewer = viewer.as_deref().and_then(parse_account_owner)?;
+            if viewer != owner && !self.friends_of(owner).await.contains(&viewer) {
+                return None;
+            }
+        }
+        Some(player)
     }
 
     /// Get all registered players.
     async fn all_players(&self) -> Vec<Player> {
+        let hidden = self.shadow_banned().await;
         let mut players = Vec::new();
         self.state
             .players
-            .for_each_index_value(|_, player| {
-                players.push(player.into_owned());
+            .for_each_index_value(|owner, player| {
+                if !hidden.contains(&owner) && !player.privacy.friends_only_profile {
+                    players.push(player.into_owned());
+                }
                 Ok(())
             })
             .await
@@ -87,138 +207,263 @@ impl QueryRoot {
         players
     }
 
-    /// Get the leaderboard, sorted by XP (descending).
-    async fn leaderboard(&self, limit: Option<i32>) -> Vec<LeaderboardEntry> {
+    /// Get the leaderboard, sorted by `order_by` (XP by default, descending).
+    /// Given a scoring category, lists the players who earned XP in it, with
+    /// their XP and level counting only those runs.
+    async fn leaderboard(
+        &self,
+        limit: Option<i32>,
+        #[graphql(visible = "since_v2")] category: Option<ScoringCategory>,
+        #[graphql(visible = "since_v2")] order_by: Option<LeaderboardMetric>,
+    ) -> Vec<LeaderboardEntry> {
         let limit = limit.unwrap_or(100) as usize;
-        let mut entries = Vec::new();
+        self.ranked_leaderboard(limit, category, order_by.unwrap_or_default())
+            .await
+    }
 
+    /// Get the rookie leaderboard: the players who are still new by the
+    /// rookie criteria, ranked by XP against each other only.
+    #[graphql(visible = "since_v2")]
+    async fn rookie_leaderboard(&self, limit: Option<i32>) -> Vec<LeaderboardEntry> {
+        let limit = limit.unwrap_or(100) as usize;
+        let criteria = *self.state.rookie_criteria.get();
+        let now = self.runtime.system_time().micros();
+        let hidden = self.hidden_from_leaderboards().await;
+        let mut candidates = Vec::new();
         self.state
-            .leaderboard
-            .for_each_index_value(|_, entry| {
-                entries.push(entry.into_owned());
+            .rookie_leaderboard
+            .for_each_index_value(|owner, entry| {
+                if !hidden.contains(&owner) {
+                    candidates.push(entry.into_owned());
+                }
                 Ok(())
             })
             .await
             .ok();
 
-        // Sort by total XP descending
-        entries.sort_by(|a, b| b.total_xp.cmp(&a.total_xp));
+        // Skip rookies who aged out since the last graduation sweep
+        let mut entries = Vec::with_capacity(candidates.len());
+        for entry in candidates {
+            let registered_at = self
+                .state
+                .players
+                .get(&entry.wallet_address)
+                .await
+                .ok()
+                .flatten()
+                .map_or(0, |player| player.registered_at);
+            if criteria.is_rookie(registered_at, entry.level, now) {
+                entries.push(entry);
+            }
+        }
 
-        // Assign ranks
+        entries.sort_by_key(|e| (std::cmp::Reverse(e.total_xp), e.wallet_address));
         for (i, entry) in entries.iter_mut().enumerate() {
             entry.rank = (i + 1) as u32;
+            entry.metric_value = entry.total_xp;
         }
-
         entries.truncate(limit);
         entries
     }
 
-    /// Get a player's rank on the leaderboard.
-    async fn player_rank(&self, wallet: String) -> Option<i32> {
-        let owner = parse_account_owner(&wallet)?;
+    /// Get who is placed on the rookie leaderboard.
+    #[graphql(visible = "since_v2")]
+    async fn rookie_criteria(&self) -> RookieCriteria {
+        *self.state.rookie_criteria.get()
+    }
 
-        let mut entries: Vec<LeaderboardEntry> = Vec::new();
-        self.state
-            .leaderboard
-            .for_each_index_value(|_, entry| {
-                entries.push(entry.into_owned());
-                Ok(())
-            })
+    /// Get the current form leaderboard: players ranked by recent XP, each
+    /// day's counting for less the older it is, so active players rank
+    /// above dormant ones.
+    #[graphql(visible = "since_v2")]
+    async fn form_leaderboard(&self, limit: Option<i32>) -> Vec<LeaderboardEntry> {
+        let limit = limit.unwrap_or(100) as usize;
+        self.ranked_leaderboard(limit, None, LeaderboardMetric::CurrentForm)
             .await
-            .ok();
+    }
 
-        entries.sort_by(|a, b| b.total_xp.cmp(&a.total_xp));
+    /// Get one page of the XP leaderboard. The first page is read from the
+    /// latest snapshot; its `next_page_token` pins later pages to the same
+    /// snapshot, so entries don't shift between pages while XP changes.
+    #[graphql(visible = "since_v2")]
+    async fn leaderboard_page(
+        &self,
+        limit: Option<i32>,
+        page_token: Option<String>,
+    ) -> async_graphql::Result<LeaderboardPage> {
+        let limit = limit.unwrap_or(100).max(1) as usize;
+        let token = match page_token {
+            Some(token) => PageToken::parse(&token).ok_or("Invalid page token")?,
+            None => PageToken {
+                snapshot_id: self
+                    .state
+                    .latest_snapshot
+                    .get()
+                    .ok_or("No leaderboard snapshot yet")?,
+                offset: 0,
+            },
+        };
+        let snapshot = self
+            .state
+            .leaderboard_snapshots
+            .get(&token.snapshot_id)
+            .await?
+            .ok_or("Page token has expired")?;
 
-        entries
-            .iter()
-            .position(|e| e.wallet_address == owner)
-            .map(|pos| (pos + 1) as i32)
+        // Players who hid themselves since the snapshot are skipped
+        let hidden = self.hidden_from_leaderboards().await;
+        let mut entries = Vec::new();
+        for entry in snapshot.entries.iter().skip(token.offset).take(limit) {
+            if hidden.contains(&entry.wallet_address) {
+                continue;
+            }
+            let Some(current) = self.state.leaderboard.get(&entry.wallet_address).await? else {
+                continue;
+            };
+            entries.push(LeaderboardEntry {
+                total_xp: entry.total_xp,
+                level: calculate_level(entry.total_xp),
+                rank: entry.rank,
+                metric_value: entry.total_xp,
+                ..current
+            });
+        }
+
+        let next_offset = token.offset.saturating_add(limit);
+        let next_page_token = (next_offset < snapshot.entries.len()).then(|| {
+            PageToken {
+                snapshot_id: snapshot.id,
+                offset: next_offset,
+            }
+            .encode()
+        });
+        Ok(LeaderboardPage {
+            entries,
+            snapshot_id: snapshot.id,
+            taken_at: snapshot.taken_at,
+            next_page_token,
+            truncated: snapshot.truncated,
+        })
+    }
+
+    /// Get a player's rank on the leaderboard.
+    ///
+    /// A shadow-banned or opted-out player is ranked as if they were still
+    /// listed, so they can see their own standing.
+    async fn player_rank(&self, wallet: String) -> Option<i32> {
+        let owner = parse_account_owner(&wallet)?;
+        self.rank_of(owner).await
     }
 
-    /// Get recent game scores.
+    /// Get recent game scores, most recently stored first. Only the latest
+    /// 500 scores stored are kept for this feed.
     async fn recent_scores(&self, limit: Option<i32>) -> Vec<GameScore> {
         let limit = limit.unwrap_or(50) as usize;
-        let mut scores = Vec::new();
-
-        self.state
-            .game_scores
-            .for_each_index_value(|_, score| {
-                scores.push(score.into_owned());
-                Ok(())
-            })
+        let hidden = self.hidden_score_history().await;
+        let mut scores = self
+            .state
+            .recent_scores
+            .elements()
             .await
-            .ok();
-
-        // Sort by timestamp descending (most recent first)
-        scores.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+            .unwrap_or_default();
+        scores.retain(|score| !hidden.contains(&score.player));
+        scores.reverse();
         scores.truncate(limit);
         scores
     }
 
-    /// Get scores for a specific game type.
-    async fn game_scores(&self, game_type: GameType, limit: Option<i32>) -> Vec<GameScore> {
+    /// Get scores for a specific game type, optionally restricted to one
+    /// scoring category.
+    async fn game_scores(
+        &self,
+        game_type: GameType,
+        limit: Option<i32>,
+        #[graphql(visible = "since_v2")] category: Option<ScoringCategory>,
+    ) -> Vec<GameScore> {
         let limit = limit.unwrap_or(50) as usize;
-        let mut scores = Vec::new();
-
-        self.state
-            .game_scores
-            .for_each_index_value(|_, score| {
-                if score.game_type == game_type {
-                    scores.push(score.into_owned());
-                }
-                Ok(())
-            })
-            .await
-            .ok();
-
-        // Sort by score descending
-        scores.sort_by(|a, b| b.score.cmp(&a.score));
+        let mut scores = self.sorted_game_scores(game_type, category).await;
         scores.truncate(limit);
         scores
     }
 
-    /// Get high scores for a specific game type (best per player).
+    /// Get one page of a player's scores, most recently stored first,
+    /// optionally only one game's. Pages are read from the player's own
+    /// score log, so other players' scores are never scanned. Players
+    /// hiding their score history have no scores listed.
+    #[graphql(visible = "since_v2")]
+    async fn player_scores(
+        &self,
+        wallet: String,
+        game_type: Option<GameType>,
+        limit: Option<i32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<ScoreConnection> {
+        let owner = parse_account_owner(&wallet).ok_or("Invalid wallet")?;
+        let end: u64 = match after {
+            Some(cursor) => cursor.parse().map_err(|_| "Invalid cursor")?,
+            None => u64::MAX,
+        };
+        if self.hidden_score_history().await.contains(&owner) {
+            return Ok(ScoreConnection {
+                nodes: Vec::new(),
+                page_info: PageInfo::default(),
+            });
+        }
+        let (nodes, next) = self
+            .player_scores_before(owner, end, page_size(limit), |score| {
+                game_type.is_none_or(|game| score.game_type == game)
+            })
+            .await?;
+        Ok(ScoreConnection {
+            nodes,
+            page_info: PageInfo::new(next.map(|index| index.to_string()), next.is_some()),
+        })
+    }
+
+    /// Get high scores for a specific game type (best per player). Assisted
+    /// runs are ranked separately from standard ones; standard by default.
     async fn game_high_scores(
         &self,
         game_type: GameType,
         limit: Option<i32>,
+        #[graphql(visible = "since_v2")] category: Option<ScoringCategory>,
     ) -> Vec<GameHighScoreEntry> {
         let limit = limit.unwrap_or(50) as usize;
+        let category = category.unwrap_or_default();
+        let hidden = self.hidden_from_leaderboards().await;
         let mut player_best: std::collections::HashMap<AccountOwner, GameScore> =
             std::collections::HashMap::new();
 
-        self.state
-            .game_scores
-            .for_each_index_value(|_, score| {
-                if score.game_type == game_type {
-                    let score_owned = score.into_owned();
-                    let entry = player_best.entry(score_owned.player.clone()).or_insert(score_owned.clone());
-                    if score_owned.score > entry.score {
-                        *entry = score_owned;
-                    }
+        for score in self.all_scores().await {
+            if score.game_type == game_type
+                && score.category == category
+                && !hidden.contains(&score.player)
+            {
+                let entry = player_best.entry(score.player).or_insert(score.clone());
+                if score.score > entry.score {
+                    *entry = score;
                 }
-                Ok(())
-            })
-            .await
-            .ok();
+            }
+        }
 
         let mut high_scores: Vec<GameHighScoreEntry> = Vec::new();
 
         for (player_owner, score) in player_best {
-            // Get username from players
-            let username = self
+            // Get names from players
+            let (username, display_name) = self
                 .state
                 .players
                 .get(&player_owner)
                 .await
                 .ok()
                 .flatten()
-                .map(|p| p.username)
-                .unwrap_or_else(|| "Unknown".to_string());
+                .map(|p| (p.username, p.display_name))
+                .unwrap_or_else(|| ("Unknown".to_string(), "Unknown".to_string()));
 
             high_scores.push(GameHighScoreEntry {
                 player: score.player,
                 username,
+                display_name,
                 score: score.score,
                 xp_earned: score.xp_earned,
                 timestamp: score.timestamp,
@@ -233,23 +478,2961 @@ impl QueryRoot {
 
     /// Get arcade statistics.
     async fn stats(&self) -> ArcadeStats {
-        let mut total_players = 0u64;
+        self.arcade_stats().await
+    }
+
+    /// Get the estimated entry counts and sizes of the major views, largest
+    /// first, to see what is driving state growth. Reads every entry of the
+    /// maps it covers, so it is slow on a large chain.
+    #[graphql(visible = "since_v2")]
+    async fn storage_report(&self) -> StorageReport {
+        let state = &self.state;
+        StorageReport::new(vec![
+            map_usage("players", &state.players).await,
+            log_usage("score_log", &state.score_log).await,
+            map_usage("score_positions", &state.score_positions).await,
+            map_usage("score_receipts", &state.score_receipts).await,
+            player_scores_usage(&state.player_scores).await,
+            map_usage("leaderboard", &state.leaderboard).await,
+            map_usage("rookie_leaderboard", &state.rookie_leaderboard).await,
+            map_usage("champion_scores", &state.champion_scores).await,
+            map_usage("leaderboard_snapshots", &state.leaderboard_snapshots).await,
+            map_usage("season_archive", &state.season_archive).await,
+            log_usage("milestones", &state.milestones).await,
+            log_usage("journal", &state.journal).await,
+        ])
+    }
+
+    /// Get games played per UTC hour of the week, Monday 00:00 first.
+    #[graphql(visible = "since_v2")]
+    async fn play_heatmap(&self) -> Vec<HeatmapCell> {
+        self.state.play_heatmap.get().cells()
+    }
+
+    /// Get the bucketed score distribution of a game. If `wallet` is given,
+    /// also place that player's best run within it.
+    #[graphql(visible = "since_v2")]
+    async fn score_distribution(
+        &self,
+        game_type: GameType,
+        wallet: Option<String>,
+    ) -> Option<ScoreDistribution> {
+        let histogram = self
+            .state
+            .score_histograms
+            .get(&game_type)
+            .await
+            .ok()
+            .flatten()?;
+        let total_scores = histogram.total();
+
+        let owner = wallet.as_deref().and_then(parse_account_owner);
+        let player_best = match owner {
+            Some(owner) => self.best_score(owner, game_type).await,
+            None => None,
+        };
+        let player_percentile = player_best.filter(|_| total_scores > 0).map(|best| {
+            let beaten = if game_type.lower_score_is_better() {
+                histogram.count_above(best)
+            } else {
+                histogram.count_below(best)
+            };
+            beaten as f64 * 100.0 / total_scores as f64
+        });
+
+        Some(ScoreDistribution {
+            game_type,
+            total_scores,
+            buckets: histogram.buckets(),
+            player_best,
+            player_percentile,
+        })
+    }
+
+    /// Get per-game score statistics (approximate median, p90 and p99 of the
+    /// raw scores).
+    #[graphql(visible = "since_v2")]
+    async fn game_stats(&self, game_type: GameType) -> GameStats {
+        let histogram = self
+            .state
+            .score_histograms
+            .get(&game_type)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| ScoreHistogram::new(game_type));
+        GameStats::from_histogram(game_type, &histogram)
+    }
+
+    /// Dry-run a score submission closing game session `session_id`
+    /// through the same checks the contract runs, without changing any
+    /// state, so clients can see why it would be rejected before sending a
+    /// transaction.
+    #[graphql(visible = "since_v2")]
+    #[allow(clippy::too_many_arguments)]
+    async fn validate_submission(
+        &self,
+        wallet: String,
+        game_type: GameType,
+        score: u64,
+        bonus_data: Option<u64>,
+        device_id: Option<String>,
+        session_id: Option<u64>,
+        signature: Option<ScoreSignature>,
+    ) -> SubmissionCheck {
+        let submission = ScoreSubmission {
+            game_type,
+            score,
+            bonus_data,
+            device_id,
+        };
+        let player = match parse_account_owner(&wallet) {
+            Some(owner) => self.state.players.get(&owner).await.ok().flatten(),
+            None => None,
+        };
+        let now = self.runtime.system_time().micros();
+        let result = match &player {
+            Some(player) => {
+                let mut pending = PendingSubmissions::default();
+                async {
+                    self.state
+                        .check_session(&player.owner, session_id, &submission, now)
+                        .await?;
+                    self.state
+                        .check_score_signature(
+                            &player.owner,
+                            &submission,
+                            signature.as_ref(),
+                            &mut pending,
+                        )
+                        .await?;
+                    self.state
+                        .check_submission(player, &submission, now, &mut pending)
+                        .await
+                }
+                .await
+            }
+            None => Err(ArcadeError::PlayerNotRegistered),
+        };
+
+        match (result, player) {
+            (Ok(()), Some(player)) => {
+                let mut circuit = self
+                    .state
+                    .daily_circuits
+                    .get(&player.owner)
+                    .await
+                    .ok()
+                    .flatten()
+                    .unwrap_or_default();
+                let circuit_bonus = if circuit.record(game_type, player.local_day(now)) {
+                    circuit::FULL_CIRCUIT_BONUS_XP
+                } else {
+                    0
+                };
+                let factors = self.state.xp_factors(&player, &submission, now).await;
+                let xp_earned = self.state.scoring_config.get().xp(&factors);
+                let mentored = self
+                    .state
+                    .mentorships
+                    .contains_key(&player.owner)
+                    .await
+                    .unwrap_or(false);
+                let mentorship_bonus = if mentored {
+                    mentorship::bonus_xp(xp_earned)
+                } else {
+                    0
+                };
+                SubmissionCheck {
+                    accepted: true,
+                    error: None,
+                    xp_earned: Some(xp_earned + mentorship_bonus + circuit_bonus),
+                }
+            }
+            (result, _) => SubmissionCheck {
+                accepted: false,
+                error: result.err().map(|e| e.to_string()),
+                xp_earned: None,
+            },
+        }
+    }
+
+    /// Get the chain's current time and, on the hub, when its state last
+    /// changed.
+    #[graphql(visible = "since_v2")]
+    async fn chain_time(&self) -> ChainTime {
+        let now = self.runtime.system_time().micros();
+        let is_hub = *self.state.hub_chain_id.get() == Some(self.runtime.chain_id());
+        ChainTime {
+            now,
+            hub_last_known: (*self.state.last_updated_at.get()).filter(|_| is_hub),
+        }
+    }
+
+    /// Get the microseconds until the next day boundary, when daily play
+    /// limits and daily challenges reset, measured by chain time. Boundaries
+    /// follow `wallet`'s timezone if given, and UTC otherwise.
+    #[graphql(visible = "since_v2")]
+    async fn daily_challenge_resets_in(&self, wallet: Option<String>) -> u64 {
+        let now = self.runtime.system_time().micros();
+        let player = match wallet.as_deref().and_then(parse_account_owner) {
+            Some(owner) => self.state.players.get(&owner).await.ok().flatten(),
+            None => None,
+        };
+        let offset = player.map_or(0, |player| {
+            i64::from(player.utc_offset_minutes) * 60 * 1_000_000
+        });
+        let local_now = now.saturating_add_signed(offset);
+        (day_index(local_now) + 1) * MICROS_PER_DAY - local_now
+    }
+
+    /// Get the microseconds until the current season ends, measured by chain
+    /// time. `None` while no season is running.
+    #[graphql(visible = "since_v2")]
+    async fn season_ends_in(&self) -> Option<u64> {
+        let now = self.runtime.system_time().micros();
+        let season = (*self.state.current_season.get())?;
+        Some(season.ends_at.saturating_sub(now))
+    }
+
+    /// Get the season in progress, if seasons have started.
+    #[graphql(visible = "since_v2")]
+    async fn current_season(&self) -> Option<Season> {
+        *self.state.current_season.get()
+    }
 
+    /// Get the current season's XP leaderboard.
+    #[graphql(visible = "since_v2")]
+    async fn season_leaderboard(&self, limit: Option<i32>) -> Vec<SeasonStanding> {
+        let limit = limit.unwrap_or(100) as usize;
+        let Some(season) = *self.state.current_season.get() else {
+            return Vec::new();
+        };
+        let hidden = self.hidden_from_leaderboards().await;
+        let mut entries = Vec::new();
         self.state
-            .players
-            .for_each_index(|_| {
-                total_players += 1;
+            .seasonal_xp
+            .for_each_index_value(|owner, seasonal| {
+                let xp = seasonal.in_season(season.number);
+                if xp > 0 && !hidden.contains(&owner) {
+                    entries.push((owner, xp));
+                }
                 Ok(())
             })
             .await
             .ok();
+        let mut standings = seasons::rank(entries);
+        standings.truncate(limit);
+        standings
+    }
 
-        ArcadeStats {
-            total_players,
-            total_games_played: *self.state.total_games_played.get(),
-            total_xp_earned: *self.state.total_xp_earned.get(),
+    /// Get every finished season, most recent first.
+    #[graphql(visible = "since_v2")]
+    async fn archived_seasons(&self) -> Vec<Season> {
+        let mut archived = Vec::new();
+        self.state
+            .season_archive
+            .for_each_index_value(|_, archive| {
+                archived.push(archive.season);
+                Ok(())
+            })
+            .await
+            .ok();
+        archived.reverse();
+        archived
+    }
+
+    /// Get a season's theme and cosmetic reward set, if it has one.
+    #[graphql(visible = "since_v2")]
+    async fn season_theme(&self, season: u32) -> Option<SeasonTheme> {
+        self.state.season_themes.get(&season).await.ok().flatten()
+    }
+
+    /// Get the cosmetics a player owns, oldest first (hub only).
+    #[graphql(visible = "since_v2")]
+    async fn cosmetics(&self, wallet: String) -> Vec<OwnedCosmetic> {
+        let Some(owner) = parse_account_owner(&wallet) else {
+            return Vec::new();
+        };
+        self.cosmetics_of(owner).await
+    }
+
+    /// Get a finished season's final standings, up to `limit` of them.
+    /// Players who hid themselves since are left out, keeping their rank.
+    #[graphql(visible = "since_v2")]
+    async fn season_archive(&self, season: u32, limit: Option<i32>) -> Option<SeasonArchive> {
+        let limit = limit.unwrap_or(100) as usize;
+        let mut archive = self
+            .state
+            .season_archive
+            .get(&season)
+            .await
+            .ok()
+            .flatten()?;
+        let hidden = self.hidden_from_leaderboards().await;
+        archive
+            .standings
+            .retain(|standing| !hidden.contains(&standing.wallet_address));
+        archive.standings.truncate(limit);
+        Some(archive)
+    }
+
+    /// Get everything the home screen needs in one round trip: stats, the
+    /// top five players, the latest milestone, the crossover events running
+    /// now, today's daily challenge and, if `wallet` is supplied, that
+    /// player's own snapshot.
+    #[graphql(visible = "since_v2")]
+    async fn arcade_summary(&self, wallet: Option<String>) -> ArcadeSummary {
+        let owner = wallet.as_deref().and_then(parse_account_owner);
+        let me = match owner {
+            Some(owner) => self.player_snapshot(owner).await,
+            None => None,
+        };
+        let current_events = self.current_crossover_events();
+        let today = self.today_of(owner).await;
+        let daily_challenge = DailyChallenge::for_day(today, *self.state.hub_chain_id.get());
+        let milestone_count = self.state.milestones.count();
+        let latest_milestone = match milestone_count.checked_sub(1) {
+            Some(index) => self.state.milestones.get(index).await.ok().flatten(),
+            None => None,
+        };
+
+        ArcadeSummary {
+            stats: self.arcade_stats().await,
+            top_players: self
+                .ranked_leaderboard(5, None, LeaderboardMetric::TotalXp)
+                .await,
+            latest_milestone,
+            current_events,
+            daily_challenge,
+            me,
         }
     }
+
+    /// Get an appeal by its ID.
+    #[graphql(visible = "since_v2")]
+    async fn appeal(&self, appeal_id: u64) -> Option<Appeal> {
+        self.state.appeals.get(&appeal_id).await.ok().flatten()
+    }
+
+    /// Get appeals, optionally filtered by status (oldest first).
+    #[graphql(visible = "since_v2")]
+    async fn appeals(&self, status: Option<AppealStatus>) -> Vec<Appeal> {
+        let mut appeals = Vec::new();
+        self.state
+            .appeals
+            .for_each_index_value(|_, appeal| {
+                if status.is_none_or(|status| appeal.status == status) {
+                    appeals.push(appeal.into_owned());
+                }
+                Ok(())
+            })
+            .await
+            .ok();
+        appeals
+    }
+
+    /// Get the bond required to file an appeal.
+    #[graphql(visible = "since_v2")]
+    async fn appeal_bond(&self) -> Amount {
+        *self.state.appeal_bond.get()
+    }
+
+    /// Get the accounts awaiting a moderator's review (oldest first).
+    #[graphql(visible = "since_v2")]
+    async fn moderation_queue(&self) -> Vec<ModerationFlag> {
+        let mut flags = Vec::new();
+        self.state
+            .moderation_queue
+            .for_each_index_value(|_, flag| {
+                flags.push(flag.into_owned());
+                Ok(())
+            })
+            .await
+            .ok();
+        flags.sort_by_key(|flag| flag.id);
+        flags
+    }
+
+    /// Get the humanity check settings.
+    #[graphql(visible = "since_v2")]
+    async fn humanity_check_config(&self) -> HumanityCheckConfig {
+        self.state.humanity_checks.get().clone()
+    }
+
+    /// Get a player's open humanity challenge, which their second wallet
+    /// signs to complete it.
+    #[graphql(visible = "since_v2")]
+    async fn humanity_challenge(&self, wallet: String) -> Option<HumanityChallenge> {
+        let owner = parse_account_owner(&wallet)?;
+        self.state.humanity_challenges.get(&owner).await.ok()?
+    }
+
+    /// Get the wallet that vouched for a player's last completed humanity
+    /// challenge.
+    #[graphql(visible = "since_v2")]
+    async fn humanity_verifier(&self, wallet: String) -> Option<AccountOwner> {
+        let owner = parse_account_owner(&wallet)?;
+        self.state.humanity_verifiers.get(&owner).await.ok()?
+    }
+
+    /// Get a player's operations on this chain, measured against the daily
+    /// soft quota.
+    #[graphql(visible = "since_v2")]
+    async fn usage(&self, wallet: String) -> Option<UsageReport> {
+        let owner = parse_account_owner(&wallet)?;
+        let usage = self
+            .state
+            .operation_usage
+            .get(&owner)
+            .await
+            .ok()
+            .flatten()?;
+        let today = day_index(self.runtime.system_time().micros());
+        Some(UsageReport::new(
+            usage,
+            *self.state.usage_quota.get(),
+            today,
+        ))
+    }
+
+    /// Get the daily soft quotas on operations.
+    #[graphql(visible = "since_v2")]
+    async fn usage_quota(&self) -> UsageQuota {
+        *self.state.usage_quota.get()
+    }
+
+    /// Get the smurf detection thresholds.
+    #[graphql(visible = "since_v2")]
+    async fn smurf_detection_config(&self) -> SmurfDetectionConfig {
+        self.state.smurf_detection.get().clone()
+    }
+
+    /// Get the running totals of held, refunded and forfeited appeal bonds.
+    #[graphql(visible = "since_v2")]
+    async fn bond_accounts(&self) -> BondAccounts {
+        self.state.bond_accounts.get().clone()
+    }
+
+    /// Get how far the unfinished pass over an invariant section has got.
+    #[graphql(visible = "since_v2")]
+    async fn invariant_check(&self, section: InvariantSection) -> Option<InvariantCheck> {
+        self.state
+            .invariant_checks
+            .get(&section)
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// Get up to `limit` journal entries starting at sequence number
+    /// `start`, oldest first, for replaying the state's history.
+    #[graphql(visible = "since_v2")]
+    async fn journal_entries(
+        &self,
+        start: u64,
+        limit: Option<i32>,
+    ) -> async_graphql::Result<Vec<JournalEntry>> {
+        let limit = limit.unwrap_or(100).clamp(1, MAX_INDEXER_PAGE) as usize;
+        let length = self.state.journal.count();
+        let start = (start as usize).min(length);
+        let end = start.saturating_add(limit).min(length);
+        Ok(self.state.journal.read(start..end).await?)
+    }
+
+    /// Get the journal's checkpoints of the arcade totals, oldest first.
+    #[graphql(visible = "since_v2")]
+    async fn journal_checkpoints(&self) -> Vec<JournalCheckpoint> {
+        self.state
+            .journal_checkpoints
+            .read(..)
+            .await
+            .unwrap_or_default()
+    }
+
+    /// Get how far the unfinished replay of a group of derived views has
+    /// got.
+    #[graphql(visible = "since_v2")]
+    async fn replay_progress(&self, target: ReplayTarget) -> Option<ReplayProgress> {
+        self.state.replays.get(&target).await.ok().flatten()
+    }
+
+    /// Get the maintenance window, while the arcade is read-only.
+    #[graphql(visible = "since_v2")]
+    async fn maintenance(&self) -> Option<MaintenanceWindow> {
+        let now = self.runtime.system_time().micros();
+        self.state
+            .maintenance
+            .get()
+            .filter(|window| window.is_active(now))
+    }
+
+    /// Get the kiosk-mode settings.
+    #[graphql(visible = "since_v2")]
+    async fn kiosk_config(&self) -> KioskConfig {
+        self.state.kiosk_config.get().clone()
+    }
+
+    /// Get a kiosk device's submission activity.
+    #[graphql(visible = "since_v2")]
+    async fn device_activity(&self, device_id: String) -> Option<DeviceActivity> {
+        self.state
+            .device_activity
+            .get(&device_id)
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// Get all accounts authorized to relay submissions.
+    #[graphql(visible = "since_v2")]
+    async fn gateways(&self) -> Vec<AccountOwner> {
+        self.state.gateways.indices().await.unwrap_or_default()
+    }
+
+    /// Get the last nonce relayed for a player (the next must be higher).
+    #[graphql(visible = "since_v2")]
+    async fn gateway_nonce(&self, wallet: String) -> Option<u64> {
+        let owner = parse_account_owner(&wallet)?;
+        self.state.gateway_nonces.get(&owner).await.ok().flatten()
+    }
+
+    /// Get the operator key that signs registration vouchers.
+    #[graphql(visible = "since_v2")]
+    async fn voucher_signer(&self) -> Option<AccountOwner> {
+        *self.state.voucher_signer.get()
+    }
+
+    /// Get the key of the deployment admin whose signed exports are accepted.
+    #[graphql(visible = "since_v2")]
+    async fn migration_source(&self) -> Option<AccountOwner> {
+        *self.state.migration_source.get()
+    }
+
+    /// Get a player's exported progress, for the admin to sign over.
+    #[graphql(visible = "since_v2")]
+    async fn progress_export(&self, wallet: String) -> Option<ProgressExport> {
+        let owner = parse_account_owner(&wallet)?;
+        self.state.progress_exports.get(&owner).await.ok()?
+    }
+
+    /// Get the progress a player imported from other deployments.
+    #[graphql(visible = "since_v2")]
+    async fn progress_imports(&self, wallet: String) -> Vec<ProgressImport> {
+        let Some(owner) = parse_account_owner(&wallet) else {
+            return Vec::new();
+        };
+        let mut imports = Vec::new();
+        self.state
+            .progress_imports
+            .for_each_index_value(|_, import| {
+                if import.player == owner {
+                    imports.push(import.into_owned());
+                }
+                Ok(())
+            })
+            .await
+            .ok();
+        imports.sort_by_key(|import| import.imported_at);
+        imports
+    }
+
+    /// Get the game server key that signs submissions, if anti-cheat mode
+    /// is on.
+    #[graphql(visible = "since_v2")]
+    async fn score_signer(&self) -> Option<AccountOwner> {
+        *self.state.score_signer.get()
+    }
+
+    /// Check whether a registration voucher has already been used.
+    #[graphql(visible = "since_v2")]
+    async fn voucher_consumed(&self, voucher_id: u64) -> bool {
+        self.state
+            .consumed_vouchers
+            .contains_key(&voucher_id)
+            .await
+            .unwrap_or(false)
+    }
+
+    /// Get all username reservations that haven't expired yet.
+    #[graphql(visible = "since_v2")]
+    async fn active_reservations(&self) -> Vec<UsernameReservation> {
+        let now = self.runtime.system_time().micros();
+        let mut reservations = Vec::new();
+        self.state
+            .reservations
+            .for_each_index_value(|_, reservation| {
+                if reservation.is_active(now) {
+                    reservations.push(reservation.into_owned());
+                }
+                Ok(())
+            })
+            .await
+            .ok();
+        reservations.sort_by_key(|r| r.expires_at);
+        reservations
+    }
+
+    /// Get the active reservation of a username, if any.
+    #[graphql(visible = "since_v2")]
+    async fn username_reservation(&self, username: String) -> Option<UsernameReservation> {
+        let now = self.runtime.system_time().micros();
+        let key = usernames::normalize_username(&username);
+        self.state
+            .reservations
+            .get(&key)
+            .await
+            .ok()
+            .flatten()
+            .filter(|reservation| reservation.is_active(now))
+    }
+
+    /// Get the account holding a username, unless it was released and its
+    /// grace period has passed.
+    #[graphql(visible = "since_v2")]
+    async fn username_claim(&self, username: String) -> Option<UsernameClaim> {
+        self.held_username_claim(&usernames::normalize_username(&username))
+            .await
+    }
+
+    /// Whether `wallet` (or anyone, if omitted) could register a username
+    /// right now: it is valid, and neither auctioned, held by another
+    /// account nor reserved for someone else. A blocked name is only
+    /// available to the account it is reserved for.
+    #[graphql(visible = "since_v2")]
+    async fn username_available(&self, username: String, wallet: Option<String>) -> bool {
+        if validate_username(&username).is_err() {
+            return false;
+        }
+        let owner = wallet.as_deref().and_then(parse_account_owner);
+        let key = usernames::normalize_username(&username);
+        if self
+            .state
+            .auctioned_usernames
+            .contains_key(&key)
+            .await
+            .unwrap_or(false)
+        {
+            return false;
+        }
+        let held_by_other = |holder: AccountOwner| owner != Some(holder);
+        if let Some(claim) = self.held_username_claim(&key).await {
+            if held_by_other(claim.owner) {
+                return false;
+            }
+        }
+        let now = self.runtime.system_time().micros();
+        match self.state.reservations.get(&key).await.ok().flatten() {
+            Some(reservation) if reservation.is_active(now) => !held_by_other(reservation.owner),
+            _ => {
+                let blocked = self
+                    .state
+                    .blocked_usernames
+                    .indices()
+                    .await
+                    .unwrap_or_default();
+                !usernames::is_blocked_username(&username, &blocked)
+            }
+        }
+    }
+
+    /// Get the terms usernames may not contain, as blocklist keys.
+    #[graphql(visible = "since_v2")]
+    async fn blocked_usernames(&self) -> Vec<String> {
+        self.state
+            .blocked_usernames
+            .indices()
+            .await
+            .unwrap_or_default()
+    }
+
+    /// Get the inactivity and grace periods for username recycling.
+    #[graphql(visible = "since_v2")]
+    async fn username_recycling_config(&self) -> UsernameRecyclingConfig {
+        self.state.username_recycling.get().clone()
+    }
+
+    /// Get username auctions, most recently started first, optionally only
+    /// those not yet settled.
+    #[graphql(visible = "since_v2")]
+    async fn username_auctions(&self, open_only: Option<bool>) -> Vec<UsernameAuction> {
+        let open_only = open_only.unwrap_or(false);
+        let mut auctions = Vec::new();
+        self.state
+            .username_auctions
+            .for_each_index_value(|_, auction| {
+                if !open_only || auction.result.is_none() {
+                    auctions.push(auction.into_owned());
+                }
+                Ok(())
+            })
+            .await
+            .ok();
+        auctions.sort_by_key(|auction| std::cmp::Reverse(auction.id));
+        auctions
+    }
+
+    /// Get a username auction by ID, with its phase at the current time.
+    #[graphql(visible = "since_v2")]
+    async fn username_auction(&self, auction_id: u64) -> Option<UsernameAuctionStatus> {
+        let auction = self
+            .state
+            .username_auctions
+            .get(&auction_id)
+            .await
+            .ok()
+            .flatten()?;
+        let now = self.runtime.system_time().micros();
+        Some(UsernameAuctionStatus {
+            phase: auction.phase(now),
+            auction,
+        })
+    }
+
+    /// Get the commitment to place for a sealed bid of `amount` by `wallet`.
+    /// Ask only a node you run, as the amount and salt must stay private
+    /// until the reveal.
+    #[graphql(visible = "since_v2")]
+    async fn username_bid_commitment(
+        &self,
+        auction_id: u64,
+        wallet: String,
+        amount: Amount,
+        salt: String,
+    ) -> Option<CryptoHash> {
+        let owner = parse_account_owner(&wallet)?;
+        Some(name_auctions::bid_commitment(
+            auction_id, owner, amount, &salt,
+        ))
+    }
+
+    /// Get the arcade-wide milestones reached so far, oldest first.
+    #[graphql(visible = "since_v2")]
+    async fn milestones(&self) -> Vec<Milestone> {
+        self.state.milestones.read(..).await.unwrap_or_default()
+    }
+
+    /// Get a stored leaderboard snapshot.
+    #[graphql(visible = "since_v2")]
+    async fn leaderboard_snapshot(&self, snapshot_id: u64) -> Option<LeaderboardSnapshot> {
+        self.state
+            .leaderboard_snapshots
+            .get(&snapshot_id)
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// Get the rank movers between two stored snapshots.
+    #[graphql(visible = "since_v2")]
+    async fn leaderboard_diff(
+        &self,
+        from_snapshot: u64,
+        to_snapshot: u64,
+    ) -> Option<LeaderboardDiff> {
+        let snapshots = &self.state.leaderboard_snapshots;
+        let from = snapshots.get(&from_snapshot).await.ok()??;
+        let to = snapshots.get(&to_snapshot).await.ok()??;
+        Some(LeaderboardDiff::between(&from, &to))
+    }
+
+    /// Get the rank movers between the latest snapshots taken at or before
+    /// each of two timestamps.
+    #[graphql(visible = "since_v2")]
+    async fn leaderboard_diff_by_time(
+        &self,
+        from_time: u64,
+        to_time: u64,
+    ) -> Option<LeaderboardDiff> {
+        let from = self.snapshot_at(from_time).await?;
+        let to = self.snapshot_at(to_time).await?;
+        Some(LeaderboardDiff::between(&from, &to))
+    }
+
+    /// Get today's daily challenge, in `wallet`'s timezone if given and UTC
+    /// otherwise.
+    #[graphql(visible = "since_v2")]
+    async fn todays_challenge(&self, wallet: Option<String>) -> DailyChallenge {
+        let owner = wallet.as_deref().and_then(parse_account_owner);
+        let today = self.today_of(owner).await;
+        DailyChallenge::for_day(today, *self.state.hub_chain_id.get())
+    }
+
+    /// Get the best runs of a day's daily challenge (today in UTC by
+    /// default), highest score first. Leaderboards are kept for
+    /// [`challenges::CHALLENGE_BOARD_DAYS`] days.
+    #[graphql(visible = "since_v2")]
+    async fn daily_challenge_leaderboard(
+        &self,
+        day: Option<u64>,
+        limit: Option<i32>,
+    ) -> Vec<ChallengeEntry> {
+        let limit = limit.unwrap_or(10) as usize;
+        let day = match day {
+            Some(day) => day,
+            None => self.today_of(None).await,
+        };
+        let Some(board) = self.state.challenge_boards.get(&day).await.ok().flatten() else {
+            return Vec::new();
+        };
+        let hidden = self.hidden_from_leaderboards().await;
+        board
+            .entries()
+            .iter()
+            .filter(|entry| !hidden.contains(&entry.player))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// Get this month's challenge, in `wallet`'s timezone if given and UTC
+    /// otherwise.
+    #[graphql(visible = "since_v2")]
+    async fn monthly_challenge(&self, wallet: Option<String>) -> MonthlyChallenge {
+        let owner = wallet.as_deref().and_then(parse_account_owner);
+        let today = self.today_of(owner).await;
+        MonthlyChallenge::for_month(streaks::month_index(today))
+    }
+
+    /// Get a player's progress on this month's challenge, in their timezone.
+    #[graphql(visible = "since_v2")]
+    async fn monthly_progress(&self, wallet: String) -> Option<MonthlyProgress> {
+        let owner = parse_account_owner(&wallet)?;
+        Some(self.monthly_progress_of(owner).await)
+    }
+
+    /// Get the participants in a month's challenge (this month in UTC by
+    /// default) with the most progress, most first. `month` counts
+    /// `year * 12 + month - 1`.
+    #[graphql(visible = "since_v2")]
+    async fn monthly_challenge_leaderboard(
+        &self,
+        month: Option<u64>,
+        limit: Option<i32>,
+    ) -> Vec<MonthlyProgress> {
+        let limit = limit.unwrap_or(10) as usize;
+        let month = match month {
+            Some(month) => month,
+            None => streaks::month_index(self.today_of(None).await),
+        };
+        let Some(board) = self.state.monthly_boards.get(&month).await.ok().flatten() else {
+            return Vec::new();
+        };
+        let hidden = self.hidden_from_leaderboards().await;
+        board
+            .entries()
+            .iter()
+            .filter(|entry| !hidden.contains(&entry.player))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// Get the last `days` days of daily challenges (30 by default, at most
+    /// a year), oldest first, with whether the player completed each, in
+    /// their timezone.
+    #[graphql(visible = "since_v2")]
+    async fn challenge_history(&self, wallet: String, days: Option<i32>) -> Vec<ChallengeDay> {
+        let Some(owner) = parse_account_owner(&wallet) else {
+            return Vec::new();
+        };
+        self.challenge_history_of(owner, days).await
+    }
+
+    /// Get a player's play tally for their most recent day of play.
+    #[graphql(visible = "since_v2")]
+    async fn daily_play(&self, wallet: String) -> Option<DailyPlay> {
+        let owner = parse_account_owner(&wallet)?;
+        self.state.daily_play.get(&owner).await.ok().flatten()
+    }
+
+    /// Get the players a player has blocked.
+    #[graphql(visible = "since_v2")]
+    async fn blocked_players(&self, wallet: String) -> Vec<AccountOwner> {
+        let Some(owner) = parse_account_owner(&wallet) else {
+            return Vec::new();
+        };
+        self.blocked_by(owner).await
+    }
+
+    /// Get everything about one player under a single node.
+    #[graphql(visible = "since_v2")]
+    async fn me(&self, wallet: String) -> Option<Me> {
+        let owner = parse_account_owner(&wallet)?;
+        Some(Me {
+            owner,
+            root: self.clone(),
+        })
+    }
+
+    /// Get the API versions this service supports and the one this request
+    /// was answered with.
+    async fn api_version(&self, ctx: &Context<'_>) -> ApiVersion {
+        let requested = ctx
+            .data_opt::<RequestedApiVersion>()
+            .map_or(API_VERSION, |version| version.0);
+        ApiVersion {
+            current: API_VERSION,
+            minimum: MIN_API_VERSION,
+            requested,
+        }
+    }
+
+    /// Get whether the application is a developer sandbox, whose players
+    /// and scores are never synced to the hub.
+    #[graphql(visible = "since_v2")]
+    async fn sandbox(&self) -> bool {
+        self.runtime
+            .application_parameters()
+            .is_some_and(|parameters| parameters.sandbox)
+    }
+
+    /// Get the applications receiving forwarded events and their delivery
+    /// chains.
+    #[graphql(visible = "since_v2")]
+    async fn event_bridges(&self) -> Vec<BridgeSubscriber> {
+        let mut subscribers = Vec::new();
+        self.state
+            .bridge_subscriptions
+            .for_each_index_value(|application_id, subscription| {
+                subscribers.push(BridgeSubscriber {
+                    application_id,
+                    chains: subscription.chains().collect(),
+                });
+                Ok(())
+            })
+            .await
+            .unwrap_or_default();
+        subscribers
+    }
+
+    /// Get the chains whose sync streams this chain subscribed to.
+    #[graphql(visible = "since_v2")]
+    async fn sync_sources(&self) -> Vec<ChainId> {
+        self.state.sync_sources.indices().await.unwrap_or_default()
+    }
+
+    /// Get one page of players for indexers, ordered by their storage key.
+    /// The order never changes, so paging with `after_owner` visits every
+    /// public player exactly once, including players who register midway.
+    /// Hidden players are skipped, so a page may come back short.
+    #[graphql(visible = "since_v2")]
+    async fn players_page(
+        &self,
+        after_owner: Option<String>,
+        limit: Option<i32>,
+    ) -> async_graphql::Result<PlayersPage> {
+        let limit = limit.unwrap_or(100).clamp(1, MAX_INDEXER_PAGE) as usize;
+        let after_key = match after_owner {
+            Some(wallet) => {
+                let owner = parse_account_owner(&wallet).ok_or("Invalid after_owner")?;
+                Some(bcs::to_bytes(&owner)?)
+            }
+            None => None,
+        };
+        let (players, last_scanned, more) = self.players_after(after_key, limit).await?;
+        Ok(PlayersPage {
+            players,
+            next_after_owner: last_scanned.filter(|_| more),
+        })
+    }
+
+    /// Get one page of public players in storage order. The order never
+    /// changes, so paging with `after` visits every public player exactly
+    /// once and only reads the players on the page. Hidden players are
+    /// skipped, so a page may come back short.
+    #[graphql(visible = "since_v2")]
+    async fn players_connection(
+        &self,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<PlayerConnection> {
+        let after_key = match after {
+            Some(cursor) => Some(hex::decode(cursor).map_err(|_| "Invalid cursor")?),
+            None => None,
+        };
+        let (nodes, last_scanned, more) = self.players_after(after_key, page_size(first)).await?;
+        let cursor = match last_scanned {
+            Some(owner) => Some(hex::encode(bcs::to_bytes(&owner)?)),
+            None => None,
+        };
+        Ok(PlayerConnection {
+            nodes,
+            page_info: PageInfo::new(cursor, more),
+        })
+    }
+
+    /// Get one page of recent game scores, most recently stored first.
+    /// Pages are cut from the end of the score log, so only the scores on
+    /// the page are read. Hidden scores are skipped.
+    #[graphql(visible = "since_v2")]
+    async fn recent_scores_connection(
+        &self,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> async_graphql::Result<ScoreConnection> {
+        let end = match after {
+            Some(cursor) => cursor.parse().map_err(|_| "Invalid cursor")?,
+            None => usize::MAX,
+        };
+        let hidden = self.hidden_score_history().await;
+        let (nodes, next_end) = self
+            .scores_before(end, page_size(first), |score| {
+                !hidden.contains(&score.player)
+            })
+            .await;
+        Ok(ScoreConnection {
+            nodes,
+            page_info: PageInfo::new(next_end.map(|end| end.to_string()), next_end.is_some()),
+        })
+    }
+
+    /// Get one page of the leaderboard, sorted like `leaderboard`. The
+    /// cursor is an offset into the live ranking, so entries can shift
+    /// between pages while XP changes; use `leaderboardPage` for stable
+    /// pages.
+    #[graphql(visible = "since_v2")]
+    async fn leaderboard_connection(
+        &self,
+        first: Option<i32>,
+        after: Option<String>,
+        category: Option<ScoringCategory>,
+        order_by: Option<LeaderboardMetric>,
+    ) -> async_graphql::Result<LeaderboardConnection> {
+        let entries = self
+            .ranked_leaderboard(usize::MAX, category, order_by.unwrap_or_default())
+            .await;
+        let (nodes, page_info) = offset_page(entries, first, after)?;
+        Ok(LeaderboardConnection { nodes, page_info })
+    }
+
+    /// Get one page of scores for a specific game type, sorted like
+    /// `gameScores`. The cursor is an offset into the sorted scores.
+    #[graphql(visible = "since_v2")]
+    async fn game_scores_connection(
+        &self,
+        game_type: GameType,
+        first: Option<i32>,
+        after: Option<String>,
+        category: Option<ScoringCategory>,
+    ) -> async_graphql::Result<ScoreConnection> {
+        let scores = self.sorted_game_scores(game_type, category).await;
+        let (nodes, page_info) = offset_page(scores, first, after)?;
+        Ok(ScoreConnection { nodes, page_info })
+    }
+
+    /// Get one page of the scores played on `chain_id` (by default this
+    /// chain) for indexers, in ascending ID order. IDs are numbered per
+    /// chain and never reused, so paging with `after_id` visits every
+    /// public score from the chain exactly once. Hidden scores are skipped,
+    /// so a page may come back short.
+    #[graphql(visible = "since_v2")]
+    async fn scores_page(
+        &self,
+        chain_id: Option<ChainId>,
+        after_id: Option<u64>,
+        limit: Option<i32>,
+    ) -> async_graphql::Result<ScoresPage> {
+        let limit = limit.unwrap_or(100).clamp(1, MAX_INDEXER_PAGE) as usize;
+        let chain_id = chain_id.unwrap_or_else(|| self.runtime.chain_id());
+        // Keys are stored little-endian, so storage order isn't ID order
+        let mut keys = self.state.score_positions.indices().await?;
+        keys.retain(|(chain, id)| {
+            *chain == chain_id && after_id.is_none_or(|after_id| *id > after_id)
+        });
+        keys.sort_unstable();
+        let more = keys.len() > limit;
+        keys.truncate(limit);
+
+        let positions = self
+            .state
+            .score_positions
+            .multi_get(&keys)
+            .await?
+            .into_iter()
+            .flatten()
+            .map(|position| position as usize)
+            .collect();
+        let hidden = self.hidden_score_history().await;
+        let scores = self
+            .state
+            .score_log
+            .multi_get(positions)
+            .await?
+            .into_iter()
+            .flatten()
+            .filter(|score| !hidden.contains(&score.player))
+            .collect();
+        Ok(ScoresPage {
+            scores,
+            next_after_id: keys.last().map(|(_, id)| *id).filter(|_| more),
+        })
+    }
+
+    /// Get up to `limit` scores from the score log starting at position
+    /// `start`, oldest first, for replaying history. Scores hidden from
+    /// feeds or removed by moderation come back as null so positions stay
+    /// aligned.
+    #[graphql(visible = "since_v2")]
+    async fn score_log(
+        &self,
+        start: u64,
+        limit: Option<i32>,
+    ) -> async_graphql::Result<ScoreLogRange> {
+        let limit = limit.unwrap_or(100).clamp(1, MAX_INDEXER_PAGE) as usize;
+        let length = self.state.score_log.count();
+        let start = (start as usize).min(length);
+        let end = start.saturating_add(limit).min(length);
+        let hidden = self.hidden_score_history().await;
+        let removed = self.removed_scores().await;
+        let scores = (start as u64..)
+            .zip(self.state.score_log.read(start..end).await?)
+            .map(|(position, score)| {
+                (!hidden.contains(&score.player) && !removed.contains(&position)).then_some(score)
+            })
+            .collect();
+        Ok(ScoreLogRange {
+            start: start as u64,
+            scores,
+            length: length as u64,
+        })
+    }
+
+    /// Get the score a receipt was issued for, to check a shared run is
+    /// genuine and whose it is.
+    #[graphql(visible = "since_v2")]
+    async fn verify_score(&self, receipt: CryptoHash) -> Option<GameScore> {
+        let position = self.state.score_receipts.get(&receipt).await.ok()??;
+        let score = self.state.score_log.get(position as usize).await.ok()??;
+        (score.receipt() == receipt).then_some(score)
+    }
+
+    /// Get how many entries archived leaderboard snapshots keep; `None`
+    /// keeps them whole.
+    #[graphql(visible = "since_v2")]
+    async fn history_retention(&self) -> Option<u32> {
+        *self.state.history_top_k.get()
+    }
+
+    /// Get each game's recent share of play and the XP multiplier it
+    /// currently earns, which a running crossover event may override.
+    #[graphql(visible = "since_v2")]
+    async fn game_multipliers(&self) -> Vec<GameMultiplier> {
+        let now = self.runtime.system_time().micros();
+        let schedule = self.state.crossover_schedule.get();
+        let mut multipliers = self
+            .state
+            .play_share
+            .get()
+            .multipliers(now, self.state.balance_config.get());
+        for multiplier in &mut multipliers {
+            if let Some(percent) = schedule.xp_multiplier(multiplier.game_type, now) {
+                multiplier.multiplier_percent = percent;
+            }
+        }
+        multipliers
+    }
+
+    /// Get the crossover events running now or yet to start, soonest first.
+    #[graphql(visible = "since_v2")]
+    async fn crossover_events(&self) -> Vec<CrossoverEvent> {
+        let now = self.runtime.system_time().micros();
+        self.state.crossover_schedule.get().upcoming(now)
+    }
+
+    /// Get the crossover events running now, highest precedence first.
+    #[graphql(visible = "since_v2")]
+    async fn active_crossover_events(&self) -> Vec<CrossoverEvent> {
+        self.current_crossover_events()
+    }
+
+    /// Get every raffle, most recently scheduled first (hub only).
+    #[graphql(visible = "since_v2")]
+    async fn raffles(&self, limit: Option<i32>) -> Vec<Raffle> {
+        let limit = limit.unwrap_or(10) as usize;
+        let mut raffles = Vec::new();
+        self.state
+            .raffles
+            .for_each_index_value(|_, raffle| {
+                raffles.push(raffle.into_owned());
+                Ok(())
+            })
+            .await
+            .ok();
+        raffles.reverse();
+        raffles.truncate(limit);
+        raffles
+    }
+
+    /// Get a raffle by ID (hub only).
+    #[graphql(visible = "since_v2")]
+    async fn raffle(&self, raffle_id: u64) -> Option<Raffle> {
+        self.state.raffles.get(&raffle_id).await.ok().flatten()
+    }
+
+    /// Get the tickets a player holds in a raffle not yet drawn.
+    #[graphql(visible = "since_v2")]
+    async fn raffle_tickets(&self, raffle_id: u64, wallet: String) -> u64 {
+        let Some(owner) = parse_account_owner(&wallet) else {
+            return 0;
+        };
+        self.state
+            .raffle_tickets
+            .get(&(raffle_id, owner))
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or(0)
+    }
+
+    /// Get the commitment to publish for a raffle secret. Ask only a node
+    /// you run, as the secret must stay private until the draw.
+    #[graphql(visible = "since_v2")]
+    async fn raffle_commitment(&self, secret: String) -> CryptoHash {
+        raffles::commitment(&secret)
+    }
+
+    /// Get every boss battle, most recently scheduled first (hub only).
+    #[graphql(visible = "since_v2")]
+    async fn boss_battles(&self, limit: Option<i32>) -> Vec<BossBattle> {
+        let limit = limit.unwrap_or(10) as usize;
+        let mut battles = Vec::new();
+        self.state
+            .boss_battles
+            .for_each_index_value(|_, battle| {
+                battles.push(battle.into_owned());
+                Ok(())
+            })
+            .await
+            .ok();
+        battles.reverse();
+        battles.truncate(limit);
+        battles
+    }
+
+    /// Get a boss battle by ID (hub only).
+    #[graphql(visible = "since_v2")]
+    async fn boss_battle(&self, battle_id: u64) -> Option<BossBattle> {
+        self.state.boss_battles.get(&battle_id).await.ok().flatten()
+    }
+
+    /// Get the damage a player has dealt in a boss battle not yet settled.
+    #[graphql(visible = "since_v2")]
+    async fn boss_damage(&self, battle_id: u64, wallet: String) -> u64 {
+        let Some(owner) = parse_account_owner(&wallet) else {
+            return 0;
+        };
+        self.state
+            .boss_damage
+            .get(&(battle_id, owner))
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or(0)
+    }
+
+    /// Get the XP a player has been rewarded for defeating bosses (hub only).
+    #[graphql(visible = "since_v2")]
+    async fn boss_rewards(&self, wallet: String) -> BossRewards {
+        let Some(owner) = parse_account_owner(&wallet) else {
+            return BossRewards::default();
+        };
+        self.state
+            .boss_rewards
+            .get(&owner)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_default()
+    }
+
+    /// Get the community games, optionally only those with `status`, in
+    /// the order they were submitted (hub only).
+    #[graphql(visible = "since_v2")]
+    async fn community_games(&self, status: Option<CommunityGameStatus>) -> Vec<CommunityGame> {
+        let mut games = Vec::new();
+        self.state
+            .community_games
+            .for_each_index_value(|_, game| {
+                if status.is_none_or(|status| status == game.status) {
+                    games.push(game.into_owned());
+                }
+                Ok(())
+            })
+            .await
+            .ok();
+        games
+    }
+
+    /// Get a community game by ID (hub only).
+    #[graphql(visible = "since_v2")]
+    async fn community_game(&self, game_id: u64) -> Option<CommunityGame> {
+        self.state
+            .community_games
+            .get(&game_id)
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// Get a community game's best runs, one per player, highest first
+    /// (hub only).
+    #[graphql(visible = "since_v2")]
+    async fn community_leaderboard(
+        &self,
+        game_id: u64,
+        limit: Option<i32>,
+    ) -> Vec<CommunityLeaderboardEntry> {
+        let limit = limit.unwrap_or(10).max(0) as usize;
+        let Ok(Some(leaderboard)) = self.state.community_leaderboards.get(&game_id).await else {
+            return Vec::new();
+        };
+        leaderboard.entries().iter().take(limit).cloned().collect()
+    }
+
+    /// Get the XP a player has earned in community games, kept apart from
+    /// their arcade XP (hub only).
+    #[graphql(visible = "since_v2")]
+    async fn community_xp(&self, wallet: String) -> u64 {
+        let Some(owner) = parse_account_owner(&wallet) else {
+            return 0;
+        };
+        self.state
+            .community_xp
+            .get(&owner)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or(0)
+    }
+
+    /// Get every game, whether it is accepting submissions now and the
+    /// daily hours it is closed.
+    #[graphql(visible = "since_v2")]
+    async fn games(&self) -> Vec<GameInfo> {
+        let now = self.runtime.system_time().micros();
+        let windows = self.state.submission_windows.get();
+        GameType::ALL
+            .iter()
+            .map(|game_type| GameInfo::new(*game_type, windows, now))
+            .collect()
+    }
+
+    /// Get the plausibility bounds submissions of each game must fall
+    /// within.
+    #[graphql(visible = "since_v2")]
+    async fn score_bounds(&self) -> Vec<ScoreBounds> {
+        let config = self.state.plausibility.get();
+        GameType::ALL
+            .iter()
+            .map(|game_type| config.bounds_for(*game_type))
+            .collect()
+    }
+
+    /// Get the underplayed-game bonus settings.
+    #[graphql(visible = "since_v2")]
+    async fn balance_config(&self) -> BalanceConfig {
+        self.state.balance_config.get().clone()
+    }
+
+    /// Get the live scoring config.
+    #[graphql(visible = "since_v2")]
+    async fn scoring_config(&self) -> ScoringConfig {
+        self.state.scoring_config.get().clone()
+    }
+
+    /// Get the running scoring canary, or the last one to end, with the XP
+    /// tallied under both configs.
+    #[graphql(visible = "since_v2")]
+    async fn scoring_canary(&self) -> Option<ScoringCanary> {
+        self.state.scoring_canary.get().clone()
+    }
+
+    /// Get a player's active mentorship as an apprentice.
+    #[graphql(visible = "since_v2")]
+    async fn mentorship(&self, wallet: String) -> Option<Mentorship> {
+        let owner = parse_account_owner(&wallet)?;
+        self.state.mentorships.get(&owner).await.ok().flatten()
+    }
+
+    /// Get the top mentors, by apprentices graduated and then bonus XP
+    /// earned from apprentices.
+    #[graphql(visible = "since_v2")]
+    async fn mentor_leaderboard(&self, limit: Option<i32>) -> Vec<MentorRecord> {
+        let limit = limit.unwrap_or(10) as usize;
+        let hidden = self.hidden_from_leaderboards().await;
+        let mut records = Vec::new();
+        self.state
+            .mentors
+            .for_each_index_value(|mentor, record| {
+                if !hidden.contains(&mentor) && (record.graduates > 0 || record.bonus_xp > 0) {
+                    records.push(record.into_owned());
+                }
+                Ok(())
+            })
+            .await
+            .ok();
+        records.sort_by(|a, b| {
+            b.graduates
+                .cmp(&a.graduates)
+                .then(b.bonus_xp.cmp(&a.bonus_xp))
+        });
+        records.truncate(limit);
+        records
+    }
+
+    /// Get each game's standing record (best standard-category run) with
+    /// the reactions left on it.
+    #[graphql(visible = "since_v2")]
+    async fn hall_of_fame(&self) -> Vec<HallOfFameRecord> {
+        let hidden = self.hidden_from_leaderboards().await;
+        let mut scores = Vec::new();
+        self.state
+            .hall_of_fame
+            .for_each_index_value(|_, score| {
+                if !hidden.contains(&score.player) {
+                    scores.push(score.into_owned());
+                }
+                Ok(())
+            })
+            .await
+            .ok();
+        let mut records = Vec::with_capacity(scores.len());
+        for score in scores {
+            let reactions = self.reaction_counts(ReactionTarget::Record(score.id)).await;
+            records.push(HallOfFameRecord { score, reactions });
+        }
+        records
+    }
+
+    /// Get the reactions left on a hall-of-fame record or leaderboard entry,
+    /// most used first.
+    #[graphql(visible = "since_v2")]
+    async fn reactions(&self, target: ReactionTarget) -> Vec<EmoteCount> {
+        self.reaction_counts(target).await
+    }
+
+    /// Get every achievement that can be unlocked.
+    #[graphql(visible = "since_v2")]
+    async fn achievement_catalog(&self) -> Vec<AchievementDefinition> {
+        Achievement::ALL
+            .into_iter()
+            .map(Achievement::definition)
+            .collect()
+    }
+
+    /// Get a player's progress on every achievement. Friends-only profiles
+    /// show none.
+    #[graphql(visible = "since_v2")]
+    async fn player_achievements(&self, wallet: String) -> Vec<AchievementStatus> {
+        let Some(owner) = parse_account_owner(&wallet) else {
+            return Vec::new();
+        };
+        match self.state.players.get(&owner).await.ok().flatten() {
+            Some(player) if !player.privacy.friends_only_profile => {
+                self.achievements_of(&player).await
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Get the event achievements a player unlocked, oldest first.
+    /// Friends-only profiles show none.
+    #[graphql(visible = "since_v2")]
+    async fn event_achievements(&self, wallet: String) -> Vec<EventAchievementUnlock> {
+        let Some(owner) = parse_account_owner(&wallet) else {
+            return Vec::new();
+        };
+        match self.state.players.get(&owner).await.ok().flatten() {
+            Some(player) if !player.privacy.friends_only_profile => {
+                self.event_achievements_of(owner).await
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Get a player's open game session, if they have one that hasn't
+    /// timed out.
+    #[graphql(visible = "since_v2")]
+    async fn open_session(&self, wallet: String) -> Option<GameSession> {
+        let owner = parse_account_owner(&wallet)?;
+        self.open_session_of(owner).await
+    }
+
+    /// Get a player's daily streak and shields.
+    #[graphql(visible = "since_v2")]
+    async fn streak(&self, wallet: String) -> Option<DailyStreak> {
+        let owner = parse_account_owner(&wallet)?;
+        self.state.streaks.get(&owner).await.ok().flatten()
+    }
+
+    /// Get a player's personal goals, oldest first. Friends-only profiles
+    /// show none.
+    #[graphql(visible = "since_v2")]
+    async fn goals(&self, wallet: String) -> Vec<GoalProgress> {
+        let Some(owner) = parse_account_owner(&wallet) else {
+            return Vec::new();
+        };
+        let public = self
+            .state
+            .players
+            .get(&owner)
+            .await
+            .ok()
+            .flatten()
+            .is_some_and(|player| !player.privacy.friends_only_profile);
+        if !public {
+            return Vec::new();
+        }
+        self.goals_of(owner).await
+    }
+
+    /// Get how many players follow a player and how many they follow.
+    #[graphql(visible = "since_v2")]
+    async fn follow_counts(&self, wallet: String) -> Option<FollowCounts> {
+        let owner = parse_account_owner(&wallet)?;
+        Some(self.follow_counts_of(owner).await)
+    }
+
+    /// Get the personal bests of the players a player follows, newest
+    /// first. Players hiding their score history are left out.
+    #[graphql(visible = "since_v2")]
+    async fn following_activity(&self, wallet: String, limit: Option<i32>) -> Vec<GameScore> {
+        let limit = limit.unwrap_or(20) as usize;
+        let Some(owner) = parse_account_owner(&wallet) else {
+            return Vec::new();
+        };
+        let following = self
+            .state
+            .following
+            .get(&owner)
+            .await
+            .ok()
+            .flatten()
+            .map(|follows| follows.owners())
+            .unwrap_or_default();
+        let hidden = self.hidden_score_history().await;
+
+        let mut feed = Vec::new();
+        for followed in following {
+            if hidden.contains(&followed) {
+                continue;
+            }
+            if let Ok(Some(bests)) = self.state.personal_bests.get(&followed).await {
+                feed.extend(bests.runs().cloned());
+            }
+        }
+        feed.sort_by_key(|score| std::cmp::Reverse(score.timestamp));
+        feed.truncate(limit);
+        feed
+    }
+
+    /// Get a player's kudos counters.
+    #[graphql(visible = "since_v2")]
+    async fn kudos(&self, wallet: String) -> Option<KudosRecord> {
+        let owner = parse_account_owner(&wallet)?;
+        self.state.kudos.get(&owner).await.ok().flatten()
+    }
+
+    /// Get the players who have received the most kudos.
+    #[graphql(visible = "since_v2")]
+    async fn most_endorsed(&self, limit: Option<i32>) -> Vec<KudosRecord> {
+        let limit = limit.unwrap_or(10) as usize;
+        let hidden = self.hidden_from_leaderboards().await;
+        let mut records = Vec::new();
+        self.state
+            .kudos
+            .for_each_index_value(|owner, record| {
+                if !hidden.contains(&owner) && record.received > 0 {
+                    records.push(record.into_owned());
+                }
+                Ok(())
+            })
+            .await
+            .ok();
+        records.sort_by_key(|record| std::cmp::Reverse(record.received));
+        records.truncate(limit);
+        records
+    }
+
+    /// Get when each of `wallets` was last seen playing and what they're
+    /// playing now. Players hiding their score history are reported as
+    /// never seen.
+    #[graphql(visible = "since_v2")]
+    async fn presence(&self, wallets: Vec<String>) -> Vec<PlayerPresence> {
+        let now = self.runtime.system_time().micros();
+        let hidden = self.hidden_score_history().await;
+        let mut presences = Vec::with_capacity(wallets.len());
+        for owner in wallets
+            .iter()
+            .filter_map(|wallet| parse_account_owner(wallet))
+        {
+            let presence = if hidden.contains(&owner) {
+                None
+            } else {
+                self.state.presence.get(&owner).await.ok().flatten()
+            };
+            presences.push(PlayerPresence::new(owner, presence, now));
+        }
+        presences
+    }
+
+    /// Get a player's friends: the players they added who added them back.
+    #[graphql(visible = "since_v2")]
+    async fn friends(&self, wallet: String) -> Vec<AccountOwner> {
+        let Some(owner) = parse_account_owner(&wallet) else {
+            return Vec::new();
+        };
+        self.friends_of(owner).await
+    }
+
+    /// Get the players who added a player as a friend without being added
+    /// back.
+    #[graphql(visible = "since_v2")]
+    async fn friend_requests(&self, wallet: String) -> Vec<AccountOwner> {
+        let Some(owner) = parse_account_owner(&wallet) else {
+            return Vec::new();
+        };
+        self.state
+            .friends
+            .get(&owner)
+            .await
+            .ok()
+            .flatten()
+            .map(|friends| friends.requests())
+            .unwrap_or_default()
+    }
+
+    /// Get the XP leaderboard of a player and their friends, ranked only
+    /// against each other. Friends hidden from leaderboards are left out;
+    /// the player themselves always appears.
+    #[graphql(visible = "since_v2")]
+    async fn friends_leaderboard(
+        &self,
+        wallet: String,
+        limit: Option<i32>,
+    ) -> Vec<LeaderboardEntry> {
+        let Some(owner) = parse_account_owner(&wallet) else {
+            return Vec::new();
+        };
+        let limit = limit.unwrap_or(10) as usize;
+        let hidden = self.hidden_from_leaderboards().await;
+        let mut entries = Vec::new();
+        if let Some(entry) = self.state.leaderboard.get(&owner).await.ok().flatten() {
+            entries.push(entry);
+        }
+        for friend in self.friends_of(owner).await {
+            if hidden.contains(&friend) {
+                continue;
+            }
+            if let Some(entry) = self.state.leaderboard.get(&friend).await.ok().flatten() {
+                entries.push(entry);
+            }
+        }
+
+        entries.sort_by_key(|e| (std::cmp::Reverse(e.total_xp), e.wallet_address));
+        for (i, entry) in entries.iter_mut().enumerate() {
+            entry.rank = (i + 1) as u32;
+            entry.metric_value = entry.total_xp;
+        }
+        entries.truncate(limit);
+        entries
+    }
+
+    /// Get when each of a player's friends was last seen playing and what
+    /// they're playing now. Friends hiding their score history are
+    /// reported as never seen.
+    #[graphql(visible = "since_v2")]
+    async fn friends_presence(&self, wallet: String) -> Vec<PlayerPresence> {
+        let Some(owner) = parse_account_owner(&wallet) else {
+            return Vec::new();
+        };
+        let now = self.runtime.system_time().micros();
+        let hidden = self.hidden_score_history().await;
+        let mut presences = Vec::new();
+        for friend in self.friends_of(owner).await {
+            let presence = if hidden.contains(&friend) {
+                None
+            } else {
+                self.state.presence.get(&friend).await.ok().flatten()
+            };
+            presences.push(PlayerPresence::new(friend, presence, now));
+        }
+        presences
+    }
+
+    /// Get a guild by ID.
+    #[graphql(visible = "since_v2")]
+    async fn guild(&self, guild_id: u64) -> Option<Guild> {
+        self.state.guilds.get(&guild_id).await.ok().flatten()
+    }
+
+    /// Get a guild by name (case-insensitive).
+    #[graphql(visible = "since_v2")]
+    async fn guild_by_name(&self, name: String) -> Option<Guild> {
+        let key = guilds::normalize_guild_name(&name);
+        let guild_id = self.state.guild_names.get(&key).await.ok()??;
+        self.state.guilds.get(&guild_id).await.ok().flatten()
+    }
+
+    /// Get the guild leaderboard: guilds ranked by the XP their members
+    /// earned while in them.
+    #[graphql(visible = "since_v2")]
+    async fn guild_leaderboard(&self, limit: Option<i32>) -> Vec<GuildStanding> {
+        let limit = limit.unwrap_or(10) as usize;
+        let mut standings = self.guild_standings().await;
+        standings.truncate(limit);
+        standings
+    }
+
+    /// Get a guild's place on the guild leaderboard.
+    #[graphql(visible = "since_v2")]
+    async fn guild_standing(&self, guild_id: u64) -> Option<GuildStanding> {
+        self.guild_standings()
+            .await
+            .into_iter()
+            .find(|standing| standing.guild_id == guild_id)
+    }
+
+    /// Get the guild a player belongs to.
+    #[graphql(visible = "since_v2")]
+    async fn player_guild(&self, wallet: String) -> Option<Guild> {
+        let owner = parse_account_owner(&wallet)?;
+        self.guild_of(owner).await
+    }
+
+    /// Get a guild's members with their play since joining, ranked by XP
+    /// contributed. Members who haven't played since joining come last, so
+    /// leaders can spot inactive members.
+    #[graphql(visible = "since_v2")]
+    async fn guild_contributors(&self, guild_id: u64) -> Vec<GuildContributor> {
+        let Some(guild) = self.state.guilds.get(&guild_id).await.ok().flatten() else {
+            return Vec::new();
+        };
+        let mut contributors = Vec::with_capacity(guild.members.len());
+        for member in &guild.members {
+            let contribution = self
+                .state
+                .guild_contributions
+                .get(&member.owner)
+                .await
+                .ok()
+                .flatten()
+                .filter(|contribution| contribution.guild_id == guild_id);
+            contributors.push(GuildContributor::new(member, contribution.as_ref()));
+        }
+        contributors.sort_by(|a, b| {
+            b.xp_contributed
+                .cmp(&a.xp_contributed)
+                .then(b.last_played_at.cmp(&a.last_played_at))
+        });
+        contributors
+    }
+
+    /// Get a guild's accumulated XP, level and progress towards the next.
+    #[graphql(visible = "since_v2")]
+    async fn guild_progress(&self, guild_id: u64) -> Option<GuildProgress> {
+        if !self.state.guilds.contains_key(&guild_id).await.ok()? {
+            return None;
+        }
+        let xp = self
+            .state
+            .guild_xp
+            .get(&guild_id)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or(0);
+        Some(GuildProgress::new(guild_id, xp))
+    }
+
+    /// Get every guild level and what it unlocks.
+    #[graphql(visible = "since_v2")]
+    async fn guild_tiers(&self) -> Vec<GuildTier> {
+        guilds::GUILD_TIERS.to_vec()
+    }
+
+    /// Get the hash to register an invite code under with
+    /// `createInviteCode`.
+    #[graphql(visible = "since_v2")]
+    async fn invite_code_hash(&self, code: String) -> CryptoHash {
+        invites::hash_code(&code)
+    }
+
+    /// Get a guild's invite codes that can still be redeemed.
+    #[graphql(visible = "since_v2")]
+    async fn guild_invite_codes(&self, guild_id: u64) -> Vec<InviteCode> {
+        let now = self.runtime.system_time().micros();
+        let mut codes = Vec::new();
+        self.state
+            .invite_codes
+            .for_each_index_value(|_, code| {
+                if code.guild_id == guild_id && code.is_active(now) {
+                    codes.push(code.into_owned());
+                }
+                Ok(())
+            })
+            .await
+            .ok();
+        codes.sort_by_key(|code| code.created_at);
+        codes
+    }
+
+    /// Get a guild's polls, newest first, optionally only those still open.
+    #[graphql(visible = "since_v2")]
+    async fn guild_polls(&self, guild_id: u64, open_only: Option<bool>) -> Vec<GuildPollResult> {
+        let now = self.runtime.system_time().micros();
+        let open_only = open_only.unwrap_or(false);
+        let mut results = Vec::new();
+        self.state
+            .guild_polls
+            .for_each_index_value(|_, poll| {
+                let open = poll.is_open(now);
+                if poll.guild_id == guild_id && (open || !open_only) {
+                    results.push(GuildPollResult {
+                        winner: poll.winner(),
+                        poll: poll.into_owned(),
+                        open,
+                    });
+                }
+                Ok(())
+            })
+            .await
+            .ok();
+        results.sort_by_key(|result| std::cmp::Reverse(result.poll.id));
+        results
+    }
+
+    /// Get how a guild's prizes are split.
+    #[graphql(visible = "since_v2")]
+    async fn guild_payout_rule(&self, guild_id: u64) -> GuildPayoutRule {
+        self.state
+            .guild_payout_rules
+            .get(&guild_id)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_default()
+    }
+
+    /// Get the prizes awarded to a guild, most recent first.
+    #[graphql(visible = "since_v2")]
+    async fn guild_prizes(&self, guild_id: u64) -> Vec<GuildPrize> {
+        let mut prizes = Vec::new();
+        self.state
+            .guild_prizes
+            .for_each_index_value(|_, prize| {
+                if prize.guild_id == guild_id {
+                    prizes.push(prize.into_owned());
+                }
+                Ok(())
+            })
+            .await
+            .ok();
+        prizes.sort_by_key(|prize| std::cmp::Reverse(prize.id));
+        prizes
+    }
+
+    /// Get each member's share of a guild prize.
+    #[graphql(visible = "since_v2")]
+    async fn guild_prize_claims(&self, prize_id: u64) -> Vec<PrizeClaim> {
+        let mut claims = Vec::new();
+        self.state
+            .prize_claims
+            .for_each_index_value(|(id, _), claim| {
+                if id == prize_id {
+                    claims.push(claim.into_owned());
+                }
+                Ok(())
+            })
+            .await
+            .ok();
+        claims
+    }
+
+    /// Get a player's shares of guild prizes, most recent first, optionally
+    /// only those not yet collected.
+    #[graphql(visible = "since_v2")]
+    async fn prize_claims(&self, wallet: String, unclaimed_only: Option<bool>) -> Vec<PrizeClaim> {
+        let Some(owner) = parse_account_owner(&wallet) else {
+            return Vec::new();
+        };
+        let unclaimed_only = unclaimed_only.unwrap_or(false);
+        let mut claims = Vec::new();
+        self.state
+            .prize_claims
+            .for_each_index_value(|(_, member), claim| {
+                if member == owner && (!unclaimed_only || claim.claimed_at.is_none()) {
+                    claims.push(claim.into_owned());
+                }
+                Ok(())
+            })
+            .await
+            .ok();
+        claims.sort_by_key(|claim| std::cmp::Reverse(claim.prize_id));
+        claims
+    }
+
+    /// Get a guild's most recent management actions, newest first.
+    #[graphql(visible = "since_v2")]
+    async fn guild_audit(&self, guild_id: u64, limit: Option<i32>) -> Vec<GuildAuditEntry> {
+        let limit = limit.unwrap_or(50) as usize;
+        self.state
+            .guild_audit
+            .get(&guild_id)
+            .await
+            .ok()
+            .flatten()
+            .map(|trail| trail.latest(limit))
+            .unwrap_or_default()
+    }
+
+    /// Get all banned kiosk devices.
+    #[graphql(visible = "since_v2")]
+    async fn banned_devices(&self) -> Vec<String> {
+        self.state
+            .banned_devices
+            .indices()
+            .await
+            .unwrap_or_default()
+    }
+}
+
+impl QueryRoot {
+    /// The claim on a normalized username, unless it was released and its
+    /// grace period has passed.
+    async fn held_username_claim(&self, key: &String) -> Option<UsernameClaim> {
+        let now = self.runtime.system_time().micros();
+        let claim = self.state.username_claims.get(key).await.ok().flatten()?;
+        let last_seen_at = self
+            .state
+            .presence
+            .get(&claim.owner)
+            .await
+            .ok()
+            .flatten()
+            .map(|presence| presence.last_seen_at);
+        (!claim.is_recyclable(last_seen_at, now)).then_some(claim)
+    }
+
+    /// Public leaderboard entries ranked by `metric`, truncated to `limit`.
+    async fn ranked_leaderboard(
+        &self,
+        limit: usize,
+        category: Option<ScoringCategory>,
+        metric: LeaderboardMetric,
+    ) -> Vec<LeaderboardEntry> {
+        let hidden = self.hidden_from_leaderboards().await;
+        let mut entries = Vec::new();
+
+        self.state
+            .leaderboard
+            .for_each_index_value(|owner, entry| {
+                if !hidden.contains(&owner) {
+                    entries.push(entry.into_owned());
+                }
+                Ok(())
+            })
+            .await
+            .ok();
+
+        // A category board ranks players by the XP they earned in it, so XP
+        // earned under other rules doesn't carry over when players switch
+        if let Some(category) = category {
+            let mut in_category = Vec::new();
+            for mut entry in entries {
+                let xp = self
+                    .state
+                    .category_xp
+                    .get(&(entry.wallet_address, category))
+                    .await
+                    .ok()
+                    .flatten()
+                    .unwrap_or(0);
+                if xp > 0 {
+                    entry.total_xp = xp;
+                    entry.level = calculate_level(xp);
+                    in_category.push(entry);
+                }
+            }
+            entries = in_category;
+        }
+
+        // Look up the metric for each entry, from the index that backs it
+        match metric {
+            LeaderboardMetric::TotalXp => {
+                for entry in &mut entries {
+                    entry.metric_value = entry.total_xp;
+                }
+            }
+            LeaderboardMetric::Level => {
+                for entry in &mut entries {
+                    entry.metric_value = u64::from(entry.level);
+                }
+            }
+            LeaderboardMetric::GamesPlayed => {
+                for entry in &mut entries {
+                    entry.metric_value = self
+                        .state
+                        .players
+                        .get(&entry.wallet_address)
+                        .await
+                        .ok()
+                        .flatten()
+                        .map_or(0, |player| player.games_played);
+                }
+            }
+            LeaderboardMetric::SeasonalXp => {
+                let season = self
+                    .state
+                    .current_season
+                    .get()
+                    .map_or(0, |season| season.number);
+                for entry in &mut entries {
+                    entry.metric_value = self
+                        .state
+                        .seasonal_xp
+                        .get(&entry.wallet_address)
+                        .await
+                        .ok()
+                        .flatten()
+                        .map_or(0, |seasonal| seasonal.in_season(season));
+                }
+            }
+            LeaderboardMetric::CurrentForm => {
+                let now = self.runtime.system_time().micros();
+                for entry in &mut entries {
+                    entry.metric_value = self
+                        .state
+                        .recent_form
+                        .get(&entry.wallet_address)
+                        .await
+                        .ok()
+                        .flatten()
+                        .map_or(0, |form| form.score(now));
+                }
+            }
+            LeaderboardMetric::ChampionScore => {
+                for entry in &mut entries {
+                    entry.metric_value = self
+                        .state
+                        .champion_scores
+                        .get(&entry.wallet_address)
+                        .await
+                        .ok()
+                        .flatten()
+                        .map_or(0, |champion| champion.total());
+                }
+            }
+        }
+
+        // Sort by the metric descending, then by total XP
+        entries.sort_by_key(|e| std::cmp::Reverse((e.metric_value, e.total_xp)));
+
+        // Assign ranks
+        for (i, entry) in entries.iter_mut().enumerate() {
+            entry.rank = (i + 1) as u32;
+        }
+
+        entries.truncate(limit);
+        entries
+    }
+
+    /// A player's rank, counting them even if they are hidden from the
+    /// public leaderboard.
+    async fn rank_of(&self, owner: AccountOwner) -> Option<i32> {
+        let hidden = self.hidden_from_leaderboards().await;
+
+        let mut entries: Vec<LeaderboardEntry> = Vec::new();
+        self.state
+            .leaderboard
+            .for_each_index_value(|entry_owner, entry| {
+                if entry_owner == owner || !hidden.contains(&entry_owner) {
+                    entries.push(entry.into_owned());
+                }
+                Ok(())
+            })
+            .await
+            .ok();
+
+        entries.sort_by(|a, b| b.total_xp.cmp(&a.total_xp));
+
+        entries
+            .iter()
+            .position(|e| e.wallet_address == owner)
+            .map(|pos| (pos + 1) as i32)
+    }
+
+    /// Arcade statistics, excluding shadow-banned players.
+    async fn arcade_stats(&self) -> ArcadeStats {
+        let mut hidden_players = 0u64;
+        for owner in self.shadow_banned().await {
+            if self
+                .state
+                .players
+                .contains_key(&owner)
+                .await
+                .unwrap_or(false)
+            {
+                hidden_players += 1;
+            }
+        }
+
+        ArcadeStats {
+            total_players: self
+                .state
+                .total_players
+                .get()
+                .saturating_sub(hidden_players),
+            total_games_played: *self.state.total_games_played.get(),
+            total_xp_earned: *self.state.total_xp_earned.get(),
+        }
+    }
+
+    /// Every logged score not removed by moderation, oldest first.
+    async fn all_scores(&self) -> Vec<GameScore> {
+        let removed = self.removed_scores().await;
+        let scores = self.state.score_log.read(..).await.unwrap_or_default();
+        (0..)
+            .zip(scores)
+            .filter(|(position, _)| !removed.contains(position))
+            .map(|(_, score)| score)
+            .collect()
+    }
+
+    /// A player's best score in a game.
+    async fn best_score(&self, owner: AccountOwner, game_type: GameType) -> Option<u64> {
+        let count = self.player_score_count(&owner).await;
+        let scores = self
+            .player_score_range(&owner, 0, count)
+            .await
+            .unwrap_or_default();
+        let mut best: Option<u64> = None;
+        for (_, score) in scores {
+            if score.game_type == game_type {
+                best = Some(match best {
+                    Some(best) if game_type.lower_score_is_better() => best.min(score.score),
+                    Some(best) => best.max(score.score),
+                    None => score.score,
+                });
+            }
+        }
+        best
+    }
+
+    /// Up to `limit` scores matching `filter` stored before log position
+    /// `end`, newest first, along with the position the next page ends at
+    /// if the log goes on past them.
+    async fn scores_before(
+        &self,
+        end: usize,
+        limit: usize,
+        filter: impl Fn(&GameScore) -> bool,
+    ) -> (Vec<GameScore>, Option<usize>) {
+        let removed = self.removed_scores().await;
+        let mut scores = Vec::new();
+        let mut end = end.min(self.state.score_log.count());
+        while end > 0 {
+            let start = end.saturating_sub(limit.max(1));
+            let Ok(chunk) = self.state.score_log.read(start..end).await else {
+                break;
+            };
+            for (position, score) in (start..end).zip(chunk).rev() {
+                if scores.len() == limit {
+                    return (scores, Some(position + 1));
+                }
+                if !removed.contains(&(position as u64)) && filter(&score) {
+                    scores.push(score);
+                }
+            }
+            end = start;
+        }
+        (scores, None)
+    }
+
+    /// How many scores `owner` has in their score log.
+    async fn player_score_count(&self, owner: &AccountOwner) -> u64 {
+        match self.state.player_scores.try_load_entry(owner).await {
+            Ok(Some(log)) => log.count() as u64,
+            _ => 0,
+        }
+    }
+
+    /// `owner`'s scores from index `start` up to `end` among theirs, oldest
+    /// first, each with its index. Scores removed by moderation are skipped.
+    async fn player_score_range(
+        &self,
+        owner: &AccountOwner,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<(u64, GameScore)>, ViewError> {
+        let Some(log) = self.state.player_scores.try_load_entry(owner).await? else {
+            return Ok(Vec::new());
+        };
+        let removed = self.removed_scores().await;
+        let indexed = (start..)
+            .zip(log.read(start as usize..end as usize).await?)
+            .filter(|(_, position)| !removed.contains(position))
+            .collect::<Vec<_>>();
+        let positions = indexed
+            .iter()
+            .map(|(_, position)| *position as usize)
+            .collect();
+        let scores = self.state.score_log.multi_get(positions).await?;
+        Ok(indexed
+            .into_iter()
+            .zip(scores)
+            .filter_map(|((index, _), score)| Some((index, score?)))
+            .collect())
+    }
+
+    /// Up to `limit` of `owner`'s scores matching `filter` stored before
+    /// their `end`th, newest first, along with the index the next page ends
+    /// at if they have older scores.
+    async fn player_scores_before(
+        &self,
+        owner: AccountOwner,
+        end: u64,
+        limit: usize,
+        filter: impl Fn(&GameScore) -> bool,
+    ) -> Result<(Vec<GameScore>, Option<u64>), ViewError> {
+        let mut scores = Vec::new();
+        let mut end = end.min(self.player_score_count(&owner).await);
+        while end > 0 {
+            let start = end.saturating_sub(limit.max(1) as u64);
+            for (index, score) in self
+                .player_score_range(&owner, start, end)
+                .await?
+                .into_iter()
+                .rev()
+            {
+                if scores.len() == limit {
+                    return Ok((scores, Some(index + 1)));
+                }
+                if filter(&score) {
+                    scores.push(score);
+                }
+            }
+            end = start;
+        }
+        Ok((scores, None))
+    }
+
+    /// The public scores of a game, optionally restricted to one scoring
+    /// category, best first.
+    async fn sorted_game_scores(
+        &self,
+        game_type: GameType,
+        category: Option<ScoringCategory>,
+    ) -> Vec<GameScore> {
+        let hidden = self.hidden_score_history().await;
+        let mut scores = self.all_scores().await;
+        scores.retain(|score| {
+            score.game_type == game_type
+                && category.is_none_or(|category| score.category == category)
+                && !hidden.contains(&score.player)
+        });
+
+        // Sort by score descending
+        scores.sort_by(|a, b| b.score.cmp(&a.score));
+        scores
+    }
+
+    /// Up to `limit` public players stored after the key `after_key`, with
+    /// the last player scanned and whether more follow.
+    async fn players_after(
+        &self,
+        after_key: Option<Vec<u8>>,
+        limit: usize,
+    ) -> async_graphql::Result<(Vec<Player>, Option<AccountOwner>, bool)> {
+        let hidden = self.shadow_banned().await;
+        let mut players = Vec::new();
+        let mut last_scanned = None;
+        let mut more = false;
+        self.state
+            .players
+            .for_each_index_value_while(|owner, player| {
+                if let Some(after_key) = &after_key {
+                    if bcs::to_bytes(&owner)? <= *after_key {
+                        return Ok(true);
+                    }
+                }
+                if players.len() == limit {
+                    more = true;
+                    return Ok(false);
+                }
+                last_scanned = Some(owner);
+                if !hidden.contains(&owner) && !player.privacy.friends_only_profile {
+                    players.push(player.into_owned());
+                }
+                Ok(true)
+            })
+            .await?;
+        Ok((players, last_scanned, more))
+    }
+
+    /// The latest snapshot taken at or before `timestamp`.
+    async fn snapshot_at(&self, timestamp: u64) -> Option<LeaderboardSnapshot> {
+        let mut latest: Option<LeaderboardSnapshot> = None;
+        self.state
+            .leaderboard_snapshots
+            .for_each_index_value(|_, snapshot| {
+                let newer = latest.as_ref().is_none_or(|latest| snapshot.id > latest.id);
+                if snapshot.taken_at <= timestamp && newer {
+                    latest = Some(snapshot.into_owned());
+                }
+                Ok(())
+            })
+            .await
+            .ok();
+        latest
+    }
+
+    /// The owners a player has blocked.
+    async fn blocked_by(&self, owner: AccountOwner) -> Vec<AccountOwner> {
+        self.state
+            .blocklists
+            .get(&owner)
+            .await
+            .ok()
+            .flatten()
+            .map(|blocklist| blocklist.owners())
+            .unwrap_or_default()
+    }
+
+    /// A player's profile together with their current rank.
+    async fn player_snapshot(&self, owner: AccountOwner) -> Option<PlayerSnapshot> {
+        let player = self.state.players.get(&owner).await.ok().flatten()?;
+        let rank = self.rank_of(owner).await;
+        let xp = self.xp_buckets_of(&player).await;
+        Some(PlayerSnapshot { player, rank, xp })
+    }
+
+    /// `player`'s XP this local day, week and month, summed from their
+    /// runs newest first until one predates all three periods.
+    async fn xp_buckets_of(&self, player: &Player) -> XpBuckets {
+        let now = self.runtime.system_time().micros();
+        let mut buckets = XpBuckets::new(
+            now,
+            player.utc_offset_minutes,
+            player.locale.first_day_of_week,
+        );
+        let mut end = self.player_score_count(&player.owner).await;
+        while end > 0 {
+            let start = end.saturating_sub(MAX_INDEXER_PAGE as u64);
+            let Ok(scores) = self.player_score_range(&player.owner, start, end).await else {
+                break;
+            };
+            for (_, score) in scores.into_iter().rev() {
+                if score.timestamp < buckets.earliest_start() {
+                    return buckets;
+                }
+                buckets.record(score.timestamp, score.xp_earned);
+            }
+            end = start;
+        }
+        buckets
+    }
+
+    /// The guild `owner` belongs to.
+    async fn guild_of(&self, owner: AccountOwner) -> Option<Guild> {
+        let guild_id = self.state.player_guilds.get(&owner).await.ok()??;
+        self.state.guilds.get(&guild_id).await.ok().flatten()
+    }
+
+    /// Load the set of shadow-banned players, which are hidden from public views.
+    async fn shadow_banned(&self) -> HashSet<AccountOwner> {
+        self.state
+            .shadow_banned
+            .indices()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .collect()
+    }
+
+    /// Players hidden from leaderboards: banned, shadow-banned or opted out.
+    async fn hidden_from_leaderboards(&self) -> HashSet<AccountOwner> {
+        let mut hidden = self.shadow_banned().await;
+        for index in [&self.state.banned_players, &self.state.leaderboard_opt_outs] {
+            hidden.extend(index.indices().await.unwrap_or_default());
+        }
+        hidden
+    }
+
+    /// The last `days` days of daily challenges for `owner`, in their
+    /// timezone.
+    async fn challenge_history_of(
+        &self,
+        owner: AccountOwner,
+        days: Option<i32>,
+    ) -> Vec<ChallengeDay> {
+        let today = self.today_of(Some(owner)).await;
+        let completions = self
+            .state
+            .challenge_completions
+            .get(&owner)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        let days = days.unwrap_or(30).max(1) as u64;
+        challenges::history(&completions, today, days, *self.state.hub_chain_id.get())
+    }
+
+    /// `owner`'s progress on this month's challenge in their timezone, which
+    /// starts from nothing until their first run of the month.
+    async fn monthly_progress_of(&self, owner: AccountOwner) -> MonthlyProgress {
+        let month = streaks::month_index(self.today_of(Some(owner)).await);
+        self.state
+            .monthly_progress
+            .get(&owner)
+            .await
+            .ok()
+            .flatten()
+            .filter(|progress| progress.month == month)
+            .unwrap_or_else(|| MonthlyProgress::new(owner, month))
+    }
+
+    /// The crossover events running now, highest precedence first.
+    fn current_crossover_events(&self) -> Vec<CrossoverEvent> {
+        let now = self.runtime.system_time().micros();
+        self.state
+            .crossover_schedule
+            .get()
+            .active(now)
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Today's day index in `owner`'s timezone, or in UTC if not given or
+    /// not registered.
+    async fn today_of(&self, owner: Option<AccountOwner>) -> u64 {
+        let now = self.runtime.system_time().micros();
+        let player = match owner {
+            Some(owner) => self.state.players.get(&owner).await.ok().flatten(),
+            None => None,
+        };
+        player.map_or_else(|| day_index(now), |player| player.local_day(now))
+    }
+
+    /// `owner`'s open game session, unless it has timed out.
+    async fn open_session_of(&self, owner: AccountOwner) -> Option<GameSession> {
+        let now = self.runtime.system_time().micros();
+        self.state
+            .game_sessions
+            .get(&owner)
+            .await
+            .ok()
+            .flatten()
+            .filter(|session| !session.has_expired(now))
+    }
+
+    /// The cosmetics `owner` owns, oldest first.
+    async fn cosmetics_of(&self, owner: AccountOwner) -> Vec<OwnedCosmetic> {
+        self.state
+            .cosmetics
+            .get(&owner)
+            .await
+            .ok()
+            .flatten()
+            .map(|inventory| inventory.items().to_vec())
+            .unwrap_or_default()
+    }
+
+    /// The event achievements `owner` unlocked, oldest first.
+    async fn event_achievements_of(&self, owner: AccountOwner) -> Vec<EventAchievementUnlock> {
+        self.state
+            .event_achievements
+            .get(&owner)
+            .await
+            .ok()
+            .flatten()
+            .map(|progress| progress.unlocked().to_vec())
+            .unwrap_or_default()
+    }
+
+    /// `player`'s progress on every achievement.
+    async fn achievements_of(&self, player: &Player) -> Vec<AchievementStatus> {
+        self.state
+            .achievements
+            .get(&player.owner)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_default()
+            .statuses(player)
+    }
+
+    /// `owner`'s goals with their current status, oldest first.
+    async fn goals_of(&self, owner: AccountOwner) -> Vec<GoalProgress> {
+        let now = self.runtime.system_time().micros();
+        self.state
+            .goals
+            .get(&owner)
+            .await
+            .ok()
+            .flatten()
+            .map(|goals| goals.progress(now))
+            .unwrap_or_default()
+    }
+
+    /// The players who added `owner` as a friend and were added back.
+    async fn friends_of(&self, owner: AccountOwner) -> Vec<AccountOwner> {
+        self.state
+            .friends
+            .get(&owner)
+            .await
+            .ok()
+            .flatten()
+            .map(|friends| friends.friends())
+            .unwrap_or_default()
+    }
+
+    /// Every guild, ranked by XP.
+    async fn guild_standings(&self) -> Vec<GuildStanding> {
+        let mut guilds = Vec::new();
+        self.state
+            .guilds
+            .for_each_index_value(|_, guild| {
+                guilds.push(guild.into_owned());
+                Ok(())
+            })
+            .await
+            .ok();
+        let mut ranked = Vec::with_capacity(guilds.len());
+        for guild in guilds {
+            let xp = self
+                .state
+                .guild_xp
+                .get(&guild.id)
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or(0);
+            ranked.push((guild, xp));
+        }
+        guilds::rank_guilds(ranked)
+    }
+
+    /// How many players follow `owner` and how many they follow.
+    async fn follow_counts_of(&self, owner: AccountOwner) -> FollowCounts {
+        let followers = self
+            .state
+            .follower_counts
+            .get(&owner)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or(0);
+        let following = self
+            .state
+            .following
+            .get(&owner)
+            .await
+            .ok()
+            .flatten()
+            .map_or(0, |follows| follows.len() as u64);
+        FollowCounts {
+            owner,
+            followers,
+            following,
+        }
+    }
+
+    /// The reactions left on `target`, most used first.
+    async fn reaction_counts(&self, target: ReactionTarget) -> Vec<EmoteCount> {
+        self.state
+            .reactions
+            .get(&target)
+            .await
+            .ok()
+            .flatten()
+            .map(|tally| tally.counts())
+            .unwrap_or_default()
+    }
+
+    /// Players whose scores are hidden from score feeds: shadow-banned or
+    /// opted out.
+    async fn hidden_score_history(&self) -> HashSet<AccountOwner> {
+        let mut hidden = self.shadow_banned().await;
+        hidden.extend(
+            self.state
+                .history_opt_outs
+                .indices()
+                .await
+                .unwrap_or_default(),
+        );
+        hidden
+    }
+
+    /// Log positions of scores removed by moderation.
+    async fn removed_scores(&self) -> HashSet<u64> {
+        self.state
+            .removed_scores
+            .indices()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .collect()
+    }
+}
+
+/// A single player's view of the arcade, resolved lazily field by field.
+struct Me {
+    owner: AccountOwner,
+    root: QueryRoot,
+}
+
+#[Object]
+impl Me {
+    /// The player's wallet address.
+    async fn wallet(&self) -> AccountOwner {
+        self.owner
+    }
+
+    /// The player's profile, if registered.
+    async fn player(&self) -> Option<Player> {
+        self.root
+            .state
+            .players
+            .get(&self.owner)
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// The player's leaderboard rank, even if hidden from public listings.
+    async fn rank(&self) -> Option<i32> {
+        self.root.rank_of(self.owner).await
+    }
+
+    /// The player's progress on this month's challenge.
+    async fn monthly_progress(&self) -> MonthlyProgress {
+        self.root.monthly_progress_of(self.owner).await
+    }
+
+    /// The player's daily challenge calendar for the last `days` days.
+    async fn challenge_history(&self, days: Option<i32>) -> Vec<ChallengeDay> {
+        self.root.challenge_history_of(self.owner, days).await
+    }
+
+    /// The player's play tally for their most recent day of play.
+    async fn daily_play(&self) -> Option<DailyPlay> {
+        self.root
+            .state
+            .daily_play
+            .get(&self.owner)
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// The games the player completed on their most recent day of play.
+    async fn daily_circuit(&self) -> Option<DailyCircuit> {
+        self.root
+            .state
+            .daily_circuits
+            .get(&self.owner)
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// The player's most recent scores. `me` doesn't authenticate the
+    /// wallet it is given, so a hidden score history stays hidden here too.
+    async fn recent_scores(&self, limit: Option<i32>) -> Vec<GameScore> {
+        let limit = limit.unwrap_or(20) as usize;
+        if self.root.hidden_score_history().await.contains(&self.owner) {
+            return Vec::new();
+        }
+        self.root
+            .player_scores_before(self.owner, u64::MAX, limit, |_| true)
+            .await
+            .map(|(scores, _)| scores)
+            .unwrap_or_default()
+    }
+
+    /// The username the player currently holds a reservation for.
+    async fn reservation(&self) -> Option<UsernameReservation> {
+        let now = self.root.runtime.system_time().micros();
+        let state = &self.root.state;
+        let key = state.owner_reservations.get(&self.owner).await.ok()??;
+        state
+            .reservations
+            .get(&key)
+            .await
+            .ok()
+            .flatten()
+            .filter(|reservation| reservation.is_active(now))
+    }
+
+    /// Appeals the player has filed (oldest first).
+    async fn appeals(&self) -> Vec<Appeal> {
+        let mut appeals = Vec::new();
+        self.root
+            .state
+            .appeals
+            .for_each_index_value(|_, appeal| {
+                if appeal.appellant == self.owner {
+                    appeals.push(appeal.into_owned());
+                }
+                Ok(())
+            })
+            .await
+            .ok();
+        appeals
+    }
+
+    /// The players this player has blocked.
+    async fn blocked_players(&self) -> Vec<AccountOwner> {
+        self.root.blocked_by(self.owner).await
+    }
+
+    /// The player's progress on every achievement.
+    async fn achievements(&self) -> Vec<AchievementStatus> {
+        match self
+            .root
+            .state
+            .players
+            .get(&self.owner)
+            .await
+            .ok()
+            .flatten()
+        {
+            Some(player) => self.root.achievements_of(&player).await,
+            None => Vec::new(),
+        }
+    }
+
+    /// The player's open game session, unless it has timed out.
+    async fn open_session(&self) -> Option<GameSession> {
+        self.root.open_session_of(self.owner).await
+    }
+
+    /// The event achievements the player unlocked, oldest first.
+    async fn event_achievements(&self) -> Vec<EventAchievementUnlock> {
+        self.root.event_achievements_of(self.owner).await
+    }
+
+    /// The player's daily streak and shields.
+    async fn streak(&self) -> Option<DailyStreak> {
+        self.root
+            .state
+            .streaks
+            .get(&self.owner)
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// The player's personal goals, oldest first.
+    async fn goals(&self) -> Vec<GoalProgress> {
+        self.root.goals_of(self.owner).await
+    }
+
+    /// The players this player follows.
+    async fn following(&self) -> Vec<AccountOwner> {
+        self.root
+            .state
+            .following
+            .get(&self.owner)
+            .await
+            .ok()
+            .flatten()
+            .map(|follows| follows.owners())
+            .unwrap_or_default()
+    }
+
+    /// The player's friends.
+    async fn friends(&self) -> Vec<AccountOwner> {
+        self.root.friends_of(self.owner).await
+    }
+
+    /// The player's XP in the current season.
+    async fn seasonal_xp(&self) -> u64 {
+        let Some(season) = *self.root.state.current_season.get() else {
+            return 0;
+        };
+        self.root
+            .state
+            .seasonal_xp
+            .get(&self.owner)
+            .await
+            .ok()
+            .flatten()
+            .map_or(0, |seasonal| seasonal.in_season(season.number))
+    }
+
+    /// The cosmetics the player owns, oldest first.
+    async fn cosmetics(&self) -> Vec<OwnedCosmetic> {
+        self.root.cosmetics_of(self.owner).await
+    }
+
+    /// How many players follow this player and how many they follow.
+    async fn follow_counts(&self) -> FollowCounts {
+        self.root.follow_counts_of(self.owner).await
+    }
+
+    /// The emotes the player has left on a target.
+    async fn reactions(&self, target: ReactionTarget) -> Vec<Emote> {
+        self.root
+            .state
+            .player_reactions
+            .get(&(self.owner, target))
+            .await
+            .ok()
+            .flatten()
+            .map(|reactions| reactions.emotes())
+            .unwrap_or_default()
+    }
+
+    /// The player's kudos counters.
+    async fn kudos(&self) -> Option<KudosRecord> {
+        self.root.state.kudos.get(&self.owner).await.ok().flatten()
+    }
+
+    /// Kudos the player can still send today.
+    async fn kudos_remaining(&self) -> u32 {
+        let now = self.root.runtime.system_time().micros();
+        let today = match self.root.state.players.get(&self.owner).await {
+            Ok(Some(player)) => player.local_day(now),
+            _ => day_index(now),
+        };
+        self.root
+            .state
+            .kudos_allowances
+            .get(&self.owner)
+            .await
+            .ok()
+            .flatten()
+            .map_or(social::DAILY_KUDOS as u32, |allowance| {
+                allowance.remaining(today)
+            })
+    }
+
+    /// The player's active mentorship as an apprentice.
+    async fn mentorship(&self) -> Option<Mentorship> {
+        self.root
+            .state
+            .mentorships
+            .get(&self.owner)
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// Mentors who have offered to take the player on.
+    async fn mentorship_offers(&self) -> Vec<AccountOwner> {
+        self.root
+            .state
+            .mentorship_offers
+            .get(&self.owner)
+            .await
+            .ok()
+            .flatten()
+            .map(|offers| offers.mentors())
+            .unwrap_or_default()
+    }
+
+    /// The guild the player belongs to.
+    async fn guild(&self) -> Option<Guild> {
+        self.root.guild_of(self.owner).await
+    }
+
+    /// The player's apprentices and record as a mentor.
+    async fn mentor_record(&self) -> Option<MentorRecord> {
+        self.root
+            .state
+            .mentors
+            .get(&self.owner)
+            .await
+            .ok()
+            .flatten()
+    }
+}
+
+/// The page size of a connection query asked for `first` items.
+fn page_size(first: Option<i32>) -> usize {
+    first
+        .unwrap_or(DEFAULT_CONNECTION_PAGE)
+        .clamp(1, MAX_INDEXER_PAGE) as usize
+}
+
+/// Cut the page of `first` items following the offset cursor `after` out of
+/// `items`.
+fn offset_page<T>(
+    items: Vec<T>,
+    first: Option<i32>,
+    after: Option<String>,
+) -> async_graphql::Result<(Vec<T>, PageInfo)> {
+    let offset: usize = match after {
+        Some(cursor) => cursor.parse().map_err(|_| "Invalid cursor")?,
+        None => 0,
+    };
+    let end = offset.saturating_add(page_size(first));
+    let has_next_page = items.len() > end;
+    let nodes = items.into_iter().skip(offset).take(end - offset).collect();
+    Ok((nodes, PageInfo::new(Some(end.to_string()), has_next_page)))
+}
+
+/// The size of every entry of `map`, named `view`.
+async fn map_usage<I, V>(view: &str, map: &MapView<I, V>) -> ViewUsage
+where
+    I: Send + Serialize + DeserializeOwned,
+    V: Clone + Sync + Send + Serialize + DeserializeOwned + 'static,
+{
+    let mut entries = 0;
+    let mut approximate_bytes = 0;
+    map.for_each_index_value(|index, value| {
+        entries += 1;
+        let size = bcs::serialized_size(&index)? + bcs::serialized_size(value.as_ref())?;
+        approximate_bytes += size as u64;
+        Ok(())
+    })
+    .await
+    .ok();
+    ViewUsage {
+        view: view.to_string(),
+        entries,
+        approximate_bytes,
+    }
+}
+
+/// The size of the per-player score logs: one position per score, plus
+/// each player's key.
+async fn player_scores_usage(logs: &CollectionView<AccountOwner, LogView<u64>>) -> ViewUsage {
+    let mut entries = 0;
+    let mut approximate_bytes = 0;
+    let logs = logs.try_load_all_entries().await.unwrap_or_default();
+    for (owner, log) in &logs {
+        let count = log.count() as u64;
+        entries += count;
+        approximate_bytes += bcs::serialized_size(owner).unwrap_or(0) as u64 + count * 8;
+    }
+    ViewUsage {
+        view: "player_scores".to_string(),
+        entries,
+        approximate_bytes,
+    }
+}
+
+/// The size of `log`, named `view`, extrapolated from its latest entries.
+async fn log_usage<T>(view: &str, log: &LogView<T>) -> ViewUsage
+where
+    T: Clone + Send + Sync + Serialize + DeserializeOwned,
+{
+    let count = log.count();
+    let sample = log
+        .read(count.saturating_sub(LOG_SAMPLE_SIZE)..count)
+        .await
+        .unwrap_or_default();
+    let sample_bytes = sample
+        .iter()
+        .filter_map(|entry| bcs::serialized_size(entry).ok())
+        .sum::<usize>();
+    ViewUsage::extrapolated(view, count as u64, sample.len() as u64, sample_bytes as u64)
 }
 
 /// Parse a wallet address string to AccountOwner.
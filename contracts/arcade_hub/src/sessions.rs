@@ -0,0 +1,102 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Game sessions: a player starts a game before playing it and submits its
+//! score by closing the session.
+//!
+//! Each player has at most one open session; starting another sets it
+//! aside, where only a batch of runs can close it. A session can only be
+//! closed once, and only before it times out, which
+//! stops the same run being submitted twice and gives the contract a start
+//! time to check runs against. A session started on a kiosk device can only
+//! be closed from that device.
+
+use async_graphql::SimpleObject;
+use linera_sdk::linera_base_types::{AccountOwner, BcsSignable, ChainId, CryptoHash};
+use serde::{Deserialize, Serialize};
+
+use crate::{ArcadeError, GameType, ScoreSubmission, MAX_SCORE_BATCH_SIZE};
+
+/// How long a session stays open, in microseconds (30 minutes).
+pub const SESSION_TIMEOUT_MICROS: u64 = 30 * 60 * 1_000_000;
+
+/// Most sessions a player can have set aside; setting aside another drops
+/// the oldest.
+pub const MAX_SET_ASIDE_SESSIONS: usize = MAX_SCORE_BATCH_SIZE;
+
+/// A game a player has started but not yet submitted.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct GameSession {
+    pub id: u64,
+    pub player: AccountOwner,
+    pub game_type: GameType,
+    pub started_at: u64,
+    /// Seeds the game's layout, so the run can be replayed.
+    pub seed: u64,
+    /// The kiosk device the game was started on, if any.
+    pub device_id: Option<String>,
+}
+
+impl GameSession {
+    /// Start session `id` on `chain_id` at `now`, deriving its seed from all
+    /// three.
+    pub fn start(
+        id: u64,
+        player: AccountOwner,
+        game_type: GameType,
+        device_id: Option<String>,
+        chain_id: ChainId,
+        now: u64,
+    ) -> Self {
+        let hash = CryptoHash::new(&SessionSeedPreimage {
+            chain_id,
+            session_id: id,
+            started_at: now,
+        });
+        Self {
+            id,
+            player,
+            game_type,
+            started_at: now,
+            seed: <[u64; 4]>::from(hash)[0],
+            device_id,
+        }
+    }
+
+    /// Whether the session has timed out at `now`.
+    pub fn has_expired(&self, now: u64) -> bool {
+        now >= self.started_at.saturating_add(SESSION_TIMEOUT_MICROS)
+    }
+
+    /// Check `submission` at `now` can close the session `session_id`.
+    pub fn check_close(
+        &self,
+        session_id: u64,
+        submission: &ScoreSubmission,
+        now: u64,
+    ) -> Result<(), ArcadeError> {
+        if self.id != session_id {
+            return Err(ArcadeError::SessionNotFound);
+        }
+        if self.game_type != submission.game_type {
+            return Err(ArcadeError::SessionGameMismatch);
+        }
+        if self.device_id != submission.device_id {
+            return Err(ArcadeError::SessionDeviceMismatch);
+        }
+        if self.has_expired(now) {
+            return Err(ArcadeError::SessionExpired);
+        }
+        Ok(())
+    }
+}
+
+/// The value a session's seed is hashed from.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct SessionSeedPreimage {
+    chain_id: ChainId,
+    session_id: u64,
+    started_at: u64,
+}
+
+impl BcsSignable<'_> for SessionSeedPreimage {}
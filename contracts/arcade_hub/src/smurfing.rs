@@ -0,0 +1,129 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Smurf detection: experienced players farming rookie rewards on fresh
+//! accounts.
+//!
+//! The hub keeps a score histogram per game built only from new accounts'
+//! first games. An early run far better than that histogram's median is
+//! suspicious, and an account with enough suspicious early runs is flagged
+//! into the moderation queue. Flags only ask for a moderator's attention;
+//! nothing happens to the account until one acts.
+
+use async_graphql::{Enum, InputObject, SimpleObject};
+use linera_sdk::linera_base_types::AccountOwner;
+use serde::{Deserialize, Serialize};
+
+use crate::ArcadeError;
+
+/// Smurf detection thresholds, managed by the admin.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject, InputObject)]
+#[graphql(input_name = "SmurfDetectionConfigInput")]
+pub struct SmurfDetectionConfig {
+    /// How many of an account's first games are screened and counted
+    /// towards the new-player distributions (0 = detection off).
+    pub early_games: u32,
+    /// How far above the new-player median, in percent of it, a run must
+    /// be to count as suspicious (300 = three times the median). Where
+    /// lower scores are better, the run must be this much below instead.
+    pub median_multiple_percent: u32,
+    /// Suspicious early runs that get an account flagged.
+    pub suspicious_runs: u32,
+    /// Early runs a game's distribution needs before its median is
+    /// trusted.
+    pub min_samples: u64,
+}
+
+impl Default for SmurfDetectionConfig {
+    fn default() -> Self {
+        Self {
+            early_games: 10,
+            median_multiple_percent: 300,
+            suspicious_runs: 3,
+            min_samples: 50,
+        }
+    }
+}
+
+impl SmurfDetectionConfig {
+    /// Check the thresholds can flag anyone at all without flagging
+    /// everyone.
+    pub fn validate(&self) -> Result<(), ArcadeError> {
+        let multiple_ok = self.median_multiple_percent > 100;
+        let runs_ok =
+            self.early_games == 0 || (1..=self.early_games).contains(&self.suspicious_runs);
+        if !multiple_ok || !runs_ok {
+            return Err(ArcadeError::InvalidSmurfDetectionConfig);
+        }
+        Ok(())
+    }
+
+    /// Whether `score` is far enough from the new-player `median` to be
+    /// suspicious.
+    pub fn is_suspicious(&self, score: u64, median: u64, lower_is_better: bool) -> bool {
+        let multiple = u128::from(self.median_multiple_percent);
+        if lower_is_better {
+            u128::from(score) * multiple < u128::from(median) * 100
+        } else {
+            u128::from(score) * 100 > u128::from(median.max(1)) * multiple
+        }
+    }
+}
+
+/// An account's screened early runs.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct EarlyRuns {
+    games: u32,
+    suspicious: u32,
+    flagged: bool,
+}
+
+impl EarlyRuns {
+    /// Whether the account's next run is still one of its early games.
+    pub fn is_early(&self, config: &SmurfDetectionConfig) -> bool {
+        self.games < config.early_games
+    }
+
+    /// Count an early run, returning whether the account should be flagged
+    /// now.
+    pub fn record(&mut self, suspicious: bool, config: &SmurfDetectionConfig) -> bool {
+        self.games += 1;
+        if suspicious {
+            self.suspicious += 1;
+        }
+        if self.flagged || self.suspicious < config.suspicious_runs {
+            return false;
+        }
+        self.flagged = true;
+        true
+    }
+
+    /// How many early runs were suspicious.
+    pub fn suspicious(&self) -> u32 {
+        self.suspicious
+    }
+
+    /// How many early runs were screened.
+    pub fn games(&self) -> u32 {
+        self.games
+    }
+}
+
+/// Why an account was flagged for moderation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Enum)]
+pub enum FlagReason {
+    /// Early runs far better than new players usually manage.
+    SuspectedSmurf,
+}
+
+/// An account awaiting a moderator's review.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct ModerationFlag {
+    pub id: u64,
+    pub player: AccountOwner,
+    pub reason: FlagReason,
+    /// Suspicious runs among the early games screened when flagged.
+    pub suspicious_runs: u32,
+    pub games_screened: u32,
+    pub flagged_at: u64,
+}
@@ -0,0 +1,156 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Stored leaderboard snapshots and the rank movement between them.
+
+use std::collections::BTreeMap;
+
+use async_graphql::SimpleObject;
+use linera_sdk::linera_base_types::AccountOwner;
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of entries kept in a snapshot.
+pub const MAX_SNAPSHOT_ENTRIES: usize = 1000;
+
+/// How often the hub refreshes its automatic snapshot, in microseconds
+/// (15 minutes).
+pub const SNAPSHOT_REFRESH_INTERVAL_MICROS: u64 = 15 * 60 * 1_000_000;
+
+/// Number of automatic snapshots retained; older ones are pruned. A page
+/// token stays valid until its snapshot is pruned.
+pub const MAX_AUTOMATIC_SNAPSHOTS: usize = 4;
+
+/// Archived snapshots examined per operation while trimming leaderboard
+/// history down to the retention limit.
+pub const HISTORY_PRUNE_CHUNK: u64 = 8;
+
+/// One ranked player in a snapshot.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct SnapshotEntry {
+    pub wallet_address: AccountOwner,
+    pub rank: u32,
+    pub total_xp: u64,
+}
+
+/// The public leaderboard as it stood at one point in time.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct LeaderboardSnapshot {
+    pub id: u64,
+    pub taken_at: u64,
+    /// Whether the hub took this snapshot on its own for pagination, rather
+    /// than on admin request.
+    pub automatic: bool,
+    /// Entries in rank order, truncated to [`MAX_SNAPSHOT_ENTRIES`].
+    pub entries: Vec<SnapshotEntry>,
+    /// Whether players ranked below the last entry were left out.
+    pub truncated: bool,
+}
+
+impl LeaderboardSnapshot {
+    /// Drop every entry ranked below `top_k`, returning whether any were
+    /// dropped.
+    pub fn retain_top(&mut self, top_k: u32) -> bool {
+        let before = self.entries.len();
+        self.entries.truncate(top_k as usize);
+        let dropped = self.entries.len() < before;
+        self.truncated |= dropped;
+        dropped
+    }
+}
+
+/// A position within a snapshot, handed to clients as an opaque
+/// `"<snapshot_id>:<offset>"` token.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PageToken {
+    pub snapshot_id: u64,
+    pub offset: usize,
+}
+
+impl PageToken {
+    /// Encode the token for a client.
+    pub fn encode(&self) -> String {
+        format!("{}:{}", self.snapshot_id, self.offset)
+    }
+
+    /// Decode a token received from a client.
+    pub fn parse(token: &str) -> Option<Self> {
+        let (snapshot_id, offset) = token.split_once(':')?;
+        Some(Self {
+            snapshot_id: snapshot_id.parse().ok()?,
+            offset: offset.parse().ok()?,
+        })
+    }
+}
+
+/// How one player's rank changed between two snapshots.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct RankMove {
+    pub wallet_address: AccountOwner,
+    /// Rank in the earlier snapshot, if the player was listed.
+    pub previous_rank: Option<u32>,
+    pub current_rank: u32,
+    /// Places gained (positive) or lost (negative); 0 for new entries.
+    pub change: i64,
+    pub xp_gained: u64,
+}
+
+/// Rank movement between two snapshots.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, SimpleObject)]
+pub struct LeaderboardDiff {
+    pub from_snapshot: u64,
+    pub to_snapshot: u64,
+    /// Players who climbed, biggest gain first.
+    pub risers: Vec<RankMove>,
+    /// Players who dropped, biggest loss first.
+    pub fallers: Vec<RankMove>,
+    /// Players listed only in the later snapshot, best rank first.
+    pub new_entries: Vec<RankMove>,
+}
+
+impl LeaderboardDiff {
+    /// Compare two snapshots.
+    pub fn between(from: &LeaderboardSnapshot, to: &LeaderboardSnapshot) -> Self {
+        let previous = from
+            .entries
+            .iter()
+            .map(|entry| (entry.wallet_address, entry))
+            .collect::<BTreeMap<_, _>>();
+
+        let mut diff = LeaderboardDiff {
+            from_snapshot: from.id,
+            to_snapshot: to.id,
+            ..LeaderboardDiff::default()
+        };
+        for entry in &to.entries {
+            match previous.get(&entry.wallet_address) {
+                Some(before) => {
+                    let change = i64::from(before.rank) - i64::from(entry.rank);
+                    let movement = RankMove {
+                        wallet_address: entry.wallet_address,
+                        previous_rank: Some(before.rank),
+                        current_rank: entry.rank,
+                        change,
+                        xp_gained: entry.total_xp.saturating_sub(before.total_xp),
+                    };
+                    if change > 0 {
+                        diff.risers.push(movement);
+                    } else if change < 0 {
+                        diff.fallers.push(movement);
+                    }
+                }
+                None => diff.new_entries.push(RankMove {
+                    wallet_address: entry.wallet_address,
+                    previous_rank: None,
+                    current_rank: entry.rank,
+                    change: 0,
+                    xp_gained: entry.total_xp,
+                }),
+            }
+        }
+
+        diff.risers
+            .sort_by_key(|movement| std::cmp::Reverse(movement.change));
+        diff.fallers.sort_by_key(|movement| movement.change);
+        diff
+    }
+}
@@ -0,0 +1,285 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Player-to-player interactions and the rules that gate them.
+
+use std::collections::BTreeSet;
+
+use async_graphql::SimpleObject;
+use linera_sdk::linera_base_types::AccountOwner;
+use serde::{Deserialize, Serialize};
+
+use crate::{ArcadeError, GameType};
+
+/// Maximum number of owners a player can block.
+pub const MAX_BLOCKED_PLAYERS: usize = 500;
+
+/// Maximum number of players one player can follow.
+pub const MAX_FOLLOWING: usize = 500;
+
+/// Maximum number of players one player can add as friends.
+pub const MAX_FRIENDS: usize = 200;
+
+/// Maximum number of players who can have added one player as a friend.
+pub const MAX_FRIEND_REQUESTS: usize = 1000;
+
+/// Kudos a player can send per day in their timezone.
+pub const DAILY_KUDOS: usize = 5;
+
+/// How long after their last run a player still counts as active, in
+/// microseconds (10 minutes).
+pub const PRESENCE_ACTIVE_MICROS: u64 = 10 * 60 * 1_000_000;
+
+/// The owners a player refuses follows, friend requests, kudos, mentorship
+/// offers and guild invites from.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Blocklist {
+    blocked: BTreeSet<AccountOwner>,
+}
+
+impl Blocklist {
+    /// Block `target`. Blocking an already blocked owner is a no-op.
+    pub fn block(&mut self, target: AccountOwner) -> Result<(), ArcadeError> {
+        if !self.blocked.contains(&target) && self.blocked.len() >= MAX_BLOCKED_PLAYERS {
+            return Err(ArcadeError::BlocklistFull);
+        }
+        self.blocked.insert(target);
+        Ok(())
+    }
+
+    /// Unblock `target`.
+    pub fn unblock(&mut self, target: &AccountOwner) {
+        self.blocked.remove(target);
+    }
+
+    /// Whether `sender` is blocked.
+    pub fn contains(&self, sender: &AccountOwner) -> bool {
+        self.blocked.contains(sender)
+    }
+
+    /// Whether no one is blocked.
+    pub fn is_empty(&self) -> bool {
+        self.blocked.is_empty()
+    }
+
+    /// The blocked owners, in a stable order.
+    pub fn owners(&self) -> Vec<AccountOwner> {
+        self.blocked.iter().copied().collect()
+    }
+}
+
+/// Reject an interaction from `sender` if `recipient_blocklist` blocks them.
+pub fn ensure_not_blocked(
+    recipient_blocklist: Option<&Blocklist>,
+    sender: &AccountOwner,
+) -> Result<(), ArcadeError> {
+    if recipient_blocklist.is_some_and(|blocklist| blocklist.contains(sender)) {
+        return Err(ArcadeError::BlockedByPlayer);
+    }
+    Ok(())
+}
+
+/// When a player was last seen and what they were playing.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Presence {
+    pub last_seen_at: u64,
+    pub last_game: GameType,
+}
+
+impl Presence {
+    /// Note a run of `game_type` finished at `timestamp`, unless a later
+    /// one was already seen.
+    pub fn record(presence: Option<Presence>, game_type: GameType, timestamp: u64) -> Presence {
+        match presence {
+            Some(presence) if presence.last_seen_at > timestamp => presence,
+            _ => Presence {
+                last_seen_at: timestamp,
+                last_game: game_type,
+            },
+        }
+    }
+}
+
+/// A player's presence as seen at query time.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct PlayerPresence {
+    pub wallet: AccountOwner,
+    pub last_seen_at: Option<u64>,
+    /// Whether the player finished a run in the last
+    /// [`PRESENCE_ACTIVE_MICROS`].
+    pub active: bool,
+    /// The game the player is on, while active.
+    pub current_game: Option<GameType>,
+}
+
+impl PlayerPresence {
+    pub fn new(wallet: AccountOwner, presence: Option<Presence>, now: u64) -> Self {
+        let active = presence.is_some_and(|presence| {
+            now.saturating_sub(presence.last_seen_at) < PRESENCE_ACTIVE_MICROS
+        });
+        Self {
+            wallet,
+            last_seen_at: presence.map(|presence| presence.last_seen_at),
+            active,
+            current_game: presence
+                .filter(|_| active)
+                .map(|presence| presence.last_game),
+        }
+    }
+}
+
+/// A player's kudos counters, shown on their profile.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct KudosRecord {
+    pub owner: AccountOwner,
+    pub received: u64,
+    pub given: u64,
+}
+
+impl KudosRecord {
+    pub fn new(owner: AccountOwner) -> Self {
+        Self {
+            owner,
+            received: 0,
+            given: 0,
+        }
+    }
+}
+
+/// The players a player has sent kudos to on their most recent day of
+/// sending.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, SimpleObject)]
+pub struct KudosAllowance {
+    pub day: u64,
+    pub recipients: Vec<AccountOwner>,
+}
+
+impl KudosAllowance {
+    /// Spend one of `today`'s kudos on `recipient`. Each recipient can get
+    /// at most one per day from the same sender.
+    pub fn send(&mut self, recipient: AccountOwner, today: u64) -> Result<(), ArcadeError> {
+        if self.day != today {
+            *self = KudosAllowance {
+                day: today,
+                recipients: Vec::new(),
+            };
+        }
+        if self.recipients.contains(&recipient) {
+            return Err(ArcadeError::KudosAlreadySent);
+        }
+        if self.recipients.len() >= DAILY_KUDOS {
+            return Err(ArcadeError::DailyKudosLimitReached);
+        }
+        self.recipients.push(recipient);
+        Ok(())
+    }
+
+    /// Kudos left to send on `today`.
+    pub fn remaining(&self, today: u64) -> u32 {
+        if self.day != today {
+            return DAILY_KUDOS as u32;
+        }
+        DAILY_KUDOS.saturating_sub(self.recipients.len()) as u32
+    }
+}
+
+/// The players one player follows. Unlike friendships, follows are one-way.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct FollowList {
+    following: BTreeSet<AccountOwner>,
+}
+
+impl FollowList {
+    /// Follow `target`, returning whether they weren't followed yet.
+    pub fn follow(&mut self, target: AccountOwner) -> Result<bool, ArcadeError> {
+        if !self.following.contains(&target) && self.following.len() >= MAX_FOLLOWING {
+            return Err(ArcadeError::FollowingListFull);
+        }
+        Ok(self.following.insert(target))
+    }
+
+    /// Stop following `target`, returning whether they were followed.
+    pub fn unfollow(&mut self, target: &AccountOwner) -> bool {
+        self.following.remove(target)
+    }
+
+    /// Whether no one is followed.
+    pub fn is_empty(&self) -> bool {
+        self.following.is_empty()
+    }
+
+    /// How many players are followed.
+    pub fn len(&self) -> usize {
+        self.following.len()
+    }
+
+    /// The followed players, in a stable order.
+    pub fn owners(&self) -> Vec<AccountOwner> {
+        self.following.iter().copied().collect()
+    }
+}
+
+/// The players one player added as friends and those who added them.
+/// Two players are friends once each has added the other; until then, the
+/// addition is a friend request.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct FriendList {
+    added: BTreeSet<AccountOwner>,
+    added_by: BTreeSet<AccountOwner>,
+}
+
+impl FriendList {
+    /// Add `target`, returning whether they weren't added yet.
+    pub fn add(&mut self, target: AccountOwner) -> Result<bool, ArcadeError> {
+        if !self.added.contains(&target) && self.added.len() >= MAX_FRIENDS {
+            return Err(ArcadeError::FriendListFull);
+        }
+        Ok(self.added.insert(target))
+    }
+
+    /// Remove `target`, returning whether they were added.
+    pub fn remove(&mut self, target: &AccountOwner) -> bool {
+        self.added.remove(target)
+    }
+
+    /// Check `sender` can add this player.
+    pub fn check_request(&self, sender: &AccountOwner) -> Result<(), ArcadeError> {
+        if !self.added_by.contains(sender) && self.added_by.len() >= MAX_FRIEND_REQUESTS {
+            return Err(ArcadeError::TooManyFriendRequests);
+        }
+        Ok(())
+    }
+
+    /// Note that `sender` added or removed this player.
+    pub fn set_added_by(&mut self, sender: AccountOwner, added: bool) {
+        if added {
+            self.added_by.insert(sender);
+        } else {
+            self.added_by.remove(&sender);
+        }
+    }
+
+    /// Whether no one was added and no one added this player.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.added_by.is_empty()
+    }
+
+    /// The player's friends, in a stable order.
+    pub fn friends(&self) -> Vec<AccountOwner> {
+        self.added.intersection(&self.added_by).copied().collect()
+    }
+
+    /// The players who added this player without being added back, in a
+    /// stable order.
+    pub fn requests(&self) -> Vec<AccountOwner> {
+        self.added_by.difference(&self.added).copied().collect()
+    }
+}
+
+/// Follow counters shown on a player's profile.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct FollowCounts {
+    pub owner: AccountOwner,
+    pub followers: u64,
+    pub following: u64,
+}
@@ -3,10 +3,58 @@
 
 //! State management for the Arcade Hub application.
 
-use arcade_hub::{GameScore, LeaderboardEntry, Player};
+use std::collections::{BTreeMap, BTreeSet};
+
+use arcade_hub::{
+    achievements::AchievementProgress,
+    analytics::{PlayHeatmap, ScoreHistogram},
+    balance::{BalanceConfig, PlayShare},
+    bosses::{BossBattle, BossRewards},
+    bridge::{BridgeInbox, BridgeSubscription},
+    challenges::{ChallengeBoard, ChallengeCompletions, MonthlyBoard, MonthlyProgress},
+    circuit::DailyCircuit,
+    community_games::{CommunityGame, CommunityLeaderboard},
+    cosmetics::CosmeticInventory,
+    crossovers::{CrossoverSchedule, EventAchievementProgress},
+    dispute::{Appeal, BondAccounts},
+    goals::PlayerGoals,
+    guild_prizes::{GuildPayoutRule, GuildPrize, PrizeClaim},
+    guilds::{Guild, GuildAuditTrail, GuildContribution},
+    humanity::{HumanityChallenge, HumanityCheckConfig},
+    invariants::{InvariantCheck, InvariantSection},
+    invites::InviteCode,
+    journal::{JournalCheckpoint, JournalEntry, ReplayProgress, ReplayTarget},
+    kiosk::{self, DeviceActivity, KioskConfig},
+    limits::DailyPlay,
+    mentorship::{MentorRecord, Mentorship, MentorshipOffers},
+    migration::{ProgressExport, ProgressImport},
+    milestones::Milestone,
+    name_auctions::UsernameAuction,
+    plausibility::PlausibilityConfig,
+    polls::GuildPoll,
+    raffles::Raffle,
+    rankings::{ChampionScore, PersonalBests, RecentForm, RookieCriteria},
+    reactions::{PlayerReactions, ReactionTally, ReactionTarget},
+    score_signing::ScoreSignature,
+    scoring::{ScoringCanary, ScoringConfig, XpFactors},
+    seasons::{Season, SeasonArchive, SeasonTheme, SeasonalXp},
+    sessions::GameSession,
+    smurfing::{EarlyRuns, ModerationFlag, SmurfDetectionConfig},
+    snapshots::LeaderboardSnapshot,
+    social::{Blocklist, FollowList, FriendList, KudosAllowance, KudosRecord, Presence},
+    streaks::DailyStreak,
+    submission_windows::SubmissionWindows,
+    usage::{OperationUsage, UsageQuota},
+    usernames::{UsernameClaim, UsernameRecyclingConfig, UsernameReservation},
+    ArcadeError, ArcadeResponse, GameScore, GameType, LeaderboardEntry, MaintenanceWindow, Player,
+    ScoreSubmission, ScoringCategory,
+};
 use linera_sdk::{
-    linera_base_types::{AccountOwner, ChainId},
-    views::{linera_views, MapView, RegisterView, RootView, ViewStorageContext},
+    linera_base_types::{AccountOwner, Amount, ApplicationId, ChainId, CryptoHash},
+    views::{
+        linera_views, CollectionView, LogView, MapView, QueueView, RegisterView, RootView, SetView,
+        ViewStorageContext,
+    },
 };
 
 /// The application state stored on each chain.
@@ -15,16 +63,574 @@ use linera_sdk::{
 pub struct ArcadeHubState {
     /// The chain ID of the hub chain (for routing messages).
     pub hub_chain_id: RegisterView<Option<ChainId>>,
+    /// Whether syncs are also sent straight to the hub as messages.
+    pub direct_syncs: RegisterView<bool>,
+    /// Timestamp of the last block that touched this application's state.
+    pub last_updated_at: RegisterView<Option<u64>>,
+    /// The account allowed to run moderation operations.
+    pub admin: RegisterView<Option<AccountOwner>>,
+    /// The current or last maintenance window, during which operations are
+    /// rejected.
+    pub maintenance: RegisterView<Option<MaintenanceWindow>>,
     /// All registered players on this chain (keyed by wallet address).
     pub players: MapView<AccountOwner, Player>,
     /// Leaderboard entries (keyed by wallet address).
     pub leaderboard: MapView<AccountOwner, LeaderboardEntry>,
-    /// All game scores (keyed by score ID).
-    pub game_scores: MapView<u64, GameScore>,
+    /// Leaderboard entries of the players who are still rookies, kept
+    /// alongside `leaderboard`.
+    pub rookie_leaderboard: MapView<AccountOwner, LeaderboardEntry>,
+    /// Who is placed on the rookie leaderboard.
+    pub rookie_criteria: RegisterView<RookieCriteria>,
+    /// Every game score stored on this chain, in the order it was stored.
+    pub score_log: LogView<GameScore>,
+    /// The latest `RECENT_SCORES_CAPACITY` scores stored, oldest first.
+    pub recent_scores: QueueView<GameScore>,
+    /// The log position of each score, by the chain it was played on and
+    /// its ID there.
+    pub score_positions: MapView<(ChainId, u64), u64>,
+    /// The log position of the score behind each receipt.
+    pub score_receipts: MapView<CryptoHash, u64>,
+    /// The log positions of scores removed by moderation, which are no
+    /// longer looked up, listed or counted.
+    pub removed_scores: SetView<u64>,
+    /// The origin chain and ID of every score synced to the hub, so
+    /// redelivered syncs are applied once.
+    pub synced_scores: SetView<(ChainId, u64)>,
+    /// Each player's own append-only log of the positions of their scores
+    /// in the score log, so their history is read without scanning anyone
+    /// else's scores, and can be dropped on its own.
+    pub player_scores: CollectionView<AccountOwner, LogView<u64>>,
     /// Counter for generating unique score IDs.
     pub score_counter: RegisterView<u64>,
+    /// Each player's open game session.
+    pub game_sessions: MapView<AccountOwner, GameSession>,
+    /// Sessions each player set aside by starting another game, oldest
+    /// first, which a batch of runs can still close until they time out.
+    pub set_aside_sessions: MapView<AccountOwner, Vec<GameSession>>,
+    /// Counter for generating unique session IDs.
+    pub session_counter: RegisterView<u64>,
     /// Total number of games played.
     pub total_games_played: RegisterView<u64>,
+    /// Number of registered players.
+    pub total_players: RegisterView<u64>,
     /// Total XP earned across all players.
     pub total_xp_earned: RegisterView<u64>,
+    /// Each player's champion score (sum of best runs per game).
+    pub champion_scores: MapView<AccountOwner, ChampionScore>,
+    /// XP each player earned from runs in each scoring category, which
+    /// ranks the category leaderboards.
+    pub category_xp: MapView<(AccountOwner, ScoringCategory), u64>,
+    /// Each player's XP per day over the current form window.
+    pub recent_form: MapView<AccountOwner, RecentForm>,
+    /// The best standard-category run of each game.
+    pub hall_of_fame: MapView<GameType, GameScore>,
+    /// Reaction counts per target.
+    pub reactions: MapView<ReactionTarget, ReactionTally>,
+    /// The emotes each player has left on each target.
+    pub player_reactions: MapView<(AccountOwner, ReactionTarget), PlayerReactions>,
+    /// Each player's best-scoring run in each game.
+    pub personal_bests: MapView<AccountOwner, PersonalBests>,
+    /// Games played per UTC hour of the week.
+    pub play_heatmap: RegisterView<PlayHeatmap>,
+    /// Bucketed distribution of every score, per game.
+    pub score_histograms: MapView<GameType, ScoreHistogram>,
+    /// Players hidden from public leaderboards and aggregates.
+    pub shadow_banned: SetView<AccountOwner>,
+    /// Players whose submissions are rejected, also hidden from public
+    /// leaderboards.
+    pub banned_players: SetView<AccountOwner>,
+    /// Players who opted out of public leaderboards.
+    pub leaderboard_opt_outs: SetView<AccountOwner>,
+    /// Players who opted out of public score feeds.
+    pub history_opt_outs: SetView<AccountOwner>,
+    /// Bond required to file an appeal.
+    pub appeal_bond: RegisterView<Amount>,
+    /// Score appeals (keyed by appeal ID).
+    pub appeals: MapView<u64, Appeal>,
+    /// Counter for generating unique appeal IDs.
+    pub appeal_counter: RegisterView<u64>,
+    /// Running totals of appeal bonds.
+    pub bond_accounts: RegisterView<BondAccounts>,
+    /// Kiosk-mode settings.
+    pub kiosk_config: RegisterView<KioskConfig>,
+    /// Plausibility bounds on submitted scores.
+    pub plausibility: RegisterView<PlausibilityConfig>,
+    /// Underplayed-game bonus settings.
+    pub balance_config: RegisterView<BalanceConfig>,
+    /// The live scoring config.
+    pub scoring_config: RegisterView<ScoringConfig>,
+    /// The running scoring canary, or the last one to end.
+    pub scoring_canary: RegisterView<Option<ScoringCanary>>,
+    /// Daily hours when each game is closed for submissions.
+    pub submission_windows: RegisterView<SubmissionWindows>,
+    /// Each player's operations on this chain.
+    pub operation_usage: MapView<AccountOwner, OperationUsage>,
+    /// The response to each idempotency key a signer has used.
+    pub idempotent_responses: MapView<(AccountOwner, String), ArcadeResponse>,
+    /// Daily soft quotas on operations.
+    pub usage_quota: RegisterView<UsageQuota>,
+    /// Smurf detection thresholds.
+    pub smurf_detection: RegisterView<SmurfDetectionConfig>,
+    /// Bucketed distribution of new accounts' early scores, per game.
+    pub early_score_histograms: MapView<GameType, ScoreHistogram>,
+    /// Each account's screened early runs.
+    pub early_runs: MapView<AccountOwner, EarlyRuns>,
+    /// Accounts awaiting a moderator's review (keyed by flag ID).
+    pub moderation_queue: MapView<u64, ModerationFlag>,
+    /// The unfinished pass over each invariant section.
+    pub invariant_checks: MapView<InvariantSection, InvariantCheck>,
+    /// Every state-changing operation and sync, in order.
+    pub journal: LogView<JournalEntry>,
+    /// The arcade totals every `JOURNAL_CHECKPOINT_INTERVAL` journal entries.
+    pub journal_checkpoints: LogView<JournalCheckpoint>,
+    /// The unfinished replay of each group of derived views.
+    pub replays: MapView<ReplayTarget, ReplayProgress>,
+    /// Counter for generating unique moderation flag IDs.
+    pub moderation_flag_counter: RegisterView<u64>,
+    /// Humanity check settings.
+    pub humanity_checks: RegisterView<HumanityCheckConfig>,
+    /// Each challenged account's open humanity challenge.
+    pub humanity_challenges: MapView<AccountOwner, HumanityChallenge>,
+    /// Counter for generating unique humanity challenge IDs.
+    pub humanity_challenge_counter: RegisterView<u64>,
+    /// The wallet that vouched for each player completing a challenge.
+    pub humanity_verifiers: MapView<AccountOwner, AccountOwner>,
+    /// How many players each wallet has vouched for.
+    pub humanity_vouches: MapView<AccountOwner, u32>,
+    /// Plays per game over the rolling balance window.
+    pub play_share: RegisterView<PlayShare>,
+    /// Crossover events that haven't ended yet.
+    pub crossover_schedule: RegisterView<CrossoverSchedule>,
+    /// Each player's runs towards and unlocks of event achievements.
+    pub event_achievements: MapView<AccountOwner, EventAchievementProgress>,
+    /// Per-device submission activity (keyed by device ID).
+    pub device_activity: MapView<String, DeviceActivity>,
+    /// Devices whose submissions are rejected.
+    pub banned_devices: SetView<String>,
+    /// Accounts allowed to relay attested submissions.
+    pub gateways: SetView<AccountOwner>,
+    /// Last relayed nonce per player, for replay protection.
+    pub gateway_nonces: MapView<AccountOwner, u64>,
+    /// The operator key that signs registration vouchers.
+    pub voucher_signer: RegisterView<Option<AccountOwner>>,
+    /// Each player's exported progress; their record is locked once listed.
+    pub progress_exports: MapView<AccountOwner, ProgressExport>,
+    /// The old deployment's admin key, whose signed exports can be imported.
+    pub migration_source: RegisterView<Option<AccountOwner>>,
+    /// Progress imported from other deployments (keyed by record hash).
+    pub progress_imports: MapView<CryptoHash, ProgressImport>,
+    /// The game server key that signs submissions, in anti-cheat mode.
+    pub score_signer: RegisterView<Option<AccountOwner>>,
+    /// Nonce of each player's last server-signed submission.
+    pub score_nonces: MapView<AccountOwner, u64>,
+    /// Consumed voucher IDs and the owner that used them.
+    pub consumed_vouchers: MapView<u64, AccountOwner>,
+    /// Username reservations (keyed by normalized username).
+    pub reservations: MapView<String, UsernameReservation>,
+    /// The normalized username each owner currently holds a reservation for.
+    pub owner_reservations: MapView<AccountOwner, String>,
+    /// Terms usernames may not contain, as blocklist keys.
+    pub blocked_usernames: SetView<String>,
+    /// Username auctions (keyed by auction ID).
+    pub username_auctions: MapView<u64, UsernameAuction>,
+    /// Counter for generating unique username auction IDs.
+    pub username_auction_counter: RegisterView<u64>,
+    /// The unsettled auction of each normalized username being auctioned.
+    pub auctioned_usernames: MapView<String, u64>,
+    /// The account holding each registered username (keyed by normalized
+    /// username).
+    pub username_claims: MapView<String, UsernameClaim>,
+    /// Inactivity and grace periods for username recycling.
+    pub username_recycling: RegisterView<UsernameRecyclingConfig>,
+    /// Each player's blocklist (keyed by the blocking player).
+    pub blocklists: MapView<AccountOwner, Blocklist>,
+    /// Each player's unlocked achievements and progress towards the rest.
+    pub achievements: MapView<AccountOwner, AchievementProgress>,
+    /// Each player's daily streak and shields.
+    pub streaks: MapView<AccountOwner, DailyStreak>,
+    /// Each player's personal goals.
+    pub goals: MapView<AccountOwner, PlayerGoals>,
+    /// The players each player follows.
+    pub following: MapView<AccountOwner, FollowList>,
+    /// How many players follow each player.
+    pub follower_counts: MapView<AccountOwner, u64>,
+    /// The players each player added as friends and those who added them.
+    pub friends: MapView<AccountOwner, FriendList>,
+    /// Each player's kudos counters.
+    pub kudos: MapView<AccountOwner, KudosRecord>,
+    /// The kudos each player has sent today.
+    pub kudos_allowances: MapView<AccountOwner, KudosAllowance>,
+    /// Active mentorships (keyed by apprentice).
+    pub mentorships: MapView<AccountOwner, Mentorship>,
+    /// Each mentor's apprentices and track record (keyed by mentor).
+    pub mentors: MapView<AccountOwner, MentorRecord>,
+    /// Pending mentorship offers (keyed by apprentice).
+    pub mentorship_offers: MapView<AccountOwner, MentorshipOffers>,
+    /// Guilds (keyed by guild ID).
+    pub guilds: MapView<u64, Guild>,
+    /// Counter for generating unique guild IDs.
+    pub guild_counter: RegisterView<u64>,
+    /// The hub's ID for each guild synced from another chain, keyed by the
+    /// chain it was founded on and its ID there.
+    pub synced_guilds: MapView<(ChainId, u64), u64>,
+    /// The guild holding each normalized guild name.
+    pub guild_names: MapView<String, u64>,
+    /// The guild each player belongs to.
+    pub player_guilds: MapView<AccountOwner, u64>,
+    /// XP each guild's members have earned while in it (keyed by guild ID).
+    pub guild_xp: MapView<u64, u64>,
+    /// Each player's contribution to their current guild.
+    pub guild_contributions: MapView<AccountOwner, GuildContribution>,
+    /// Guild invite codes (keyed by the code's hash).
+    pub invite_codes: MapView<CryptoHash, InviteCode>,
+    /// Guild polls (keyed by poll ID).
+    pub guild_polls: MapView<u64, GuildPoll>,
+    /// Counter for generating unique poll IDs.
+    pub poll_counter: RegisterView<u64>,
+    /// How each guild's prizes are split (keyed by guild ID); guilds not
+    /// listed split them equally.
+    pub guild_payout_rules: MapView<u64, GuildPayoutRule>,
+    /// Prizes awarded to guilds (keyed by prize ID, hub only).
+    pub guild_prizes: MapView<u64, GuildPrize>,
+    /// Counter for generating unique guild prize IDs.
+    pub guild_prize_counter: RegisterView<u64>,
+    /// Each member's share of each guild prize (keyed by prize ID and
+    /// member, hub only).
+    pub prize_claims: MapView<(u64, AccountOwner), PrizeClaim>,
+    /// Each guild's recent management actions (keyed by guild ID).
+    pub guild_audit: MapView<u64, GuildAuditTrail>,
+    /// When each player was last seen playing.
+    pub presence: MapView<AccountOwner, Presence>,
+    /// Each player's play on their most recent day, for play limits.
+    pub daily_play: MapView<AccountOwner, DailyPlay>,
+    /// The days on which each player completed the daily challenge.
+    pub challenge_completions: MapView<AccountOwner, ChallengeCompletions>,
+    /// Each recent day's daily challenge leaderboard (keyed by day).
+    pub challenge_boards: MapView<u64, ChallengeBoard>,
+    /// The days with a challenge leaderboard, in the order first played.
+    pub challenge_board_days: QueueView<u64>,
+    /// Each player's progress on their current monthly challenge.
+    pub monthly_progress: MapView<AccountOwner, MonthlyProgress>,
+    /// Each month's challenge participants with the most progress (keyed
+    /// by calendar month).
+    pub monthly_boards: MapView<u64, MonthlyBoard>,
+    /// Games each player completed on their most recent day of play.
+    pub daily_circuits: MapView<AccountOwner, DailyCircuit>,
+    /// Arcade-wide milestones reached so far, in order (hub only).
+    pub milestones: LogView<Milestone>,
+    /// Stored leaderboard snapshots (keyed by snapshot ID).
+    pub leaderboard_snapshots: MapView<u64, LeaderboardSnapshot>,
+    /// Counter for generating unique snapshot IDs.
+    pub snapshot_counter: RegisterView<u64>,
+    /// The most recently taken snapshot.
+    pub latest_snapshot: RegisterView<Option<u64>>,
+    /// How many entries archived snapshots keep; `None` keeps them whole.
+    pub history_top_k: RegisterView<Option<u32>>,
+    /// The next archived snapshot to trim to `history_top_k`.
+    pub history_prune_cursor: RegisterView<u64>,
+    /// The season in progress, once the admin has started seasons.
+    pub current_season: RegisterView<Option<Season>>,
+    /// Each player's XP in the latest season they played.
+    pub seasonal_xp: MapView<AccountOwner, SeasonalXp>,
+    /// Final standings of finished seasons (keyed by season number).
+    pub season_archive: MapView<u32, SeasonArchive>,
+    /// Themes of current and future seasons (keyed by season number).
+    pub season_themes: MapView<u32, SeasonTheme>,
+    /// Each player's cosmetics (hub only).
+    pub cosmetics: MapView<AccountOwner, CosmeticInventory>,
+    /// Raffles (keyed by raffle ID, hub only).
+    pub raffles: MapView<u64, Raffle>,
+    /// Counter for generating unique raffle IDs.
+    pub raffle_counter: RegisterView<u64>,
+    /// Raffles not yet drawn.
+    pub open_raffles: SetView<u64>,
+    /// Tickets each player holds in each raffle not yet drawn.
+    pub raffle_tickets: MapView<(u64, AccountOwner), u64>,
+    /// Boss battles (keyed by battle ID, hub only).
+    pub boss_battles: MapView<u64, BossBattle>,
+    /// Counter for generating unique boss battle IDs.
+    pub boss_battle_counter: RegisterView<u64>,
+    /// Boss battles not yet settled.
+    pub open_boss_battles: SetView<u64>,
+    /// Damage each player has dealt in each boss battle not yet settled.
+    pub boss_damage: MapView<(u64, AccountOwner), u64>,
+    /// Each player's boss battle rewards (hub only).
+    pub boss_rewards: MapView<AccountOwner, BossRewards>,
+    /// Community games (keyed by game ID, hub only).
+    pub community_games: MapView<u64, CommunityGame>,
+    /// Counter for generating unique community game IDs.
+    pub community_game_counter: RegisterView<u64>,
+    /// Community games waiting for review.
+    pub pending_community_games: SetView<u64>,
+    /// Each approved community game's leaderboard.
+    pub community_leaderboards: MapView<u64, CommunityLeaderboard>,
+    /// XP each player has earned in community games (hub only).
+    pub community_xp: MapView<AccountOwner, u64>,
+    /// Automatic snapshots still retained, oldest first.
+    pub automatic_snapshots: QueueView<u64>,
+    /// Applications receiving forwarded events and their delivery chains.
+    pub bridge_subscriptions: MapView<ApplicationId, BridgeSubscription>,
+    /// Events forwarded to this chain, awaiting each subscriber's drain.
+    pub bridge_inboxes: MapView<ApplicationId, BridgeInbox>,
+    /// The chains whose sync streams this chain subscribed to.
+    pub sync_sources: SetView<ChainId>,
+    /// The stream index after the last sync applied from each chain, by
+    /// message or from its sync stream. Syncs below it are repeats.
+    pub next_sync_index: MapView<ChainId, u32>,
+}
+
+/// Updates staged while validating submissions, persisted by the contract
+/// only once every check has passed.
+#[derive(Default)]
+pub struct PendingSubmissions {
+    pub devices: BTreeMap<String, DeviceActivity>,
+    pub plays: BTreeMap<AccountOwner, DailyPlay>,
+    pub score_nonces: BTreeMap<AccountOwner, u64>,
+    /// Sessions a batch of runs closes, open or set aside.
+    pub sessions: BTreeMap<AccountOwner, BTreeSet<u64>>,
+}
+
+/// Submission checks shared by the contract and the service's dry run.
+impl ArcadeHubState {
+    /// Run every check a score submission must pass, staging the resulting
+    /// updates in `pending`.
+    pub async fn check_submission(
+        &self,
+        player: &Player,
+        submission: &ScoreSubmission,
+        timestamp: u64,
+        pending: &mut PendingSubmissions,
+    ) -> Result<(), ArcadeError> {
+        if self
+            .banned_players
+            .contains(&player.owner)
+            .await
+            .unwrap_or(false)
+        {
+            return Err(ArcadeError::PlayerBanned);
+        }
+        if self
+            .humanity_challenges
+            .contains_key(&player.owner)
+            .await
+            .unwrap_or(false)
+        {
+            return Err(ArcadeError::HumanityCheckRequired);
+        }
+        if self
+            .progress_exports
+            .contains_key(&player.owner)
+            .await
+            .unwrap_or(false)
+        {
+            return Err(ArcadeError::ProgressLocked);
+        }
+        self.submission_windows
+            .get()
+            .check(submission.game_type, timestamp)?;
+        self.plausibility
+            .get()
+            .bounds_for(submission.game_type)
+            .check(submission)?;
+        self.check_device_submission(
+            submission.device_id.as_deref(),
+            timestamp,
+            &mut pending.devices,
+        )
+        .await?;
+        self.check_play_limits(player, submission.game_type, timestamp, &mut pending.plays)
+            .await
+    }
+
+    /// Check a submission by `owner` carries the game server's signature,
+    /// if a score signer is registered, staging its nonce in `pending`.
+    pub async fn check_score_signature(
+        &self,
+        owner: &AccountOwner,
+        submission: &ScoreSubmission,
+        signature: Option<&ScoreSignature>,
+        pending: &mut PendingSubmissions,
+    ) -> Result<(), ArcadeError> {
+        let Some(signer) = *self.score_signer.get() else {
+            return Ok(());
+        };
+        let signature = signature.ok_or(ArcadeError::ScoreSignatureRequired)?;
+        signature.verify(&signer, *owner, submission)?;
+
+        let last_nonce = match pending.score_nonces.get(owner) {
+            Some(nonce) => Some(*nonce),
+            None => self.score_nonces.get(owner).await.ok().flatten(),
+        };
+        if last_nonce.is_some_and(|last| signature.nonce <= last) {
+            return Err(ArcadeError::StaleNonce);
+        }
+        pending.score_nonces.insert(*owner, signature.nonce);
+        Ok(())
+    }
+
+    /// Check `owner` has the open session `session_id` for `submission`
+    /// at `timestamp`.
+    pub async fn check_session(
+        &self,
+        owner: &AccountOwner,
+        session_id: Option<u64>,
+        submission: &ScoreSubmission,
+        timestamp: u64,
+    ) -> Result<(), ArcadeError> {
+        let session_id = session_id.ok_or(ArcadeError::SessionRequired)?;
+        let session = self
+            .game_sessions
+            .get(owner)
+            .await
+            .ok()
+            .flatten()
+            .ok_or(ArcadeError::SessionNotFound)?;
+        session.check_close(session_id, submission, timestamp)
+    }
+
+    /// Check `owner` has the session `session_id` for `submission` at
+    /// `timestamp`, open or set aside, and that no other run in the batch
+    /// closes it.
+    pub async fn check_batch_session(
+        &self,
+        owner: &AccountOwner,
+        session_id: u64,
+        submission: &ScoreSubmission,
+        timestamp: u64,
+        pending: &mut PendingSubmissions,
+    ) -> Result<(), ArcadeError> {
+        let closing = pending.sessions.entry(*owner).or_default();
+        if closing.contains(&session_id) {
+            return Err(ArcadeError::SessionNotFound);
+        }
+        let open = self.game_sessions.get(owner).await.ok().flatten();
+        let set_aside = self
+            .set_aside_sessions
+            .get(owner)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        let session = open
+            .into_iter()
+            .chain(set_aside)
+            .find(|session| session.id == session_id)
+            .ok_or(ArcadeError::SessionNotFound)?;
+        session.check_close(session_id, submission, timestamp)?;
+        closing.insert(session_id);
+        Ok(())
+    }
+
+    /// What a submission's XP is worked out from, whatever the scoring
+    /// config: its base XP, any multiplier for a crossover event or an
+    /// underplayed game, and whether it beats the game's p90 score on this
+    /// chain.
+    pub async fn xp_factors(
+        &self,
+        player: &Player,
+        submission: &ScoreSubmission,
+        timestamp: u64,
+    ) -> XpFactors {
+        let game_type = submission.game_type;
+        let base_xp = game_type.calculate_category_xp(
+            submission.score,
+            submission.bonus_data,
+            player.scoring_category,
+        );
+        // A running crossover event's multiplier replaces the balance one
+        let multiplier = match self
+            .crossover_schedule
+            .get()
+            .xp_multiplier(game_type, timestamp)
+        {
+            Some(multiplier) => multiplier,
+            None => self.play_share.get().multiplier_percent(
+                game_type,
+                timestamp,
+                self.balance_config.get(),
+            ),
+        };
+        let top_decile = self
+            .score_histograms
+            .get(&game_type)
+            .await
+            .ok()
+            .flatten()
+            .is_some_and(|histogram| {
+                histogram.beats_top_decile(submission.score, game_type.lower_score_is_better())
+            });
+        XpFactors {
+            game_type,
+            base_xp,
+            multiplier_percent: multiplier,
+            top_decile,
+        }
+    }
+
+    /// Validate a submission's device ID and count it against the device's
+    /// limit, staging the updated activity in `pending`.
+    async fn check_device_submission(
+        &self,
+        device_id: Option<&str>,
+        timestamp: u64,
+        pending: &mut BTreeMap<String, DeviceActivity>,
+    ) -> Result<(), ArcadeError> {
+        let config = self.kiosk_config.get().clone();
+        let Some(device_id) = device_id else {
+            return if config.require_device_id {
+                Err(ArcadeError::DeviceIdRequired)
+            } else {
+                Ok(())
+            };
+        };
+
+        kiosk::validate_device_id(device_id)?;
+        let device_id = device_id.to_string();
+        if self
+            .banned_devices
+            .contains(&device_id)
+            .await
+            .unwrap_or(false)
+        {
+            return Err(ArcadeError::DeviceBanned);
+        }
+
+        let mut activity = match pending.get(&device_id) {
+            Some(activity) => activity.clone(),
+            None => self
+                .device_activity
+                .get(&device_id)
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or_else(|| DeviceActivity::new(device_id.clone())),
+        };
+        activity.record(timestamp, config.max_submissions_per_hour)?;
+        pending.insert(device_id, activity);
+        Ok(())
+    }
+
+    /// Count a game against the player's daily play limits, staging the
+    /// updated tally in `pending`.
+    async fn check_play_limits(
+        &self,
+        player: &Player,
+        game_type: GameType,
+        timestamp: u64,
+        pending: &mut BTreeMap<AccountOwner, DailyPlay>,
+    ) -> Result<(), ArcadeError> {
+        let mut play = match pending.get(&player.owner) {
+            Some(play) => play.clone(),
+            None => self
+                .daily_play
+                .get(&player.owner)
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or_default(),
+        };
+        play.record(&player.play_limits, game_type, player.local_day(timestamp))?;
+        pending.insert(player.owner, play);
+        Ok(())
+    }
 }
@@ -0,0 +1,56 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Storage usage estimates, to see what drives state growth.
+
+use async_graphql::SimpleObject;
+use serde::{Deserialize, Serialize};
+
+/// Entries read from the end of a log to estimate the size of the rest.
+pub const LOG_SAMPLE_SIZE: usize = 100;
+
+/// The estimated footprint of one view.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct ViewUsage {
+    pub view: String,
+    pub entries: u64,
+    /// Serialized size of the entries' keys and values, in bytes. Logs are
+    /// extrapolated from their latest [`LOG_SAMPLE_SIZE`] entries.
+    pub approximate_bytes: u64,
+}
+
+impl ViewUsage {
+    /// The usage of a log of `entries`, given the size of a sample of
+    /// `sampled` of them.
+    pub fn extrapolated(view: &str, entries: u64, sampled: u64, sample_bytes: u64) -> Self {
+        let approximate_bytes = match sampled {
+            0 => 0,
+            _ => (u128::from(sample_bytes) * u128::from(entries) / u128::from(sampled)) as u64,
+        };
+        Self {
+            view: view.to_string(),
+            entries,
+            approximate_bytes,
+        }
+    }
+}
+
+/// The estimated footprint of the major views, largest first.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct StorageReport {
+    pub views: Vec<ViewUsage>,
+    pub total_entries: u64,
+    pub total_bytes: u64,
+}
+
+impl StorageReport {
+    /// Total up `views`, ordering them largest first.
+    pub fn new(mut views: Vec<ViewUsage>) -> Self {
+        views.sort_by_key(|usage| std::cmp::Reverse(usage.approximate_bytes));
+        Self {
+            total_entries: views.iter().map(|usage| usage.entries).sum(),
+            total_bytes: views.iter().map(|usage| usage.approximate_bytes).sum(),
+            views,
+        }
+    }
+}
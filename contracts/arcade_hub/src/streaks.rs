@@ -0,0 +1,202 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Daily play streaks and the shields that protect them.
+//!
+//! A streak counts consecutive days, in the player's timezone, with at
+//! least one run. Shields are earned every [`SHIELD_EARN_INTERVAL_DAYS`] days of streak or bought, up to
+//! [`SHIELDS_PER_MONTH`] a calendar month. Nothing happens on the missed day
+//! itself: when the player next plays, the gap is covered by shields if
+//! they hold enough, one per missed day, and the streak resets otherwise.
+
+use async_graphql::SimpleObject;
+use linera_sdk::linera_base_types::Amount;
+use serde::{Deserialize, Serialize};
+
+use crate::{runtime::RuntimeFacade, ArcadeError, Player};
+
+/// Shields a player can gain per calendar month, earned and bought alike.
+pub const SHIELDS_PER_MONTH: u32 = 3;
+
+/// Shields a player can hold at once.
+pub const MAX_SHIELDS: u32 = 3;
+
+/// Days of streak that earn a shield.
+pub const SHIELD_EARN_INTERVAL_DAYS: u32 = 7;
+
+/// Price of one shield.
+pub const SHIELD_PRICE: Amount = Amount::from_millis(50);
+
+/// A player's daily streak and shields.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, SimpleObject)]
+pub struct DailyStreak {
+    /// Consecutive days played, as of `last_day`. Lapses if the player
+    /// misses more days than they hold shields for.
+    pub current: u32,
+    pub longest: u32,
+    /// The last day played, in the player's timezone.
+    pub last_day: Option<u64>,
+    /// Shields held.
+    pub shields: u32,
+    /// Shields spent on missed days, over all time.
+    pub shields_used: u32,
+    /// The calendar month (`year * 12 + month - 1`) `shields_gained` counts.
+    pub shields_month: u64,
+    /// Shields earned or bought in `shields_month`.
+    pub shields_gained: u32,
+}
+
+impl DailyStreak {
+    /// Count a run on `day`, covering any missed days with shields, and
+    /// return the shields spent doing so.
+    pub fn record_play(&mut self, day: u64) -> u32 {
+        let mut spent = 0;
+        match self.last_day {
+            Some(last_day) if day <= last_day => return 0,
+            Some(last_day) => {
+                let missed = day - last_day - 1;
+                if missed == 0 {
+                    self.current += 1;
+                } else if missed <= u64::from(self.shields) {
+                    spent = missed as u32;
+                    self.shields -= spent;
+                    self.shields_used = self.shields_used.saturating_add(spent);
+                    self.current += 1;
+                } else {
+                    self.current = 1;
+                }
+            }
+            None => self.current = 1,
+        }
+        self.last_day = Some(day);
+        self.longest = self.longest.max(self.current);
+
+        if self.current.is_multiple_of(SHIELD_EARN_INTERVAL_DAYS) {
+            // Earned shields beyond the limits are simply not granted
+            self.gain_shield(day).ok();
+        }
+        spent
+    }
+
+    /// Add a shield gained on `day`, within the monthly and holding limits.
+    pub fn gain_shield(&mut self, day: u64) -> Result<(), ArcadeError> {
+        let month = month_index(day);
+        if self.shields_month != month {
+            self.shields_month = month;
+            self.shields_gained = 0;
+        }
+        if self.shields >= MAX_SHIELDS {
+            return Err(ArcadeError::TooManyStreakShields);
+        }
+        if self.shields_gained >= SHIELDS_PER_MONTH {
+            return Err(ArcadeError::StreakShieldLimitReached);
+        }
+        self.shields += 1;
+        self.shields_gained += 1;
+        Ok(())
+    }
+}
+
+/// Count a run by `player` now, on the day it is in their timezone, and
+/// return the shields spent on days missed since their last run.
+pub fn record_run(
+    runtime: &mut impl RuntimeFacade,
+    player: &Player,
+    streak: &mut DailyStreak,
+) -> u32 {
+    streak.record_play(player.local_day(runtime.now_micros()))
+}
+
+/// The calendar month (`year * 12 + month - 1`) containing `day`.
+pub fn month_index(day: u64) -> u64 {
+    // Civil-from-days, with years starting in March
+    let z = day + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    year * 12 + month - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use linera_sdk::linera_base_types::{AccountOwner, CryptoHash};
+
+    use super::*;
+    use crate::{runtime::MockRuntime, MICROS_PER_DAY};
+
+    const HOUR: u64 = 60 * 60 * 1_000_000;
+
+    /// Noon UTC on some day.
+    const NOON: u64 = 20_000 * MICROS_PER_DAY + 12 * HOUR;
+
+    fn player(utc_offset_minutes: i32) -> Player {
+        let owner = AccountOwner::Address32(CryptoHash::test_hash("player"));
+        let mut player = Player::new(owner, "player".to_string(), 0);
+        player.utc_offset_minutes = utc_offset_minutes;
+        player
+    }
+
+    #[test]
+    fn streak_extends_once_per_day() {
+        let mut runtime = MockRuntime::at(NOON);
+        let player = player(0);
+        let mut streak = DailyStreak::default();
+
+        record_run(&mut runtime, &player, &mut streak);
+        runtime.advance(HOUR);
+        record_run(&mut runtime, &player, &mut streak);
+        assert_eq!(streak.current, 1);
+
+        runtime.advance(MICROS_PER_DAY);
+        record_run(&mut runtime, &player, &mut streak);
+        assert_eq!(streak.current, 2);
+        assert_eq!(streak.longest, 2);
+    }
+
+    #[test]
+    fn streak_rolls_over_at_local_midnight() {
+        // UTC+10:00, so it's 22:00 local time and midnight is two hours away
+        let mut runtime = MockRuntime::at(NOON);
+        let player = player(10 * 60);
+        let mut streak = DailyStreak::default();
+
+        record_run(&mut runtime, &player, &mut streak);
+        runtime.advance(HOUR);
+        record_run(&mut runtime, &player, &mut streak);
+        assert_eq!(streak.current, 1);
+
+        runtime.advance(2 * HOUR);
+        record_run(&mut runtime, &player, &mut streak);
+        assert_eq!(streak.current, 2);
+    }
+
+    #[test]
+    fn missed_day_spends_a_shield_or_resets() {
+        let mut runtime = MockRuntime::at(NOON);
+        let player = player(0);
+        let mut streak = DailyStreak {
+            shields: 1,
+            ..DailyStreak::default()
+        };
+
+        record_run(&mut runtime, &player, &mut streak);
+        runtime.advance(2 * MICROS_PER_DAY);
+        assert_eq!(record_run(&mut runtime, &player, &mut streak), 1);
+        assert_eq!(streak.current, 2);
+        assert_eq!(streak.shields, 0);
+
+        runtime.advance(2 * MICROS_PER_DAY);
+        assert_eq!(record_run(&mut runtime, &player, &mut streak), 0);
+        assert_eq!(streak.current, 1);
+        assert_eq!(streak.longest, 2);
+    }
+}
@@ -0,0 +1,149 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Daily hours when a game stops accepting submissions.
+//!
+//! The admin can close a game for a few recurring windows of UTC time each
+//! day, say while its scoring is being rebalanced. While a window is open,
+//! runs of the game can't be started or submitted, and the `games` query
+//! says when the game reopens. Scores synced to the hub were accepted on
+//! their own chain, so windows don't apply to them.
+
+use std::collections::BTreeMap;
+
+use async_graphql::{InputObject, SimpleObject};
+use serde::{Deserialize, Serialize};
+
+use crate::{ArcadeError, GameType, MICROS_PER_DAY};
+
+/// Closed windows one game can have.
+pub const MAX_CLOSED_WINDOWS: usize = 4;
+
+/// Minutes in a UTC day.
+pub const MINUTES_PER_DAY: u32 = 24 * 60;
+
+const MICROS_PER_MINUTE: u64 = 60 * 1_000_000;
+
+/// A daily window of UTC time during which a game is closed. A window
+/// ending before it starts runs past midnight.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, SimpleObject, InputObject)]
+#[graphql(input_name = "ClosedWindowInput")]
+pub struct ClosedWindow {
+    /// Minutes after UTC midnight the window opens.
+    pub start_minute: u32,
+    /// Minutes after UTC midnight the window closes.
+    pub end_minute: u32,
+}
+
+impl ClosedWindow {
+    /// Whether the window covers `minute` of the day.
+    fn contains(&self, minute: u32) -> bool {
+        if self.start_minute < self.end_minute {
+            (self.start_minute..self.end_minute).contains(&minute)
+        } else {
+            minute >= self.start_minute || minute < self.end_minute
+        }
+    }
+}
+
+/// Each game's closed windows, managed by the admin.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SubmissionWindows {
+    closed: BTreeMap<GameType, Vec<ClosedWindow>>,
+}
+
+impl SubmissionWindows {
+    /// Replace `game_type`'s closed windows; an empty list keeps the game
+    /// open around the clock.
+    pub fn set(
+        &mut self,
+        game_type: GameType,
+        windows: Vec<ClosedWindow>,
+    ) -> Result<(), ArcadeError> {
+        let window_ok = |window: &ClosedWindow| {
+            window.start_minute < MINUTES_PER_DAY
+                && window.end_minute < MINUTES_PER_DAY
+                && window.start_minute != window.end_minute
+        };
+        if windows.len() > MAX_CLOSED_WINDOWS || !windows.iter().all(window_ok) {
+            return Err(ArcadeError::InvalidSubmissionWindows);
+        }
+        if windows.is_empty() {
+            self.closed.remove(&game_type);
+        } else {
+            self.closed.insert(game_type, windows);
+        }
+        Ok(())
+    }
+
+    /// `game_type`'s closed windows.
+    pub fn windows(&self, game_type: GameType) -> &[ClosedWindow] {
+        self.closed.get(&game_type).map_or(&[], Vec::as_slice)
+    }
+
+    /// Whether `game_type` is closed at `timestamp`.
+    pub fn is_closed(&self, game_type: GameType, timestamp: u64) -> bool {
+        let minute = minute_of_day(timestamp);
+        self.windows(game_type)
+            .iter()
+            .any(|window| window.contains(minute))
+    }
+
+    /// When `game_type` reopens if it is closed at `timestamp`; `None` if
+    /// it is open, or its windows cover the whole day.
+    pub fn reopens_at(&self, game_type: GameType, timestamp: u64) -> Option<u64> {
+        let windows = self.windows(game_type);
+        let mut reopens_at = timestamp;
+        // Each step leaves one window, so overlapping windows are all left
+        // within as many steps as there are windows
+        for _ in 0..=windows.len() {
+            let minute = minute_of_day(reopens_at);
+            let Some(window) = windows.iter().find(|window| window.contains(minute)) else {
+                return (reopens_at != timestamp).then_some(reopens_at);
+            };
+            let day_start = reopens_at - reopens_at % MICROS_PER_DAY;
+            let end = day_start + u64::from(window.end_minute) * MICROS_PER_MINUTE;
+            reopens_at = if window.end_minute > minute {
+                end
+            } else {
+                end + MICROS_PER_DAY
+            };
+        }
+        None
+    }
+
+    /// Check `game_type` accepts submissions at `timestamp`.
+    pub fn check(&self, game_type: GameType, timestamp: u64) -> Result<(), ArcadeError> {
+        if self.is_closed(game_type, timestamp) {
+            return Err(ArcadeError::GameClosed { game_type });
+        }
+        Ok(())
+    }
+}
+
+/// The minute of the UTC day `timestamp` falls in.
+fn minute_of_day(timestamp: u64) -> u32 {
+    ((timestamp % MICROS_PER_DAY) / MICROS_PER_MINUTE) as u32
+}
+
+/// A game and whether it is accepting submissions.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct GameInfo {
+    pub game_type: GameType,
+    pub accepting_submissions: bool,
+    /// When the game reopens, while it is closed for part of the day.
+    pub reopens_at: Option<u64>,
+    pub closed_windows: Vec<ClosedWindow>,
+}
+
+impl GameInfo {
+    /// `game_type`'s status at `now`.
+    pub fn new(game_type: GameType, windows: &SubmissionWindows, now: u64) -> Self {
+        Self {
+            game_type,
+            accepting_submissions: !windows.is_closed(game_type, now),
+            reopens_at: windows.reopens_at(game_type, now),
+            closed_windows: windows.windows(game_type).to_vec(),
+        }
+    }
+}
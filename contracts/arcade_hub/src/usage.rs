@@ -0,0 +1,218 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Per-player operation accounting and soft quotas.
+//!
+//! Every operation a player signs on this chain is counted, along with the
+//! size of its serialized form as a rough stand-in for the fuel and storage
+//! it costs. Soft quotas never reject anything: going over one only logs a
+//! warning and shows up in the player's usage report, so heavy users can
+//! be spotted before the hard limits (play limits, kiosk device limits)
+//! start turning them away.
+
+use async_graphql::{InputObject, SimpleObject};
+use serde::{Deserialize, Serialize};
+
+use crate::{day_index, runtime::RuntimeFacade};
+
+/// Daily soft quotas, managed by the admin.
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize, SimpleObject, InputObject,
+)]
+#[graphql(input_name = "UsageQuotaInput")]
+pub struct UsageQuota {
+    /// Operations per UTC day before a warning (0 = no quota).
+    pub daily_operations: u32,
+    /// Serialized operation bytes per UTC day before a warning (0 = no
+    /// quota).
+    pub daily_bytes: u64,
+}
+
+impl UsageQuota {
+    /// Whether `operations` and `bytes` used in a day exceed the quota.
+    pub fn is_exceeded_by(&self, operations: u32, bytes: u64) -> bool {
+        (self.daily_operations > 0 && operations > self.daily_operations)
+            || (self.daily_bytes > 0 && bytes > self.daily_bytes)
+    }
+}
+
+/// How many times one kind of operation was executed.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct OperationCount {
+    /// The operation's name, as in [`crate::Operation::name`].
+    pub operation: String,
+    pub count: u64,
+}
+
+/// A player's operations on this chain.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, SimpleObject)]
+pub struct OperationUsage {
+    pub total_operations: u64,
+    /// Serialized size of all the player's operations, in bytes.
+    pub total_bytes: u64,
+    /// The UTC day the daily counts are for.
+    pub day: u64,
+    pub operations_today: u32,
+    pub bytes_today: u64,
+    /// Operations by name, in name order.
+    pub by_operation: Vec<OperationCount>,
+    /// The last day the player went over a quota.
+    pub last_warned_day: Option<u64>,
+}
+
+impl OperationUsage {
+    /// Count an operation `name` of `bytes` on `today`, returning whether
+    /// it took the player over `quota` for the first time today.
+    pub fn record(&mut self, name: &str, bytes: u64, today: u64, quota: &UsageQuota) -> bool {
+        if self.day != today {
+            self.day = today;
+            self.operations_today = 0;
+            self.bytes_today = 0;
+        }
+        self.total_operations = self.total_operations.saturating_add(1);
+        self.total_bytes = self.total_bytes.saturating_add(bytes);
+        self.operations_today = self.operations_today.saturating_add(1);
+        self.bytes_today = self.bytes_today.saturating_add(bytes);
+        match self
+            .by_operation
+            .binary_search_by(|count| count.operation.as_str().cmp(name))
+        {
+            Ok(index) => self.by_operation[index].count += 1,
+            Err(index) => self.by_operation.insert(
+                index,
+                OperationCount {
+                    operation: name.to_string(),
+                    count: 1,
+                },
+            ),
+        }
+
+        if self.last_warned_day == Some(today)
+            || !quota.is_exceeded_by(self.operations_today, self.bytes_today)
+        {
+            return false;
+        }
+        self.last_warned_day = Some(today);
+        true
+    }
+}
+
+/// Count an operation `name` of `bytes` signed now, returning whether it
+/// took the player over `quota` for the first time in the UTC day.
+pub fn record_operation(
+    runtime: &mut impl RuntimeFacade,
+    usage: &mut OperationUsage,
+    name: &str,
+    bytes: u64,
+    quota: &UsageQuota,
+) -> bool {
+    usage.record(name, bytes, day_index(runtime.now_micros()), quota)
+}
+
+/// A player's usage measured against the quota when queried.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct UsageReport {
+    pub usage: OperationUsage,
+    pub quota: UsageQuota,
+    /// Whether today's usage is over the quota.
+    pub over_quota: bool,
+}
+
+impl UsageReport {
+    /// Report `usage` against `quota` on `today`; counts from an earlier
+    /// day read as zero.
+    pub fn new(mut usage: OperationUsage, quota: UsageQuota, today: u64) -> Self {
+        if usage.day != today {
+            usage.day = today;
+            usage.operations_today = 0;
+            usage.bytes_today = 0;
+        }
+        Self {
+            over_quota: quota.is_exceeded_by(usage.operations_today, usage.bytes_today),
+            usage,
+            quota,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{runtime::MockRuntime, MICROS_PER_DAY};
+
+    const QUOTA: UsageQuota = UsageQuota {
+        daily_operations: 2,
+        daily_bytes: 0,
+    };
+
+    #[test]
+    fn quota_warns_once_per_day() {
+        let mut runtime = MockRuntime::at(20_000 * MICROS_PER_DAY);
+        let mut usage = OperationUsage::default();
+
+        assert!(!record_operation(
+            &mut runtime,
+            &mut usage,
+            "SubmitScore",
+            10,
+            &QUOTA
+        ));
+        assert!(!record_operation(
+            &mut runtime,
+            &mut usage,
+            "SubmitScore",
+            10,
+            &QUOTA
+        ));
+        assert!(record_operation(
+            &mut runtime,
+            &mut usage,
+            "SubmitScore",
+            10,
+            &QUOTA
+        ));
+        assert!(!record_operation(
+            &mut runtime,
+            &mut usage,
+            "SubmitScore",
+            10,
+            &QUOTA
+        ));
+        assert_eq!(usage.operations_today, 4);
+    }
+
+    #[test]
+    fn quota_window_resets_at_utc_midnight() {
+        let mut runtime = MockRuntime::at(20_001 * MICROS_PER_DAY - 2);
+        let mut usage = OperationUsage::default();
+
+        for _ in 0..3 {
+            record_operation(&mut runtime, &mut usage, "SubmitScore", 10, &QUOTA);
+        }
+        assert_eq!(usage.last_warned_day, Some(20_000));
+
+        runtime.advance(2);
+        assert!(!record_operation(
+            &mut runtime,
+            &mut usage,
+            "Register",
+            5,
+            &QUOTA
+        ));
+        assert_eq!(usage.day, 20_001);
+        assert_eq!(usage.operations_today, 1);
+        assert_eq!(usage.bytes_today, 5);
+        assert_eq!(usage.total_operations, 4);
+        assert_eq!(usage.by_operation.len(), 2);
+
+        record_operation(&mut runtime, &mut usage, "Register", 5, &QUOTA);
+        assert!(record_operation(
+            &mut runtime,
+            &mut usage,
+            "Register",
+            5,
+            &QUOTA
+        ));
+        assert_eq!(usage.last_warned_day, Some(20_001));
+    }
+}
@@ -0,0 +1,258 @@
+// Copyright (c) Linera Arcade Hub
+// SPDX-License-Identifier: Apache-2.0
+
+//! Username vouchers, reservations, claims and related registration rules.
+//!
+//! A registered username is claimed by its account. Deleting the account,
+//! or the admin flagging it as inactive, releases the name, which goes back
+//! to the pool once a grace period has passed. Recycling happens lazily,
+//! whenever someone next checks whether the name is available.
+
+use async_graphql::{Enum, InputObject, SimpleObject};
+use linera_sdk::linera_base_types::{AccountOwner, BcsSignable, CryptoHash};
+use serde::{Deserialize, Serialize};
+
+use crate::{check_account_signature, validate_username, ArcadeError};
+
+/// How long a username reservation is held, in microseconds (7 days).
+pub const RESERVATION_DURATION_MICROS: u64 = 7 * 24 * 60 * 60 * 1_000_000;
+
+/// Most terms the username blocklist can hold.
+pub const MAX_BLOCKED_USERNAMES: u64 = 500;
+
+/// Normalize a username for uniqueness checks (case-insensitive).
+pub fn normalize_username(username: &str) -> String {
+    username.to_lowercase()
+}
+
+/// Reduce a username to the form the blocklist matches on: normalized,
+/// with separators dropped, so `Ad_Min` is caught by `admin`.
+pub fn blocklist_key(username: &str) -> String {
+    normalize_username(username).replace(['_', '-'], "")
+}
+
+/// Check `term` can go on the username blocklist, returning the key it is
+/// stored under.
+pub fn validate_blocked_username(term: &str) -> Result<String, ArcadeError> {
+    validate_username(term)?;
+    let key = blocklist_key(term);
+    if key.len() < 3 {
+        return Err(ArcadeError::InvalidUsernameLength);
+    }
+    Ok(key)
+}
+
+/// How many fallback handles the hub tries for a player whose name was
+/// already taken there.
+pub const MAX_FALLBACK_ATTEMPTS: u32 = 8;
+
+/// The handle the hub gives `owner` on its `attempt`th try when `username`
+/// was already taken there: the name cut short, followed by a tag derived
+/// from the owner.
+pub fn fallback_username(username: &str, owner: &AccountOwner, attempt: u32) -> String {
+    let mut stem = String::new();
+    for c in username.chars() {
+        if stem.len() + c.len_utf8() > 9 {
+            break;
+        }
+        stem.push(c);
+    }
+    let tag = CryptoHash::new(&FallbackTag {
+        owner: *owner,
+        attempt,
+    })
+    .to_string();
+    format!("{stem}_{}", &tag[..10])
+}
+
+/// The value a fallback handle's tag is hashed from.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct FallbackTag {
+    owner: AccountOwner,
+    attempt: u32,
+}
+
+impl BcsSignable<'_> for FallbackTag {}
+
+/// Whether `username` contains any of the blocklist `keys`.
+pub fn is_blocked_username(username: &str, keys: &[String]) -> bool {
+    let key = blocklist_key(username);
+    keys.iter().any(|blocked| key.contains(blocked.as_str()))
+}
+
+/// A username held for an owner ahead of full registration.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct UsernameReservation {
+    pub username: String,
+    pub owner: AccountOwner,
+    pub reserved_at: u64,
+    pub expires_at: u64,
+}
+
+impl UsernameReservation {
+    /// Create a reservation starting at `timestamp`.
+    pub fn new(username: String, owner: AccountOwner, timestamp: u64) -> Self {
+        Self {
+            username,
+            owner,
+            reserved_at: timestamp,
+            expires_at: timestamp.saturating_add(RESERVATION_DURATION_MICROS),
+        }
+    }
+
+    /// Whether the reservation still holds the name at `timestamp`.
+    pub fn is_active(&self, timestamp: u64) -> bool {
+        timestamp < self.expires_at
+    }
+}
+
+/// A voucher signed off-chain by the operator key, reserving a username for
+/// an owner (e.g. issued by an event signup site).
+#[derive(Clone, Debug, Serialize, Deserialize, InputObject)]
+pub struct RegistrationVoucher {
+    /// Unique voucher ID chosen by the issuer; each ID can be consumed once.
+    pub voucher_id: u64,
+    pub username: String,
+    pub owner: AccountOwner,
+    /// Expiry timestamp in microseconds.
+    pub expires_at: u64,
+    /// Hex-encoded BCS `AccountSignature` by the operator over a
+    /// [`VoucherPayload`].
+    pub signature: String,
+}
+
+impl RegistrationVoucher {
+    /// Check that the voucher was issued by `signer` for `owner` and
+    /// `username`, and hasn't expired at `timestamp`.
+    pub fn verify(
+        &self,
+        signer: &AccountOwner,
+        owner: &AccountOwner,
+        username: &str,
+        timestamp: u64,
+    ) -> Result<(), ArcadeError> {
+        if &self.owner != owner || self.username != username {
+            return Err(ArcadeError::InvalidVoucher);
+        }
+        if timestamp >= self.expires_at {
+            return Err(ArcadeError::VoucherExpired);
+        }
+        let payload = VoucherPayload {
+            voucher_id: self.voucher_id,
+            username: self.username.clone(),
+            owner: self.owner,
+            expires_at: self.expires_at,
+        };
+        if !check_account_signature(&self.signature, signer, &payload) {
+            return Err(ArcadeError::InvalidVoucher);
+        }
+        Ok(())
+    }
+}
+
+/// The payload the operator signs when issuing a voucher.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VoucherPayload {
+    pub voucher_id: u64,
+    pub username: String,
+    pub owner: AccountOwner,
+    pub expires_at: u64,
+}
+
+impl BcsSignable<'_> for VoucherPayload {}
+
+/// When released usernames go back to the pool, managed by the admin.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject, InputObject)]
+#[graphql(input_name = "UsernameRecyclingConfigInput")]
+pub struct UsernameRecyclingConfig {
+    /// Days without a finished run before the admin may flag an account as
+    /// inactive (0 = inactive accounts keep their names).
+    pub inactivity_days: u32,
+    /// Days a released username stays held before anyone else may take it.
+    pub grace_period_days: u32,
+}
+
+impl Default for UsernameRecyclingConfig {
+    fn default() -> Self {
+        Self {
+            inactivity_days: 365,
+            grace_period_days: 30,
+        }
+    }
+}
+
+impl UsernameRecyclingConfig {
+    /// Check the grace period isn't longer than the inactivity period, so
+    /// a flagged account can't be held back for longer than it sat idle.
+    pub fn validate(&self) -> Result<(), ArcadeError> {
+        if self.inactivity_days != 0 && self.grace_period_days > self.inactivity_days {
+            return Err(ArcadeError::InvalidUsernameRecyclingConfig);
+        }
+        Ok(())
+    }
+
+    /// Whether an account last seen at `last_seen_at` is inactive at `now`.
+    pub fn is_inactive(&self, last_seen_at: u64, now: u64) -> bool {
+        self.inactivity_days != 0
+            && now.saturating_sub(last_seen_at) >= days_to_micros(self.inactivity_days)
+    }
+
+    /// When a username released at `now` may be taken by someone else.
+    pub fn release_time(&self, now: u64) -> u64 {
+        now.saturating_add(days_to_micros(self.grace_period_days))
+    }
+}
+
+fn days_to_micros(days: u32) -> u64 {
+    u64::from(days) * 24 * 60 * 60 * 1_000_000
+}
+
+/// Why a username was released.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Enum)]
+pub enum ReleaseReason {
+    /// The account was deleted by its owner.
+    AccountDeleted,
+    /// The admin flagged the account as inactive.
+    Inactive,
+}
+
+/// A username released by its holder, pending the grace period.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct UsernameRelease {
+    pub reason: ReleaseReason,
+    pub released_at: u64,
+    /// When the username goes back to the pool.
+    pub recyclable_at: u64,
+}
+
+/// A registered username and the account holding it.
+#[derive(Clone, Debug, Serialize, Deserialize, SimpleObject)]
+pub struct UsernameClaim {
+    pub username: String,
+    pub owner: AccountOwner,
+    pub claimed_at: u64,
+    pub release: Option<UsernameRelease>,
+}
+
+impl UsernameClaim {
+    /// Claim `username` for `owner` at `timestamp`.
+    pub fn new(username: String, owner: AccountOwner, timestamp: u64) -> Self {
+        Self {
+            username,
+            owner,
+            claimed_at: timestamp,
+            release: None,
+        }
+    }
+
+    /// Whether someone else may take the username at `now`, given when its
+    /// holder was `last_seen_at`. An inactive holder who played again
+    /// after being flagged keeps the name.
+    pub fn is_recyclable(&self, last_seen_at: Option<u64>, now: u64) -> bool {
+        self.release.is_some_and(|release| {
+            now >= release.recyclable_at
+                && (release.reason == ReleaseReason::AccountDeleted
+                    || last_seen_at.is_none_or(|seen| seen <= release.released_at))
+        })
+    }
+}